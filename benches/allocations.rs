@@ -0,0 +1,156 @@
+//! spark-signals Allocation Benchmarks
+//!
+//! Counts heap allocations instead of time: every benchmark here reports
+//! "allocations" per iteration through a custom criterion measurement.
+//! Kept out of `signals.rs` so its counting allocator doesn't skew timings.
+//!
+//! ## Run Commands
+//! ```bash
+//! cargo bench --bench allocations       # All allocation benchmarks
+//! cargo bench --bench allocations -- --test
+//! ```
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode, Throughput,
+};
+use spark_signals::{effect_sync, signal};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// =============================================================================
+// ALLOCATION COUNTING
+// =============================================================================
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Criterion measurement: heap allocations made between `start` and `end`.
+struct Allocations;
+
+impl Measurement for Allocations {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> usize {
+        ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    fn end(&self, start: usize) -> usize {
+        // Criterion rejects samples that measure zero. One extra count per
+        // sample is a constant, which the slope of linear sampling drops.
+        ALLOCATIONS.load(Ordering::Relaxed) - start + 1
+    }
+
+    fn add(&self, v1: &usize, v2: &usize) -> usize {
+        v1 + v2
+    }
+
+    fn zero(&self) -> usize {
+        0
+    }
+
+    fn to_f64(&self, value: &usize) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &AllocationFormatter
+    }
+}
+
+struct AllocationFormatter;
+
+impl ValueFormatter for AllocationFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let (per, unit) = match *throughput {
+            Throughput::Bytes(n) | Throughput::BytesDecimal(n) => (n, "allocs/byte"),
+            Throughput::Elements(n) => (n, "allocs/elem"),
+        };
+        for value in values {
+            *value /= per as f64;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+}
+
+// =============================================================================
+// FANOUT
+// Dependency lists up to INLINE_DEPS stay inline, so re-running small
+// effects allocates nothing for dep bookkeeping
+// =============================================================================
+
+fn fanout_allocations(c: &mut Criterion<Allocations>) {
+    let mut g = c.benchmark_group("allocations/fanout");
+    g.sampling_mode(SamplingMode::Linear);
+
+    for deps in [1usize, 2, 4, 8] {
+        g.bench_with_input(BenchmarkId::new("small_deps", deps), &deps, |b, &deps| {
+            let trigger = signal(0i32);
+            let others: Vec<_> = (1..deps).map(|i| signal(i as i32)).collect();
+
+            let _effects: Vec<_> = (0..100).map(|_| {
+                let t = trigger.clone();
+                let others = others.clone();
+                effect_sync(move || {
+                    let sum: i32 = t.get() + others.iter().map(|s| s.get()).sum::<i32>();
+                    black_box(sum);
+                })
+            }).collect();
+
+            let mut i = 0i32;
+            b.iter(|| {
+                trigger.set(i);
+                i = i.wrapping_add(1);
+            })
+        });
+    }
+
+    g.finish();
+}
+
+// =============================================================================
+// BENCHMARK GROUPS
+// =============================================================================
+
+/// Counts are exact, so every sample is equal: plotting their (zero-width)
+/// distribution would fail.
+fn allocations() -> Criterion<Allocations> {
+    Criterion::default().with_measurement(Allocations).without_plots()
+}
+
+criterion_group!(
+    name = fanout;
+    config = allocations();
+    targets = fanout_allocations
+);
+
+criterion_main!(fanout);
@@ -15,13 +15,14 @@
 //! cargo bench -- "derived/"             # Derived-only
 //! cargo bench -- "stress/"              # Stress tests
 //! cargo bench -- --test                 # Quick compile check
+//! cargo bench --bench allocations       # Allocations per iteration
 //! ```
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use spark_signals::{
-    batch, create_selector_eq, derived, dirty_set, effect, effect_scope, effect_sync,
-    linked_signal, reactive_prop, signal, slot, slot_array, tracked_slot_array, untrack,
-    PropValue, ReactiveMap, ReactiveSet, ReactiveVec,
+    batch, create_dense_selector, create_selector_eq, derived, dirty_set, dirty_set_dense, effect, effect_scope, effect_sync,
+    linked_signal, reactive_prop, signal, signal_cell, slot, slot_array, tracked_slot_array, untrack,
+    PropValue, ReactiveMap, ReactiveSet, ReactiveVec, SignalArena,
 };
 
 // =============================================================================
@@ -71,6 +72,13 @@ fn signal_operations(c: &mut Criterion) {
         b.iter(|| untrack(|| black_box(peek_sig.get())))
     });
 
+    // Copy fast path: Cell storage, bit equality
+    let cell = signal_cell(42i32);
+    g.bench_function("cell_get", |b| b.iter(|| black_box(cell.get())));
+    g.bench_function("cell_update", |b| {
+        b.iter(|| cell.update(|v| *v = v.wrapping_add(1)))
+    });
+
     g.finish();
 }
 
@@ -274,6 +282,48 @@ fn selector_operations(c: &mut Criterion) {
         b.iter(|| black_box(is_selector.is_selected(&1)))
     });
 
+    // Dense (Vec-indexed) is_selected check
+    let dense_source = signal(Some(1usize));
+    let dense_selector = create_dense_selector(1024, { let s = dense_source.clone(); move || s.get() });
+    g.bench_function("dense_is_selected", |b| {
+        b.iter(|| black_box(dense_selector.is_selected(black_box(1))))
+    });
+
+    // Subscribed is_selected inside a tracking context (hash insert vs Vec scan)
+    for count in [100, 1000] {
+        g.bench_with_input(BenchmarkId::new("tracked_reads", count), &count, |b, &count| {
+            let selected = signal(0i32);
+            let selector = create_selector_eq({ let s = selected.clone(); move || s.get() });
+            let trigger = signal(0u32);
+            let _e = effect_sync({
+                let trigger = trigger.clone();
+                move || {
+                    trigger.get();
+                    for i in 0..count {
+                        black_box(selector.is_selected(&i));
+                    }
+                }
+            });
+            b.iter(|| trigger.update(|n| *n += 1))
+        });
+
+        g.bench_with_input(BenchmarkId::new("dense_tracked_reads", count), &count, |b, &count| {
+            let selected = signal(Some(0usize));
+            let selector = create_dense_selector(count as usize, { let s = selected.clone(); move || s.get() });
+            let trigger = signal(0u32);
+            let _e = effect_sync({
+                let trigger = trigger.clone();
+                move || {
+                    trigger.get();
+                    for i in 0..count as usize {
+                        black_box(selector.is_selected(i));
+                    }
+                }
+            });
+            b.iter(|| trigger.update(|n| *n += 1))
+        });
+    }
+
     // O(2) vs O(n) comparison
     for count in [10, 100, 500] {
         // Selector O(2) approach
@@ -293,6 +343,23 @@ fn selector_operations(c: &mut Criterion) {
             })
         });
 
+        // Dense selector O(2) approach
+        g.bench_with_input(BenchmarkId::new("dense_o2_change", count), &count, |b, &count| {
+            let selected = signal(Some(0usize));
+            let selector = create_dense_selector(count as usize, { let s = selected.clone(); move || s.get() });
+
+            let _effects: Vec<_> = (0..count as usize).map(|i| {
+                let sel = selector.clone();
+                effect_sync(move || { black_box(sel.is_selected(i)); })
+            }).collect();
+
+            let mut i = 0usize;
+            b.iter(|| {
+                i = (i + 1) % count as usize;
+                selected.set(black_box(Some(i)));
+            })
+        });
+
         // Naive O(n) approach for comparison
         g.bench_with_input(BenchmarkId::new("naive_on", count), &count, |b, &count| {
             let selected = signal(0i32);
@@ -639,6 +706,29 @@ fn fanout_stress(c: &mut Criterion) {
         });
     }
 
+    // Small effects (1-8 deps); benches/allocations.rs counts what they allocate
+    for deps in [1usize, 2, 4, 8] {
+        g.bench_with_input(BenchmarkId::new("small_deps", deps), &deps, |b, &deps| {
+            let trigger = signal(0i32);
+            let others: Vec<_> = (1..deps).map(|i| signal(i as i32)).collect();
+
+            let _effects: Vec<_> = (0..100).map(|_| {
+                let t = trigger.clone();
+                let others = others.clone();
+                effect_sync(move || {
+                    let sum: i32 = t.get() + others.iter().map(|s| s.get()).sum::<i32>();
+                    black_box(sum);
+                })
+            }).collect();
+
+            let mut i = 0i32;
+            b.iter(|| {
+                trigger.set(i);
+                i = i.wrapping_add(1);
+            })
+        });
+    }
+
     g.finish();
 }
 
@@ -730,7 +820,7 @@ fn ecs_stress(c: &mut Criterion) {
     let mut g = c.benchmark_group("stress/ecs");
 
     // Position + Velocity update (1000 entities)
-    let pv_dirty = dirty_set();
+    let pv_dirty = dirty_set_dense(1000);
     let positions = tracked_slot_array::<(f32, f32)>(Some((0.0, 0.0)), pv_dirty.clone());
     let velocities = slot_array::<(f32, f32)>(Some((0.0, 0.0)));
 
@@ -769,6 +859,21 @@ fn ecs_stress(c: &mut Criterion) {
         })
     });
 
+    // Mass creation: one Rc per signal vs one slab
+    g.bench_function("create_10k_signals", |b| {
+        b.iter(|| black_box((0..10_000).map(|i| signal(i as f32)).collect::<Vec<_>>()))
+    });
+
+    g.bench_function("create_10k_arena", |b| {
+        b.iter(|| {
+            let arena = SignalArena::with_capacity(10_000);
+            for i in 0..10_000 {
+                black_box(arena.alloc(i as f32));
+            }
+            arena
+        })
+    });
+
     g.finish();
 }
 
@@ -781,9 +886,9 @@ fn batch_stress(c: &mut Criterion) {
 
     for count in [10, 100, 1000] {
         g.bench_with_input(BenchmarkId::new("signals", count), &count, |b, &count| {
-            let signals: Vec<_> = (0..count).map(|i| signal(i)).collect();
+            let signals: Vec<_> = (0..count).map(signal).collect();
 
-            let signals_c: Vec<_> = signals.iter().cloned().collect();
+            let signals_c: Vec<_> = signals.to_vec();
             let _e = effect_sync(move || {
                 let sum: i32 = signals_c.iter().map(|s| s.get()).sum();
                 black_box(sum);
@@ -0,0 +1,22 @@
+// Regenerates include/spark_signals.h from src/ffi.rs when built with
+// `--features cbindgen`.
+
+fn main() {
+    #[cfg(feature = "cbindgen")]
+    generate_header();
+}
+
+#[cfg(feature = "cbindgen")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_root_or_default(&crate_dir);
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{crate_dir}/src/ffi.rs"))
+        .generate()
+        .expect("cbindgen: failed to generate the ffi header")
+        .write_to_file(format!("{crate_dir}/include/spark_signals.h"));
+}
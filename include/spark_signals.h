@@ -0,0 +1,86 @@
+#ifndef SPARK_SIGNALS_H
+#define SPARK_SIGNALS_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit. */
+
+#include <stdbool.h>
+
+/**
+ * A running effect. Freeing it stops the effect.
+ */
+typedef struct SparkEffect SparkEffect;
+
+/**
+ * A signal holding an `f64`.
+ */
+typedef struct SparkSignal SparkSignal;
+
+/**
+ * Effect body called from Rust with the `user_data` it was registered with.
+ */
+typedef void (*SparkEffectCallback)(void *user_data);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a signal holding `initial`. Release it with `spark_signal_free`.
+ */
+struct SparkSignal *spark_signal_new_f64(double initial);
+
+/**
+ * Read the signal's value. Inside an effect callback, the effect subscribes
+ * to it. Returns 0 for a null handle.
+ *
+ * # Safety
+ *
+ * `signal` must be null or a live handle from `spark_signal_new_f64`.
+ */
+double spark_signal_get(const struct SparkSignal *signal);
+
+/**
+ * Write the signal's value, running the effects that read it.
+ * Returns false if the value was equal (NaN equals NaN) or the handle null.
+ *
+ * # Safety
+ *
+ * `signal` must be null or a live handle from `spark_signal_new_f64`.
+ */
+bool spark_signal_set(const struct SparkSignal *signal, double value);
+
+/**
+ * Release a signal handle. Effects that read it keep their own reference.
+ *
+ * # Safety
+ *
+ * `signal` must be null or a live handle from `spark_signal_new_f64`, and
+ * must not be used afterwards.
+ */
+void spark_signal_free(struct SparkSignal *signal);
+
+/**
+ * Run `callback(user_data)` now and again whenever a signal it read through
+ * `spark_signal_get` changes. Release it with `spark_effect_free`.
+ *
+ * # Safety
+ *
+ * `user_data` must stay valid for `callback` until the effect is freed.
+ */
+struct SparkEffect *spark_effect_new(SparkEffectCallback callback, void *user_data);
+
+/**
+ * Stop an effect and release its handle.
+ *
+ * # Safety
+ *
+ * `effect` must be null or a live handle from `spark_effect_new`, and must
+ * not be used afterwards.
+ */
+void spark_effect_free(struct SparkEffect *effect);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SPARK_SIGNALS_H */
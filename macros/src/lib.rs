@@ -0,0 +1,216 @@
+// ============================================================================
+// spark-signals-macros - Derive macros for spark-signals
+// ============================================================================
+//
+// Re-exported by spark-signals behind the `macros` feature; depend on that
+// instead of this crate. Generated code refers to `::spark_signals`.
+// ============================================================================
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident,
+    PathArguments, Type,
+};
+
+/// Derive a builder and SlotArray wiring for a struct of `PropValue<T>`
+/// fields.
+///
+/// For `struct ButtonProps` this generates:
+///
+/// - `ButtonProps::builder()` returning a `ButtonPropsBuilder`, with one
+///   setter per field that takes a value, a signal or a closure
+///   (anything `IntoPropValue`), and `build()`
+/// - `ButtonPropsSlots`, one `SlotArray` per field, and
+///   `ButtonProps::into_slots(self, &slots, index)` which binds every field
+///   to its array at `index` in one batch
+///
+/// Fields left unset at `build()` are static `Default::default()`, or the
+/// expression given with `#[props(default = ...)]`.
+///
+/// # Example
+///
+/// ```ignore
+/// use spark_signals::{signal, Props, PropValue};
+///
+/// #[derive(Props)]
+/// struct ButtonProps {
+///     label: PropValue<String>,
+///     #[props(default = 1)]
+///     width: PropValue<u32>,
+///     disabled: PropValue<bool>,
+/// }
+///
+/// let busy = signal(false);
+/// let props = ButtonProps::builder()
+///     .label(String::from("Save"))
+///     .disabled(busy.clone())
+///     .build();
+///
+/// let slots = ButtonPropsSlots::new();
+/// props.into_slots(&slots, 0);
+/// assert_eq!(slots.width.get(0), Some(1));
+///
+/// busy.set(true);
+/// assert_eq!(slots.disabled.get(0), Some(true));
+/// ```
+#[proc_macro_derive(Props, attributes(props))]
+pub fn derive_props(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_props(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// A `PropValue<T>` field
+struct PropField {
+    name: Ident,
+    ty: Type,
+    default: Option<Expr>,
+}
+
+fn expand_props(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "#[derive(Props)] needs a struct with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "#[derive(Props)] only supports structs",
+            ));
+        }
+    };
+
+    let mut props = Vec::new();
+    for field in fields {
+        let name = field.ident.clone().expect("named field");
+        let ty = prop_value_inner(&field.ty).ok_or_else(|| {
+            Error::new_spanned(&field.ty, "#[derive(Props)] fields must be PropValue<T>")
+        })?;
+        let mut default = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("props")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    default = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `default = ...`"))
+                }
+            })?;
+        }
+        props.push(PropField { name, ty, default });
+    }
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let builder = format_ident!("{}Builder", ident);
+    let slots = format_ident!("{}Slots", ident);
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let names: Vec<&Ident> = props.iter().map(|p| &p.name).collect();
+    let types: Vec<&Type> = props.iter().map(|p| &p.ty).collect();
+    let defaults = props.iter().map(|p| match &p.default {
+        Some(expr) => quote!(#expr),
+        None => quote!(::core::default::Default::default()),
+    });
+
+    let builder_doc = format!("Builder for [`{ident}`], from `{ident}::builder()`.");
+    let slots_doc = format!("One `SlotArray` per field of [`{ident}`].");
+    let setter_docs = names
+        .iter()
+        .map(|name| format!("Set `{name}` to a value, a signal or a getter closure."));
+
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder #generics #where_clause {
+            #( #names: ::core::option::Option<::spark_signals::PropValue<#types>>, )*
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(
+                #[doc = #setter_docs]
+                #vis fn #names<M>(
+                    mut self,
+                    value: impl ::spark_signals::IntoPropValue<#types, M>,
+                ) -> Self {
+                    self.#names = ::core::option::Option::Some(value.into_prop_value());
+                    self
+                }
+            )*
+
+            /// Build the props; unset fields get their default.
+            #vis fn build(self) -> #ident #ty_generics {
+                #ident {
+                    #(
+                        #names: self.#names.unwrap_or_else(|| {
+                            ::spark_signals::PropValue::Static(#defaults)
+                        }),
+                    )*
+                }
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Start building props.
+            #vis fn builder() -> #builder #ty_generics {
+                #builder {
+                    #( #names: ::core::option::Option::None, )*
+                }
+            }
+
+            /// Bind every field to its array in `slots` at `index`, in one batch.
+            #vis fn into_slots(self, slots: &#slots #ty_generics, index: usize) {
+                ::spark_signals::batch(|| {
+                    #( slots.#names.bind(index, self.#names); )*
+                });
+            }
+        }
+
+        #[doc = #slots_doc]
+        #vis struct #slots #generics #where_clause {
+            #( #vis #names: ::spark_signals::SlotArray<#types>, )*
+        }
+
+        impl #impl_generics #slots #ty_generics #where_clause {
+            /// Empty arrays, one per field.
+            #vis fn new() -> Self {
+                Self {
+                    #( #names: ::spark_signals::slot_array(::core::option::Option::None), )*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::default::Default for #slots #ty_generics #where_clause {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    })
+}
+
+/// `T` for a field typed `PropValue<T>` (by last path segment).
+fn prop_value_inner(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "PropValue" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner.clone()),
+        _ => None,
+    }
+}
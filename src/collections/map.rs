@@ -5,15 +5,122 @@
 // ============================================================================
 
 use std::borrow::Borrow;
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map::{Iter, Keys, Values};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 use std::rc::Rc;
 
 use crate::core::context::with_context;
 use crate::core::types::{AnySource, SourceInner};
+use crate::primitives::effect::effect;
+use crate::reactivity::batching::{batch, untrack};
+use crate::reactivity::middleware::{middleware_source, run_write_middleware};
 use crate::reactivity::tracking::{notify_write, track_read};
 
+// =============================================================================
+// LRU STATE
+// =============================================================================
+
+/// Callback for entries evicted from a size-bounded map.
+type EvictFn<K, V> = Box<dyn FnMut(K, V)>;
+
+/// Recency bookkeeping for maps created with `with_capacity_lru`.
+///
+/// Interior mutability lets `&self` reads record use without tracking.
+struct LruState<K, V> {
+    max_entries: usize,
+
+    /// Monotonic use counter
+    tick: Cell<u64>,
+
+    /// Last use of each key
+    last_used: RefCell<HashMap<K, u64>>,
+
+    /// Keys by last use, oldest first
+    order: RefCell<BTreeMap<u64, K>>,
+
+    on_evict: Option<EvictFn<K, V>>,
+}
+
+impl<K, V> LruState<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            tick: Cell::new(0),
+            last_used: RefCell::new(HashMap::new()),
+            order: RefCell::new(BTreeMap::new()),
+            on_evict: None,
+        }
+    }
+
+    fn next_tick(&self) -> u64 {
+        let tick = self.tick.get() + 1;
+        self.tick.set(tick);
+        tick
+    }
+
+    /// Mark a key as used now (no-op for unknown keys).
+    fn touch<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let tick = self.next_tick();
+        if let Some(last) = self.last_used.borrow_mut().get_mut(key) {
+            let mut order = self.order.borrow_mut();
+            if let Some(k) = order.remove(last) {
+                order.insert(tick, k);
+            }
+            *last = tick;
+        }
+    }
+
+    /// Mark a key as used now, adding it if unknown.
+    fn record(&self, key: &K) {
+        if self.last_used.borrow().contains_key(key) {
+            self.touch(key);
+        } else {
+            let tick = self.next_tick();
+            self.last_used.borrow_mut().insert(key.clone(), tick);
+            self.order.borrow_mut().insert(tick, key.clone());
+        }
+    }
+
+    fn forget<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(last) = self.last_used.borrow_mut().remove(key) {
+            self.order.borrow_mut().remove(&last);
+        }
+    }
+
+    fn clear(&self) {
+        self.last_used.borrow_mut().clear();
+        self.order.borrow_mut().clear();
+    }
+
+    fn least_recent(&self) -> Option<K> {
+        self.order.borrow().values().next().cloned()
+    }
+
+    /// Same capacity and recency, without the callback.
+    fn clone_without_callback(&self) -> Self {
+        Self {
+            max_entries: self.max_entries,
+            tick: Cell::new(self.tick.get()),
+            last_used: RefCell::new(self.last_used.borrow().clone()),
+            order: RefCell::new(self.order.borrow().clone()),
+            on_evict: None,
+        }
+    }
+}
+
 // =============================================================================
 // REACTIVE MAP
 // =============================================================================
@@ -62,6 +169,9 @@ where
 
     /// Size signal
     size: Rc<SourceInner<usize>>,
+
+    /// Recency tracking and eviction (size-bounded maps only)
+    lru: Option<LruState<K, V>>,
 }
 
 impl<K, V> ReactiveMap<K, V>
@@ -75,6 +185,7 @@ where
             key_signals: HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             size: Rc::new(SourceInner::new(0)),
+            lru: None,
         }
     }
 
@@ -85,10 +196,12 @@ where
             key_signals: HashMap::with_capacity(capacity),
             version: Rc::new(SourceInner::new(0)),
             size: Rc::new(SourceInner::new(0)),
+            lru: None,
         }
     }
 
     /// Create a reactive map from an iterator.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
         let data: HashMap<K, V> = iter.into_iter().collect();
         let len = data.len();
@@ -97,18 +210,85 @@ where
             key_signals: HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             size: Rc::new(SourceInner::new(len)),
+            lru: None,
+        }
+    }
+
+    /// Create a size-bounded map that evicts least-recently-used entries.
+    ///
+    /// Inserting a new key when the map already holds `max_entries` evicts
+    /// the entry that was least recently read or written. Reads (`get`,
+    /// `get_tracked`) record use without adding any reactive dependency.
+    /// An evicted key is notified like a removal, and the entry is handed
+    /// to the `on_evict` callback, if set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_entries` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveMap;
+    ///
+    /// let mut cache = ReactiveMap::with_capacity_lru(2);
+    /// cache.insert("a", 1);
+    /// cache.insert("b", 2);
+    ///
+    /// // Reading "a" makes "b" the least recently used
+    /// assert_eq!(cache.get("a"), Some(&1));
+    /// cache.insert("c", 3);
+    ///
+    /// assert!(!cache.contains_key("b"));
+    /// assert_eq!(cache.len(), 2);
+    /// ```
+    pub fn with_capacity_lru(max_entries: usize) -> Self {
+        assert!(max_entries > 0, "LRU map needs room for at least one entry");
+        let mut map = Self::with_capacity(max_entries);
+        map.lru = Some(LruState::new(max_entries));
+        map
+    }
+
+    /// Set the callback that receives entries evicted by the LRU cap.
+    ///
+    /// Runs after the evicted key's signal has been notified. Has no effect
+    /// on maps without a cap.
+    pub fn on_evict<F>(&mut self, f: F)
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        if let Some(lru) = &mut self.lru {
+            lru.on_evict = Some(Box::new(f));
+        }
+    }
+
+    /// The LRU cap, if this map was created with `with_capacity_lru`.
+    pub fn lru_capacity(&self) -> Option<usize> {
+        self.lru.as_ref().map(|lru| lru.max_entries)
+    }
+
+    /// Evict least-recently-used entries until the map fits its cap.
+    fn evict_overflow(&mut self) {
+        loop {
+            let key = match &self.lru {
+                Some(lru) if self.data.len() > lru.max_entries => lru.least_recent(),
+                _ => None,
+            };
+            let Some(key) = key else {
+                break;
+            };
+
+            if let Some(value) = self.remove_exact(&key) {
+                if let Some(on_evict) = self.lru.as_mut().and_then(|lru| lru.on_evict.as_mut()) {
+                    on_evict(key, value);
+                }
+            }
         }
     }
 
     /// Get or create a signal for a key.
     fn get_key_signal(&mut self, key: &K) -> Rc<SourceInner<i32>> {
-        if let Some(sig) = self.key_signals.get(key) {
-            sig.clone()
-        } else {
-            let sig = Rc::new(SourceInner::new(0));
-            self.key_signals.insert(key.clone(), sig.clone());
-            sig
-        }
+        ensure_key_signal(&mut self.key_signals, key)
     }
 
     /// Increment a signal's value (trigger update).
@@ -215,6 +395,10 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
+        if let Some(lru) = &self.lru {
+            lru.touch(key);
+        }
+
         if let Some(sig) = self.key_signals.get(key) {
             track_read(sig.clone() as Rc<dyn AnySource>);
             return self.data.get(key);
@@ -244,6 +428,10 @@ where
     where
         V: 'static,
     {
+        if let Some(lru) = &self.lru {
+            lru.touch(key);
+        }
+
         if let Some(sig) = self.key_signals.get(key) {
             track_read(sig.clone() as Rc<dyn AnySource>);
             return self.data.get(key);
@@ -271,10 +459,15 @@ where
     ///
     /// If the map did not have this key present, `None` is returned.
     /// If the map did have this key present, the value is updated, and the old value is returned.
-    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    /// If write middleware vetoes the value, the map is unchanged and the
+    /// rejected value is returned.
+    pub fn insert(&mut self, key: K, mut value: V) -> Option<V>
     where
         V: PartialEq + 'static,
     {
+        if !self.pass_middleware(&key, &mut value) {
+            return Some(value);
+        }
         let is_new = !self.data.contains_key(&key);
         let old_value = self.data.insert(key.clone(), value);
 
@@ -306,14 +499,24 @@ where
             }
         }
 
+        if let Some(lru) = &self.lru {
+            lru.record(&key);
+        }
+        self.evict_overflow();
+
         old_value
     }
 
     /// Inserts a key-value pair, always notifying even if value is the same.
-    pub fn insert_always_notify(&mut self, key: K, value: V) -> Option<V>
+    ///
+    /// A value vetoed by write middleware is returned like in `insert()`.
+    pub fn insert_always_notify(&mut self, key: K, mut value: V) -> Option<V>
     where
         V: 'static,
     {
+        if !self.pass_middleware(&key, &mut value) {
+            return Some(value);
+        }
         let is_new = !self.data.contains_key(&key);
         let old_value = self.data.insert(key.clone(), value);
 
@@ -326,9 +529,48 @@ where
 
         Self::increment(&sig);
 
+        if let Some(lru) = &self.lru {
+            lru.record(&key);
+        }
+        self.evict_overflow();
+
         old_value
     }
 
+    /// Run write middleware over a value for `key`. Returns false if it was
+    /// vetoed.
+    fn pass_middleware(&self, key: &K, value: &mut V) -> bool
+    where
+        V: 'static,
+    {
+        match middleware_source(&self.version) {
+            Some(source) => run_write_middleware(&source, self.data.get(key), value),
+            None => true,
+        }
+    }
+
+    // =========================================================================
+    // ENTRY
+    // =========================================================================
+
+    /// Gets the entry for a key, for in-place insert-or-modify.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveMap;
+    ///
+    /// let mut counts: ReactiveMap<&str, u32> = ReactiveMap::new();
+    /// for word in ["a", "b", "a"] {
+    ///     counts.entry(word).and_modify(|n| *n += 1).or_insert(1);
+    /// }
+    /// assert_eq!(counts.get("a"), Some(&2));
+    /// assert_eq!(counts.get("b"), Some(&1));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        Entry { map: self, key }
+    }
+
     // =========================================================================
     // REMOVE (delete)
     // =========================================================================
@@ -339,6 +581,10 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
+        if let Some(lru) = &self.lru {
+            lru.forget(key);
+        }
+
         if let Some(value) = self.data.remove(key) {
             // Mark key signal as deleted (-1) and remove it
             if let Some(sig) = self.key_signals.remove(key) {
@@ -356,6 +602,10 @@ where
 
     /// Removes a key from the map with exact key type.
     pub fn remove_exact(&mut self, key: &K) -> Option<V> {
+        if let Some(lru) = &self.lru {
+            lru.forget(key);
+        }
+
         if let Some(value) = self.data.remove(key) {
             // Mark key signal as deleted (-1)
             if let Some(sig) = self.key_signals.remove(key) {
@@ -377,6 +627,10 @@ where
 
     /// Clears the map, removing all key-value pairs.
     pub fn clear(&mut self) {
+        if let Some(lru) = &self.lru {
+            lru.clear();
+        }
+
         if !self.data.is_empty() {
             // Mark all key signals as deleted
             for sig in self.key_signals.values() {
@@ -391,6 +645,112 @@ where
         }
     }
 
+    // =========================================================================
+    // BULK OPERATIONS - one size/version notification per call
+    // =========================================================================
+
+    /// Inserts every pair from the iterator.
+    ///
+    /// Keys whose value changed are notified individually, but size and
+    /// version are notified once for the whole call (and all of it runs in
+    /// one batch). Pairs vetoed by write middleware are skipped.
+    pub fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        V: PartialEq + 'static,
+    {
+        batch(|| {
+            let len_before = self.data.len();
+            let mut added = false;
+
+            for (key, mut value) in iter {
+                if !self.pass_middleware(&key, &mut value) {
+                    continue;
+                }
+                let changed = match self.data.get(&key) {
+                    Some(old) => *old != value,
+                    None => {
+                        added = true;
+                        true
+                    }
+                };
+                self.data.insert(key.clone(), value);
+
+                if changed {
+                    if let Some(sig) = self.key_signals.get(&key) {
+                        Self::increment(sig);
+                    }
+                }
+                if let Some(lru) = &self.lru {
+                    lru.record(&key);
+                }
+            }
+
+            if self.data.len() != len_before {
+                self.set_size(self.data.len());
+            }
+            if added {
+                self.increment_version();
+            }
+            self.evict_overflow();
+        });
+    }
+
+    /// Keeps only the entries `f` returns true for.
+    ///
+    /// Removed keys are notified individually; size and version once.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let removed: Vec<K> = self
+            .data
+            .iter()
+            .filter(|(k, v)| !f(k, v))
+            .map(|(k, _)| k.clone())
+            .collect();
+        if removed.is_empty() {
+            return;
+        }
+
+        batch(|| {
+            for key in &removed {
+                self.data.remove(key);
+                if let Some(lru) = &self.lru {
+                    lru.forget(key);
+                }
+                if let Some(sig) = self.key_signals.remove(key) {
+                    Self::set_and_notify(&sig, -1);
+                }
+            }
+            self.set_size(self.data.len());
+            self.increment_version();
+        });
+    }
+
+    /// Removes every entry, returning them.
+    ///
+    /// Notifies like `clear()`.
+    pub fn drain(&mut self) -> std::collections::hash_map::IntoIter<K, V> {
+        let data = std::mem::take(&mut self.data);
+        if let Some(lru) = &self.lru {
+            lru.clear();
+        }
+
+        if !data.is_empty() {
+            batch(|| {
+                for sig in self.key_signals.values() {
+                    Self::set_and_notify(sig, -1);
+                }
+                self.key_signals.clear();
+
+                self.set_size(0);
+                self.increment_version();
+            });
+        }
+        data.into_iter()
+    }
+
     // =========================================================================
     // ITERATION (tracks version)
     // =========================================================================
@@ -419,6 +779,63 @@ where
         self.data.iter()
     }
 
+    /// Returns an iterator over the keys that prepares per-key tracking.
+    ///
+    /// Tracks the version signal like `keys()`, and gives each key it yields
+    /// a key signal, so a later `get()` of that key tracks just the key.
+    /// With plain `keys()`, such a `get()` falls back to the version signal
+    /// and misses value changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveMap;
+    /// use spark_signals::{batch, effect};
+    /// use std::cell::{Cell, RefCell};
+    /// use std::rc::Rc;
+    ///
+    /// let scores = Rc::new(RefCell::new(ReactiveMap::from_iter([("ann", 1), ("bob", 2)])));
+    /// let runs = Rc::new(Cell::new(0));
+    /// let _watch = effect({
+    ///     let (scores, runs) = (scores.clone(), runs.clone());
+    ///     move || {
+    ///         let mut scores = scores.borrow_mut();
+    ///         let keys: Vec<_> = scores.keys_tracked().copied().collect();
+    ///         for key in keys.iter().filter(|k| k.starts_with('a')) {
+    ///             scores.get(key);
+    ///         }
+    ///         runs.set(runs.get() + 1);
+    ///     }
+    /// });
+    ///
+    /// batch(|| scores.borrow_mut().insert("bob", 20)); // not read: no re-run
+    /// assert_eq!(runs.get(), 1);
+    /// batch(|| scores.borrow_mut().insert("ann", 10));
+    /// assert_eq!(runs.get(), 2);
+    /// ```
+    pub fn keys_tracked(&mut self) -> KeysTracked<'_, K, V> {
+        track_read(self.version.clone() as Rc<dyn AnySource>);
+        KeysTracked {
+            keys: self.data.keys(),
+            key_signals: &mut self.key_signals,
+        }
+    }
+
+    /// Returns an iterator over key-value pairs that tracks each entry it
+    /// yields.
+    ///
+    /// Tracks the version signal like `iter()`, plus the key signal of every
+    /// entry actually reached: an effect that stops after a few entries
+    /// (`find`, `take`, ...) re-runs for structural changes and changes to
+    /// those entries, not for other values.
+    pub fn iter_tracked(&mut self) -> IterTracked<'_, K, V> {
+        track_read(self.version.clone() as Rc<dyn AnySource>);
+        IterTracked {
+            iter: self.data.iter(),
+            key_signals: &mut self.key_signals,
+        }
+    }
+
     /// Iterates over each key-value pair.
     ///
     /// Tracks the version signal.
@@ -445,12 +862,80 @@ where
 
     /// Gets mutable access to underlying data without tracking.
     ///
-    /// **Warning**: Mutations here won't trigger reactive updates!
+    /// **Warning**: Mutations here won't trigger reactive updates, and keys
+    /// inserted here are never evicted by an LRU cap!
     pub fn raw_mut(&mut self) -> &mut HashMap<K, V> {
         &mut self.data
     }
 }
 
+// =============================================================================
+// WATCH_KEY
+// =============================================================================
+
+impl<K, V> ReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + PartialEq + 'static,
+{
+    /// Call `callback` with the value of `key` now and whenever it changes.
+    ///
+    /// The watcher depends on that key's signal while the key exists, and on
+    /// the version signal (for insertion) while it doesn't, so changes to
+    /// other keys never wake it. Removal calls back with `None`. The callback
+    /// runs untracked; returns a dispose function.
+    ///
+    /// Write to the map inside a `batch()` so its borrow is released before
+    /// the watcher runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::batch;
+    /// use spark_signals::collections::ReactiveMap;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let scores = Rc::new(RefCell::new(ReactiveMap::new()));
+    /// let seen = Rc::new(RefCell::new(Vec::new()));
+    /// let _stop = ReactiveMap::watch_key(&scores, "ada", {
+    ///     let seen = seen.clone();
+    ///     move |score: Option<&i32>| seen.borrow_mut().push(score.copied())
+    /// });
+    ///
+    /// batch(|| scores.borrow_mut().insert("ada", 10));
+    /// batch(|| scores.borrow_mut().insert("bob", 7)); // other key: no call
+    /// batch(|| scores.borrow_mut().remove("ada"));
+    /// assert_eq!(*seen.borrow(), [None, Some(10), None]);
+    /// ```
+    pub fn watch_key<F>(this: &Rc<RefCell<Self>>, key: K, mut callback: F) -> impl FnOnce()
+    where
+        F: FnMut(Option<&V>) + 'static,
+    {
+        let map = this.clone();
+        let mut previous: Option<Option<V>> = None;
+
+        effect(move || {
+            let current = {
+                let mut map = map.borrow_mut();
+                if map.data.contains_key(&key) {
+                    track_read(map.get_key_signal(&key) as Rc<dyn AnySource>);
+                } else {
+                    track_read(map.version.clone() as Rc<dyn AnySource>);
+                }
+                map.data.get(&key).cloned()
+            };
+
+            // Structural changes wake a watcher of a missing key
+            if previous.as_ref() == Some(&current) {
+                return;
+            }
+            untrack(|| callback(current.as_ref()));
+            previous = Some(current);
+        })
+    }
+}
+
 impl<K, V> Default for ReactiveMap<K, V>
 where
     K: Eq + Hash + Clone,
@@ -467,8 +952,11 @@ where
 {
     fn clone(&self) -> Self {
         // Create a new reactive map with same data but fresh signals
-        // This is intentional - clones get independent reactivity
-        Self::from_iter(self.data.clone())
+        // This is intentional - clones get independent reactivity.
+        // An LRU cap carries over; the eviction callback doesn't.
+        let mut map = Self::from_iter(self.data.clone());
+        map.lru = self.lru.as_ref().map(LruState::clone_without_callback);
+        map
     }
 }
 
@@ -485,6 +973,303 @@ where
     }
 }
 
+// =============================================================================
+// READONLY VIEW
+// =============================================================================
+
+impl<K, V> ReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    /// A view of a shared map that can read it (tracked) but not write it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveMap;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let scores = Rc::new(RefCell::new(ReactiveMap::new()));
+    /// let view = ReactiveMap::as_readonly(&scores);
+    ///
+    /// scores.borrow_mut().insert("ada", 10);
+    /// assert_eq!(view.get(&"ada"), Some(10));
+    /// assert_eq!(view.with(|map| map.values().sum::<i32>()), 10);
+    /// ```
+    pub fn as_readonly(this: &Rc<RefCell<Self>>) -> ReadonlyReactiveMap<K, V> {
+        ReadonlyReactiveMap { map: this.clone() }
+    }
+}
+
+/// Read-only handle to a shared [`ReactiveMap`], from
+/// [`ReactiveMap::as_readonly()`].
+///
+/// Every read tracks like the map's own. Clones share the map.
+pub struct ReadonlyReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    map: Rc<RefCell<ReactiveMap<K, V>>>,
+}
+
+impl<K, V> ReadonlyReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    // `Borrow` is in scope for key lookups, so name `RefCell::borrow`
+    fn read(&self) -> std::cell::Ref<'_, ReactiveMap<K, V>> {
+        RefCell::borrow(&self.map)
+    }
+
+    /// Number of entries (tracks the size signal).
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// True if the map is empty (tracks the size signal).
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// True if the map has `key` (tracks the key, or the version if absent).
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.read().contains_key(key)
+    }
+
+    /// A clone of the value for `key`.
+    ///
+    /// Tracks just that key while it exists, like `get_tracked()`.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone + 'static,
+    {
+        self.map.borrow_mut().get_tracked(key).cloned()
+    }
+
+    /// The keys, in iteration order (tracks the version signal).
+    pub fn keys(&self) -> Vec<K> {
+        self.read().keys().cloned().collect()
+    }
+
+    /// Read the map through a shared reference.
+    ///
+    /// Don't call the view's other methods from inside `f`: the map is
+    /// borrowed until it returns.
+    pub fn with<R>(&self, f: impl FnOnce(&ReactiveMap<K, V>) -> R) -> R {
+        f(&self.read())
+    }
+}
+
+impl<K, V> ReadonlyReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + PartialEq + 'static,
+{
+    /// [`ReactiveMap::watch_key()`] through the view.
+    pub fn watch_key<F>(&self, key: K, callback: F) -> impl FnOnce()
+    where
+        F: FnMut(Option<&V>) + 'static,
+    {
+        ReactiveMap::watch_key(&self.map, key, callback)
+    }
+}
+
+impl<K, V> Clone for ReadonlyReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K, V> From<Rc<RefCell<ReactiveMap<K, V>>>> for ReadonlyReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn from(map: Rc<RefCell<ReactiveMap<K, V>>>) -> Self {
+        Self { map }
+    }
+}
+
+impl<K, V> std::fmt::Debug for ReadonlyReactiveMap<K, V>
+where
+    K: Eq + Hash + Clone + std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ReadonlyReactiveMap")
+            .field(&*self.read())
+            .finish()
+    }
+}
+
+// =============================================================================
+// TRACKED ITERATORS
+// =============================================================================
+
+/// The key signal for `key`, created if missing.
+fn ensure_key_signal<K: Eq + Hash + Clone>(
+    key_signals: &mut HashMap<K, Rc<SourceInner<i32>>>,
+    key: &K,
+) -> Rc<SourceInner<i32>> {
+    if let Some(sig) = key_signals.get(key) {
+        return sig.clone();
+    }
+    let sig = Rc::new(SourceInner::new(0));
+    key_signals.insert(key.clone(), sig.clone());
+    sig
+}
+
+/// Iterator returned by [`ReactiveMap::keys_tracked()`].
+pub struct KeysTracked<'a, K, V> {
+    keys: Keys<'a, K, V>,
+    key_signals: &'a mut HashMap<K, Rc<SourceInner<i32>>>,
+}
+
+impl<'a, K, V> Iterator for KeysTracked<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        let key = self.keys.next()?;
+        ensure_key_signal(self.key_signals, key);
+        Some(key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+/// Iterator returned by [`ReactiveMap::iter_tracked()`].
+pub struct IterTracked<'a, K, V> {
+    iter: Iter<'a, K, V>,
+    key_signals: &'a mut HashMap<K, Rc<SourceInner<i32>>>,
+}
+
+impl<'a, K, V> Iterator for IterTracked<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let (key, value) = self.iter.next()?;
+        let sig = ensure_key_signal(self.key_signals, key);
+        track_read(sig as Rc<dyn AnySource>);
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+// =============================================================================
+// ENTRY
+// =============================================================================
+
+/// A view into one key of a [`ReactiveMap`], created by `ReactiveMap::entry`.
+///
+/// Every method notifies like the equivalent `insert`: a new key bumps size
+/// and version, a modified value bumps its key signal.
+pub struct Entry<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    map: &'a mut ReactiveMap<K, V>,
+    key: K,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+    V: 'static,
+{
+    /// The entry's key.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Modify the value in place if the key is present.
+    ///
+    /// Always notifies the key (the closure may have changed anything).
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Some(value) = self.map.data.get_mut(&self.key) {
+            f(value);
+            if let Some(sig) = self.map.key_signals.get(&self.key) {
+                ReactiveMap::<K, V>::increment(sig);
+            }
+            if let Some(lru) = &self.map.lru {
+                lru.record(&self.key);
+            }
+        }
+        self
+    }
+
+    /// Insert `default` if the key is absent, then return the value.
+    ///
+    /// Writes through the returned reference are not notified; use
+    /// `and_modify()` to change an existing value reactively.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default` if the key is absent, then return the value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        let map = self.map;
+        if !map.data.contains_key(&self.key) {
+            map.data.insert(self.key.clone(), default());
+            let sig = map.get_key_signal(&self.key);
+            map.set_size(map.data.len());
+            map.increment_version();
+            ReactiveMap::<K, V>::increment(&sig);
+            if let Some(lru) = &map.lru {
+                lru.record(&self.key);
+            }
+            // The new key is the most recent; only older entries go
+            map.evict_overflow();
+        }
+
+        map.data
+            .get_mut(&self.key)
+            .expect("a freshly inserted key is never the least recently used")
+    }
+
+    /// Insert `V::default()` if the key is absent, then return the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<K, V> std::fmt::Debug for Entry<'_, K, V>
+where
+    K: Eq + Hash + Clone + std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", &self.key)
+            .field("value", &self.map.data.get(&self.key))
+            .finish()
+    }
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -495,6 +1280,33 @@ mod tests {
     use crate::effect_sync;
     use std::cell::{Cell, RefCell};
 
+    #[test]
+    fn iter_tracked_tracks_only_visited_entries() {
+        let map = Rc::new(RefCell::new(ReactiveMap::from_iter([(1, 'a'), (2, 'b')])));
+        let first = Rc::new(Cell::new(None));
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (map, first, runs) = (map.clone(), first.clone(), runs.clone());
+            move || {
+                let mut map = map.borrow_mut();
+                first.set(map.iter_tracked().next().map(|(k, v)| (*k, *v)));
+                runs.set(runs.get() + 1);
+            }
+        });
+        let (visited, _) = first.get().unwrap();
+        let other = 3 - visited;
+
+        batch(|| map.borrow_mut().insert(other, 'x'));
+        assert_eq!(runs.get(), 1);
+        batch(|| map.borrow_mut().insert(visited, 'y'));
+        assert_eq!(runs.get(), 2);
+        assert_eq!(first.get().map(|(_, v)| v), Some('y'));
+
+        // Structural changes re-run like iter()
+        batch(|| map.borrow_mut().remove(&other));
+        assert_eq!(runs.get(), 3);
+    }
+
     #[test]
     fn create_empty_map() {
         let map: ReactiveMap<String, i32> = ReactiveMap::new();
@@ -679,6 +1491,175 @@ mod tests {
         assert_eq!(map2.get(&"key".to_string()), Some(&42));
     }
 
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut map = ReactiveMap::with_capacity_lru(3);
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        map.on_evict({
+            let evicted = evicted.clone();
+            move |k, v| (*evicted).borrow_mut().push((k, v))
+        });
+
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("c", 3);
+
+        // Reads and overwrites both count as use
+        map.get("a");
+        map.insert("b", 20);
+
+        map.insert("d", 4);
+        map.insert("e", 5);
+
+        assert_eq!(*(*evicted).borrow(), vec![("c", 3), ("a", 1)]);
+        assert_eq!(map.raw().len(), 3);
+        assert_eq!(map.lru_capacity(), Some(3));
+
+        // Removed keys no longer count toward recency
+        map.remove("b");
+        map.insert("f", 6);
+        assert_eq!((*evicted).borrow().len(), 2);
+    }
+
+    #[test]
+    fn lru_eviction_notifies_key_signal() {
+        use crate::batch;
+
+        let mut map = ReactiveMap::with_capacity_lru(1);
+        map.insert("a".to_string(), 1);
+        let map_rc = Rc::new(RefCell::new(map));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _effect = effect_sync({
+            let map = map_rc.clone();
+            let seen = seen.clone();
+            move || {
+                let value = (*map).borrow_mut().get_tracked(&"a".to_string()).copied();
+                (*seen).borrow_mut().push(value);
+            }
+        });
+
+        // Reading recorded use but did not re-run anything
+        assert_eq!(*(*seen).borrow(), vec![Some(1)]);
+
+        batch(|| {
+            (*map_rc).borrow_mut().insert("b".to_string(), 2);
+        });
+        assert_eq!(*(*seen).borrow(), vec![Some(1), None]);
+    }
+
+    #[test]
+    fn lru_cap_survives_clone() {
+        let mut map = ReactiveMap::with_capacity_lru(1);
+        map.insert(1, "one");
+
+        let mut copy = map.clone();
+        copy.insert(2, "two");
+        assert!(!copy.contains_key(&1));
+        assert!(map.contains_key(&1));
+        assert_eq!(ReactiveMap::<i32, i32>::new().lru_capacity(), None);
+    }
+
+    #[test]
+    fn entry_inserts_and_modifies() {
+        let mut map: ReactiveMap<&str, Vec<i32>> = ReactiveMap::new();
+        map.entry("a").or_default().push(1);
+        map.entry("a").or_default().push(2);
+        map.entry("b").and_modify(|v| v.push(9)).or_insert_with(|| vec![3]);
+        map.entry("b").and_modify(|v| v.push(4)).or_insert(vec![0]);
+
+        assert_eq!(map.get("a"), Some(&vec![1, 2]));
+        assert_eq!(map.get("b"), Some(&vec![3, 4]));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn bulk_operations_notify_version_once() {
+        use crate::batch;
+
+        let map: ReactiveMap<i32, i32> = ReactiveMap::new();
+        let map_rc = Rc::new(RefCell::new(map));
+
+        let runs = Rc::new(Cell::new(0));
+        let _effect = effect_sync({
+            let (map, runs) = (map_rc.clone(), runs.clone());
+            move || {
+                for _ in (*map).borrow().keys() {}
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        batch(|| (*map_rc).borrow_mut().extend((0..1000).map(|i| (i, i))));
+        assert_eq!(runs.get(), 2);
+        assert_eq!((*map_rc).borrow().len(), 1000);
+
+        batch(|| (*map_rc).borrow_mut().retain(|k, _| k % 2 == 0));
+        assert_eq!(runs.get(), 3);
+        assert_eq!((*map_rc).borrow().len(), 500);
+
+        // Nothing removed: no notification
+        batch(|| (*map_rc).borrow_mut().retain(|_, _| true));
+        assert_eq!(runs.get(), 3);
+
+        let drained = batch(|| (*map_rc).borrow_mut().drain().count());
+        assert_eq!(drained, 500);
+        assert_eq!(runs.get(), 4);
+        assert!((*map_rc).borrow().is_empty());
+    }
+
+    #[test]
+    fn extend_notifies_changed_keys_only() {
+        use crate::batch;
+
+        let mut map: ReactiveMap<&str, i32> = ReactiveMap::from_iter([("a", 1), ("b", 2)]);
+        map.get_tracked(&"a");
+        map.get_tracked(&"b");
+        let map_rc = Rc::new(RefCell::new(map));
+
+        let a_runs = Rc::new(Cell::new(0));
+        let _effect = effect_sync({
+            let (map, runs) = (map_rc.clone(), a_runs.clone());
+            move || {
+                (*map).borrow().get("a");
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        batch(|| (*map_rc).borrow_mut().extend([("a", 1), ("b", 3)]));
+        assert_eq!(a_runs.get(), 1);
+
+        batch(|| (*map_rc).borrow_mut().extend([("a", 5)]));
+        assert_eq!(a_runs.get(), 2);
+    }
+
+    #[test]
+    fn watch_key_ignores_other_keys() {
+        use crate::batch;
+
+        let map_rc = Rc::new(RefCell::new(ReactiveMap::from_iter([("a", 1), ("b", 2)])));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let stop = ReactiveMap::watch_key(&map_rc, "a", {
+            let seen = seen.clone();
+            move |value: Option<&i32>| seen.borrow_mut().push(value.copied())
+        });
+        assert_eq!(*(*seen).borrow(), [Some(1)]);
+
+        batch(|| (*map_rc).borrow_mut().insert("b", 20));
+        batch(|| (*map_rc).borrow_mut().insert("c", 3));
+        assert_eq!((*seen).borrow().len(), 1);
+
+        batch(|| (*map_rc).borrow_mut().insert("a", 10));
+        batch(|| (*map_rc).borrow_mut().remove("a"));
+        // Missing key: structural changes elsewhere don't call back
+        batch(|| (*map_rc).borrow_mut().remove("c"));
+        batch(|| (*map_rc).borrow_mut().insert("a", 5));
+        assert_eq!(*(*seen).borrow(), [Some(1), Some(10), None, Some(5)]);
+
+        stop();
+        batch(|| (*map_rc).borrow_mut().insert("a", 6));
+        assert_eq!((*seen).borrow().len(), 4);
+    }
+
     #[test]
     fn debug_format() {
         let mut map: ReactiveMap<String, i32> = ReactiveMap::new();
@@ -688,4 +1669,21 @@ mod tests {
         assert!(debug.contains("ReactiveMap"));
         assert!(debug.contains("key"));
     }
+
+    #[test]
+    fn readonly_view_tracks_per_key() {
+        let map = Rc::new(RefCell::new(ReactiveMap::from_iter([("ada", 1), ("bob", 2)])));
+        let view = ReactiveMap::as_readonly(&map);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (view, seen) = (view.clone(), seen.clone());
+            move || seen.borrow_mut().push(view.get(&"ada"))
+        });
+
+        batch(|| map.borrow_mut().insert("bob", 20));
+        batch(|| map.borrow_mut().insert("ada", 10));
+        batch(|| map.borrow_mut().remove("ada"));
+        assert_eq!(*RefCell::borrow(&seen), [Some(1), Some(10), None]);
+        assert_eq!((view.len(), view.keys()), (1, vec!["bob"]));
+    }
 }
@@ -12,9 +12,13 @@
 // ============================================================================
 
 mod map;
+mod projection;
 mod set;
+mod set_ops;
 mod vec;
 
-pub use map::ReactiveMap;
+pub use map::{Entry, IterTracked, KeysTracked, ReactiveMap, ReadonlyReactiveMap};
+pub use projection::DerivedVec;
 pub use set::ReactiveSet;
-pub use vec::ReactiveVec;
+pub use set_ops::DerivedSet;
+pub use vec::{ReactiveVec, ReadonlyReactiveVec};
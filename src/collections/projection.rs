@@ -0,0 +1,429 @@
+// ============================================================================
+// spark-signals - ReactiveVec Projections
+// Live mapped / filtered views of a shared ReactiveVec
+// ============================================================================
+//
+// A derived over a whole list recomputes every element on every change:
+//
+//     let visible = derived(move || items.unwrap_value().into_iter().filter(..).collect());
+//
+// `map_reactive` / `filter_reactive` keep the previous input and output
+// instead. While the list keeps its structure, the per-index write counts
+// tell which items were set, and only those are cloned and passed to `f`.
+// After a structural change they skip the unchanged prefix and suffix of the
+// list, run `f` only for the middle, and splice the result into the output
+// vec. Either way readers of untouched output indices aren't notified.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::collections::ReactiveVec;
+use crate::primitives::effect::effect_sync;
+use crate::primitives::props::UnwrapProp;
+use crate::reactivity::batching::{batch, untrack};
+
+/// Lengths of the common prefix and suffix of two lists (not overlapping).
+fn common_ends<T: PartialEq>(old: &[T], new: &[T]) -> (usize, usize) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (prefix, suffix)
+}
+
+/// What changed in the source since the previous run.
+enum Change {
+    /// Same structure; these indices now hold a different item.
+    Set(Vec<usize>),
+    /// `old` of the previous list was replaced by `new` of the current one.
+    Splice { old: Range<usize>, new: Range<usize> },
+}
+
+/// The source as of the previous run.
+struct Seen<T> {
+    items: Vec<T>,
+    versions: Vec<i32>,
+    structure: Option<i32>,
+}
+
+impl<T: Clone + PartialEq + 'static> Seen<T> {
+    /// Read `source`, tracking its structure and every index, and bring
+    /// `items` up to date. Only changed items are cloned.
+    fn update(&mut self, source: &mut ReactiveVec<T>) -> Option<Change> {
+        let structure = source.structure_version();
+        let len = source.len();
+        let versions: Vec<i32> = (0..len).map(|i| source.index_version(i)).collect();
+        let data = source.raw();
+
+        let change = if self.structure == Some(structure) {
+            let set: Vec<usize> = (0..len)
+                .filter(|&i| versions[i] != self.versions[i] && data[i] != self.items[i])
+                .collect();
+            for &i in &set {
+                self.items[i] = data[i].clone();
+            }
+            (!set.is_empty()).then_some(Change::Set(set))
+        } else {
+            let (prefix, suffix) = common_ends(&self.items, data);
+            if prefix == len && prefix == self.items.len() {
+                None
+            } else {
+                let old = prefix..self.items.len() - suffix;
+                let new = prefix..len - suffix;
+                self.items.splice(old.clone(), data[new.clone()].iter().cloned());
+                Some(Change::Splice { old, new })
+            }
+        };
+
+        self.structure = Some(structure);
+        self.versions = versions;
+        change
+    }
+}
+
+// =============================================================================
+// DERIVED VEC
+// =============================================================================
+
+/// A read-only `ReactiveVec` kept up to date from a source vec.
+/// Created with [`ReactiveVec::map_reactive()`] or
+/// [`ReactiveVec::filter_reactive()`].
+///
+/// Reads are fine-grained like the output vec's own (`get` tracks one
+/// index). The projection stops when the last clone is dropped.
+pub struct DerivedVec<U> {
+    output: Rc<RefCell<ReactiveVec<U>>>,
+    _dispose: Rc<dyn Fn()>,
+}
+
+impl<U> Drop for DerivedVec<U> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self._dispose) == 1 {
+            (self._dispose)();
+        }
+    }
+}
+
+impl<U: Clone + 'static> DerivedVec<U> {
+    /// The element at `index`, tracking that index.
+    pub fn get(&self, index: usize) -> Option<U> {
+        self.output.borrow_mut().get_tracked(index).cloned()
+    }
+
+    /// Number of elements (tracks the length).
+    pub fn len(&self) -> usize {
+        self.output.borrow().len()
+    }
+
+    /// True if there are no elements (tracks the length).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All elements (tracks every index).
+    pub fn to_vec(&self) -> Vec<U> {
+        self.output.unwrap_value()
+    }
+
+    /// Read the output vec with a closure.
+    pub fn with<R>(&self, f: impl FnOnce(&ReactiveVec<U>) -> R) -> R {
+        f(&self.output.borrow())
+    }
+
+    /// The shared output vec, e.g. to project it further.
+    ///
+    /// Writing to it is overwritten by the next update.
+    pub fn output(&self) -> &Rc<RefCell<ReactiveVec<U>>> {
+        &self.output
+    }
+}
+
+impl<U> Clone for DerivedVec<U> {
+    fn clone(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<U: std::fmt::Debug> std::fmt::Debug for DerivedVec<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedVec")
+            .field("items", self.output.borrow().raw())
+            .finish()
+    }
+}
+
+/// Run `project` on every change of `source`, with the source's current
+/// items and an output vec to update.
+fn project<T, U, P>(source: &Rc<RefCell<ReactiveVec<T>>>, mut project: P) -> DerivedVec<U>
+where
+    T: Clone + PartialEq + 'static,
+    U: 'static,
+    P: FnMut(Change, &[T], &mut ReactiveVec<U>) + 'static,
+{
+    let output = Rc::new(RefCell::new(ReactiveVec::new()));
+    let dispose = effect_sync({
+        let (source, output) = (source.clone(), output.clone());
+        let mut seen = Seen { items: Vec::new(), versions: Vec::new(), structure: None };
+        move || {
+            let Some(change) = seen.update(&mut source.borrow_mut()) else {
+                return;
+            };
+            // Batched so readers of the output run after its borrow is released
+            untrack(|| batch(|| project(change, &seen.items, &mut output.borrow_mut())));
+        }
+    });
+
+    let dispose = std::cell::Cell::new(Some(dispose));
+    DerivedVec {
+        output,
+        _dispose: Rc::new(move || {
+            if let Some(dispose) = dispose.take() {
+                dispose();
+            }
+        }),
+    }
+}
+
+// =============================================================================
+// PROJECTIONS
+// =============================================================================
+
+impl<T: Clone + PartialEq + 'static> ReactiveVec<T> {
+    /// A live vec of `f(item)` for every item of `this`.
+    ///
+    /// `f` only runs for items that were `set()` to a new value or, after a
+    /// structural change, for the changed part of the list (between the
+    /// unchanged prefix and suffix). Only those output indices are written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::batch;
+    /// use spark_signals::collections::ReactiveVec;
+    /// use std::cell::{Cell, RefCell};
+    /// use std::rc::Rc;
+    ///
+    /// let items = Rc::new(RefCell::new(ReactiveVec::from_vec(vec![1, 2, 3])));
+    /// let calls = Rc::new(Cell::new(0));
+    /// let squares = ReactiveVec::map_reactive(&items, {
+    ///     let calls = calls.clone();
+    ///     move |n: &i32| {
+    ///         calls.set(calls.get() + 1);
+    ///         n * n
+    ///     }
+    /// });
+    /// assert_eq!(squares.to_vec(), [1, 4, 9]);
+    ///
+    /// batch(|| items.borrow_mut().push(4));
+    /// assert_eq!(squares.to_vec(), [1, 4, 9, 16]);
+    /// assert_eq!(calls.get(), 4); // only the new item was mapped
+    /// ```
+    pub fn map_reactive<U, F>(this: &Rc<RefCell<Self>>, f: F) -> DerivedVec<U>
+    where
+        U: Clone + 'static,
+        F: Fn(&T) -> U + 'static,
+    {
+        project(this, move |change, items, output| match change {
+            Change::Set(indices) => {
+                for i in indices {
+                    output.set(i, f(&items[i]));
+                }
+            }
+            Change::Splice { old, new } => {
+                let mapped: Vec<U> = items[new].iter().map(&f).collect();
+                output.splice(old, mapped);
+            }
+        })
+    }
+
+    /// A live vec of the items of `this` that `predicate` accepts.
+    ///
+    /// `predicate` only runs for items that were set or are in the changed
+    /// part of the list, and only the matching part of the output changes.
+    pub fn filter_reactive<P>(this: &Rc<RefCell<Self>>, predicate: P) -> DerivedVec<T>
+    where
+        P: Fn(&T) -> bool + 'static,
+    {
+        let mut kept: Vec<bool> = Vec::new();
+        project(this, move |change, items, output| match change {
+            Change::Set(indices) => {
+                for i in indices {
+                    let keep = predicate(&items[i]);
+                    let at = kept[..i].iter().filter(|&&k| k).count();
+                    match (kept[i], keep) {
+                        (true, true) => {
+                            output.set(at, items[i].clone());
+                        }
+                        (true, false) => {
+                            output.remove(at);
+                        }
+                        (false, true) => output.insert(at, items[i].clone()),
+                        (false, false) => {}
+                    }
+                    kept[i] = keep;
+                }
+            }
+            Change::Splice { old, new } => {
+                // Output range of the old middle
+                let start = kept[..old.start].iter().filter(|&&k| k).count();
+                let end = start + kept[old.clone()].iter().filter(|&&k| k).count();
+
+                let middle = &items[new];
+                let new_kept: Vec<bool> = middle.iter().map(&predicate).collect();
+                let matching: Vec<T> = middle
+                    .iter()
+                    .zip(&new_kept)
+                    .filter(|(_, keep)| **keep)
+                    .map(|(item, _)| item.clone())
+                    .collect();
+
+                if end > start || !matching.is_empty() {
+                    output.splice(start..end, matching);
+                }
+                kept.splice(old, new_kept);
+            }
+        })
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    type Shared<T> = Rc<RefCell<ReactiveVec<T>>>;
+
+    fn shared<T>(items: Vec<T>) -> Shared<T> {
+        Rc::new(RefCell::new(ReactiveVec::from_vec(items)))
+    }
+
+    fn counting<T, U>(calls: Rc<Cell<u32>>, f: impl Fn(&T) -> U) -> impl Fn(&T) -> U {
+        move |item| {
+            calls.set(calls.get() + 1);
+            f(item)
+        }
+    }
+
+    #[test]
+    fn map_only_recomputes_the_changed_middle() {
+        let items = shared(vec![1, 2, 3, 4]);
+        let calls = Rc::new(Cell::new(0));
+        let doubled = ReactiveVec::map_reactive(&items, counting(calls.clone(), |n: &i32| n * 2));
+        assert_eq!(calls.get(), 4);
+
+        batch(|| {
+            items.borrow_mut().set(2, 30);
+        });
+        assert_eq!(doubled.to_vec(), [2, 4, 60, 8]);
+        assert_eq!(calls.get(), 5);
+
+        batch(|| {
+            items.borrow_mut().insert(0, 0);
+        });
+        assert_eq!(doubled.to_vec(), [0, 2, 4, 60, 8]);
+        assert_eq!(calls.get(), 6);
+
+        batch(|| {
+            items.borrow_mut().drain(1..3);
+        });
+        assert_eq!(doubled.to_vec(), [0, 60, 8]);
+        assert_eq!(calls.get(), 6);
+    }
+
+    #[test]
+    fn map_only_recomputes_the_indices_that_were_set() {
+        let items = shared(vec![1, 2, 3, 4, 5]);
+        let calls = Rc::new(Cell::new(0));
+        let doubled = ReactiveVec::map_reactive(&items, counting(calls.clone(), |n: &i32| n * 2));
+        assert_eq!(calls.get(), 5);
+
+        // Far apart: the middle between them isn't remapped
+        batch(|| {
+            let mut items = items.borrow_mut();
+            items.set(0, 10);
+            items.set(4, 50);
+        });
+        assert_eq!(doubled.to_vec(), [20, 4, 6, 8, 100]);
+        assert_eq!(calls.get(), 7);
+
+        // Writing an equal value maps nothing
+        batch(|| {
+            items.borrow_mut().set(2, 3);
+        });
+        assert_eq!(calls.get(), 7);
+    }
+
+    #[test]
+    fn filter_updates_incrementally() {
+        let items = shared(vec![1, 2, 3, 4, 5, 6]);
+        let calls = Rc::new(Cell::new(0));
+        let even =
+            ReactiveVec::filter_reactive(&items, counting(calls.clone(), |n: &i32| n % 2 == 0));
+        assert_eq!(even.to_vec(), [2, 4, 6]);
+        assert_eq!(calls.get(), 6);
+
+        batch(|| items.borrow_mut().push(8));
+        assert_eq!(even.to_vec(), [2, 4, 6, 8]);
+        assert_eq!(calls.get(), 7);
+
+        // 3 -> 10 becomes even; 4 -> 5 stops being even
+        batch(|| {
+            let mut items = items.borrow_mut();
+            items.set(2, 10);
+            items.set(3, 5);
+        });
+        assert_eq!(even.to_vec(), [2, 10, 6, 8]);
+        assert_eq!(calls.get(), 9);
+
+        // Only the two set indices are tested again
+        batch(|| {
+            let mut items = items.borrow_mut();
+            items.set(0, 12);
+            items.set(6, 9);
+        });
+        assert_eq!(even.to_vec(), [12, 2, 10, 6]);
+        assert_eq!(calls.get(), 11);
+    }
+
+    #[test]
+    fn untouched_output_indices_are_not_notified() {
+        let items = shared(vec![1, 2, 3]);
+        let labels = ReactiveVec::map_reactive(&items, |n: &i32| n.to_string());
+
+        let first_runs = Rc::new(Cell::new(0));
+        let _reader = effect_sync({
+            let (labels, first_runs) = (labels.clone(), first_runs.clone());
+            move || {
+                labels.get(0);
+                first_runs.set(first_runs.get() + 1);
+            }
+        });
+
+        batch(|| {
+            items.borrow_mut().set(2, 9);
+        });
+        assert_eq!(labels.get(2).as_deref(), Some("9"));
+        assert_eq!(first_runs.get(), 1);
+
+        batch(|| items.borrow_mut().push(4));
+        assert_eq!(first_runs.get(), 1);
+
+        batch(|| {
+            items.borrow_mut().set(0, 7);
+        });
+        assert_eq!(labels.get(0).as_deref(), Some("7"));
+        assert_eq!(first_runs.get(), 2);
+    }
+}
@@ -5,15 +5,20 @@
 // ============================================================================
 
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::collections::hash_set::Iter;
 use std::collections::HashSet;
 use std::hash::Hash;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 use crate::core::context::with_context;
 use crate::core::types::{AnySource, SourceInner};
+use crate::reactivity::middleware::{admit_added, middleware_source};
 use crate::reactivity::tracking::{notify_write, track_read};
 
+/// Items touched since a reader last drained it (see `change_log`).
+pub(super) type ChangeLog<T> = Rc<RefCell<Vec<T>>>;
+
 // =============================================================================
 // REACTIVE SET
 // =============================================================================
@@ -63,6 +68,9 @@ where
 
     /// Size signal
     size: Rc<SourceInner<usize>>,
+
+    /// Change logs of live derived sets (`union_with` etc.)
+    change_logs: Vec<Weak<RefCell<Vec<T>>>>,
 }
 
 impl<T> ReactiveSet<T>
@@ -76,6 +84,7 @@ where
             item_signals: std::collections::HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             size: Rc::new(SourceInner::new(0)),
+            change_logs: Vec::new(),
         }
     }
 
@@ -86,10 +95,12 @@ where
             item_signals: std::collections::HashMap::with_capacity(capacity),
             version: Rc::new(SourceInner::new(0)),
             size: Rc::new(SourceInner::new(0)),
+            change_logs: Vec::new(),
         }
     }
 
     /// Create a reactive set from an iterator.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let data: HashSet<T> = iter.into_iter().collect();
         let len = data.len();
@@ -98,6 +109,7 @@ where
             item_signals: std::collections::HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             size: Rc::new(SourceInner::new(len)),
+            change_logs: Vec::new(),
         }
     }
 
@@ -152,6 +164,34 @@ where
         Self::increment(&self.version);
     }
 
+    /// Start recording inserted and removed items into a new log.
+    ///
+    /// The log stops receiving items once its last `Rc` is dropped.
+    pub(super) fn change_log(&mut self) -> ChangeLog<T> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        self.change_logs.push(Rc::downgrade(&log));
+        log
+    }
+
+    /// Record touched items in every live change log.
+    fn log_changes<'a>(&mut self, items: impl IntoIterator<Item = &'a T> + Clone)
+    where
+        T: 'a,
+    {
+        self.change_logs.retain(|log| {
+            let Some(log) = log.upgrade() else {
+                return false;
+            };
+            log.borrow_mut().extend(items.clone().into_iter().cloned());
+            true
+        });
+    }
+
+    /// Track structural changes without reading anything.
+    pub(super) fn track_version(&self) {
+        track_read(self.version.clone() as Rc<dyn AnySource>);
+    }
+
     // =========================================================================
     // SIZE
     // =========================================================================
@@ -235,11 +275,19 @@ where
 
     /// Adds a value to the set.
     ///
-    /// Returns true if the value was newly inserted.
-    pub fn insert(&mut self, item: T) -> bool {
+    /// Returns true if the value was newly inserted, false if it was
+    /// already present or write middleware vetoed it.
+    pub fn insert(&mut self, item: T) -> bool
+    where
+        T: 'static,
+    {
+        let Some(item) = admit_added(middleware_source(&self.version).as_ref(), item) else {
+            return false;
+        };
         let is_new = self.data.insert(item.clone());
 
         if is_new {
+            self.log_changes([&item]);
             let sig = self.get_item_signal(&item);
             Self::set_and_notify_bool(&sig, true);
             self.set_size(self.data.len());
@@ -261,9 +309,10 @@ where
         T: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        let existed = self.data.remove(item);
+        let removed = self.data.take(item);
 
-        if existed {
+        if let Some(removed) = &removed {
+            self.log_changes([removed]);
             // Mark item signal as deleted and remove it
             if let Some(sig) = self.item_signals.remove(item) {
                 Self::set_and_notify_bool(&sig, false);
@@ -273,7 +322,7 @@ where
             self.increment_version();
         }
 
-        existed
+        removed.is_some()
     }
 
     /// Removes a value from the set with exact type.
@@ -281,6 +330,7 @@ where
         let existed = self.data.remove(item);
 
         if existed {
+            self.log_changes([item]);
             // Mark item signal as deleted
             if let Some(sig) = self.item_signals.remove(item) {
                 Self::set_and_notify_bool(&sig, false);
@@ -300,14 +350,15 @@ where
     /// Clears the set, removing all values.
     pub fn clear(&mut self) {
         if !self.data.is_empty() {
+            let items = std::mem::take(&mut self.data);
+            self.log_changes(&items);
+
             // Mark all item signals as deleted
             for sig in self.item_signals.values() {
                 Self::set_and_notify_bool(sig, false);
             }
             self.item_signals.clear();
 
-            self.data.clear();
-
             self.set_size(0);
             self.increment_version();
         }
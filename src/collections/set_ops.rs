@@ -0,0 +1,310 @@
+// ============================================================================
+// spark-signals - ReactiveSet Algebra
+// Live unions, intersections and differences of shared ReactiveSets
+// ============================================================================
+//
+// A derived computing `a.union(&b)` rebuilds the whole result on every
+// insert. `union_with` / `intersection_with` / `difference_with` keep the
+// result in a ReactiveSet instead, and each input records the items it
+// inserts or removes in a change log. On a change only the logged items are
+// re-checked against both inputs, so a mutation costs O(changed items) and
+// only those items' readers are notified.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::collections::ReactiveSet;
+use crate::primitives::effect::effect_sync;
+use crate::reactivity::batching::{batch, untrack};
+
+// =============================================================================
+// DERIVED SET
+// =============================================================================
+
+/// A read-only `ReactiveSet` kept up to date from two source sets.
+/// Created with [`ReactiveSet::union_with()`],
+/// [`ReactiveSet::intersection_with()`] or [`ReactiveSet::difference_with()`].
+///
+/// Reads are fine-grained like the output set's own (`contains` tracks one
+/// item). The result stops updating when the last clone is dropped.
+pub struct DerivedSet<T>
+where
+    T: Eq + Hash + Clone,
+{
+    output: Rc<RefCell<ReactiveSet<T>>>,
+    _dispose: Rc<dyn Fn()>,
+}
+
+impl<T> Drop for DerivedSet<T>
+where
+    T: Eq + Hash + Clone,
+{
+    fn drop(&mut self) {
+        if Rc::strong_count(&self._dispose) == 1 {
+            (self._dispose)();
+        }
+    }
+}
+
+impl<T> DerivedSet<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// True if `item` is in the result, tracking that item.
+    pub fn contains(&self, item: &T) -> bool {
+        self.output.borrow_mut().contains_tracked(item)
+    }
+
+    /// Number of items (tracks the size).
+    pub fn len(&self) -> usize {
+        self.output.borrow().len()
+    }
+
+    /// True if there are no items (tracks the size).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All items (tracks structural changes).
+    pub fn to_set(&self) -> HashSet<T> {
+        self.output.borrow().iter().cloned().collect()
+    }
+
+    /// Read the output set with a closure.
+    pub fn with<R>(&self, f: impl FnOnce(&ReactiveSet<T>) -> R) -> R {
+        f(&self.output.borrow())
+    }
+
+    /// The shared output set, e.g. to combine it further.
+    ///
+    /// Writing to it is overwritten by the next update.
+    pub fn output(&self) -> &Rc<RefCell<ReactiveSet<T>>> {
+        &self.output
+    }
+}
+
+impl<T> Clone for DerivedSet<T>
+where
+    T: Eq + Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for DerivedSet<T>
+where
+    T: Eq + Hash + Clone + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedSet")
+            .field("items", self.output.borrow().raw())
+            .finish()
+    }
+}
+
+/// Keep `{x | keep(x in a, x in b)}` up to date in a new output set.
+fn combine<T, K>(
+    a: &Rc<RefCell<ReactiveSet<T>>>,
+    b: &Rc<RefCell<ReactiveSet<T>>>,
+    keep: K,
+) -> DerivedSet<T>
+where
+    T: Eq + Hash + Clone + 'static,
+    K: Fn(bool, bool) -> bool + 'static,
+{
+    let logs = [a.borrow_mut().change_log(), b.borrow_mut().change_log()];
+
+    let initial: Vec<T> = {
+        let (a, b) = (a.borrow(), b.borrow());
+        let candidates = a.raw().union(b.raw());
+        candidates
+            .filter(|x| keep(a.raw().contains(*x), b.raw().contains(*x)))
+            .cloned()
+            .collect()
+    };
+    let output = Rc::new(RefCell::new(ReactiveSet::from_iter(initial)));
+
+    let dispose = effect_sync({
+        let (a, b, output) = (a.clone(), b.clone(), output.clone());
+        move || {
+            let changes: Vec<(T, bool)> = {
+                let (a, b) = (a.borrow(), b.borrow());
+                a.track_version();
+                b.track_version();
+                logs.iter()
+                    .flat_map(|log| log.take())
+                    .map(|x| {
+                        let kept = keep(a.raw().contains(&x), b.raw().contains(&x));
+                        (x, kept)
+                    })
+                    .collect()
+            };
+
+            // Batched so readers of the output run after its borrow is released
+            untrack(|| {
+                batch(|| {
+                    let mut output = output.borrow_mut();
+                    for (x, kept) in changes {
+                        if kept {
+                            output.insert(x);
+                        } else {
+                            output.remove_exact(&x);
+                        }
+                    }
+                })
+            });
+        }
+    });
+
+    let dispose = Cell::new(Some(dispose));
+    DerivedSet {
+        output,
+        _dispose: Rc::new(move || {
+            if let Some(dispose) = dispose.take() {
+                dispose();
+            }
+        }),
+    }
+}
+
+// =============================================================================
+// SET ALGEBRA
+// =============================================================================
+
+impl<T> ReactiveSet<T>
+where
+    T: Eq + Hash + Clone + 'static,
+{
+    /// A live set of the items in `this` or `other`.
+    ///
+    /// Updated incrementally: each insert or remove on either input re-checks
+    /// only that item. Write to the inputs inside a `batch()` so their borrow
+    /// is released before the update runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::batch;
+    /// use spark_signals::collections::ReactiveSet;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let mine = Rc::new(RefCell::new(ReactiveSet::from_iter(["rust", "ui"])));
+    /// let yours = Rc::new(RefCell::new(ReactiveSet::from_iter(["ui"])));
+    /// let all = ReactiveSet::union_with(&mine, &yours);
+    /// assert_eq!(all.len(), 2);
+    ///
+    /// batch(|| yours.borrow_mut().insert("perf"));
+    /// assert!(all.contains(&"perf"));
+    ///
+    /// // Still in `yours`: stays in the union
+    /// batch(|| mine.borrow_mut().remove("ui"));
+    /// assert!(all.contains(&"ui"));
+    /// ```
+    pub fn union_with(this: &Rc<RefCell<Self>>, other: &Rc<RefCell<Self>>) -> DerivedSet<T> {
+        combine(this, other, |in_a, in_b| in_a || in_b)
+    }
+
+    /// A live set of the items in both `this` and `other`.
+    ///
+    /// Updated incrementally, like [`ReactiveSet::union_with()`].
+    pub fn intersection_with(this: &Rc<RefCell<Self>>, other: &Rc<RefCell<Self>>) -> DerivedSet<T> {
+        combine(this, other, |in_a, in_b| in_a && in_b)
+    }
+
+    /// A live set of the items in `this` but not in `other`.
+    ///
+    /// Updated incrementally, like [`ReactiveSet::union_with()`].
+    pub fn difference_with(this: &Rc<RefCell<Self>>, other: &Rc<RefCell<Self>>) -> DerivedSet<T> {
+        combine(this, other, |in_a, in_b| in_a && !in_b)
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Shared<T> = Rc<RefCell<ReactiveSet<T>>>;
+
+    fn shared<const N: usize>(items: [i32; N]) -> Shared<i32> {
+        Rc::new(RefCell::new(ReactiveSet::from_iter(items)))
+    }
+
+    fn sorted(set: &DerivedSet<i32>) -> Vec<i32> {
+        let mut items: Vec<i32> = set.to_set().into_iter().collect();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn results_follow_both_inputs() {
+        let a = shared([1, 2, 3]);
+        let b = shared([3, 4]);
+        let union = ReactiveSet::union_with(&a, &b);
+        let both = ReactiveSet::intersection_with(&a, &b);
+        let only_a = ReactiveSet::difference_with(&a, &b);
+        assert_eq!(sorted(&union), [1, 2, 3, 4]);
+        assert_eq!(sorted(&both), [3]);
+        assert_eq!(sorted(&only_a), [1, 2]);
+
+        batch(|| {
+            b.borrow_mut().insert(1);
+            a.borrow_mut().remove(&3);
+        });
+        assert_eq!(sorted(&union), [1, 2, 3, 4]);
+        assert_eq!(sorted(&both), [1]);
+        assert_eq!(sorted(&only_a), [2]);
+
+        batch(|| b.borrow_mut().clear());
+        assert_eq!(sorted(&union), [1, 2]);
+        assert!(both.is_empty());
+        assert_eq!(sorted(&only_a), [1, 2]);
+    }
+
+    #[test]
+    fn only_changed_items_are_notified() {
+        let a = shared([1, 2]);
+        let b = shared([2, 3]);
+        let both = ReactiveSet::intersection_with(&a, &b);
+
+        let runs = Rc::new(Cell::new(0));
+        let _reader = effect_sync({
+            let (both, runs) = (both.clone(), runs.clone());
+            move || {
+                both.contains(&2);
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        batch(|| a.borrow_mut().insert(3));
+        assert!(both.contains(&3));
+        assert_eq!(runs.get(), 1);
+
+        batch(|| b.borrow_mut().remove(&2));
+        assert!(!both.contains(&2));
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn dropping_the_result_stops_logging() {
+        let a = shared([1]);
+        let b = shared([]);
+        let union = ReactiveSet::union_with(&a, &b);
+        let output = union.output().clone();
+        drop(union);
+
+        batch(|| a.borrow_mut().insert(2));
+        assert_eq!(output.borrow().raw().len(), 1);
+    }
+}
@@ -4,12 +4,17 @@
 // Rust-specific addition (TypeScript uses array proxies instead)
 // ============================================================================
 
-use std::ops::{Index, IndexMut};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
 use std::rc::Rc;
 use std::slice::{Iter, IterMut};
 
 use crate::core::context::with_context;
 use crate::core::types::{AnySource, SourceInner};
+use crate::primitives::props::UnwrapProp;
+use crate::reactivity::batching::batch;
+use crate::reactivity::middleware::{admit_added, middleware_source, run_write_middleware};
 use crate::reactivity::tracking::{notify_write, track_read};
 
 // =============================================================================
@@ -62,8 +67,14 @@ pub struct ReactiveVec<T> {
 
     /// Length signal
     length: Rc<SourceInner<usize>>,
+
+    /// Order kept by `insert_sorted()`, if created with `new_sorted_by()`
+    compare: Option<Comparator<T>>,
 }
 
+/// Ordering of a sorted vec
+type Comparator<T> = Rc<dyn Fn(&T, &T) -> Ordering>;
+
 impl<T> ReactiveVec<T> {
     /// Create a new empty reactive vec.
     pub fn new() -> Self {
@@ -72,6 +83,7 @@ impl<T> ReactiveVec<T> {
             index_signals: std::collections::HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             length: Rc::new(SourceInner::new(0)),
+            compare: None,
         }
     }
 
@@ -82,6 +94,7 @@ impl<T> ReactiveVec<T> {
             index_signals: std::collections::HashMap::with_capacity(capacity),
             version: Rc::new(SourceInner::new(0)),
             length: Rc::new(SourceInner::new(0)),
+            compare: None,
         }
     }
 
@@ -93,10 +106,12 @@ impl<T> ReactiveVec<T> {
             index_signals: std::collections::HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             length: Rc::new(SourceInner::new(len)),
+            compare: None,
         }
     }
 
     /// Create a reactive vec from an iterator.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let data: Vec<T> = iter.into_iter().collect();
         let len = data.len();
@@ -105,9 +120,43 @@ impl<T> ReactiveVec<T> {
             index_signals: std::collections::HashMap::new(),
             version: Rc::new(SourceInner::new(0)),
             length: Rc::new(SourceInner::new(len)),
+            compare: None,
+        }
+    }
+
+    /// Create an empty vec kept in the order of `compare` by
+    /// [`insert_sorted()`](Self::insert_sorted).
+    ///
+    /// Other writes (`push`, `set`, ...) don't keep the order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveVec;
+    ///
+    /// // Highest score first
+    /// let mut board = ReactiveVec::new_sorted_by(|a: &u32, b: &u32| b.cmp(a));
+    /// board.insert_sorted(50);
+    /// board.insert_sorted(80);
+    /// assert_eq!(board.insert_sorted(60), 1);
+    /// assert_eq!(board.as_slice(), &[80, 60, 50]);
+    /// assert_eq!(board.binary_search(&50), Ok(2));
+    /// ```
+    pub fn new_sorted_by<F>(compare: F) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        Self {
+            compare: Some(Rc::new(compare)),
+            ..Self::new()
         }
     }
 
+    /// True if created with `new_sorted_by()`.
+    pub fn is_sorted_mode(&self) -> bool {
+        self.compare.is_some()
+    }
+
     /// Get or create a signal for an index.
     fn get_index_signal(&mut self, index: usize) -> Rc<SourceInner<i32>> {
         if let Some(sig) = self.index_signals.get(&index) {
@@ -155,11 +204,28 @@ impl<T> ReactiveVec<T> {
 
     /// Notify that indices changed from start onwards.
     fn notify_indices_from(&mut self, start: usize) {
-        for (&idx, sig) in &self.index_signals {
-            if idx >= start {
+        self.notify_range(start..usize::MAX);
+    }
+
+    /// Notify every tracked index in `range`, in one batch.
+    ///
+    /// Only indices someone tracks have a signal, so this costs one step per
+    /// tracked index, however long the range.
+    fn notify_range(&self, range: Range<usize>) {
+        let touched: Vec<&Rc<SourceInner<i32>>> = self
+            .index_signals
+            .iter()
+            .filter(|(idx, _)| range.contains(idx))
+            .map(|(_, sig)| sig)
+            .collect();
+        if touched.is_empty() {
+            return;
+        }
+        batch(|| {
+            for sig in touched {
                 Self::increment(sig);
             }
-        }
+        });
     }
 
     // =========================================================================
@@ -228,6 +294,22 @@ impl<T> ReactiveVec<T> {
         None
     }
 
+    /// How often `index` was written, tracking it (for incremental readers).
+    pub(crate) fn index_version(&mut self, index: usize) -> i32 {
+        let sig = self.get_index_signal(index);
+        track_read(sig.clone() as Rc<dyn AnySource>);
+        sig.get()
+    }
+
+    /// How often the vec changed structure, tracking it.
+    ///
+    /// Writes that keep every index in place (`set`) leave it alone, so while
+    /// it holds, `index_version` tells which indices changed.
+    pub(crate) fn structure_version(&self) -> i32 {
+        track_read(self.version.clone() as Rc<dyn AnySource>);
+        self.version.get()
+    }
+
     /// Returns a mutable reference to the element at the given index.
     ///
     /// **Note**: Mutations through this reference won't automatically trigger updates.
@@ -257,12 +339,18 @@ impl<T> ReactiveVec<T> {
 
     /// Sets the value at the given index.
     ///
-    /// Returns the old value if the index was valid.
+    /// Returns the old value if the index was valid, or `value` itself if
+    /// write middleware vetoed it.
     /// Panics if the index is out of bounds.
-    pub fn set(&mut self, index: usize, value: T) -> T
+    pub fn set(&mut self, index: usize, mut value: T) -> T
     where
         T: 'static,
     {
+        if let Some(source) = middleware_source(&self.version) {
+            if !run_write_middleware(&source, Some(&self.data[index]), &mut value) {
+                return value;
+            }
+        }
         let old = std::mem::replace(&mut self.data[index], value);
         self.notify_index(index);
         old
@@ -291,6 +379,9 @@ impl<T> ReactiveVec<T> {
     where
         T: 'static,
     {
+        let Some(value) = admit_added(middleware_source(&self.version).as_ref(), value) else {
+            return;
+        };
         let new_len = self.data.len() + 1;
         self.data.push(value);
 
@@ -336,6 +427,16 @@ impl<T> ReactiveVec<T> {
     /// # Panics
     /// Panics if `index > len`.
     pub fn insert(&mut self, index: usize, value: T)
+    where
+        T: 'static,
+    {
+        if let Some(value) = admit_added(middleware_source(&self.version).as_ref(), value) {
+            self.insert_admitted(index, value);
+        }
+    }
+
+    /// `insert()` after write middleware has seen the value.
+    fn insert_admitted(&mut self, index: usize, value: T)
     where
         T: 'static,
     {
@@ -488,14 +589,14 @@ impl<T> ReactiveVec<T> {
         T: 'static,
     {
         let start_len = self.data.len();
-        self.data.extend(iter);
+        let source = middleware_source(&self.version);
+        self.data
+            .extend(iter.into_iter().filter_map(|value| admit_added(source.as_ref(), value)));
         let new_len = self.data.len();
 
         if new_len != start_len {
             // Notify new indices
-            for i in start_len..new_len {
-                self.notify_index(i);
-            }
+            self.notify_range(start_len..new_len);
 
             self.set_length(new_len);
             self.increment_version();
@@ -507,21 +608,99 @@ impl<T> ReactiveVec<T> {
     where
         T: 'static,
     {
+        if let Some(source) = middleware_source(&self.version) {
+            other.retain_mut(|value| run_write_middleware(&source, None, value));
+        }
         if !other.is_empty() {
             let start_len = self.data.len();
             self.data.append(other);
             let new_len = self.data.len();
 
             // Notify new indices
-            for i in start_len..new_len {
-                self.notify_index(i);
-            }
+            self.notify_range(start_len..new_len);
 
             self.set_length(new_len);
             self.increment_version();
         }
     }
 
+    // =========================================================================
+    // SPLICE / DRAIN
+    // =========================================================================
+
+    /// Replaces `range` with the items of `replace_with`, returning the
+    /// removed items.
+    ///
+    /// Runs in one batch: tracked indices from the start of the range are
+    /// notified (only the range itself if the length didn't change), then
+    /// length and version once.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveVec;
+    ///
+    /// let mut items = ReactiveVec::from_vec(vec![1, 2, 3, 4]);
+    /// let removed: Vec<_> = items.splice(1..3, [20, 25, 30]).collect();
+    /// assert_eq!(removed, [2, 3]);
+    /// assert_eq!(items.as_slice(), &[1, 20, 25, 30, 4]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> std::vec::IntoIter<T>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+        T: 'static,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let old_len = self.data.len();
+        let source = middleware_source(&self.version);
+        let replace_with = replace_with
+            .into_iter()
+            .filter_map(|value| admit_added(source.as_ref(), value));
+        let removed: Vec<T> = self.data.splice(range, replace_with).collect();
+        let new_len = self.data.len();
+
+        if new_len == old_len {
+            if !removed.is_empty() {
+                // Replaced in place: nothing shifted
+                batch(|| {
+                    self.notify_range(start..start + removed.len());
+                    self.increment_version();
+                });
+            }
+        } else {
+            batch(|| {
+                self.notify_range(start..usize::MAX);
+                self.index_signals.retain(|&idx, _| idx < new_len);
+                self.set_length(new_len);
+                self.increment_version();
+            });
+        }
+
+        removed.into_iter()
+    }
+
+    /// Removes `range` from the vec, returning the removed items.
+    ///
+    /// Notifies like [`splice`](Self::splice), in one batch.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    pub fn drain<R>(&mut self, range: R) -> std::vec::IntoIter<T>
+    where
+        R: RangeBounds<usize>,
+        T: 'static,
+    {
+        self.splice(range, std::iter::empty())
+    }
+
     // =========================================================================
     // ITERATION (tracks version)
     // =========================================================================
@@ -604,6 +783,63 @@ impl<T> ReactiveVec<T> {
         }
     }
 
+    /// Inserts `value` after every element that doesn't order after it,
+    /// returning its index.
+    ///
+    /// Only the inserted index and the ones after it are notified. If write
+    /// middleware vetoes the value, nothing is inserted and the index it
+    /// would have taken is returned.
+    ///
+    /// # Panics
+    /// Panics if the vec wasn't created with `new_sorted_by()`.
+    pub fn insert_sorted(&mut self, mut value: T) -> usize
+    where
+        T: 'static,
+    {
+        let compare = self.comparator();
+        // Place the value as middleware transformed it
+        let admitted = match middleware_source(&self.version) {
+            Some(source) => run_write_middleware(&source, None, &mut value),
+            None => true,
+        };
+        let index = self
+            .data
+            .partition_point(|item| compare(item, &value) != Ordering::Greater);
+        if admitted {
+            self.insert_admitted(index, value);
+        }
+        index
+    }
+
+    /// Binary searches a sorted vec for `value` with its comparator.
+    ///
+    /// Tracks the version signal, like iteration. Returns `Ok(index)` of a
+    /// matching element or `Err(index)` where it would be inserted.
+    ///
+    /// # Panics
+    /// Panics if the vec wasn't created with `new_sorted_by()`.
+    pub fn binary_search(&self, value: &T) -> Result<usize, usize> {
+        let compare = self.comparator();
+        self.binary_search_by(|item| compare(item, value))
+    }
+
+    /// Binary searches with a custom comparator (see `slice::binary_search_by`).
+    ///
+    /// Tracks the version signal.
+    pub fn binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        track_read(self.version.clone() as Rc<dyn AnySource>);
+        self.data.binary_search_by(f)
+    }
+
+    fn comparator(&self) -> Comparator<T> {
+        self.compare
+            .clone()
+            .expect("ReactiveVec is not sorted; create it with new_sorted_by()")
+    }
+
     /// Sorts the vec.
     pub fn sort(&mut self)
     where
@@ -668,7 +904,10 @@ impl<T> Default for ReactiveVec<T> {
 impl<T: Clone> Clone for ReactiveVec<T> {
     fn clone(&self) -> Self {
         // Create a new reactive vec with same data but fresh signals
-        Self::from_vec(self.data.clone())
+        Self {
+            compare: self.compare.clone(),
+            ..Self::from_vec(self.data.clone())
+        }
     }
 }
 
@@ -681,6 +920,120 @@ impl<T: std::fmt::Debug> std::fmt::Debug for ReactiveVec<T> {
     }
 }
 
+/// Lets a shared vec drive list helpers such as `keyed_each()`.
+///
+/// Reads every element, tracking the version and each index.
+impl<T: Clone> UnwrapProp<Vec<T>> for Rc<RefCell<ReactiveVec<T>>> {
+    fn unwrap_value(&self) -> Vec<T> {
+        let mut vec = self.borrow_mut();
+        let len = vec.iter().len();
+        (0..len)
+            .filter_map(|i| vec.get_tracked(i).cloned())
+            .collect()
+    }
+}
+
+// =============================================================================
+// READONLY VIEW
+// =============================================================================
+
+impl<T> ReactiveVec<T> {
+    /// A view of a shared vec that can read it (tracked) but not write it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::collections::ReactiveVec;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let todos = Rc::new(RefCell::new(ReactiveVec::from_vec(vec!["write"])));
+    /// let view = ReactiveVec::as_readonly(&todos);
+    ///
+    /// todos.borrow_mut().push("ship");
+    /// assert_eq!(view.get(1), Some("ship"));
+    /// assert_eq!(view.to_vec(), ["write", "ship"]);
+    /// ```
+    pub fn as_readonly(this: &Rc<RefCell<Self>>) -> ReadonlyReactiveVec<T> {
+        ReadonlyReactiveVec { vec: this.clone() }
+    }
+}
+
+/// Read-only handle to a shared [`ReactiveVec`], from
+/// [`ReactiveVec::as_readonly()`].
+///
+/// Every read tracks like the vec's own. Clones share the vec.
+pub struct ReadonlyReactiveVec<T> {
+    vec: Rc<RefCell<ReactiveVec<T>>>,
+}
+
+impl<T> ReadonlyReactiveVec<T> {
+    /// Number of elements (tracks the length signal).
+    pub fn len(&self) -> usize {
+        self.vec.borrow().len()
+    }
+
+    /// True if the vec is empty (tracks the length signal).
+    pub fn is_empty(&self) -> bool {
+        self.vec.borrow().is_empty()
+    }
+
+    /// A clone of the element at `index`.
+    ///
+    /// Tracks just that index while it exists, like `get_tracked()`.
+    pub fn get(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.vec.borrow_mut().get_tracked(index).cloned()
+    }
+
+    /// A clone of every element (tracks the version signal).
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.vec.borrow().as_slice().to_vec()
+    }
+
+    /// Read the vec through a shared reference.
+    ///
+    /// Don't call the view's other methods from inside `f`: the vec is
+    /// borrowed until it returns.
+    pub fn with<R>(&self, f: impl FnOnce(&ReactiveVec<T>) -> R) -> R {
+        f(&self.vec.borrow())
+    }
+}
+
+impl<T> Clone for ReadonlyReactiveVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec.clone(),
+        }
+    }
+}
+
+impl<T> From<Rc<RefCell<ReactiveVec<T>>>> for ReadonlyReactiveVec<T> {
+    fn from(vec: Rc<RefCell<ReactiveVec<T>>>) -> Self {
+        Self { vec }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for ReadonlyReactiveVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ReadonlyReactiveVec")
+            .field(&*self.vec.borrow())
+            .finish()
+    }
+}
+
+/// Drives list helpers like the shared vec it views.
+impl<T: Clone> UnwrapProp<Vec<T>> for ReadonlyReactiveVec<T> {
+    fn unwrap_value(&self) -> Vec<T> {
+        self.vec.unwrap_value()
+    }
+}
+
 impl<T> Index<usize> for ReactiveVec<T> {
     type Output = T;
 
@@ -943,4 +1296,113 @@ mod tests {
         assert!(debug.contains("ReactiveVec"));
         assert!(debug.contains("[1, 2, 3]"));
     }
+
+    #[test]
+    fn splice_and_drain() {
+        let mut vec = ReactiveVec::from_vec(vec![1, 2, 3, 4, 5]);
+
+        let removed: Vec<_> = vec.splice(1..=2, [7, 8, 9]).collect();
+        assert_eq!(removed, vec![2, 3]);
+        assert_eq!(vec.raw(), &vec![1, 7, 8, 9, 4, 5]);
+
+        let drained: Vec<_> = vec.drain(..2).collect();
+        assert_eq!(drained, vec![1, 7]);
+        assert_eq!(vec.raw(), &vec![8, 9, 4, 5]);
+
+        assert_eq!(vec.drain(2..).count(), 2);
+        assert_eq!(vec.len(), 2);
+    }
+
+    #[test]
+    fn splice_notifies_only_affected_indices_once() {
+        let vec_rc = Rc::new(RefCell::new(ReactiveVec::from_vec(vec![0, 1, 2, 3])));
+        let runs = Rc::new(RefCell::new(Vec::new()));
+
+        let track = |index: usize| {
+            let (vec, runs) = (vec_rc.clone(), runs.clone());
+            effect_sync(move || {
+                (*vec).borrow_mut().get_tracked(index);
+                (*runs).borrow_mut().push(index);
+            })
+        };
+        let _first = track(0);
+        let _last = track(3);
+        let lengths = Rc::new(Cell::new(0));
+        let _length = effect_sync({
+            let (vec, lengths) = (vec_rc.clone(), lengths.clone());
+            move || {
+                (*vec).borrow().len();
+                lengths.set(lengths.get() + 1);
+            }
+        });
+        (*runs).borrow_mut().clear();
+
+        // 100 items in the middle: index 0 is untouched, index 3 shifts
+        batch(|| {
+            (*vec_rc).borrow_mut().splice(2..2, 100..200);
+        });
+        assert_eq!(*(*runs).borrow(), vec![3]);
+        assert_eq!(lengths.get(), 2);
+
+        // Same length: only the replaced index, no length change
+        (*runs).borrow_mut().clear();
+        batch(|| {
+            (*vec_rc).borrow_mut().splice(3..4, [42]);
+        });
+        assert_eq!(*(*runs).borrow(), vec![3]);
+        assert_eq!(lengths.get(), 2);
+        assert_eq!((*vec_rc).borrow().raw()[3], 42);
+    }
+
+    #[test]
+    fn insert_sorted_notifies_only_the_suffix() {
+        let board = ReactiveVec::new_sorted_by(|a: &u32, b: &u32| b.cmp(a));
+        let vec_rc = Rc::new(RefCell::new(board));
+        for score in [30, 90, 60] {
+            (*vec_rc).borrow_mut().insert_sorted(score);
+        }
+        assert_eq!((*vec_rc).borrow().as_slice(), &[90, 60, 30]);
+
+        let runs = Rc::new(RefCell::new(Vec::new()));
+        let track = |index: usize| {
+            let (vec, runs) = (vec_rc.clone(), runs.clone());
+            effect_sync(move || {
+                (*vec).borrow_mut().get_tracked(index);
+                (*runs).borrow_mut().push(index);
+            })
+        };
+        let _top = track(0);
+        let _third = track(2);
+        let found = Rc::new(Cell::new(Err(0)));
+        let _search = effect_sync({
+            let (vec, found) = (vec_rc.clone(), found.clone());
+            move || found.set((*vec).borrow().binary_search(&75))
+        });
+        assert_eq!(found.get(), Err(1));
+        (*runs).borrow_mut().clear();
+
+        // Ties go after equal elements
+        let first = batch(|| (*vec_rc).borrow_mut().insert_sorted(75));
+        let second = batch(|| (*vec_rc).borrow_mut().insert_sorted(75));
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(*(*runs).borrow(), vec![2, 2]);
+        assert!(matches!(found.get(), Ok(1 | 2)));
+        assert!((*vec_rc).borrow().clone().is_sorted_mode());
+    }
+
+    #[test]
+    fn readonly_view_reads_shared_vec() {
+        let vec = Rc::new(RefCell::new(ReactiveVec::from_vec(vec![1, 2])));
+        let view = ReadonlyReactiveVec::from(vec.clone());
+        let total = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (view, total) = (view.clone(), total.clone());
+            move || total.set(view.to_vec().iter().sum())
+        });
+
+        batch(|| vec.borrow_mut().push(3));
+        assert_eq!(total.get(), 6);
+        assert_eq!((view.get(2), view.get(3), view.is_empty()), (Some(3), None, false));
+        assert_eq!(view.with(|v| v.last().copied()), Some(3));
+    }
 }
@@ -0,0 +1,312 @@
+// ============================================================================
+// spark-signals - Runtime Configuration
+// Tuning knobs for the reactive runtime in one place
+// ============================================================================
+//
+// Two layers:
+// - `init(config)` sets the process-wide defaults every thread starts from
+// - `set_thread_config(config)` overrides them for the current thread (the
+//   reactive graph is per-thread, so most embedders only need one of these)
+//
+// The override lives in the reactive context. A `Runtime` has its own
+// (`Runtime::set_config()`), and inside `Runtime::enter()` only it and
+// `init()` apply. Shorthand setters like `set_max_update_depth()` change
+// whichever context is current.
+//
+// The runtime reads the effective config where it needs a knob, so changes
+// apply from the next flush / effect creation / panic on. Float signals
+// capture `float_equals` when they are created.
+// ============================================================================
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::core::context::{with_context, with_thread_context};
+use crate::primitives::effect::{EffectErrorHook, PanicPolicy};
+use crate::primitives::time::Clock;
+use crate::reactivity::equality::FloatEquals;
+use crate::reactivity::scheduling::Scheduler;
+
+/// Flush passes before a flush panics with "Maximum update depth exceeded"
+pub const DEFAULT_MAX_UPDATE_DEPTH: u32 = 1000;
+
+/// Runtime tuning knobs.
+///
+/// Build one from the defaults so new knobs don't break your code:
+///
+/// ```
+/// use spark_signals::{init, Config};
+///
+/// init(Config {
+///     max_update_depth: 200,
+///     max_effect_depth: Some(64),
+///     ..Config::default()
+/// });
+/// # init(Config::default());
+/// ```
+#[derive(Clone)]
+pub struct Config {
+    /// Flush passes (effects re-triggering effects) before a flush gives up
+    /// and panics. Default: 1000.
+    pub max_update_depth: u32,
+
+    /// Maximum nesting depth of the effect tree, root effects being depth 1.
+    /// Default: unlimited. See `set_max_effect_depth()`.
+    pub max_effect_depth: Option<usize>,
+
+    /// Maximum number of child effects per effect. Default: unlimited.
+    /// See `set_max_children_per_effect()`.
+    pub max_children_per_effect: Option<usize>,
+
+    /// How `signal_f64()` / `signal_f32()` compare values. Each signal
+    /// captures the mode when it is created. Default: exact.
+    pub float_equals: FloatEquals,
+
+    /// What a panicking effect does to the flush. Default:
+    /// `PanicPolicy::Report`.
+    pub effect_panics: PanicPolicy,
+
+    /// Handler every effect panic is reported to, ahead of the thread's
+    /// `on_effect_error()` handlers (to log it, say). Default: none.
+    pub on_effect_error: Option<EffectErrorHook>,
+
+    /// Clock timers read. Default: none, meaning wall-clock time
+    /// (`SystemClock`). See `set_clock()`.
+    pub clock: Option<Arc<dyn Clock>>,
+
+    /// When queued effects run. Default: `Scheduler::Immediate`.
+    pub scheduler: Scheduler,
+
+    /// Wall-clock time one flush may spend running effects. Once it is
+    /// used up, the effects still queued wait for the next flush (the host
+    /// is asked for one if it installed a flush request). At least one
+    /// effect runs per flush. Ignored on wasm32, which has no clock.
+    /// Default: none (run until settled).
+    pub flush_budget: Option<Duration>,
+}
+
+impl Config {
+    /// The built-in defaults.
+    pub const DEFAULT: Config = Config {
+        max_update_depth: DEFAULT_MAX_UPDATE_DEPTH,
+        max_effect_depth: None,
+        max_children_per_effect: None,
+        float_equals: FloatEquals::Exact,
+        effect_panics: PanicPolicy::Report,
+        on_effect_error: None,
+        clock: None,
+        scheduler: Scheduler::Immediate,
+        flush_budget: None,
+    };
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("max_update_depth", &self.max_update_depth)
+            .field("max_effect_depth", &self.max_effect_depth)
+            .field("max_children_per_effect", &self.max_children_per_effect)
+            .field("float_equals", &self.float_equals)
+            .field("effect_panics", &self.effect_panics)
+            .field("on_effect_error", &self.on_effect_error.as_ref().map(|_| ".."))
+            .field("clock", &self.clock.as_ref().map(|_| ".."))
+            .field("scheduler", &self.scheduler)
+            .field("flush_budget", &self.flush_budget)
+            .finish()
+    }
+}
+
+/// Handlers and clocks compare by identity.
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T: ?Sized>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+        }
+
+        self.max_update_depth == other.max_update_depth
+            && self.max_effect_depth == other.max_effect_depth
+            && self.max_children_per_effect == other.max_children_per_effect
+            && self.float_equals == other.float_equals
+            && self.effect_panics == other.effect_panics
+            && same(&self.on_effect_error, &other.on_effect_error)
+            && same(&self.clock, &other.clock)
+            && self.scheduler == other.scheduler
+            && self.flush_budget == other.flush_budget
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static GLOBAL_CONFIG: RwLock<Config> = RwLock::new(Config::DEFAULT);
+
+// =============================================================================
+// PUBLIC API
+// =============================================================================
+
+/// Set the process-wide configuration.
+///
+/// Applies to every thread without a `set_thread_config()` override.
+pub fn init(config: Config) {
+    *GLOBAL_CONFIG.write().unwrap_or_else(|e| e.into_inner()) = config;
+}
+
+/// Override the configuration for the current thread only.
+pub fn set_thread_config(config: Config) {
+    with_thread_context(|ctx| *ctx.config.borrow_mut() = Some(config));
+}
+
+/// Drop the current thread's override; it follows `init()` again.
+pub fn clear_thread_config() {
+    with_thread_context(|ctx| *ctx.config.borrow_mut() = None);
+}
+
+/// The configuration in effect in the current context: the thread's, or
+/// the entered `Runtime`'s.
+pub fn current_config() -> Config {
+    with_config(Config::clone)
+}
+
+/// Read the effective configuration without cloning it.
+pub(crate) fn with_config<R>(f: impl FnOnce(&Config) -> R) -> R {
+    with_context(|ctx| match ctx.config.borrow().as_ref() {
+        Some(config) => f(config),
+        None => f(&GLOBAL_CONFIG.read().unwrap_or_else(|e| e.into_inner())),
+    })
+}
+
+/// Change one knob for the current context, starting from the effective
+/// config.
+pub(crate) fn update_config(f: impl FnOnce(&mut Config)) {
+    let mut config = current_config();
+    f(&mut config);
+    with_context(|ctx| *ctx.config.borrow_mut() = Some(config));
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thread_override_shadows_global() {
+        // Tests share the process-wide config; only touch this thread's
+        assert_eq!(current_config().max_update_depth, DEFAULT_MAX_UPDATE_DEPTH);
+
+        set_thread_config(Config {
+            max_update_depth: 5,
+            ..Config::default()
+        });
+        assert_eq!(current_config().max_update_depth, 5);
+
+        update_config(|c| c.max_effect_depth = Some(3));
+        assert_eq!(current_config().max_update_depth, 5);
+        assert_eq!(current_config().max_effect_depth, Some(3));
+
+        clear_thread_config();
+        assert_eq!(current_config(), Config::default());
+    }
+
+    #[test]
+    fn max_update_depth_limits_flush_passes() {
+        use crate::primitives::effect::effect;
+        use crate::primitives::signal::signal;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        set_thread_config(Config {
+            max_update_depth: 10,
+            ..Config::default()
+        });
+
+        // Self-invalidating effect: bounded only by the limit
+        let count = signal(0);
+        let _dispose = effect({
+            let count = count.clone();
+            move || {
+                let n = count.get();
+                count.set(n + 1);
+            }
+        });
+        let result = catch_unwind(AssertUnwindSafe(|| count.set(0)));
+        clear_thread_config();
+
+        let payload = result.unwrap_err();
+        let message = payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_default();
+        assert!(message.contains("Maximum update depth exceeded"));
+        assert!(count.get() <= 12, "ran {} times", count.get());
+    }
+
+    #[test]
+    fn panic_policy_decides_whether_effect_panics_propagate() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::sync::Mutex;
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let hook: EffectErrorHook = Arc::new({
+            let reported = reported.clone();
+            move |error| reported.lock().unwrap().push(error.message.clone())
+        });
+        let fail = signal(false);
+        let _failing = effect_sync({
+            let fail = fail.clone();
+            move || assert!(!fail.get(), "boom")
+        });
+        let trigger = |policy, hook: Option<EffectErrorHook>| {
+            set_thread_config(Config {
+                effect_panics: policy,
+                on_effect_error: hook,
+                ..Config::default()
+            });
+            let result = catch_unwind(AssertUnwindSafe(|| fail.set(true)));
+            fail.set_untracked(false);
+            clear_thread_config();
+            result.is_ok()
+        };
+
+        assert!(trigger(PanicPolicy::Report, Some(hook.clone())));
+        assert!(!trigger(PanicPolicy::Report, None));
+        assert!(!trigger(PanicPolicy::Propagate, Some(hook.clone())));
+        assert!(trigger(PanicPolicy::Contain, None));
+        assert_eq!(*reported.lock().unwrap(), ["boom", "boom"]);
+    }
+
+    #[test]
+    fn manual_scheduler_waits_for_flush() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::scheduling::flush_sync;
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let count = signal(0);
+        let seen = Rc::new(Cell::new(-1));
+        let _watch = effect_sync({
+            let (count, seen) = (count.clone(), seen.clone());
+            move || seen.set(count.get())
+        });
+        assert_eq!(seen.get(), 0);
+
+        update_config(|c| c.scheduler = Scheduler::Manual);
+        count.set(1);
+        count.set(2);
+        assert_eq!(seen.get(), 0);
+        flush_sync();
+        clear_thread_config();
+        assert_eq!(seen.get(), 2);
+    }
+}
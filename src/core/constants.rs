@@ -79,6 +79,15 @@ pub const INSPECT_EFFECT: u32 = 1 << 18;
 /// Reaction is a repeater (inline write-through forwarding node)
 pub const REPEATER: u32 = 1 << 19;
 
+/// Effect runs in the idle lane (only on `flush_idle()`)
+pub const IDLE_EFFECT: u32 = 1 << 20;
+
+/// Effect has a fixed dependency list and runs without tracking
+pub const PINNED_DEPS: u32 = 1 << 21;
+
+/// Source has `subscribe()` callbacks (writes look up its listeners)
+pub const HAS_SUBSCRIBERS: u32 = 1 << 22;
+
 // =============================================================================
 // STATUS MASK (for clearing status bits)
 // =============================================================================
@@ -86,6 +95,146 @@ pub const REPEATER: u32 = 1 << 19;
 /// Mask to clear all status bits (CLEAN, DIRTY, MAYBE_DIRTY)
 pub const STATUS_MASK: u32 = !(DIRTY | MAYBE_DIRTY | CLEAN);
 
+// =============================================================================
+// FLAG DECODING (for logs, panics, and FFI tooling)
+// =============================================================================
+
+/// Every named flag with its name, in bit order.
+pub const FLAG_NAMES: &[(u32, &str)] = &[
+    (SOURCE, "SOURCE"),
+    (DERIVED, "DERIVED"),
+    (EFFECT, "EFFECT"),
+    (RENDER_EFFECT, "RENDER_EFFECT"),
+    (ROOT_EFFECT, "ROOT_EFFECT"),
+    (BRANCH_EFFECT, "BRANCH_EFFECT"),
+    (USER_EFFECT, "USER_EFFECT"),
+    (BLOCK_EFFECT, "BLOCK_EFFECT"),
+    (UNOWNED, "UNOWNED"),
+    (DISCONNECTED, "DISCONNECTED"),
+    (CLEAN, "CLEAN"),
+    (DIRTY, "DIRTY"),
+    (MAYBE_DIRTY, "MAYBE_DIRTY"),
+    (REACTION_IS_UPDATING, "REACTION_IS_UPDATING"),
+    (DESTROYED, "DESTROYED"),
+    (INERT, "INERT"),
+    (EFFECT_RAN, "EFFECT_RAN"),
+    (EFFECT_PRESERVED, "EFFECT_PRESERVED"),
+    (INSPECT_EFFECT, "INSPECT_EFFECT"),
+    (REPEATER, "REPEATER"),
+    (IDLE_EFFECT, "IDLE_EFFECT"),
+    (PINNED_DEPS, "PINNED_DEPS"),
+    (HAS_SUBSCRIBERS, "HAS_SUBSCRIBERS"),
+];
+
+/// Name of a single flag bit, if it is a known flag.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::constants::{flag_name, DIRTY};
+///
+/// assert_eq!(flag_name(DIRTY), Some("DIRTY"));
+/// assert_eq!(flag_name(1 << 31), None);
+/// ```
+pub fn flag_name(flag: u32) -> Option<&'static str> {
+    FLAG_NAMES
+        .iter()
+        .find(|(bit, _)| *bit == flag)
+        .map(|(_, name)| *name)
+}
+
+/// A decoded flags bitmask.
+///
+/// Displays as the `|`-separated names of the set flags, in bit order.
+/// Bits that don't correspond to a known flag are shown as a trailing hex
+/// value so nothing is silently dropped.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FlagSet(u32);
+
+impl FlagSet {
+    /// The raw bitmask.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// True if every bit in `flag` is set.
+    pub fn contains(&self, flag: u32) -> bool {
+        (self.0 & flag) == flag
+    }
+
+    /// True if no bits are set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Names of the known flags that are set, in bit order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        FLAG_NAMES
+            .iter()
+            .filter(|(bit, _)| (self.0 & bit) != 0)
+            .map(|(_, name)| *name)
+    }
+
+    /// Bits that don't correspond to any known flag.
+    pub fn unknown_bits(&self) -> u32 {
+        FLAG_NAMES.iter().fold(self.0, |rest, (bit, _)| rest & !bit)
+    }
+}
+
+impl std::fmt::Display for FlagSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("0");
+        }
+
+        let mut first = true;
+        for name in self.names() {
+            if !first {
+                f.write_str("|")?;
+            }
+            f.write_str(name)?;
+            first = false;
+        }
+
+        let unknown = self.unknown_bits();
+        if unknown != 0 {
+            if !first {
+                f.write_str("|")?;
+            }
+            write!(f, "{:#x}", unknown)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for FlagSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FlagSet({})", self)
+    }
+}
+
+impl From<u32> for FlagSet {
+    fn from(bits: u32) -> Self {
+        FlagSet(bits)
+    }
+}
+
+/// Decode a raw flags bitmask into a displayable `FlagSet`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::constants::{decode_flags, DERIVED, MAYBE_DIRTY, SOURCE};
+///
+/// let flags = decode_flags(DERIVED | SOURCE | MAYBE_DIRTY);
+/// assert_eq!(flags.to_string(), "SOURCE|DERIVED|MAYBE_DIRTY");
+/// assert!(flags.contains(DERIVED));
+/// ```
+pub fn decode_flags(flags: u32) -> FlagSet {
+    FlagSet(flags)
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -118,6 +267,7 @@ mod tests {
             EFFECT_PRESERVED,
             INSPECT_EFFECT,
             REPEATER,
+            IDLE_EFFECT,
         ];
 
         for (i, &a) in all_flags.iter().enumerate() {
@@ -172,4 +322,30 @@ mod tests {
         assert_eq!(flags & CLEAN, 0);
         assert_ne!(flags & DIRTY, 0);
     }
+
+    #[test]
+    fn flag_names_cover_all_flags() {
+        let all = FLAG_NAMES.iter().fold(0, |acc, (bit, _)| acc | bit);
+        assert_eq!(all, (1 << 23) - 1);
+        assert_eq!(FLAG_NAMES.len(), 23);
+    }
+
+    #[test]
+    fn decode_flags_display() {
+        assert_eq!(decode_flags(DERIVED | SOURCE | MAYBE_DIRTY).to_string(), "SOURCE|DERIVED|MAYBE_DIRTY");
+        assert_eq!(decode_flags(0).to_string(), "0");
+        assert_eq!(decode_flags(EFFECT | (1 << 30)).to_string(), "EFFECT|0x40000000");
+        assert_eq!(format!("{:?}", decode_flags(CLEAN)), "FlagSet(CLEAN)");
+    }
+
+    #[test]
+    fn decode_flags_queries() {
+        let set = decode_flags(EFFECT | USER_EFFECT | DIRTY);
+        assert!(set.contains(EFFECT | DIRTY));
+        assert!(!set.contains(CLEAN));
+        assert_eq!(set.bits(), EFFECT | USER_EFFECT | DIRTY);
+        assert_eq!(set.names().collect::<Vec<_>>(), vec!["EFFECT", "USER_EFFECT", "DIRTY"]);
+        assert_eq!(set.unknown_bits(), 0);
+        assert!(decode_flags(0).is_empty());
+    }
 }
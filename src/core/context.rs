@@ -4,9 +4,98 @@
 // ============================================================================
 
 use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::{Rc, Weak};
 
-use super::types::{AnyReaction, AnySource};
+use super::config::Config;
+use super::types::{AnyReaction, AnySource, DepList};
+use crate::debug::graph::Registry;
+#[cfg(feature = "debug-deps")]
+use crate::primitives::effect::EffectInner;
+use crate::primitives::effect::ErrorHandler;
+use crate::primitives::incremental::IncrementalTask;
+use crate::primitives::memo::{MemoEntry, MemoKey};
+use crate::primitives::repeater::RepeaterInner;
+use crate::primitives::scope::EffectScopeInner;
+use crate::primitives::subscribe::Subscribers;
+#[cfg(feature = "profiling")]
+use crate::profiling::Profile;
+use crate::reactivity::batching::Staged;
+use crate::reactivity::middleware::WriteMiddleware;
+use crate::reactivity::scheduling::FlushHooks;
+use crate::record::RecorderState;
+use crate::shared::ArraySources;
+
+// =============================================================================
+// REACTION QUEUE
+// =============================================================================
+
+/// Reactions waiting to run, each queued at most once.
+///
+/// Entries are keyed by address, so queueing a reaction that is already
+/// waiting does nothing. `take()` hands them out parents before children,
+/// otherwise in the order they were queued.
+#[derive(Default)]
+pub struct ReactionQueue {
+    order: Vec<Weak<dyn AnyReaction>>,
+    queued: HashSet<usize>,
+}
+
+impl ReactionQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(reaction: &Weak<dyn AnyReaction>) -> usize {
+        Weak::as_ptr(reaction) as *const () as usize
+    }
+
+    /// Queue a reaction. Returns false if it was already queued.
+    pub fn push(&mut self, reaction: Weak<dyn AnyReaction>) -> bool {
+        if !self.queued.insert(Self::key(&reaction)) {
+            return false;
+        }
+        self.order.push(reaction);
+        true
+    }
+
+    /// True if the reaction is queued.
+    pub fn contains(&self, reaction: &Weak<dyn AnyReaction>) -> bool {
+        self.queued.contains(&Self::key(reaction))
+    }
+
+    /// Number of queued entries (including reactions dropped since).
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// True if nothing is queued.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// The queued entries, in the order they were queued.
+    pub fn iter(&self) -> impl Iterator<Item = &Weak<dyn AnyReaction>> {
+        self.order.iter()
+    }
+
+    /// Empty the queue, returning its live reactions with parents before
+    /// children.
+    pub fn take(&mut self) -> Vec<Weak<dyn AnyReaction>> {
+        self.queued.clear();
+        let mut live: Vec<(usize, Weak<dyn AnyReaction>)> = std::mem::take(&mut self.order)
+            .into_iter()
+            .filter_map(|weak| weak.upgrade().map(|reaction| (reaction.depth(), weak)))
+            .collect();
+        // Stable, so queue order holds within a level. Usually already in
+        // order (roots only, or children queued after their parents).
+        if !live.is_sorted_by_key(|(depth, _)| *depth) {
+            live.sort_by_key(|(depth, _)| *depth);
+        }
+        live.into_iter().map(|(_, weak)| weak).collect()
+    }
+}
 
 // =============================================================================
 // REACTIVE CONTEXT
@@ -42,7 +131,7 @@ pub struct ReactiveContext {
     // DEPENDENCY TRACKING (during reaction execution)
     // =========================================================================
     /// New dependencies collected during current reaction execution
-    pub new_deps: RefCell<Vec<Rc<dyn AnySource>>>,
+    pub(crate) new_deps: RefCell<DepList>,
 
     /// Number of existing dependencies that matched (optimization)
     pub skipped_deps: Cell<usize>,
@@ -57,13 +146,119 @@ pub struct ReactiveContext {
     pub batch_depth: Cell<u32>,
 
     /// Pending reactions to run after batch completes
-    pub pending_reactions: RefCell<Vec<Weak<dyn AnyReaction>>>,
+    pub pending_reactions: RefCell<ReactionQueue>,
 
     /// Queued root effects to process
-    pub queued_root_effects: RefCell<Vec<Weak<dyn AnyReaction>>>,
+    pub queued_root_effects: RefCell<ReactionQueue>,
 
     /// Whether we're currently flushing synchronously
     pub is_flushing_sync: Cell<bool>,
+
+    // =========================================================================
+    // DIAGNOSTICS
+    // =========================================================================
+    /// Sources (signals and deriveds) currently alive
+    pub live_sources: Cell<usize>,
+
+    /// Reactions (deriveds, effects, repeaters) currently alive
+    pub live_reactions: Cell<usize>,
+
+    /// Every source that has had a reaction, by address (for `stats()`)
+    pub linked_sources: RefCell<HashMap<usize, Weak<dyn AnySource>>>,
+
+    /// Whether writes are reported to the active `record::Recorder`
+    pub recording: Cell<bool>,
+
+    /// Effect runs so far (read by `testing::effect_run_count()`)
+    pub effect_runs: Cell<u64>,
+
+    /// Log of the active `testing::RecordingScheduler`, if any
+    pub(crate) run_log: RefCell<Option<Rc<RefCell<Vec<String>>>>>,
+
+    /// Recompute counters of deriveds watched by `testing::count_recomputations()`,
+    /// by node address
+    pub(crate) recompute_counters: RefCell<HashMap<usize, Rc<Cell<u64>>>>,
+
+    // =========================================================================
+    // WRITE MIDDLEWARE
+    // =========================================================================
+    /// Registered `add_write_middleware()` callbacks, in registration order
+    pub write_middleware: RefCell<Vec<(u64, WriteMiddleware)>>,
+
+    /// Id handed to the next registered middleware
+    pub next_middleware_id: Cell<u64>,
+
+    /// Whether middleware is running (writes it makes skip middleware)
+    pub in_write_middleware: Cell<bool>,
+
+    /// Address of the source whose write middleware is checking (0 = none)
+    pub(crate) write_middleware_target: Cell<usize>,
+
+    // =========================================================================
+    // PER-CONTEXT STATE
+    // =========================================================================
+    /// One undo journal per open `transaction()`, innermost last
+    pub(crate) transactions: RefCell<Vec<Vec<Staged>>>,
+
+    /// `on_flush_start()` / `on_flush_end()` callbacks
+    pub(crate) flush_hooks: RefCell<FlushHooks>,
+
+    /// Nesting depth of flush passes (hooks fire only at the outermost)
+    pub(crate) flush_depth: Cell<u32>,
+
+    /// Count of outermost flushes started so far
+    pub(crate) flush_epoch: Cell<u64>,
+
+    /// Number of live `TestScheduler`s (non-zero = no automatic flushing)
+    pub(crate) manual_flush: Cell<u32>,
+
+    /// Host callback asked to flush later (e.g. on a microtask)
+    pub(crate) flush_request: RefCell<Option<Rc<dyn Fn()>>>,
+
+    /// Idle effects scheduled since the last `flush_idle()`
+    pub(crate) idle_queue: RefCell<Vec<Weak<dyn AnyReaction>>>,
+
+    /// Registered `on_effect_error()` handlers, by registration id
+    pub(crate) error_handlers: RefCell<Vec<(u64, ErrorHandler)>>,
+
+    /// Id handed to the next registered error handler
+    pub(crate) next_error_handler_id: Cell<u64>,
+
+    /// Config override (`set_thread_config()` for the thread's context,
+    /// `Runtime::set_config()` for a runtime's); None = follow `init()`
+    pub(crate) config: RefCell<Option<Config>>,
+
+    /// The started `record::Recorder`, if any
+    pub(crate) recorder: RefCell<Option<Rc<RecorderState>>>,
+
+    /// The `EffectScope` collecting effects created now, if any
+    pub(crate) active_scope: RefCell<Option<Rc<EffectScopeInner>>>,
+
+    /// Deferred repeaters waiting for the next flush, in queue order
+    pub(crate) deferred_repeaters: RefCell<Vec<Weak<RepeaterInner>>>,
+
+    /// Incremental computations with work remaining, in the order they started
+    pub(crate) incremental: RefCell<Vec<Weak<dyn IncrementalTask>>>,
+
+    /// `memo_structural()` deriveds, by key and value type
+    pub(crate) memos: RefCell<HashMap<MemoKey, Vec<MemoEntry>>>,
+
+    /// `subscribe()` listeners, by source
+    pub(crate) subscribers: RefCell<Subscribers>,
+
+    /// Sources standing in for `ReactiveSharedArray`s, by array id
+    pub(crate) array_sources: RefCell<HashMap<u64, ArraySources>>,
+
+    /// Nodes known to the `debug` graph dump
+    pub(crate) debug_registry: RefCell<Registry>,
+
+    /// Effects whose last handle was dropped while the tree kept them alive
+    #[cfg(feature = "debug-deps")]
+    pub(crate) leaked_effects: RefCell<Vec<Weak<EffectInner>>>,
+
+    /// Per-node counters of the `profiling` module
+    #[cfg(feature = "profiling")]
+    pub(crate) profile: RefCell<Profile>,
 }
 
 impl ReactiveContext {
@@ -75,13 +270,46 @@ impl ReactiveContext {
             untracking: Cell::new(false),
             write_version: Cell::new(1),
             read_version: Cell::new(0),
-            new_deps: RefCell::new(Vec::new()),
+            new_deps: RefCell::new(DepList::new()),
             skipped_deps: Cell::new(0),
             untracked_writes: RefCell::new(Vec::new()),
             batch_depth: Cell::new(0),
-            pending_reactions: RefCell::new(Vec::new()),
-            queued_root_effects: RefCell::new(Vec::new()),
+            pending_reactions: RefCell::new(ReactionQueue::new()),
+            queued_root_effects: RefCell::new(ReactionQueue::new()),
             is_flushing_sync: Cell::new(false),
+            live_sources: Cell::new(0),
+            live_reactions: Cell::new(0),
+            linked_sources: RefCell::new(HashMap::new()),
+            recording: Cell::new(false),
+            effect_runs: Cell::new(0),
+            run_log: RefCell::new(None),
+            recompute_counters: RefCell::new(HashMap::new()),
+            write_middleware: RefCell::new(Vec::new()),
+            next_middleware_id: Cell::new(0),
+            in_write_middleware: Cell::new(false),
+            write_middleware_target: Cell::new(0),
+            transactions: RefCell::new(Vec::new()),
+            flush_hooks: RefCell::new(FlushHooks::default()),
+            flush_depth: Cell::new(0),
+            flush_epoch: Cell::new(0),
+            manual_flush: Cell::new(0),
+            flush_request: RefCell::new(None),
+            idle_queue: RefCell::new(Vec::new()),
+            error_handlers: RefCell::new(Vec::new()),
+            next_error_handler_id: Cell::new(0),
+            config: RefCell::new(None),
+            recorder: RefCell::new(None),
+            active_scope: RefCell::new(None),
+            deferred_repeaters: RefCell::new(Vec::new()),
+            incremental: RefCell::new(Vec::new()),
+            memos: RefCell::new(HashMap::new()),
+            subscribers: RefCell::new(Subscribers::default()),
+            array_sources: RefCell::new(HashMap::new()),
+            debug_registry: RefCell::new(Registry::default()),
+            #[cfg(feature = "debug-deps")]
+            leaked_effects: RefCell::new(Vec::new()),
+            #[cfg(feature = "profiling")]
+            profile: RefCell::new(Profile::default()),
         }
     }
 
@@ -163,7 +391,7 @@ impl ReactiveContext {
     // =========================================================================
 
     /// Swap out the new_deps list, returning the old one
-    pub fn swap_new_deps(&self, deps: Vec<Rc<dyn AnySource>>) -> Vec<Rc<dyn AnySource>> {
+    pub(crate) fn swap_new_deps(&self, deps: DepList) -> DepList {
         self.new_deps.replace(deps)
     }
 
@@ -230,24 +458,26 @@ impl ReactiveContext {
         self.batch_depth.get() > 0
     }
 
-    /// Add a pending reaction to run after batch
+    /// Add a pending reaction to run after batch (once, however often it
+    /// is added before the next take)
     pub fn add_pending_reaction(&self, reaction: Weak<dyn AnyReaction>) {
         self.pending_reactions.borrow_mut().push(reaction);
     }
 
-    /// Take all pending reactions
+    /// Take all pending reactions, parents before children
     pub fn take_pending_reactions(&self) -> Vec<Weak<dyn AnyReaction>> {
-        self.pending_reactions.replace(Vec::new())
+        self.pending_reactions.borrow_mut().take()
     }
 
-    /// Add a queued root effect
+    /// Add a queued root effect (once, however often it is added before the
+    /// next take)
     pub fn add_queued_root_effect(&self, effect: Weak<dyn AnyReaction>) {
         self.queued_root_effects.borrow_mut().push(effect);
     }
 
-    /// Take all queued root effects
+    /// Take all queued root effects, parents before children
     pub fn take_queued_root_effects(&self) -> Vec<Weak<dyn AnyReaction>> {
-        self.queued_root_effects.replace(Vec::new())
+        self.queued_root_effects.borrow_mut().take()
     }
 
     /// Set flushing sync mode, returning previous
@@ -259,6 +489,131 @@ impl ReactiveContext {
     pub fn is_flushing_sync(&self) -> bool {
         self.is_flushing_sync.get()
     }
+
+    // =========================================================================
+    // DIAGNOSTICS
+    // =========================================================================
+
+    /// Set write recording, returning previous
+    pub fn set_recording(&self, value: bool) -> bool {
+        self.recording.replace(value)
+    }
+
+    /// Check if writes are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recording.get()
+    }
+
+    /// Count one finished effect run, logging it if a recording scheduler
+    /// is active (`label` is only called then)
+    pub fn effect_ran(&self, label: impl FnOnce() -> String) {
+        self.effect_runs.set(self.effect_runs.get() + 1);
+        if let Some(log) = &*self.run_log.borrow() {
+            log.borrow_mut().push(label());
+        }
+    }
+
+    /// Number of effect runs so far
+    pub fn effect_run_count(&self) -> u64 {
+        self.effect_runs.get()
+    }
+
+    /// Count one recompute of the derived at `address`, if it is watched
+    pub(crate) fn derived_recomputed(&self, address: usize) {
+        let counters = self.recompute_counters.borrow();
+        if counters.is_empty() {
+            return;
+        }
+        if let Some(count) = counters.get(&address) {
+            count.set(count.get() + 1);
+        }
+    }
+
+    /// Count a newly created source
+    pub fn source_created(&self) {
+        self.live_sources.set(self.live_sources.get() + 1);
+    }
+
+    /// Count a dropped source
+    pub fn source_dropped(&self) {
+        self.live_sources
+            .set(self.live_sources.get().saturating_sub(1));
+    }
+
+    /// Count a newly created reaction
+    pub fn reaction_created(&self) {
+        self.live_reactions.set(self.live_reactions.get() + 1);
+    }
+
+    /// Count a dropped reaction
+    pub fn reaction_dropped(&self) {
+        self.live_reactions
+            .set(self.live_reactions.get().saturating_sub(1));
+    }
+
+    /// Remember a source gaining reactions, so `stats()` can inspect its list
+    pub fn register_linked_source(&self, source: &Rc<dyn AnySource>) {
+        let mut linked = self.linked_sources.borrow_mut();
+        let address = Rc::as_ptr(source) as *const () as usize;
+        linked
+            .entry(address)
+            .or_insert_with(|| Rc::downgrade(source));
+    }
+
+    /// Snapshot of the graph's size, for leak hunting.
+    ///
+    /// Dead entries of dropped sources are pruned from the registry here.
+    pub fn stats(&self) -> ReactiveStats {
+        let mut dead_reaction_refs = 0;
+        self.linked_sources
+            .borrow_mut()
+            .retain(|_, source| match source.upgrade() {
+                Some(source) => {
+                    dead_reaction_refs += source.dead_reaction_count();
+                    true
+                }
+                None => false,
+            });
+
+        let mut pending: Vec<*const ()> = Vec::new();
+        let queued = self.pending_reactions.borrow();
+        let roots = self.queued_root_effects.borrow();
+        for reaction in queued
+            .iter()
+            .chain(roots.iter())
+            .filter_map(|w| w.upgrade())
+        {
+            let ptr = Rc::as_ptr(&reaction) as *const ();
+            if !pending.contains(&ptr) {
+                pending.push(ptr);
+            }
+        }
+
+        ReactiveStats {
+            live_sources: self.live_sources.get(),
+            live_reactions: self.live_reactions.get(),
+            pending_effects: pending.len(),
+            queue_len: queued.len() + roots.len(),
+            dead_reaction_refs,
+        }
+    }
+}
+
+/// Counts reported by [`stats()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReactiveStats {
+    /// Signals and deriveds alive on this thread
+    pub live_sources: usize,
+    /// Deriveds, effects and repeaters alive on this thread (deriveds count
+    /// as both sources and reactions)
+    pub live_reactions: usize,
+    /// Distinct effects queued to run
+    pub pending_effects: usize,
+    /// Entries in the pending and root queues (an effect in both counts
+    /// twice; dropped effects count until the next flush)
+    pub queue_len: usize,
+    /// Reaction-list entries pointing at dropped reactions, not yet pruned
+    pub dead_reaction_refs: usize,
 }
 
 impl Default for ReactiveContext {
@@ -274,9 +629,38 @@ impl Default for ReactiveContext {
 thread_local! {
     /// The thread-local reactive context
     static CONTEXT: ReactiveContext = ReactiveContext::new();
+
+    /// Context of the innermost `Runtime::enter()`, if any
+    static ACTIVE_RUNTIME: RefCell<Option<Rc<ReactiveContext>>> = const { RefCell::new(None) };
+
+    /// `ACTIVE_RUNTIME`'s context as a plain pointer (null if none), so
+    /// `with_context` neither clones the `Rc` nor holds a borrow
+    static ACTIVE_CONTEXT: Cell<*const ReactiveContext> = const { Cell::new(std::ptr::null()) };
+}
+
+/// Make `context` the active one, returning the one it replaces.
+fn set_active(context: Option<Rc<ReactiveContext>>) -> Option<Rc<ReactiveContext>> {
+    let pointer = context.as_ref().map_or(std::ptr::null(), Rc::as_ptr);
+    let previous = ACTIVE_RUNTIME.with(|active| active.replace(context));
+    ACTIVE_CONTEXT.with(|active| active.set(pointer));
+    previous
+}
+
+/// Run `f` on the context `pointer` points to, or on the thread's own.
+fn with_active<R>(pointer: *const ReactiveContext, f: impl FnOnce(&ReactiveContext) -> R) -> R {
+    if pointer.is_null() {
+        return CONTEXT.with(f);
+    }
+    // SAFETY: a non-null pointer is the context in `ACTIVE_RUNTIME`, set by
+    // a `Runtime::enter()` still on the stack. Until that call returns, its
+    // `Rc` is either in `ACTIVE_RUNTIME` or held by the guard of an inner
+    // `enter()` (restored before the outer one returns), so the context
+    // outlives `f`.
+    f(unsafe { &*pointer })
 }
 
-/// Access the thread-local reactive context.
+/// Access the current reactive context: the innermost entered [`Runtime`]'s,
+/// or the thread's own.
 ///
 /// # Example
 ///
@@ -286,9 +670,226 @@ thread_local! {
 /// });
 /// ```
 pub fn with_context<R>(f: impl FnOnce(&ReactiveContext) -> R) -> R {
+    with_active(ACTIVE_CONTEXT.with(Cell::get), f)
+}
+
+/// Access the thread's own context, even inside a [`Runtime`].
+pub(crate) fn with_thread_context<R>(f: impl FnOnce(&ReactiveContext) -> R) -> R {
     CONTEXT.with(f)
 }
 
+/// Like `with_context`, but does nothing during thread teardown.
+///
+/// For `Drop` impls, which may run after the context is gone.
+pub(crate) fn try_with_context(f: impl FnOnce(&ReactiveContext)) {
+    match ACTIVE_CONTEXT.try_with(Cell::get) {
+        Ok(pointer) if !pointer.is_null() => with_active(pointer, f),
+        _ => {
+            let _ = CONTEXT.try_with(f);
+        }
+    }
+}
+
+// =============================================================================
+// RUNTIMES
+// =============================================================================
+
+/// An isolated reactive context on the current thread.
+///
+/// Everything that runs inside [`enter()`](Self::enter) uses the runtime's
+/// own tracking, version counters, batching, transactions and effect
+/// queues, and its own flush hooks, manual flushing (`TestScheduler`),
+/// effect error handlers, effect scopes, recorder and config override, as
+/// well as its own deferred repeaters, incremental computations, structural
+/// memos, debug and profiling registries and shared-array sources. Code
+/// sharing a thread (plugins, libraries, tests) can't batch, flush, track or
+/// reconfigure each other's work. Outside every `enter()`, the thread's own
+/// context is used as before.
+///
+/// Signals and deriveds remember the context they were created in. Writing
+/// one notifies its reactions and `subscribe()` listeners and flushes in
+/// that context, wherever the write happens; a derived always computes
+/// there. A reaction reading a node from another context panics, so graphs
+/// never mix. Writes are still staged by the current context's transaction
+/// and checked by its write middleware. Timers, the cross-thread inbox and
+/// the `init()` config are shared by the whole thread (or process).
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{batch, effect_sync, signal, Runtime};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let plugin = Runtime::new();
+/// let runs = Rc::new(Cell::new(0));
+/// let (level, _watch) = plugin.enter(|| {
+///     let level = signal(0);
+///     let watch = effect_sync({
+///         let (level, runs) = (level.clone(), runs.clone());
+///         move || {
+///             level.get();
+///             runs.set(runs.get() + 1);
+///         }
+///     });
+///     (level, watch)
+/// });
+///
+/// // A batch on the host doesn't hold back the plugin's effects
+/// batch(|| {
+///     plugin.enter(|| level.set(1));
+///     assert_eq!(runs.get(), 2);
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct Runtime {
+    context: Rc<ReactiveContext>,
+}
+
+/// Restores the previously active runtime, also on unwind.
+struct EnterGuard {
+    previous: Option<Rc<ReactiveContext>>,
+}
+
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        // Skipped during thread teardown, when nothing can enter anymore
+        if ACTIVE_RUNTIME.try_with(|_| ()).is_ok() && ACTIVE_CONTEXT.try_with(|_| ()).is_ok() {
+            set_active(previous);
+        }
+    }
+}
+
+impl Runtime {
+    /// A fresh runtime with an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` with this runtime as the current context.
+    ///
+    /// Nests: entering another runtime inside `f` switches to it until that
+    /// call returns. Reads inside `f` are not tracked by a reaction running
+    /// in the outer context.
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        enter_runtime(Some(self), f)
+    }
+
+    /// The innermost entered runtime, if any.
+    pub(crate) fn current() -> Option<Runtime> {
+        let context = ACTIVE_RUNTIME.with(|active| active.borrow().clone())?;
+        Some(Runtime { context })
+    }
+
+    /// True while this runtime is the current context.
+    pub fn is_active(&self) -> bool {
+        ACTIVE_RUNTIME.with(|active| {
+            active
+                .borrow()
+                .as_ref()
+                .is_some_and(|ctx| Rc::ptr_eq(ctx, &self.context))
+        })
+    }
+
+    /// Node counts for this runtime, like [`stats()`].
+    pub fn stats(&self) -> ReactiveStats {
+        self.context.stats()
+    }
+
+    /// Override the configuration inside this runtime, like
+    /// `set_thread_config()` does for the thread.
+    pub fn set_config(&self, config: Config) {
+        *self.context.config.borrow_mut() = Some(config);
+    }
+
+    /// Drop this runtime's override; it follows `init()` again.
+    pub fn clear_config(&self) {
+        *self.context.config.borrow_mut() = None;
+    }
+}
+
+/// Run `f` in `runtime`'s context, or the thread's own for `None`.
+///
+/// For handles that must act on the context they were created in (a
+/// `TestScheduler`, an unregister function) wherever they are used.
+pub(crate) fn enter_runtime<R>(runtime: Option<&Runtime>, f: impl FnOnce() -> R) -> R {
+    let context = runtime.map(|runtime| runtime.context.clone());
+    let previous = set_active(context);
+    let _guard = EnterGuard { previous };
+    f()
+}
+
+impl std::fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Runtime")
+            .field("active", &self.is_active())
+            .field("batch_depth", &self.context.get_batch_depth())
+            .finish()
+    }
+}
+
+// =============================================================================
+// NODE OWNERSHIP
+// =============================================================================
+
+/// The context a node was created in.
+///
+/// Writes to the node notify and flush in this context wherever they
+/// happen, and reactions in another context can't depend on it.
+#[derive(Clone)]
+pub struct Owner {
+    /// The context's address. The weak ref below keeps a runtime's address
+    /// from being reused; the thread's context outlives its nodes.
+    context: *const ReactiveContext,
+
+    /// The owning runtime; None = the thread's own context
+    runtime: Option<Weak<ReactiveContext>>,
+}
+
+impl Owner {
+    /// The current context.
+    pub(crate) fn current() -> Self {
+        Self {
+            context: with_context(|ctx| ctx as *const ReactiveContext),
+            runtime: ACTIVE_RUNTIME.with(|active| active.borrow().as_ref().map(Rc::downgrade)),
+        }
+    }
+
+    /// True if the node may be used in `ctx`: it is the owning context, or
+    /// the owning runtime was dropped (nothing else can react to the node).
+    pub(crate) fn accepts(&self, ctx: &ReactiveContext) -> bool {
+        std::ptr::eq(self.context, ctx)
+            || self.runtime.as_ref().is_some_and(|runtime| runtime.strong_count() == 0)
+    }
+
+    /// Run `f` in the owning context (in the current one if it `accepts()`).
+    pub(crate) fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        if with_context(|ctx| self.accepts(ctx)) {
+            return f();
+        }
+        match self.runtime.as_ref().and_then(Weak::upgrade) {
+            Some(context) => enter_runtime(Some(&Runtime { context }), f),
+            None => enter_runtime(None, f),
+        }
+    }
+
+    /// Like `enter`, but for `Drop` impls: hands `f` the owning context
+    /// directly and does nothing once it is gone.
+    pub(crate) fn try_with(&self, f: impl FnOnce(&ReactiveContext)) {
+        match &self.runtime {
+            None => {
+                let _ = CONTEXT.try_with(f);
+            }
+            Some(runtime) => {
+                if let Some(context) = runtime.upgrade() {
+                    f(&context);
+                }
+            }
+        }
+    }
+}
+
 // =============================================================================
 // CONVENIENCE FUNCTIONS
 // =============================================================================
@@ -322,6 +923,36 @@ pub fn read_version() -> u32 {
     with_context(|ctx| ctx.get_read_version())
 }
 
+/// Live node counts for this thread's reactive graph (or the entered
+/// [`Runtime`]'s).
+///
+/// Useful for spotting leaks in long-running sessions: take a snapshot,
+/// run a workload that should clean up after itself, and compare.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, signal, stats};
+///
+/// let before = stats();
+/// let count = signal(0);
+/// let dispose = effect_sync({
+///     let count = count.clone();
+///     move || {
+///         count.get();
+///     }
+/// });
+/// assert_eq!(stats().live_sources, before.live_sources + 1);
+/// assert_eq!(stats().live_reactions, before.live_reactions + 1);
+///
+/// dispose();
+/// drop(count);
+/// assert_eq!(stats().live_sources, before.live_sources);
+/// ```
+pub fn stats() -> ReactiveStats {
+    with_context(|ctx| ctx.stats())
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -419,6 +1050,35 @@ mod tests {
         assert_eq!(read_version(), 0);
     }
 
+    #[test]
+    fn stats_count_nodes_and_dead_reaction_refs() {
+        use crate::primitives::derived::derived;
+        use crate::primitives::signal::signal;
+
+        let before = stats();
+        let count = signal(1);
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        assert_eq!(doubled.get(), 2);
+
+        let during = stats();
+        assert_eq!(during.live_sources, before.live_sources + 2);
+        assert_eq!(during.live_reactions, before.live_reactions + 1);
+        assert_eq!(during.dead_reaction_refs, 0);
+
+        // The derived is gone, but `count` still lists it until pruned
+        drop(doubled);
+        let after = stats();
+        assert_eq!(after.live_sources, before.live_sources + 1);
+        assert_eq!(after.live_reactions, before.live_reactions);
+        assert_eq!(after.dead_reaction_refs, 1);
+
+        count.inner().cleanup_dead_reactions();
+        assert_eq!(stats().dead_reaction_refs, 0);
+    }
+
     #[test]
     fn flushing_sync_flag() {
         with_context(|ctx| {
@@ -433,4 +1093,302 @@ mod tests {
             assert!(!ctx.is_flushing_sync());
         });
     }
+
+    #[test]
+    fn pending_queue_dedups_and_runs_parents_first() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let (a, b) = (signal(0), signal(0));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let _parent = effect_sync({
+            let (a, b, log) = (a.clone(), b.clone(), log.clone());
+            move || {
+                b.get();
+                log.borrow_mut().push("parent");
+                let _child = effect_sync({
+                    let (a, log) = (a.clone(), log.clone());
+                    move || {
+                        a.get();
+                        log.borrow_mut().push("child");
+                    }
+                });
+            }
+        });
+        log.borrow_mut().clear();
+
+        batch(|| {
+            // Child queued first, and twice
+            a.set(1);
+            a.set(2);
+            b.set(1);
+            assert_eq!(stats().queue_len, 2);
+        });
+        assert_eq!(*log.borrow(), ["parent", "child"]);
+        assert_eq!(stats().queue_len, 0);
+    }
+
+    #[test]
+    fn queue_orders_by_cached_depth_then_queue_order() {
+        use crate::core::constants::EFFECT;
+        use crate::primitives::effect::EffectInner;
+
+        let node = || EffectInner::new(EFFECT, None);
+        let (root, sibling, child, grandchild) = (node(), node(), node(), node());
+        child.set_parent(Some(Rc::downgrade(&root)));
+        grandchild.set_parent(Some(Rc::downgrade(&child)));
+        assert_eq!((child.depth(), grandchild.depth()), (1, 2));
+
+        let weak = |effect: &Rc<EffectInner>| {
+            Rc::downgrade(&(effect.clone() as Rc<dyn AnyReaction>))
+        };
+        let mut queue = ReactionQueue::new();
+        for effect in [&grandchild, &sibling, &child, &root] {
+            assert!(queue.push(weak(effect)));
+        }
+        let order: Vec<_> = queue.take().into_iter().map(|w| w.as_ptr() as *const ()).collect();
+        let expected: Vec<_> = [&sibling, &root, &child, &grandchild]
+            .into_iter()
+            .map(|e| Rc::as_ptr(e) as *const ())
+            .collect();
+        assert_eq!(order, expected);
+
+        // Detaching makes an effect a root again
+        child.set_parent(None);
+        assert_eq!((child.depth(), grandchild.depth()), (0, 2));
+    }
+
+    #[test]
+    fn siblings_run_in_queue_order_and_late_queued_effects_join_the_flush() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let signals: Vec<_> = (0..3).map(|_| signal(0)).collect();
+        let relay = signal(0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let _parent = effect_sync({
+            let (signals, relay, log) = (signals.clone(), relay.clone(), log.clone());
+            move || {
+                for (i, source) in signals.iter().enumerate() {
+                    let (source, relay, log) = (source.clone(), relay.clone(), log.clone());
+                    let _child = effect_sync(move || {
+                        let value = source.get();
+                        log.borrow_mut().push(format!("child {i}"));
+                        // Queues the late effect while this flush runs
+                        if i == 0 && value > 0 {
+                            relay.set(value);
+                        }
+                    });
+                }
+            }
+        });
+        let _late = effect_sync({
+            let (relay, log) = (relay.clone(), log.clone());
+            move || log.borrow_mut().push(format!("late {}", relay.get()))
+        });
+        log.borrow_mut().clear();
+
+        batch(|| {
+            signals[2].set(1);
+            signals[0].set(1);
+            signals[1].set(1);
+        });
+        assert_eq!(*log.borrow(), ["child 2", "child 0", "child 1", "late 1"]);
+    }
+
+    #[test]
+    fn runtimes_isolate_tracking() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+
+        let runtime = Runtime::new();
+        let inner = runtime.enter(|| signal(0));
+        let runs = Rc::new(Cell::new(0));
+        let _outer = effect_sync({
+            let (runtime, inner, runs) = (runtime.clone(), inner.clone(), runs.clone());
+            move || {
+                assert!(runtime.enter(|| runtime.is_active()));
+                // Read in the runtime: not a dependency of this effect
+                runtime.enter(|| inner.get());
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        runtime.enter(|| inner.set(1));
+        assert_eq!(runs.get(), 1);
+        assert!(!runtime.is_active());
+        assert_eq!(runtime.stats().live_sources, 1);
+    }
+
+    /// A signal in `runtime` with an effect counting its runs.
+    fn watched_signal(runtime: &Runtime) -> (crate::Signal<i32>, Rc<Cell<u32>>, impl FnOnce()) {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+
+        runtime.enter(|| {
+            let value = signal(0);
+            let runs = Rc::new(Cell::new(0));
+            let dispose = effect_sync({
+                let (value, runs) = (value.clone(), runs.clone());
+                move || {
+                    value.get();
+                    runs.set(runs.get() + 1);
+                }
+            });
+            (value, runs, dispose)
+        })
+    }
+
+    #[test]
+    fn transactions_stay_in_their_runtime() {
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::transaction;
+
+        let runtime = Runtime::new();
+        let (level, runs, _dispose) = watched_signal(&runtime);
+        let host = signal(0);
+
+        let result: Result<(), ()> = transaction(|| {
+            host.set(1);
+            runtime.enter(|| level.set(5));
+            // Neither staged nor held back by the host's transaction
+            assert_eq!(runs.get(), 2);
+            Err(())
+        });
+        assert!(result.is_err());
+        assert_eq!((host.get(), level.get()), (0, 5));
+    }
+
+    #[test]
+    fn manual_flush_stays_in_its_runtime() {
+        use crate::reactivity::test_scheduler::TestScheduler;
+
+        let runtime = Runtime::new();
+        let (level, runs, _dispose) = watched_signal(&runtime);
+
+        let host_scheduler = TestScheduler::new();
+        runtime.enter(|| level.set(1));
+        assert_eq!(runs.get(), 2);
+        drop(host_scheduler);
+
+        // Installed in the runtime, it controls the runtime from anywhere
+        let scheduler = runtime.enter(TestScheduler::new);
+        runtime.enter(|| level.set(2));
+        assert_eq!((runs.get(), scheduler.pending()), (2, 1));
+        assert_eq!(scheduler.run_until_idle(), 1);
+        assert_eq!(runs.get(), 3);
+    }
+
+    #[test]
+    fn error_handlers_stay_in_their_runtime() {
+        use crate::primitives::effect::{effect_sync, on_effect_error};
+        use crate::primitives::signal::signal;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let runtime = Runtime::new();
+        let fail = runtime.enter(|| signal(false));
+        let _failing = runtime.enter(|| {
+            effect_sync({
+                let fail = fail.clone();
+                move || assert!(!fail.get(), "boom")
+            })
+        });
+        let trigger = || {
+            let result = catch_unwind(AssertUnwindSafe(|| runtime.enter(|| fail.set(true))));
+            fail.set_untracked(false);
+            result.is_ok()
+        };
+
+        let host_errors = Rc::new(Cell::new(0));
+        let remove_host = on_effect_error({
+            let host_errors = host_errors.clone();
+            move |_| host_errors.set(host_errors.get() + 1)
+        });
+        assert!(!trigger(), "the host's handler doesn't contain runtime panics");
+
+        let errors = Rc::new(Cell::new(0));
+        let remove = runtime.enter(|| {
+            let errors = errors.clone();
+            on_effect_error(move |_| errors.set(errors.get() + 1))
+        });
+        assert!(trigger());
+        // Unregisters from the runtime, though called outside it
+        remove();
+        assert!(!trigger());
+        assert_eq!((errors.get(), host_errors.get()), (1, 0));
+        remove_host();
+    }
+
+    #[test]
+    fn config_overrides_stay_in_their_runtime() {
+        use crate::core::config::current_config;
+        use crate::reactivity::scheduling::set_max_update_depth;
+
+        let runtime = Runtime::new();
+        runtime.enter(|| set_max_update_depth(7));
+        assert_eq!(runtime.enter(|| current_config().max_update_depth), 7);
+        assert_ne!(current_config().max_update_depth, 7);
+
+        runtime.clear_config();
+        assert_ne!(runtime.enter(|| current_config().max_update_depth), 7);
+    }
+
+    #[test]
+    fn writes_notify_in_the_owning_runtime() {
+        use crate::primitives::derived::derived;
+        use crate::reactivity::batching::batch;
+
+        let runtime = Runtime::new();
+        let (level, runs, _dispose) = watched_signal(&runtime);
+        let doubled = runtime.enter(|| {
+            let level = level.clone();
+            derived(move || level.get() * 2)
+        });
+        let seen = Rc::new(Cell::new(0));
+        let _sub = level.subscribe({
+            let seen = seen.clone();
+            move |new, _| seen.set(*new)
+        });
+
+        // Written outside `enter()`: the host's batch doesn't hold it back
+        batch(|| {
+            level.set(3);
+            assert_eq!((runs.get(), seen.get()), (2, 3));
+        });
+        assert_eq!(doubled.get(), 6);
+
+        // The listener lives with the signal, in the runtime
+        let listeners = || with_context(|ctx| ctx.subscribers.borrow().source_count());
+        assert_eq!(listeners(), 0);
+        assert_eq!(runtime.enter(listeners), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "created in another Runtime")]
+    fn reactions_cannot_read_another_runtimes_signals() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+
+        let runtime = Runtime::new();
+        let level = runtime.enter(|| signal(0));
+        let _watch = effect_sync(move || {
+            level.get();
+        });
+    }
+
+    #[test]
+    fn incremental_work_stays_in_its_runtime() {
+        use crate::primitives::incremental::{advance_incremental, derived_incremental, Step};
+        use std::time::Duration;
+
+        let runtime = Runtime::new();
+        let total = runtime.enter(|| derived_incremental(|| 2, |n: u32| Step::Done(n * 10)));
+
+        assert!(!advance_incremental(Duration::ZERO));
+        assert_eq!(total.get(), None);
+        runtime.enter(|| while advance_incremental(Duration::ZERO) {});
+        assert_eq!(total.get(), Some(20));
+    }
 }
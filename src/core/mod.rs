@@ -1,13 +1,18 @@
 // ============================================================================
 // spark-signals - Core Module
-// Fundamental types, traits, and context for the reactive system
+// Fundamental types, traits, context and configuration for the reactive system
 // ============================================================================
 
+pub mod config;
 pub mod constants;
 pub mod context;
 pub mod types;
 
 // Re-export commonly used items
+pub use config::{clear_thread_config, current_config, init, set_thread_config, Config};
 pub use constants::*;
-pub use context::{is_batching, is_tracking, is_untracking, read_version, with_context, write_version, ReactiveContext};
+pub use context::{
+    is_batching, is_tracking, is_untracking, read_version, stats, with_context, write_version,
+    ReactiveContext, ReactiveStats,
+};
 pub use types::{default_equals, AnyReaction, AnySource, EqualsFn, SourceInner};
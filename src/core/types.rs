@@ -7,7 +7,10 @@ use std::any::Any;
 use std::cell::{Cell, RefCell};
 use std::rc::{Rc, Weak};
 
+use smallvec::SmallVec;
+
 use super::constants::*;
+use super::context::{with_context, Owner};
 
 // =============================================================================
 // TYPE-ERASED TRAITS
@@ -69,6 +72,17 @@ pub trait AnySource: Any {
     /// Used when disconnecting a source from the reactive graph.
     fn clear_reactions(&self);
 
+    /// Number of entries in the reactions list whose reaction was dropped
+    /// (not yet pruned). Used by `stats()`.
+    fn dead_reaction_count(&self) -> usize {
+        0
+    }
+
+    /// The context this source was created in, if it records one.
+    fn owner(&self) -> Option<&Owner> {
+        None
+    }
+
     /// Check if this is a derived (has DERIVED flag)
     fn is_derived(&self) -> bool {
         self.flags() & DERIVED != 0
@@ -120,6 +134,21 @@ pub trait AnySource: Any {
     }
 }
 
+/// A handle that names a reactive node, for APIs taking explicit
+/// dependency lists (`effect_with_deps`).
+///
+/// Implemented for `Signal<T>`, `Derived<T>` and `Rc<dyn AnySource>`.
+pub trait AnySourceRef {
+    /// The node behind the handle.
+    fn any_source(&self) -> Rc<dyn AnySource>;
+}
+
+impl AnySourceRef for Rc<dyn AnySource> {
+    fn any_source(&self) -> Rc<dyn AnySource> {
+        self.clone()
+    }
+}
+
 /// Type-erased reaction interface for scheduling and updates.
 ///
 /// Implemented by `EffectInner` and `DerivedInner<T>`.
@@ -207,6 +236,20 @@ pub trait AnyReaction: Any {
         self.set_flags(self.flags() | DESTROYED);
     }
 
+    /// Global write version at the end of the last run (effects) or
+    /// computation (deriveds). A dependency with a newer write version has
+    /// changed since. `None` if not tracked, which counts as changed.
+    fn verified_version(&self) -> Option<u32> {
+        None
+    }
+
+    /// Nesting depth in the effect tree (0 for roots and deriveds), cached
+    /// when the parent is set. Queues run shallower reactions first, so
+    /// parents run before children.
+    fn depth(&self) -> usize {
+        0
+    }
+
     /// Upcast to Any for downcasting
     fn as_any(&self) -> &dyn Any;
 
@@ -218,6 +261,20 @@ pub trait AnyReaction: Any {
     fn as_derived_source(&self) -> Option<Rc<dyn AnySource>>;
 }
 
+// =============================================================================
+// DEPENDENCY LISTS
+// =============================================================================
+
+/// Number of dependencies stored inline before a `DepList` spills to the heap.
+pub(crate) const INLINE_DEPS: usize = 4;
+
+/// Dependency list used while collecting and storing reaction deps.
+///
+/// Most effects read a handful of sources, so the first `INLINE_DEPS`
+/// entries live inline - re-running a small effect allocates nothing for
+/// its dependency bookkeeping.
+pub(crate) type DepList = SmallVec<[Rc<dyn AnySource>; INLINE_DEPS]>;
+
 // =============================================================================
 // SOURCE INNER (the data behind Signal<T>)
 // =============================================================================
@@ -230,6 +287,49 @@ pub fn default_equals<T: PartialEq>(a: &T, b: &T) -> bool {
     a == b
 }
 
+/// Equality check that carries state
+type EqualsClosure<T> = dyn Fn(&T, &T) -> bool;
+
+/// A source's equality check: a plain function, or a closure carrying state.
+enum Equality<T> {
+    Fn(EqualsFn<T>),
+    Closure(Rc<EqualsClosure<T>>),
+}
+
+/// The value before a source's most recent change, for sources created
+/// with history (`signal_with_history()`, `derived_with_history()`).
+pub(crate) struct History<T> {
+    previous: RefCell<Option<T>>,
+    /// `T::clone`, captured where `T: Clone` is known
+    clone: fn(&T) -> T,
+}
+
+impl<T> History<T> {
+    pub(crate) fn new() -> Box<Self>
+    where
+        T: Clone,
+    {
+        Box::new(Self {
+            previous: RefCell::new(None),
+            clone: T::clone,
+        })
+    }
+
+    /// Keep `old` as the previous value.
+    pub(crate) fn record(&self, old: T) {
+        *self.previous.borrow_mut() = Some(old);
+    }
+
+    /// Keep a copy of `current`, about to be mutated in place.
+    pub(crate) fn record_copy(&self, current: &T) {
+        self.record((self.clone)(current));
+    }
+
+    pub(crate) fn get(&self) -> Option<T> {
+        self.previous.borrow().as_ref().map(self.clone)
+    }
+}
+
 /// The internal data for a signal source.
 ///
 /// This is separate from Signal<T> so we can implement AnySource on it
@@ -250,8 +350,14 @@ pub struct SourceInner<T> {
     /// Reactions that depend on this source (weak refs to avoid cycles)
     reactions: RefCell<Vec<Weak<dyn AnyReaction>>>,
 
-    /// Equality function for comparing values
-    equals: EqualsFn<T>,
+    /// Equality check for comparing values
+    equals: Equality<T>,
+
+    /// Previous value, if enabled with `with_history()`
+    history: Option<Box<History<T>>>,
+
+    /// The context this source was created in
+    owner: Owner,
 }
 
 impl<T> SourceInner<T> {
@@ -265,6 +371,17 @@ impl<T> SourceInner<T> {
 
     /// Create a new source with a custom equality function
     pub fn new_with_equals(value: T, equals: EqualsFn<T>) -> Self {
+        Self::with_equality(value, Equality::Fn(equals))
+    }
+
+    /// Create a new source whose equality is a closure, for checks that
+    /// carry state (a float signal's epsilon)
+    pub fn new_with_equals_closure(value: T, equals: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        Self::with_equality(value, Equality::Closure(Rc::new(equals)))
+    }
+
+    fn with_equality(value: T, equals: Equality<T>) -> Self {
+        with_context(|ctx| ctx.source_created());
         Self {
             flags: Cell::new(SOURCE | CLEAN),
             value: RefCell::new(value),
@@ -272,6 +389,35 @@ impl<T> SourceInner<T> {
             read_version: Cell::new(0),
             reactions: RefCell::new(Vec::new()),
             equals,
+            history: None,
+            owner: Owner::current(),
+        }
+    }
+
+    /// Keep the value from before each change (see `previous()`).
+    pub fn with_history(mut self) -> Self
+    where
+        T: Clone,
+    {
+        self.history = Some(History::new());
+        self
+    }
+
+    /// The value before the most recent change, if this source keeps
+    /// history and has changed.
+    pub fn previous(&self) -> Option<T> {
+        self.history.as_ref().and_then(|history| history.get())
+    }
+
+    fn record_old(&self, old: T) {
+        if let Some(history) = &self.history {
+            history.record(old);
+        }
+    }
+
+    fn record_in_place(&self) {
+        if let Some(history) = &self.history {
+            history.record_copy(&self.value.borrow());
         }
     }
 
@@ -292,11 +438,12 @@ impl<T> SourceInner<T> {
     pub fn set(&self, value: T) -> bool {
         let changed = {
             let current = self.value.borrow();
-            !(self.equals)(&current, &value)
+            !self.is_equal(&current, &value)
         };
 
         if changed {
-            *self.value.borrow_mut() = value;
+            let old = std::mem::replace(&mut *self.value.borrow_mut(), value);
+            self.record_old(old);
             self.write_version.set(self.write_version.get() + 1);
         }
 
@@ -306,6 +453,7 @@ impl<T> SourceInner<T> {
     /// Update the value in place using a closure.
     /// Returns true if there are reactions listening (value may have changed).
     pub fn update(&self, f: impl FnOnce(&mut T)) -> bool {
+        self.record_in_place();
         {
             let mut current = self.value.borrow_mut();
             f(&mut current);
@@ -319,9 +467,34 @@ impl<T> SourceInner<T> {
         has_reactions
     }
 
-    /// Get the equality function
-    pub fn equals_fn(&self) -> EqualsFn<T> {
-        self.equals
+    /// Replace the value without touching the write version or reactions
+    pub fn set_untracked(&self, value: T) {
+        let old = std::mem::replace(&mut *self.value.borrow_mut(), value);
+        self.record_old(old);
+    }
+
+    /// Mutate the value in place without touching the write version or reactions
+    pub fn update_untracked(&self, f: impl FnOnce(&mut T)) {
+        self.record_in_place();
+        f(&mut self.value.borrow_mut());
+    }
+
+    /// Compare two values with this source's equality check
+    pub fn is_equal(&self, a: &T, b: &T) -> bool {
+        match &self.equals {
+            Equality::Fn(equals) => equals(a, b),
+            Equality::Closure(equals) => equals(a, b),
+        }
+    }
+}
+
+impl<T> Drop for SourceInner<T> {
+    fn drop(&mut self) {
+        self.owner.try_with(|ctx| {
+            #[cfg(feature = "profiling")]
+            crate::profiling::forget(ctx, self as *const Self as *const () as usize);
+            ctx.source_dropped();
+        });
     }
 }
 
@@ -392,6 +565,18 @@ impl<T: 'static> AnySource for SourceInner<T> {
         self.reactions.borrow_mut().clear();
     }
 
+    fn owner(&self) -> Option<&Owner> {
+        Some(&self.owner)
+    }
+
+    fn dead_reaction_count(&self) -> usize {
+        self.reactions
+            .borrow()
+            .iter()
+            .filter(|w| w.strong_count() == 0)
+            .count()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
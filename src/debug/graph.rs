@@ -0,0 +1,691 @@
+// ============================================================================
+// spark-signals - Dependency Graph Introspection
+// A snapshot of the live reactive graph: nodes, flags, and dependency edges
+// ============================================================================
+//
+// The graph itself only links nodes to each other, so a snapshot needs
+// starting points. Nodes become known to the introspection registry when:
+// - they're given a debug name (`Signal::set_debug_name()` and friends), or
+// - they're created while the `debug-deps` feature is on (every signal,
+//   derived and effect registers itself)
+//
+// `dependency_graph()` starts from every known node and follows dependency
+// and subscriber links in both directions, so unnamed nodes connected to a
+// named one show up too.
+// ============================================================================
+
+use std::collections::{HashMap, HashSet};
+use std::rc::{Rc, Weak};
+
+use crate::core::constants::*;
+use crate::core::context::with_context;
+use crate::core::types::{AnyReaction, AnySource};
+use crate::primitives::effect::EffectInner;
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+/// A registered node, held weakly.
+enum NodeRef {
+    Source(Weak<dyn AnySource>),
+    Reaction(Weak<dyn AnyReaction>),
+}
+
+impl NodeRef {
+    fn is_alive(&self) -> bool {
+        match self {
+            NodeRef::Source(w) => w.strong_count() > 0,
+            NodeRef::Reaction(w) => w.strong_count() > 0,
+        }
+    }
+}
+
+struct Entry {
+    node: NodeRef,
+    name: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct Registry {
+    /// By node id. The weak refs keep addresses from being reused while
+    /// an entry exists.
+    entries: HashMap<usize, Entry>,
+    /// Entry count after the last pruning of dead nodes
+    live_after_prune: usize,
+}
+
+impl Registry {
+    fn insert(&mut self, id: usize, node: NodeRef, name: Option<String>) {
+        match self.entries.get_mut(&id) {
+            Some(entry) => {
+                if name.is_some() {
+                    entry.name = name;
+                }
+            }
+            None => {
+                self.entries.insert(id, Entry { node, name });
+            }
+        }
+
+        // Amortized cleanup: prune once the registry doubles
+        if self.entries.len() > 2 * self.live_after_prune.max(32) {
+            self.entries.retain(|_, entry| entry.node.is_alive());
+            self.live_after_prune = self.entries.len();
+        }
+    }
+}
+
+/// Stable id for a node: the address of its inner allocation.
+fn id_of<T: ?Sized>(node: &Rc<T>) -> usize {
+    Rc::as_ptr(node) as *const () as usize
+}
+
+/// Make a source known to `dependency_graph()`.
+pub(crate) fn register_source(source: &Rc<dyn AnySource>, name: Option<String>) {
+    let node = NodeRef::Source(Rc::downgrade(source));
+    with_context(|ctx| ctx.debug_registry.borrow_mut().insert(id_of(source), node, name));
+}
+
+/// Make a reaction known to `dependency_graph()`.
+pub(crate) fn register_reaction(reaction: &Rc<dyn AnyReaction>, name: Option<String>) {
+    let node = NodeRef::Reaction(Rc::downgrade(reaction));
+    with_context(|ctx| ctx.debug_registry.borrow_mut().insert(id_of(reaction), node, name));
+}
+
+/// The debug name registered for a node, if any.
+pub(crate) fn debug_name_of<T: ?Sized>(node: &Rc<T>) -> Option<String> {
+    name_by_id(id_of(node))
+}
+
+fn name_by_id(id: usize) -> Option<String> {
+    with_context(|ctx| {
+        ctx.debug_registry
+            .borrow()
+            .entries
+            .get(&id)
+            .filter(|entry| entry.node.is_alive())
+            .and_then(|entry| entry.name.clone())
+    })
+}
+
+// =============================================================================
+// SNAPSHOT TYPES
+// =============================================================================
+
+/// What kind of node a [`GraphNode`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Signal,
+    Derived,
+    Effect,
+    Repeater,
+}
+
+impl NodeKind {
+    fn from_flags(flags: u32) -> NodeKind {
+        if (flags & DERIVED) != 0 {
+            NodeKind::Derived
+        } else if (flags & REPEATER) != 0 {
+            NodeKind::Repeater
+        } else if (flags & EFFECT) != 0 {
+            NodeKind::Effect
+        } else {
+            NodeKind::Signal
+        }
+    }
+}
+
+/// One node of a [`DependencyGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    /// Address of the node's inner allocation; stable while the node lives
+    /// (same value as `source_id()` / `profiling::node_id()`)
+    pub id: usize,
+    pub kind: NodeKind,
+    /// Debug name (`set_debug_name()`, or `set_effect_name()` for effects)
+    pub name: Option<String>,
+    /// Raw flags; see `debug::decode_flags()`
+    pub flags: u32,
+    /// Write version for sources (signals and deriveds)
+    pub write_version: Option<u32>,
+}
+
+impl GraphNode {
+    /// The name if set, otherwise kind and address (e.g. `signal@0x5f..`).
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let kind = match self.kind {
+                    NodeKind::Signal => "signal",
+                    NodeKind::Derived => "derived",
+                    NodeKind::Effect => "effect",
+                    NodeKind::Repeater => "repeater",
+                };
+                format!("{kind}@{:#x}", self.id)
+            }
+        }
+    }
+}
+
+/// A dependency edge: `to` reads `from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphEdge {
+    /// The source being read
+    pub from: usize,
+    /// The reaction reading it
+    pub to: usize,
+}
+
+/// A snapshot of the reactive graph. Created with [`dependency_graph()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    /// Nodes, sources before the reactions that read them where possible
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// The node with this id.
+    pub fn node(&self, id: usize) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// The first node with this debug name.
+    pub fn find(&self, name: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.name.as_deref() == Some(name))
+    }
+
+    /// Nodes that `id` reads.
+    pub fn dependencies_of(&self, id: usize) -> impl Iterator<Item = &GraphNode> {
+        self.edges
+            .iter()
+            .filter(move |e| e.to == id)
+            .filter_map(|e| self.node(e.from))
+    }
+
+    /// Nodes that read `id`.
+    pub fn dependents_of(&self, id: usize) -> impl Iterator<Item = &GraphNode> {
+        self.edges
+            .iter()
+            .filter(move |e| e.from == id)
+            .filter_map(|e| self.node(e.to))
+    }
+}
+
+// =============================================================================
+// PER-NODE LISTINGS
+// =============================================================================
+
+/// A source read by a reaction, from `Effect::dependencies()` /
+/// `Derived::dependencies()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugSourceInfo {
+    /// Same id as [`GraphNode::id`]
+    pub id: usize,
+    /// `Signal` or `Derived`
+    pub kind: NodeKind,
+    /// Debug name (`set_debug_name()`)
+    pub name: Option<String>,
+    /// Raw flags; see `debug::decode_flags()`
+    pub flags: u32,
+    pub write_version: u32,
+}
+
+/// A reaction reading a source, from `Signal::subscribers()` /
+/// `Derived::subscribers()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugReactionInfo {
+    /// Same id as [`GraphNode::id`]
+    pub id: usize,
+    /// `Derived`, `Effect` or `Repeater`
+    pub kind: NodeKind,
+    /// Debug name (`set_debug_name()`, or `set_effect_name()` for effects)
+    pub name: Option<String>,
+    /// Raw flags; see `debug::decode_flags()`
+    pub flags: u32,
+}
+
+/// The debug name of a reaction: registered, or the effect's own name.
+fn reaction_name(id: usize, reaction: &Rc<dyn AnyReaction>) -> Option<String> {
+    name_by_id(id).or_else(|| {
+        reaction
+            .as_any()
+            .downcast_ref::<EffectInner>()
+            .and_then(EffectInner::name)
+    })
+}
+
+/// Debug info for one source.
+pub(crate) fn source_info(source: &Rc<dyn AnySource>) -> DebugSourceInfo {
+    let id = id_of(source);
+    DebugSourceInfo {
+        id,
+        kind: NodeKind::from_flags(source.flags()),
+        name: name_by_id(id),
+        flags: source.flags(),
+        write_version: source.write_version(),
+    }
+}
+
+/// The sources `reaction` currently reads, in dependency order.
+pub(crate) fn dependencies_of(reaction: &dyn AnyReaction) -> Vec<DebugSourceInfo> {
+    let mut out = Vec::new();
+    reaction.for_each_dep(&mut |dep| {
+        out.push(source_info(dep));
+        true
+    });
+    out
+}
+
+/// The live reactions reading `source`.
+pub(crate) fn subscribers_of(source: &dyn AnySource) -> Vec<DebugReactionInfo> {
+    let mut out = Vec::new();
+    source.for_each_reaction(&mut |reaction| {
+        let id = id_of(&reaction);
+        out.push(DebugReactionInfo {
+            id,
+            kind: NodeKind::from_flags(reaction.flags()),
+            name: reaction_name(id, &reaction),
+            flags: reaction.flags(),
+        });
+        true
+    });
+    out
+}
+
+// =============================================================================
+// WALK
+// =============================================================================
+
+/// A node to visit, in whichever of its shapes we reached it by.
+enum Visit {
+    Source(Rc<dyn AnySource>),
+    Reaction(Rc<dyn AnyReaction>),
+}
+
+/// Snapshot the live reactive graph on this thread.
+///
+/// Starts from every registered node (see the module docs) and follows
+/// links both ways. Without the `debug-deps` feature, name the nodes you
+/// care about with `set_debug_name()` first.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived, effect_sync, set_effect_name, signal};
+/// use spark_signals::debug::{dependency_graph, NodeKind};
+///
+/// let count = signal(1);
+/// count.set_debug_name("count");
+/// let doubled = derived({
+///     let count = count.clone();
+///     move || count.get() * 2
+/// });
+/// let _dispose = effect_sync({
+///     let doubled = doubled.clone();
+///     move || {
+///         set_effect_name("logger");
+///         doubled.get();
+///     }
+/// });
+///
+/// let graph = dependency_graph();
+/// let count_node = graph.find("count").unwrap();
+/// let readers: Vec<_> = graph.dependents_of(count_node.id).collect();
+/// assert_eq!(readers.len(), 1);
+/// assert_eq!(readers[0].kind, NodeKind::Derived);
+///
+/// let logger = graph.dependents_of(readers[0].id).next().unwrap();
+/// assert_eq!(logger.name.as_deref(), Some("logger"));
+/// ```
+pub fn dependency_graph() -> DependencyGraph {
+    let mut queue: Vec<Visit> = with_context(|ctx| {
+        ctx.debug_registry
+            .borrow()
+            .entries
+            .values()
+            .filter_map(|entry| match &entry.node {
+                NodeRef::Source(w) => w.upgrade().map(Visit::Source),
+                NodeRef::Reaction(w) => w.upgrade().map(Visit::Reaction),
+            })
+            .collect()
+    });
+
+    let mut graph = DependencyGraph::default();
+    let mut seen: HashSet<usize> = HashSet::new();
+    let mut edges: HashSet<GraphEdge> = HashSet::new();
+
+    while let Some(visit) = queue.pop() {
+        // A derived is both: walk it through both interfaces
+        let (source, reaction) = match visit {
+            Visit::Source(s) => {
+                let r = s.as_derived_reaction();
+                (Some(s), r)
+            }
+            Visit::Reaction(r) => {
+                let s = r.as_derived_source();
+                (s, Some(r))
+            }
+        };
+        let id = match (&source, &reaction) {
+            (Some(s), _) => id_of(s),
+            (None, Some(r)) => id_of(r),
+            (None, None) => continue,
+        };
+        if !seen.insert(id) {
+            continue;
+        }
+
+        let flags = source
+            .as_ref()
+            .map(|s| s.flags())
+            .or_else(|| reaction.as_ref().map(|r| r.flags()))
+            .unwrap_or(0);
+        let name = match &reaction {
+            Some(r) => reaction_name(id, r),
+            None => name_by_id(id),
+        };
+
+        graph.nodes.push(GraphNode {
+            id,
+            kind: NodeKind::from_flags(flags),
+            name,
+            flags,
+            write_version: source.as_ref().map(|s| s.write_version()),
+        });
+
+        if let Some(s) = &source {
+            s.for_each_reaction(&mut |r| {
+                edges.insert(GraphEdge {
+                    from: id,
+                    to: id_of(&r),
+                });
+                queue.push(Visit::Reaction(r));
+                true
+            });
+        }
+        if let Some(r) = &reaction {
+            r.for_each_dep(&mut |dep| {
+                edges.insert(GraphEdge {
+                    from: id_of(dep),
+                    to: id,
+                });
+                queue.push(Visit::Source(dep.clone()));
+                true
+            });
+        }
+    }
+
+    // Deterministic order: sources first, then by address
+    graph
+        .nodes
+        .sort_by_key(|n| ((n.flags & EFFECT) != 0, (n.flags & DERIVED) != 0, n.id));
+    graph.edges = edges.into_iter().collect();
+    graph.edges.sort_by_key(|e| (e.from, e.to));
+    graph
+}
+
+// =============================================================================
+// DOT EXPORT
+// =============================================================================
+
+/// Fill colors for a node's status.
+const DOT_DIRTY: &str = "#f4a6a6";
+const DOT_MAYBE_DIRTY: &str = "#f8d98b";
+const DOT_CLEAN: &str = "#b8e0b8";
+
+/// Quote a string for a DOT attribute.
+fn dot_quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl GraphNode {
+    /// DOT node name (names must be unique; labels need not be).
+    fn dot_id(&self) -> String {
+        format!("n{:x}", self.id)
+    }
+
+    /// DOT shape by kind: sources are ellipses, reactions boxes.
+    fn dot_shape(&self) -> &'static str {
+        match self.kind {
+            NodeKind::Signal => "ellipse",
+            NodeKind::Derived => "box",
+            NodeKind::Effect | NodeKind::Repeater => "box3d",
+        }
+    }
+
+    /// DOT fill color by status; DIRTY wins over MAYBE_DIRTY.
+    fn dot_color(&self) -> &'static str {
+        if (self.flags & DIRTY) != 0 {
+            DOT_DIRTY
+        } else if (self.flags & MAYBE_DIRTY) != 0 {
+            DOT_MAYBE_DIRTY
+        } else {
+            DOT_CLEAN
+        }
+    }
+}
+
+impl DependencyGraph {
+    /// Render as Graphviz DOT text.
+    ///
+    /// Edges point from a source to the reaction reading it. Nodes are
+    /// labeled with [`GraphNode::label()`] and filled by status: green for
+    /// clean, amber for MAYBE_DIRTY, red for DIRTY.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph signals {\n");
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [style=filled, fontname=\"monospace\"];\n");
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "    {} [label={}, shape={}, fillcolor={}];\n",
+                node.dot_id(),
+                dot_quote(&node.label()),
+                node.dot_shape(),
+                dot_quote(node.dot_color()),
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!("    n{:x} -> n{:x};\n", edge.from, edge.to));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Render the live reactive graph as Graphviz DOT text.
+///
+/// Shorthand for `dependency_graph().to_dot()`. Pipe the output through
+/// `dot -Tsvg` to see which nodes a write dirtied.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived, signal};
+/// use spark_signals::debug::to_dot;
+///
+/// let count = signal(1);
+/// count.set_debug_name("count");
+/// let doubled = derived({
+///     let count = count.clone();
+///     move || count.get() * 2
+/// });
+/// doubled.set_debug_name("doubled");
+/// doubled.get();
+///
+/// let dot = to_dot();
+/// assert!(dot.starts_with("digraph signals {"));
+/// assert!(dot.contains("label=\"count\""));
+/// assert!(dot.contains("label=\"doubled\""));
+/// ```
+pub fn to_dot() -> String {
+    dependency_graph().to_dot()
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::derived::derived;
+    use crate::primitives::effect::{effect_sync, set_effect_name};
+    use crate::primitives::signal::signal;
+
+    #[test]
+    fn walks_from_named_nodes_in_both_directions() {
+        let a = signal(1);
+        let b = signal(2);
+        let sum = derived({
+            let (a, b) = (a.clone(), b.clone());
+            move || a.get() + b.get()
+        });
+        sum.set_debug_name("sum");
+        let _dispose = effect_sync({
+            let sum = sum.clone();
+            move || {
+                sum.get();
+            }
+        });
+
+        let graph = dependency_graph();
+        let sum_node = graph.find("sum").unwrap();
+        assert_eq!(sum_node.kind, NodeKind::Derived);
+        assert_eq!(sum.debug_name().as_deref(), Some("sum"));
+
+        let mut inputs: Vec<_> = graph.dependencies_of(sum_node.id).map(|n| n.id).collect();
+        inputs.sort();
+        let mut expected = vec![id_of(a.inner()), id_of(b.inner())];
+        expected.sort();
+        assert_eq!(inputs, expected);
+
+        let readers: Vec<_> = graph.dependents_of(sum_node.id).collect();
+        assert_eq!(readers.len(), 1);
+        assert_eq!(readers[0].kind, NodeKind::Effect);
+        assert!(readers[0].label().starts_with("effect@0x"));
+    }
+
+    #[test]
+    fn snapshot_shows_status_flags() {
+        let count = signal(0);
+        count.set_debug_name("count");
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        doubled.set_debug_name("doubled");
+        let _dispose = effect_sync({
+            let doubled = doubled.clone();
+            move || {
+                set_effect_name("reader");
+                doubled.get();
+            }
+        });
+
+        let graph = dependency_graph();
+        let node = graph.find("doubled").unwrap();
+        assert_ne!(node.flags & CLEAN, 0);
+        assert_eq!(graph.find("reader").unwrap().kind, NodeKind::Effect);
+
+        // Mark dirty without letting the effect run
+        crate::reactivity::batching::batch(|| {
+            count.set(5);
+            let graph = dependency_graph();
+            assert_ne!(graph.find("doubled").unwrap().flags & DIRTY, 0);
+            assert_ne!(graph.find("reader").unwrap().flags & MAYBE_DIRTY, 0);
+            let count_node = graph.find("count").unwrap();
+            assert_eq!(
+                count_node.write_version,
+                Some(count.inner().write_version())
+            );
+        });
+    }
+
+    #[test]
+    fn dropped_nodes_leave_the_graph() {
+        let temp = signal(0);
+        temp.set_debug_name("temporary");
+        assert!(dependency_graph().find("temporary").is_some());
+
+        drop(temp);
+        assert!(dependency_graph().find("temporary").is_none());
+    }
+
+    #[test]
+    fn dot_colors_follow_status() {
+        let count = signal(0);
+        count.set_debug_name("say \"hi\"");
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        doubled.set_debug_name("doubled");
+        doubled.get();
+
+        let doubled_line = |dot: &str| {
+            dot.lines()
+                .find(|l| l.contains("label=\"doubled\""))
+                .unwrap()
+                .to_string()
+        };
+
+        let dot = to_dot();
+        assert!(dot.contains(r#"label="say \"hi\"""#));
+        assert!(doubled_line(&dot).contains(DOT_CLEAN));
+        let edge = format!(
+            "n{:x} -> n{:x};",
+            id_of(count.inner()),
+            id_of(doubled.inner())
+        );
+        assert!(dot.contains(&edge));
+
+        count.set(1);
+        assert!(doubled_line(&to_dot()).contains(DOT_DIRTY));
+    }
+
+    #[test]
+    fn listings_match_graph_ids() {
+        let count = signal(1);
+        count.set_debug_name("count");
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        let _dispose = effect_sync({
+            let doubled = doubled.clone();
+            move || {
+                set_effect_name("view");
+                doubled.get();
+            }
+        });
+
+        let deps = doubled.dependencies();
+        assert_eq!(deps.len(), 1);
+        assert_eq!((deps[0].kind, deps[0].name.as_deref()), (NodeKind::Signal, Some("count")));
+        assert_eq!(deps[0].id, id_of(count.inner()));
+
+        let readers = count.subscribers();
+        assert_eq!(readers.len(), 1);
+        assert_eq!(readers[0].kind, NodeKind::Derived);
+        let view = &doubled.subscribers()[0];
+        assert_eq!((view.kind, view.name.as_deref()), (NodeKind::Effect, Some("view")));
+
+        let graph = dependency_graph();
+        let edge = GraphEdge { from: readers[0].id, to: view.id };
+        assert!(graph.edges.contains(&edge));
+    }
+}
@@ -0,0 +1,24 @@
+// ============================================================================
+// spark-signals - Debug Utilities
+// Introspection helpers for logging, panics, and external tooling
+// ============================================================================
+//
+// Nothing in here is needed for normal operation. These helpers turn the
+// reactive graph's internal state into something a human (or a devtools
+// process on the other side of an FFI boundary) can read.
+// ============================================================================
+
+pub(crate) mod graph;
+
+pub(crate) use graph::{
+    debug_name_of, dependencies_of, register_reaction, register_source, source_info,
+    subscribers_of,
+};
+pub use graph::{
+    dependency_graph, to_dot, DebugReactionInfo, DebugSourceInfo, DependencyGraph, GraphEdge,
+    GraphNode, NodeKind,
+};
+
+pub use crate::core::constants::{decode_flags, flag_name, FlagSet, FLAG_NAMES};
+#[cfg(feature = "debug-deps")]
+pub use crate::primitives::effect::leaked_effects;
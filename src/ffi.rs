@@ -0,0 +1,175 @@
+// ============================================================================
+// spark-signals - C ABI
+// Handle-based signals and effects for non-Rust hosts (Swift, C#, C)
+// ============================================================================
+//
+// Every object crosses the boundary as an opaque pointer created by a
+// `*_new` function and released by the matching `*_free`:
+//
+//     SparkSignal *count = spark_signal_new_f64(0.0);
+//     SparkEffect *log = spark_effect_new(on_change, count);
+//     spark_signal_set(count, 1.0);   // on_change runs
+//     spark_effect_free(log);
+//     spark_signal_free(count);
+//
+// The reactive graph is thread-local: a handle must only be used on the
+// thread that created it. Build with `--features cbindgen` to regenerate
+// include/spark_signals.h.
+// ============================================================================
+
+use std::ffi::c_void;
+
+use crate::primitives::effect::{effect_sync, DisposeFn};
+use crate::primitives::signal::{signal_f64, Signal};
+
+/// A signal holding an `f64`.
+pub struct SparkSignal {
+    signal: Signal<f64>,
+}
+
+/// A running effect. Freeing it stops the effect.
+pub struct SparkEffect {
+    dispose: Option<DisposeFn>,
+}
+
+/// Effect body called from Rust with the `user_data` it was registered with.
+pub type SparkEffectCallback = extern "C" fn(user_data: *mut c_void);
+
+/// Create a signal holding `initial`. Release it with `spark_signal_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn spark_signal_new_f64(initial: f64) -> *mut SparkSignal {
+    Box::into_raw(Box::new(SparkSignal {
+        signal: signal_f64(initial),
+    }))
+}
+
+/// Read the signal's value. Inside an effect callback, the effect subscribes
+/// to it. Returns 0 for a null handle.
+///
+/// # Safety
+///
+/// `signal` must be null or a live handle from `spark_signal_new_f64`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spark_signal_get(signal: *const SparkSignal) -> f64 {
+    match unsafe { signal.as_ref() } {
+        Some(handle) => handle.signal.get(),
+        None => 0.0,
+    }
+}
+
+/// Write the signal's value, running the effects that read it.
+/// Returns false if the value was equal (NaN equals NaN) or the handle null.
+///
+/// # Safety
+///
+/// `signal` must be null or a live handle from `spark_signal_new_f64`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spark_signal_set(signal: *const SparkSignal, value: f64) -> bool {
+    match unsafe { signal.as_ref() } {
+        Some(handle) => handle.signal.set(value),
+        None => false,
+    }
+}
+
+/// Release a signal handle. Effects that read it keep their own reference.
+///
+/// # Safety
+///
+/// `signal` must be null or a live handle from `spark_signal_new_f64`, and
+/// must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spark_signal_free(signal: *mut SparkSignal) {
+    if !signal.is_null() {
+        drop(unsafe { Box::from_raw(signal) });
+    }
+}
+
+/// Run `callback(user_data)` now and again whenever a signal it read through
+/// `spark_signal_get` changes. Release it with `spark_effect_free`.
+///
+/// # Safety
+///
+/// `user_data` must stay valid for `callback` until the effect is freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spark_effect_new(
+    callback: SparkEffectCallback,
+    user_data: *mut c_void,
+) -> *mut SparkEffect {
+    let dispose = effect_sync(move || callback(user_data));
+    Box::into_raw(Box::new(SparkEffect {
+        dispose: Some(Box::new(dispose)),
+    }))
+}
+
+/// Stop an effect and release its handle.
+///
+/// # Safety
+///
+/// `effect` must be null or a live handle from `spark_effect_new`, and must
+/// not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spark_effect_free(effect: *mut SparkEffect) {
+    if effect.is_null() {
+        return;
+    }
+    let mut effect = unsafe { Box::from_raw(effect) };
+    if let Some(dispose) = effect.dispose.take() {
+        dispose();
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::ptr;
+
+    struct Seen {
+        signal: *const SparkSignal,
+        last: Cell<f64>,
+        runs: Cell<u32>,
+    }
+
+    extern "C" fn record(user_data: *mut c_void) {
+        let seen = unsafe { &*(user_data as *const Seen) };
+        seen.last.set(unsafe { spark_signal_get(seen.signal) });
+        seen.runs.set(seen.runs.get() + 1);
+    }
+
+    #[test]
+    fn effects_follow_signal_handles() {
+        let signal = spark_signal_new_f64(1.0);
+        let seen = Seen {
+            signal,
+            last: Cell::new(0.0),
+            runs: Cell::new(0),
+        };
+        let effect = unsafe { spark_effect_new(record, &seen as *const Seen as *mut c_void) };
+        assert_eq!(seen.last.get(), 1.0);
+
+        unsafe {
+            assert!(spark_signal_set(signal, 2.5));
+            assert!(!spark_signal_set(signal, 2.5));
+        }
+        assert_eq!(seen.last.get(), 2.5);
+        assert_eq!(seen.runs.get(), 2);
+
+        unsafe {
+            spark_effect_free(effect);
+            spark_signal_set(signal, 4.0);
+        }
+        assert_eq!(seen.runs.get(), 2);
+
+        unsafe {
+            spark_signal_free(signal);
+            assert_eq!(spark_signal_get(ptr::null()), 0.0);
+            assert!(!spark_signal_set(ptr::null(), 1.0));
+            spark_signal_free(ptr::null_mut());
+            spark_effect_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,292 @@
+// ============================================================================
+// spark-signals - Serde Integration
+// Serialize reactive state, and restore it into live signals
+// ============================================================================
+//
+// Two directions, with different needs:
+//
+// - Saving: `Serialize` on signals, deriveds, stores and the reactive
+//   collections writes their current value. Reads are untracked, so saving
+//   from inside an effect doesn't subscribe it to everything it saves.
+//
+// - Restoring: `Deserialize` builds fresh signals, which is fine at startup
+//   but useless once effects hold the old handles. `hydrate_from()` writes
+//   the deserialized value into existing signals and collections instead,
+//   notifying only what changed, in one batch.
+// ============================================================================
+
+use std::hash::Hash;
+
+use serde::de::{Deserialize, DeserializeOwned, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::collections::{ReactiveMap, ReactiveSet, ReactiveVec};
+use crate::primitives::derived::Derived;
+use crate::primitives::signal::{signal, Signal};
+use crate::primitives::store::Store;
+use crate::reactivity::batching::{batch, untrack};
+
+// =============================================================================
+// SERIALIZE
+// =============================================================================
+
+impl<T: Serialize> Serialize for Signal<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner().with(|value| value.serialize(serializer))
+    }
+}
+
+impl<T: Serialize + Clone + PartialEq + 'static> Serialize for Derived<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        untrack(|| self.get()).serialize(serializer)
+    }
+}
+
+impl<T: Serialize + 'static> Serialize for Store<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.peek(|value| value.serialize(serializer))
+    }
+}
+
+impl<T: Serialize> Serialize for ReactiveVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw().serialize(serializer)
+    }
+}
+
+impl<K, V> Serialize for ReactiveMap<K, V>
+where
+    K: Serialize + Eq + Hash + Clone,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw().serialize(serializer)
+    }
+}
+
+impl<T: Serialize + Eq + Hash + Clone> Serialize for ReactiveSet<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw().serialize(serializer)
+    }
+}
+
+// =============================================================================
+// DESERIALIZE
+// =============================================================================
+
+impl<'de, T: Deserialize<'de> + PartialEq + 'static> Deserialize<'de> for Signal<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(signal)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ReactiveVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(ReactiveVec::from_vec)
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for ReactiveMap<K, V>
+where
+    K: Deserialize<'de> + Eq + Hash + Clone,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        std::collections::HashMap::<K, V>::deserialize(deserializer).map(ReactiveMap::from_iter)
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Eq + Hash + Clone> Deserialize<'de> for ReactiveSet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        std::collections::HashSet::<T>::deserialize(deserializer).map(ReactiveSet::from_iter)
+    }
+}
+
+// =============================================================================
+// HYDRATE
+// =============================================================================
+
+/// Reactive state that can be restored in place. See [`hydrate_from()`].
+pub trait Hydrate {
+    /// Replace the current value with the deserialized one, notifying only
+    /// what changed.
+    fn hydrate<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error>;
+}
+
+impl<T: DeserializeOwned + 'static> Hydrate for Signal<T> {
+    fn hydrate<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error> {
+        self.set(T::deserialize(deserializer)?);
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned + 'static> Hydrate for Store<T> {
+    fn hydrate<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error> {
+        self.set(T::deserialize(deserializer)?);
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned + PartialEq + 'static> Hydrate for ReactiveVec<T> {
+    fn hydrate<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        if self.raw() != &values {
+            batch(|| {
+                self.clear();
+                self.extend(values);
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> Hydrate for ReactiveMap<K, V>
+where
+    K: DeserializeOwned + Eq + Hash + Clone,
+    V: DeserializeOwned + PartialEq + 'static,
+{
+    fn hydrate<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error> {
+        let entries = std::collections::HashMap::<K, V>::deserialize(deserializer)?;
+        batch(|| {
+            self.retain(|key, _| entries.contains_key(key));
+            self.extend(entries);
+        });
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned + Eq + Hash + Clone + 'static> Hydrate for ReactiveSet<T> {
+    fn hydrate<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error> {
+        let items = std::collections::HashSet::<T>::deserialize(deserializer)?;
+        batch(|| {
+            let removed: Vec<T> = self.raw().difference(&items).cloned().collect();
+            for item in &removed {
+                self.remove_exact(item);
+            }
+            for item in items {
+                self.insert(item);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Restore serialized state into an existing signal, store or collection.
+///
+/// Unlike deserializing a new value, effects and deriveds holding `target`
+/// keep working and see the restored state: `target` is written in place,
+/// in one batch, and only what changed is notified. On error `target` is
+/// left untouched.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, hydrate_from, signal};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let mut volume = signal(50);
+/// let saved = serde_json::to_string(&volume).unwrap();
+/// volume.set(80);
+///
+/// let heard = Rc::new(Cell::new(0));
+/// let _dispose = effect_sync({
+///     let (volume, heard) = (volume.clone(), heard.clone());
+///     move || heard.set(volume.get())
+/// });
+///
+/// let mut de = serde_json::Deserializer::from_str(&saved);
+/// hydrate_from(&mut volume, &mut de).unwrap();
+/// assert_eq!(heard.get(), 50);
+/// ```
+pub fn hydrate_from<'de, H, D>(target: &mut H, deserializer: D) -> Result<(), D::Error>
+where
+    H: Hydrate,
+    D: Deserializer<'de>,
+{
+    target.hydrate(deserializer)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::derived::derived;
+    use crate::primitives::effect::effect_sync;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn hydrate_json<H: Hydrate>(target: &mut H, json: &str) -> serde_json::Result<()> {
+        hydrate_from(target, &mut serde_json::Deserializer::from_str(json))
+    }
+
+    #[test]
+    fn round_trips_signals_and_collections() {
+        let count = signal(3);
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        let items = ReactiveVec::from_vec(vec!["a", "b"]);
+        let tags = ReactiveSet::from_iter([7]);
+        let scores = ReactiveMap::from_iter([("ada".to_string(), 10)]);
+
+        assert_eq!(serde_json::to_string(&count).unwrap(), "3");
+        assert_eq!(serde_json::to_string(&doubled).unwrap(), "6");
+        assert_eq!(serde_json::to_string(&items).unwrap(), r#"["a","b"]"#);
+        assert_eq!(serde_json::to_string(&tags).unwrap(), "[7]");
+        assert_eq!(serde_json::to_string(&scores).unwrap(), r#"{"ada":10}"#);
+
+        let back: Signal<i32> = serde_json::from_str("3").unwrap();
+        assert_eq!(back.get(), 3);
+        let back: ReactiveMap<String, i32> = serde_json::from_str(r#"{"ada":10}"#).unwrap();
+        assert_eq!(back.get("ada"), Some(&10));
+    }
+
+    #[test]
+    fn hydrating_a_map_notifies_only_changed_keys() {
+        let scores = Rc::new(RefCell::new(ReactiveMap::from_iter(
+            [("a", 1), ("b", 2), ("c", 3)].map(|(k, v)| (k.to_string(), v)),
+        )));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (scores, seen) = (scores.clone(), seen.clone());
+            move || {
+                let mut scores = scores.borrow_mut();
+                let a = scores.get_tracked(&"a".into()).copied();
+                let b = scores.get_tracked(&"b".into()).copied();
+                seen.borrow_mut().push((a, b));
+            }
+        });
+
+        // Release the borrow before the batch flushes the effect
+        let hydrate = |json: &str| batch(|| hydrate_json(&mut *scores.borrow_mut(), json));
+
+        // "a" and "b" unchanged; "c" removed and "d" added are not read
+        hydrate(r#"{"a":1,"b":2,"d":4}"#).unwrap();
+        assert_eq!(seen.borrow().len(), 1);
+
+        hydrate(r#"{"a":1,"b":5}"#).unwrap();
+        assert_eq!(*seen.borrow(), [(Some(1), Some(2)), (Some(1), Some(5))]);
+        assert_eq!(scores.borrow().len(), 2);
+    }
+
+    #[test]
+    fn failed_hydration_leaves_target_untouched() {
+        let mut items = ReactiveVec::from_vec(vec![1, 2]);
+        assert!(hydrate_json(&mut items, r#"["x"]"#).is_err());
+        assert_eq!(items.raw(), &vec![1, 2]);
+
+        hydrate_json(&mut items, "[3]").unwrap();
+        assert_eq!(items.raw(), &vec![3]);
+
+        let mut tags = ReactiveSet::from_iter([1, 2]);
+        hydrate_json(&mut tags, "[2, 3]").unwrap();
+        let mut restored: Vec<_> = tags.raw().iter().copied().collect();
+        restored.sort();
+        assert_eq!(restored, [2, 3]);
+    }
+}
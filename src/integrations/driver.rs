@@ -0,0 +1,394 @@
+// ============================================================================
+// spark-signals - ReactiveDriver
+// One-per-loop-iteration effect draining + coalesced repaint requests
+// ============================================================================
+//
+// Every GUI consumer ends up writing the same glue:
+//
+// 1. Run some per-iteration hooks (poll input, advance timers, ...)
+// 2. Drain whatever effects the event handlers scheduled
+// 3. If anything visual changed, ask the host to repaint - exactly once
+//
+// `ReactiveDriver` packages that loop body. Effects created through
+// `render_effect()` are tagged as "render" effects: whenever one runs, the
+// driver records that a repaint is needed, and the next `pump()` fires the
+// host's repaint callback once, no matter how many render effects ran.
+//
+// # winit
+//
+// ```ignore
+// let driver = ReactiveDriver::new();
+// let window = Rc::new(window);
+// driver.on_repaint({
+//     let window = window.clone();
+//     move || window.request_redraw()
+// });
+//
+// let _dispose = driver.render_effect({
+//     let title = title.clone();
+//     let window = window.clone();
+//     move || window.set_title(&title.get())
+// });
+//
+// event_loop.run(move |event, target| {
+//     match event {
+//         Event::WindowEvent { event, .. } => driver.handle_event(|| app.on_event(&event)),
+//         Event::AboutToWait => { driver.pump(); }
+//         _ => {}
+//     }
+// });
+// ```
+//
+// # egui
+//
+// ```ignore
+// impl eframe::App for MyApp {
+//     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+//         // Install the repaint hook once; egui::Context is cheap to clone
+//         if !self.hooked {
+//             let ctx = ctx.clone();
+//             self.driver.on_repaint(move || ctx.request_repaint());
+//             self.hooked = true;
+//         }
+//
+//         // Event handlers write signals inside handle_event()
+//         self.driver.pump();
+//         // ... draw UI from signal values ...
+//     }
+// }
+// ```
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::primitives::effect::effect;
+use crate::primitives::time::run_timers;
+use crate::reactivity::batching::{batch, untrack};
+use crate::reactivity::scheduling::flush_sync;
+
+/// Shared, repeatable host callback.
+type HookFn = Rc<dyn Fn()>;
+
+// =============================================================================
+// LOOP PHASE
+// =============================================================================
+
+/// When a hook registered with `ReactiveDriver::on_event()` runs within `pump()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoopPhase {
+    /// Before scheduled effects are drained (poll input, advance timers).
+    BeforeFlush,
+    /// After effects are drained, before the repaint callback fires.
+    AfterFlush,
+}
+
+// =============================================================================
+// REACTIVE DRIVER
+// =============================================================================
+
+struct DriverInner {
+    hooks: RefCell<Vec<(LoopPhase, HookFn)>>,
+    repaint: RefCell<Option<HookFn>>,
+    repaint_requested: Cell<bool>,
+    iteration: Cell<u64>,
+}
+
+/// Drives the reactive graph from a host event loop.
+///
+/// Cheap to clone - all clones share the same hooks and repaint state.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::signal;
+/// use spark_signals::integrations::ReactiveDriver;
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let driver = ReactiveDriver::new();
+/// let repaints = Rc::new(Cell::new(0));
+/// driver.on_repaint({
+///     let repaints = repaints.clone();
+///     move || repaints.set(repaints.get() + 1)
+/// });
+///
+/// let x = signal(0);
+/// let y = signal(0);
+/// let _a = driver.render_effect({ let x = x.clone(); move || { x.get(); } });
+/// let _b = driver.render_effect({ let y = y.clone(); move || { y.get(); } });
+///
+/// // Initial render
+/// assert!(driver.pump());
+/// assert_eq!(repaints.get(), 1);
+///
+/// // Two render effects run, but the host is asked to repaint once
+/// driver.handle_event(|| {
+///     x.set(1);
+///     y.set(1);
+/// });
+/// assert!(driver.pump());
+/// assert_eq!(repaints.get(), 2);
+///
+/// // Nothing changed - no repaint
+/// assert!(!driver.pump());
+/// ```
+#[derive(Clone)]
+pub struct ReactiveDriver {
+    inner: Rc<DriverInner>,
+}
+
+impl ReactiveDriver {
+    /// Create a new driver with no hooks and no repaint callback.
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(DriverInner {
+                hooks: RefCell::new(Vec::new()),
+                repaint: RefCell::new(None),
+                repaint_requested: Cell::new(false),
+                iteration: Cell::new(0),
+            }),
+        }
+    }
+
+    /// Register a hook that runs on every `pump()` at the given phase.
+    ///
+    /// Hooks run untracked, so reading signals inside them never subscribes
+    /// the surrounding effect (if `pump()` is itself called from one).
+    pub fn on_event<F>(&self, phase: LoopPhase, hook: F)
+    where
+        F: Fn() + 'static,
+    {
+        self.inner.hooks.borrow_mut().push((phase, Rc::new(hook)));
+    }
+
+    /// Set the host's repaint callback (`request_redraw`, `request_repaint`, ...).
+    ///
+    /// Replaces any previously installed callback.
+    pub fn on_repaint<F>(&self, f: F)
+    where
+        F: Fn() + 'static,
+    {
+        *self.inner.repaint.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Create an effect tagged as "render".
+    ///
+    /// Every run (including the first) marks the driver as needing a repaint.
+    /// Returns the effect's dispose function.
+    pub fn render_effect<F>(&self, mut f: F) -> impl FnOnce()
+    where
+        F: FnMut() + 'static,
+    {
+        let inner = self.inner.clone();
+        effect(move || {
+            f();
+            inner.repaint_requested.set(true);
+        })
+    }
+
+    /// Run an event handler with its signal writes batched.
+    ///
+    /// All writes made by `f` are coalesced, so each affected effect runs
+    /// at most once when the handler returns.
+    pub fn handle_event<T>(&self, f: impl FnOnce() -> T) -> T {
+        batch(f)
+    }
+
+    /// Mark the driver as needing a repaint on the next `pump()`.
+    pub fn request_repaint(&self) {
+        self.inner.repaint_requested.set(true);
+    }
+
+    /// Whether a repaint is pending for the next `pump()`.
+    pub fn needs_repaint(&self) -> bool {
+        self.inner.repaint_requested.get()
+    }
+
+    /// Number of completed `pump()` calls.
+    pub fn iteration(&self) -> u64 {
+        self.inner.iteration.get()
+    }
+
+    /// Run one loop iteration.
+    ///
+    /// Fires due timers (see `primitives::time`), runs `BeforeFlush` hooks,
+    /// drains scheduled effects, runs `AfterFlush`
+    /// hooks, then fires the repaint callback once if any render effect ran
+    /// (or `request_repaint()` was called) since the last pump.
+    ///
+    /// Returns true if a repaint was requested this iteration.
+    pub fn pump(&self) -> bool {
+        run_timers();
+        self.run_hooks(LoopPhase::BeforeFlush);
+        flush_sync();
+        self.run_hooks(LoopPhase::AfterFlush);
+
+        self.inner.iteration.set(self.inner.iteration.get() + 1);
+
+        if !self.inner.repaint_requested.replace(false) {
+            return false;
+        }
+
+        // Clone out so the callback may replace itself via on_repaint()
+        let repaint = self.inner.repaint.borrow().clone();
+        if let Some(repaint) = repaint {
+            untrack(|| repaint());
+        }
+        true
+    }
+
+    fn run_hooks(&self, phase: LoopPhase) {
+        // Snapshot so hooks may register further hooks
+        let hooks: Vec<HookFn> = self
+            .inner
+            .hooks
+            .borrow()
+            .iter()
+            .filter(|(p, _)| *p == phase)
+            .map(|(_, h)| h.clone())
+            .collect();
+
+        for hook in hooks {
+            untrack(|| hook());
+        }
+    }
+}
+
+impl Default for ReactiveDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::signal::signal;
+
+    fn counting_driver() -> (ReactiveDriver, Rc<Cell<u32>>) {
+        let driver = ReactiveDriver::new();
+        let repaints = Rc::new(Cell::new(0));
+        driver.on_repaint({
+            let repaints = repaints.clone();
+            move || repaints.set(repaints.get() + 1)
+        });
+        (driver, repaints)
+    }
+
+    #[test]
+    fn render_effects_coalesce_into_one_repaint() {
+        let (driver, repaints) = counting_driver();
+        let a = signal(0);
+        let b = signal(0);
+
+        let _ea = driver.render_effect({
+            let a = a.clone();
+            move || {
+                a.get();
+            }
+        });
+        let _eb = driver.render_effect({
+            let b = b.clone();
+            move || {
+                b.get();
+            }
+        });
+
+        assert!(driver.pump());
+        assert_eq!(repaints.get(), 1);
+
+        a.set(1);
+        b.set(1);
+        assert!(driver.needs_repaint());
+        assert!(driver.pump());
+        assert_eq!(repaints.get(), 2);
+
+        assert!(!driver.pump());
+        assert_eq!(repaints.get(), 2);
+        assert_eq!(driver.iteration(), 3);
+    }
+
+    #[test]
+    fn hooks_run_in_phase_order() {
+        let driver = ReactiveDriver::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        driver.on_event(LoopPhase::AfterFlush, {
+            let log = log.clone();
+            move || log.borrow_mut().push("after")
+        });
+        driver.on_event(LoopPhase::BeforeFlush, {
+            let log = log.clone();
+            move || log.borrow_mut().push("before")
+        });
+
+        driver.pump();
+        assert_eq!(*log.borrow(), vec!["before", "after"]);
+    }
+
+    #[test]
+    fn before_flush_writes_reach_render_effects_same_iteration() {
+        let (driver, repaints) = counting_driver();
+        let ticks = signal(0);
+        let seen = Rc::new(Cell::new(0));
+
+        let _e = driver.render_effect({
+            let ticks = ticks.clone();
+            let seen = seen.clone();
+            move || seen.set(ticks.get())
+        });
+        driver.pump();
+
+        driver.on_event(LoopPhase::BeforeFlush, {
+            let ticks = ticks.clone();
+            move || {
+                ticks.set(ticks.get() + 1);
+            }
+        });
+
+        assert!(driver.pump());
+        assert_eq!(seen.get(), 1);
+        assert_eq!(repaints.get(), 2);
+    }
+
+    #[test]
+    fn handle_event_batches_writes() {
+        let driver = ReactiveDriver::new();
+        let a = signal(0);
+        let b = signal(0);
+        let runs = Rc::new(Cell::new(0));
+
+        let _e = driver.render_effect({
+            let a = a.clone();
+            let b = b.clone();
+            let runs = runs.clone();
+            move || {
+                a.get();
+                b.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        driver.handle_event(|| {
+            a.set(1);
+            b.set(1);
+        });
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn request_repaint_without_effects() {
+        let (driver, repaints) = counting_driver();
+        assert!(!driver.pump());
+
+        driver.request_repaint();
+        assert!(driver.pump());
+        assert_eq!(repaints.get(), 1);
+    }
+}
@@ -0,0 +1,13 @@
+// ============================================================================
+// spark-signals - Host Loop Integrations
+// Glue for driving the reactive graph from GUI / game event loops
+// ============================================================================
+//
+// Enabled with the `integrations` feature. There are no dependencies on any
+// GUI crate - the driver is callback based, so it plugs into winit, egui,
+// or any hand-rolled loop the same way.
+// ============================================================================
+
+mod driver;
+
+pub use driver::{LoopPhase, ReactiveDriver};
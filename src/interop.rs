@@ -0,0 +1,285 @@
+// ============================================================================
+// spark-signals - Channel Interop
+// Bridge std::sync::mpsc channels into and out of the reactive graph
+// ============================================================================
+//
+// Existing channel-based code (worker threads, loggers, IO loops) talks in
+// `Sender`/`Receiver` pairs. Two adapters connect it to signals:
+//
+//     // Channel -> signal: the latest message, pulled on pump() or flush
+//     let status = signal_from_receiver(rx, Pump::OnFlush);
+//
+//     // Signal -> channel: every change is sent
+//     let _forward = forward_to_sender(&count, tx);
+//
+// Receivers never block the reactive thread: `pump()` drains with
+// `try_recv()`. `Pump::OnFlush` receivers are pumped at the start of every
+// `flush_sync()` / `tick()`, so a host loop that ticks once per iteration
+// picks up messages without any extra call.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+
+use crate::primitives::signal::{signal, ReadSignal, Signal};
+use crate::primitives::subscribe::Subscription;
+use crate::reactivity::batching::untrack;
+
+// =============================================================================
+// RECEIVER -> SIGNAL
+// =============================================================================
+
+/// When a [`ReceiverSignal`] pulls messages off its channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pump {
+    /// Only when [`ReceiverSignal::pump()`] is called
+    #[default]
+    Manual,
+    /// Also at the start of every `flush_sync()` / `tick()` on this thread
+    OnFlush,
+}
+
+type PumpFn = dyn Fn() -> usize;
+
+thread_local! {
+    /// `Pump::OnFlush` receivers; dead entries are pruned on the next flush
+    static ON_FLUSH: RefCell<Vec<Weak<PumpFn>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pump every live `Pump::OnFlush` receiver (called by the scheduler).
+pub(crate) fn pump_receivers() {
+    let live: Vec<Rc<PumpFn>> = ON_FLUSH.with(|pumps| {
+        let mut pumps = pumps.borrow_mut();
+        if pumps.is_empty() {
+            return Vec::new();
+        }
+        pumps.retain(|pump| pump.strong_count() > 0);
+        pumps.iter().filter_map(Weak::upgrade).collect()
+    });
+    for pump in live {
+        pump();
+    }
+}
+
+struct ReceiverInner<T> {
+    receiver: Receiver<T>,
+    signal: Signal<Option<T>>,
+    disconnected: Cell<bool>,
+}
+
+impl<T: PartialEq + 'static> ReceiverInner<T> {
+    fn pump(&self) -> usize {
+        let mut received = 0;
+        let mut latest = None;
+        loop {
+            match self.receiver.try_recv() {
+                Ok(message) => {
+                    received += 1;
+                    latest = Some(message);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.disconnected.set(true);
+                    break;
+                }
+            }
+        }
+        if latest.is_some() {
+            untrack(|| self.signal.set(latest));
+        }
+        received
+    }
+}
+
+/// A signal holding the latest message received on a channel.
+///
+/// Created by [`signal_from_receiver()`]. Messages are only pulled while
+/// this handle is alive; the signal from [`signal()`](Self::signal) keeps
+/// its last value after it is dropped.
+pub struct ReceiverSignal<T> {
+    inner: Rc<ReceiverInner<T>>,
+    /// Registration in `ON_FLUSH`, for `Pump::OnFlush`
+    _on_flush: Option<Rc<PumpFn>>,
+}
+
+impl<T: Clone + PartialEq + 'static> ReceiverSignal<T> {
+    /// The latest message, or None before the first one (tracked).
+    pub fn get(&self) -> Option<T> {
+        self.inner.signal.get()
+    }
+
+    /// A read handle on the latest message.
+    pub fn signal(&self) -> ReadSignal<Option<T>> {
+        self.inner.signal.clone().split().0
+    }
+
+    /// Pull every message waiting on the channel, without blocking. Returns
+    /// the number received.
+    ///
+    /// Only the last one is written to the signal, so effects see one change
+    /// per pump however many messages arrived.
+    pub fn pump(&self) -> usize {
+        self.inner.pump()
+    }
+
+    /// True once a pump found every sender dropped.
+    pub fn is_disconnected(&self) -> bool {
+        self.inner.disconnected.get()
+    }
+}
+
+impl<T> std::fmt::Debug for ReceiverSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReceiverSignal")
+            .field("on_flush", &self._on_flush.is_some())
+            .field("disconnected", &self.inner.disconnected.get())
+            .finish()
+    }
+}
+
+/// Follow a channel from the reactive thread: the returned signal holds the
+/// latest message received, None until the first.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::interop::{signal_from_receiver, Pump};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// let progress = signal_from_receiver(rx, Pump::Manual);
+///
+/// std::thread::spawn(move || {
+///     for pct in [10, 50, 100] {
+///         tx.send(pct).unwrap();
+///     }
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(progress.get(), None);
+/// assert_eq!(progress.pump(), 3);
+/// assert_eq!(progress.get(), Some(100));
+/// assert!(progress.is_disconnected());
+/// ```
+pub fn signal_from_receiver<T>(receiver: Receiver<T>, pump: Pump) -> ReceiverSignal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let inner = Rc::new(ReceiverInner {
+        receiver,
+        signal: signal(None),
+        disconnected: Cell::new(false),
+    });
+
+    let on_flush = match pump {
+        Pump::Manual => None,
+        Pump::OnFlush => {
+            let weak = Rc::downgrade(&inner);
+            let pump: Rc<PumpFn> =
+                Rc::new(move || weak.upgrade().map_or(0, |inner| inner.pump()));
+            ON_FLUSH.with(|pumps| pumps.borrow_mut().push(Rc::downgrade(&pump)));
+            Some(pump)
+        }
+    };
+
+    ReceiverSignal {
+        inner,
+        _on_flush: on_flush,
+    }
+}
+
+// =============================================================================
+// SIGNAL -> SENDER
+// =============================================================================
+
+/// Send every change of `signal` on `sender`.
+///
+/// Built on [`Signal::subscribe()`]: sends happen synchronously on each
+/// changing write, untracked, and stop when the returned subscription is
+/// dropped. The current value is not sent. Once the receiver is gone,
+/// sends fail silently.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::interop::forward_to_sender;
+/// use spark_signals::signal;
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// let count = signal(0);
+/// let forward = forward_to_sender(&count, tx);
+///
+/// count.set(1);
+/// count.set(1); // unchanged: not sent
+/// count.set(2);
+/// drop(forward);
+/// count.set(3);
+///
+/// assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+/// ```
+pub fn forward_to_sender<T>(signal: &Signal<T>, sender: Sender<T>) -> Subscription
+where
+    T: Clone + 'static,
+{
+    signal.subscribe(move |new, _| {
+        let _ = sender.send(new.clone());
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::reactivity::batching::tick;
+    use std::sync::mpsc;
+
+    #[test]
+    fn on_flush_receivers_are_pumped_by_tick() {
+        let (tx, rx) = mpsc::channel();
+        let status = signal_from_receiver(rx, Pump::OnFlush);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (status, seen) = (status.signal(), seen.clone());
+            move || seen.borrow_mut().push(status.get())
+        });
+
+        tx.send("loading").unwrap();
+        tx.send("ready").unwrap();
+        tick();
+        assert_eq!(*seen.borrow(), [None, Some("ready")]);
+
+        // Dropping the handle closes the channel and unregisters the pump
+        let read = status.signal();
+        drop(status);
+        assert!(tx.send("stale").is_err());
+        tick();
+        assert_eq!(read.get(), Some("ready"));
+        assert_eq!(ON_FLUSH.with(|pumps| pumps.borrow().len()), 0);
+    }
+
+    #[test]
+    fn round_trip_through_a_channel() {
+        let (tx, rx) = mpsc::channel();
+        let source = signal(0);
+        let _forward = forward_to_sender(&source, tx);
+        let mirror = signal_from_receiver(rx, Pump::Manual);
+
+        source.set(5);
+        tick();
+        assert_eq!(mirror.get(), None);
+        assert_eq!(mirror.pump(), 1);
+        assert_eq!(mirror.get(), Some(5));
+        assert!(!mirror.is_disconnected());
+
+        drop(_forward);
+        assert_eq!(mirror.pump(), 0);
+        assert!(mirror.is_disconnected());
+    }
+}
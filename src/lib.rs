@@ -8,76 +8,168 @@
 
 pub mod collections;
 pub mod core;
+pub mod debug;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "serde")]
+pub mod hydrate;
+#[cfg(feature = "integrations")]
+pub mod integrations;
+pub mod interop;
 #[macro_use]
 pub mod macros;
 pub mod primitives;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod reactivity;
+pub mod record;
 pub mod shared;
+#[cfg(feature = "async")]
+pub mod stream;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod testing;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export core items at crate root for ergonomic access
+pub use core::config::{clear_thread_config, current_config, init, set_thread_config, Config};
 pub use core::constants;
 pub use core::context::{
-    is_batching, is_tracking, is_untracking, read_version, with_context, write_version,
-    ReactiveContext,
+    is_batching, is_tracking, is_untracking, read_version, stats, with_context, write_version,
+    ReactiveContext, ReactiveStats, Runtime,
+};
+pub use core::types::{
+    default_equals, AnyReaction, AnySource, AnySourceRef, EqualsFn, SourceInner,
 };
-pub use core::types::{default_equals, AnyReaction, AnySource, EqualsFn, SourceInner};
 
 // Re-export primitives at crate root (TypeScript-like API)
+pub use primitives::any_signal::AnySignal;
+pub use primitives::arena::{ArenaSignal, SignalArena};
 pub use primitives::bind::{
     bind, bind_chain, bind_getter, bind_readonly, bind_readonly_from, bind_readonly_static,
     bind_static, bind_value, binding_has_internal_source, disconnect_binding, disconnect_source,
     is_binding, unwrap_binding, unwrap_readonly, Binding, IsBinding, ReadonlyBinding,
 };
-pub use primitives::derived::{derived, derived_with_equals, Derived, DerivedInner};
+pub use primitives::cell::{signal_cell, BitEq, SignalCell};
+pub use primitives::combinators::OptionSignal;
+pub use primitives::derived::{
+    derived, derived_with_equals, derived_with_history, Derived, DerivedInner, DerivedStats,
+};
+pub use primitives::each::{keyed_each, KeyedEach};
 pub use primitives::effect::{
-    effect, effect_root, effect_sync, effect_sync_with_cleanup, effect_tracking,
-    effect_with_cleanup, CleanupFn, DisposeFn, Effect, EffectFn, EffectInner,
+    effect, effect_async, effect_in_lane, effect_lazy, effect_once, effect_root, effect_sync,
+    effect_sync_with_cleanup, effect_times, effect_tracking, effect_with_cleanup, effect_with_deps,
+    on_cleanup, on_effect_error, set_effect_name, set_max_children_per_effect, set_max_effect_depth,
+    CleanupFn, DisposeFn, Effect, EffectError, EffectErrorHook, EffectFn, EffectInner, LazyEffect,
+    LocalBoxFuture, PanicPolicy,
 };
+#[cfg(feature = "debug-deps")]
+pub use primitives::effect::{source_id, DepChange};
+pub use primitives::fallible::derived_result;
+pub use primitives::group::{GroupError, GroupField, GroupValidator, GroupView, SignalGroup};
+pub use primitives::incremental::{advance_incremental, derived_incremental, Incremental, Step};
 pub use primitives::linked::{
     is_linked_signal, linked_signal, linked_signal_full, linked_signal_with_options,
-    IsLinkedSignal, LinkedSignal, LinkedSignalOptionsSimple, PreviousValue,
+    IsLinkedSignal, LinkedSignal, LinkedSignalOptionsSimple, PreviousValue, ResetPolicy,
+};
+pub use primitives::memo::{
+    memo_keyed, memo_structural, pure_memo, KeyedMemo, PureMemo, StructuralMemo,
+};
+pub use primitives::persisted::{
+    signal_persisted, signal_persisted_with, FileStorage, MemoryStorage, PersistedSignal,
+    StorageBackend,
+};
+pub use primitives::pipe::{pipe, Pipe};
+pub use primitives::resource::{resource, Resource};
+pub use primitives::props::{
+    into_derived, reactive_prop, IntoPropValue, MaybeSignal, PropValue, PropsBuilder, UnwrapProp,
+};
+#[cfg(feature = "macros")]
+pub use spark_signals_macros::Props;
+pub use primitives::selector::{
+    create_dense_selector, create_multi_selector, create_selector, create_selector_by,
+    create_selector_eq, DenseSelector, MultiSelector, Selector,
 };
-pub use primitives::props::{into_derived, reactive_prop, PropValue, PropsBuilder, UnwrapProp};
-pub use primitives::selector::{create_selector, create_selector_eq, Selector};
 pub use primitives::scope::{
-    effect_scope, get_current_scope, on_scope_dispose, EffectScope, ScopeCleanupFn,
+    effect_scope, effect_scope_detached, get_current_scope, on_scope_dispose, EffectScope,
+    ScopeCleanupFn,
 };
 pub use primitives::signal::{
-    mutable_source, signal, signal_f32, signal_f64, signal_with_equals, source, Signal,
-    SourceOptions,
+    mutable_source, signal, signal_f32, signal_f32_with, signal_f64, signal_f64_with, signal_shared,
+    signal_with_equals, signal_with_history, source, split_signal, ReadSignal, Signal,
+    SourceOptions, WeakSignal, WriteSignal,
 };
+pub use primitives::dirty::DirtyTracker;
 pub use primitives::slot::{
-    dirty_set, is_slot, slot, slot_array, slot_with_value, tracked_slot, tracked_slot_array,
-    DirtySet, IsSlot, Slot, SlotArray, SlotWriteError, TrackedSlot, TrackedSlotArray,
+    dirty_set, dirty_set_dense, is_slot, slot, slot_array, slot_with_value, tracked_slot,
+    tracked_slot_array, DirtySet, IsSlot, Slot, SlotArray, SlotWriteError, TrackedSlot,
+    TrackedSlotArray,
+};
+pub use primitives::snapshot::{capture, restore, Snapshot};
+pub use primitives::store::{store, Store, StoreField};
+pub use primitives::subscribe::Subscription;
+pub use primitives::time::{
+    debounced, run_timers, set_clock, signal_interval, signal_timeout, throttled,
+    until_next_timer, Clock, ManualClock, SystemClock,
 };
+pub use primitives::watch::{watch, WatchOptions, WatchSource};
+pub use primitives::writable::{derived_writable, WritableDerived};
 
 // Re-export reactivity functions
-pub use reactivity::batching::{batch, peek, tick, untrack};
+pub use reactivity::batching::{
+    await_settled, batch, is_settled, peek, start_batch, tick, transaction, untrack, BatchGuard,
+    Settled,
+};
 pub use reactivity::equality::{
-    always_equals, by_field, deep_equals, equals, never_equals, safe_equals_f32, safe_equals_f64,
-    safe_equals_option_f64, safe_not_equal_f32, safe_not_equal_f64, shallow_equals_slice,
-    shallow_equals_vec,
+    always_equals, by_field, deep_equals, default_float_equals, equals, equals_by_ptr,
+    equals_epsilon, equals_epsilon_f32, never_equals, safe_equals_f32, safe_equals_f64,
+    safe_equals_option_f64, safe_not_equal_f32, safe_not_equal_f64, set_default_float_equals,
+    shallow_equals_slice, shallow_equals_vec, FloatEquals, PtrEq,
+};
+pub use reactivity::scheduling::{
+    flush_idle, flush_sync, flush_sync_with, has_idle_work, on_flush_end, on_flush_start,
+    set_max_update_depth, try_flush, Lane, Scheduler, UpdateDepthExceeded,
 };
-pub use reactivity::scheduling::flush_sync;
+pub use reactivity::middleware::{add_write_middleware, WriteAction};
+pub use reactivity::test_scheduler::TestScheduler;
+#[cfg(feature = "sync")]
+pub use reactivity::inbox::{drain_posted, post, reactive_thread, ReactiveThread, SignalSender};
+#[cfg(feature = "sync")]
+pub use sync::{sync_derived, sync_effect, sync_signal, SyncDerived, SyncEffect, SyncSignal};
 pub use reactivity::tracking::{
     is_dirty, mark_reactions, notify_write, remove_reactions, set_signal_status, track_read,
 };
 
 // Re-export collections
-pub use collections::{ReactiveMap, ReactiveSet, ReactiveVec};
+pub use collections::{
+    ReactiveMap, ReactiveSet, ReactiveVec, ReadonlyReactiveMap, ReadonlyReactiveVec,
+};
+
+// Re-export serde helpers
+#[cfg(feature = "serde")]
+pub use hydrate::{hydrate_from, Hydrate};
 
 // Re-export repeater
-pub use primitives::repeater::{repeat, RepeaterInner};
+pub use primitives::repeater::{
+    flush_deferred_repeaters, repeat, repeat_with_mode, repeater, repeater_map, repeater_when,
+    RepeatMode, RepeatSource, RepeatTarget, RepeaterInner,
+};
 
 // Re-export shared memory primitives (for FFI bridges)
 pub use shared::{
-    wait_for_wake, wait_for_wake_timeout, MutableSharedArray, MutableSharedF32Array,
-    ReactiveSharedArray, ReactiveSharedF32Array, ReactiveSharedI32Array, ReactiveSharedU32Array,
-    ReactiveSharedU8Array, SharedBufferContext,
+    wait_for_wake, wait_for_wake_timeout, AtomicElement, MutableSharedArray,
+    MutableSharedF32Array, ReactiveSharedArray, ReactiveSharedF32Array, ReactiveSharedI32Array,
+    ReactiveSharedU32Array, ReactiveSharedU8Array, SharedBufferContext,
 };
 
 // Re-export new shared primitives (Layer 1 + Notifier)
-pub use shared::notify::{platform_wake, AtomicsNotifier, Notifier, NoopNotifier};
+pub use shared::bridge::{bind_shared_to_signals, SharedBinding, SlotSink};
+pub use shared::interpolated::InterpolatedSharedArray;
+pub use shared::layout::{LayoutError, RegionInfo, RegionKind, SharedLayout, SharedRegions};
+pub use shared::notify::{
+    notify_all, notify_one, platform_wake, AtomicsNotifier, Notifier, NoopNotifier,
+};
 pub use shared::shared_slot_buffer::SharedSlotBuffer;
 
 // =============================================================================
@@ -136,6 +228,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn phase1_success_criteria_4_heterogeneous_storage() {
         let int_source: Rc<dyn AnySource> = Rc::new(SourceInner::new(42i32));
         let string_source: Rc<dyn AnySource> = Rc::new(SourceInner::new(String::from("hello")));
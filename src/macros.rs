@@ -18,16 +18,42 @@
 /// // Use:
 /// let sum = derived(cloned!(a, b => move || a.get() + b.get()));
 /// ```
+///
+/// A capture can also name an expression to clone, e.g. a struct field:
+///
+/// ```rust
+/// use spark_signals::{cloned, derived, signal, Signal};
+///
+/// struct Counter {
+///     count: Signal<i32>,
+/// }
+///
+/// let counter = Counter { count: signal(2) };
+/// let doubled = derived(cloned!(count = counter.count => move || count.get() * 2));
+/// assert_eq!(doubled.get(), 4);
+/// ```
 #[macro_export]
 macro_rules! cloned {
-    ($($n:ident),+ => $e:expr) => {
+    ($($n:ident $(= $src:expr)?),+ => $e:expr) => {
         {
-            $( let $n = $n.clone(); )+
+            $( let $n = $crate::__cloned_capture!($n $(, $src)?); )+
             $e
         }
     };
 }
 
+/// Clone one `cloned!` capture: the variable itself or the named expression.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cloned_capture {
+    ($n:ident) => {
+        $n.clone()
+    };
+    ($n:ident, $src:expr) => {
+        $src.clone()
+    };
+}
+
 /// Create a derived signal with automatic variable capturing.
 ///
 /// Wraps `derived(cloned!(... => move || ...))`.
@@ -45,8 +71,8 @@ macro_rules! cloned {
 #[macro_export]
 macro_rules! derived {
     // Case 1: With dependencies
-    ($($deps:ident),+ => $body:expr) => {
-        $crate::derived($crate::cloned!($($deps),+ => move || $body))
+    ($($deps:ident $(= $src:expr)?),+ => $body:expr) => {
+        $crate::derived($crate::cloned!($($deps $(= $src)?),+ => move || $body))
     };
     // Case 2: No dependencies (just expression)
     ($body:expr) => {
@@ -71,8 +97,8 @@ macro_rules! derived {
 #[macro_export]
 macro_rules! effect {
     // Case 1: With dependencies
-    ($($deps:ident),+ => $body:expr) => {
-        $crate::effect($crate::cloned!($($deps),+ => move || $body))
+    ($($deps:ident $(= $src:expr)?),+ => $body:expr) => {
+        $crate::effect($crate::cloned!($($deps $(= $src)?),+ => move || $body))
     };
     // Case 2: No dependencies
     ($body:expr) => {
@@ -80,6 +106,37 @@ macro_rules! effect {
     };
 }
 
+/// Create a synchronous effect with automatic variable capturing.
+///
+/// Wraps `effect_sync(cloned!(... => move || ...))`. Keep the returned
+/// dispose function alive for as long as the effect should run.
+///
+/// # Usage
+///
+/// ```rust
+/// use spark_signals::{effect_sync, signal};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let count = signal(1);
+/// let seen = Rc::new(Cell::new(0));
+///
+/// let _dispose = effect_sync!(count, seen => seen.set(count.get()));
+/// count.set(5);
+/// assert_eq!(seen.get(), 5);
+/// ```
+#[macro_export]
+macro_rules! effect_sync {
+    // Case 1: With dependencies
+    ($($deps:ident $(= $src:expr)?),+ => $body:expr) => {
+        $crate::effect_sync($crate::cloned!($($deps $(= $src)?),+ => move || $body))
+    };
+    // Case 2: No dependencies
+    ($body:expr) => {
+        $crate::effect_sync(move || $body)
+    };
+}
+
 /// Create a prop getter with automatic variable capturing.
 ///
 /// Wraps `PropValue::Getter(Box::new(cloned!(... => move || ...)))`.
@@ -97,11 +154,41 @@ macro_rules! effect {
 #[macro_export]
 macro_rules! prop {
     // Case 1: With dependencies
-    ($($deps:ident),+ => $body:expr) => {
-        $crate::PropValue::Getter(Box::new($crate::cloned!($($deps),+ => move || $body)))
+    ($($deps:ident $(= $src:expr)?),+ => $body:expr) => {
+        $crate::PropValue::Getter(Box::new($crate::cloned!($($deps $(= $src)?),+ => move || $body)))
     };
     // Case 2: No dependencies (just expression)
     ($body:expr) => {
         $crate::PropValue::Getter(Box::new(move || $body))
     };
 }
+
+/// Assert that a closure causes exactly `n` effect runs on this thread, and
+/// return the closure's result.
+///
+/// # Usage
+///
+/// ```rust
+/// use spark_signals::{assert_effect_runs, effect, signal};
+///
+/// let count = signal(0);
+/// let _dispose = effect({
+///     let count = count.clone();
+///     move || {
+///         count.get();
+///     }
+/// });
+///
+/// assert_effect_runs!(1, || count.set(1));
+/// assert_effect_runs!(0, || count.set(1)); // unchanged value
+/// ```
+#[macro_export]
+macro_rules! assert_effect_runs {
+    ($n:expr, $f:expr) => {{
+        let before = $crate::testing::effect_run_count();
+        let result = ($f)();
+        let runs = $crate::testing::effect_run_count() - before;
+        assert_eq!(runs, $n as u64, "expected {} effect runs, got {}", $n, runs);
+        result
+    }};
+}
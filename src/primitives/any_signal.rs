@@ -0,0 +1,203 @@
+// ============================================================================
+// spark-signals - AnySignal
+// Type-erased signal handle with downcasting back to Signal<T>
+// ============================================================================
+//
+// `Signal::as_any_source()` erases a signal to its graph node, which is
+// enough for tracking but loses the value. `AnySignal` keeps the TypeId and
+// a monomorphized reader next to the node, so heterogeneous registries
+// (devtools, inspectors, keyed stores) can hold one handle per signal and
+// still get typed handles or boxed values back out.
+// ============================================================================
+
+use std::any::{Any, TypeId};
+use std::rc::Rc;
+
+use crate::core::types::{AnySource, AnySourceRef, SourceInner};
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::untrack;
+
+/// Reads the node as `Box<dyn Any>`; built for one concrete `T`.
+type ReadFn = fn(&Rc<dyn Any>) -> Box<dyn Any>;
+
+/// Writes a clone of a `&dyn Any` holding a `T`; None for another type.
+type WriteFn = fn(&Rc<dyn Any>, &dyn Any) -> Option<bool>;
+
+/// A type-erased [`Signal`] that can be downcast back to `Signal<T>`.
+///
+/// Clones share the signal, like `Signal` clones.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{signal, AnySignal};
+///
+/// let registry: Vec<AnySignal> = vec![signal(1).into(), signal("two").into()];
+///
+/// let count = registry[0].downcast::<i32>().unwrap();
+/// count.set(5);
+/// assert_eq!(registry[0].get_any().downcast_ref::<i32>(), Some(&5));
+///
+/// assert!(registry[1].downcast::<i32>().is_none());
+/// assert!(registry[1].is::<&str>());
+/// ```
+#[derive(Clone)]
+pub struct AnySignal {
+    /// The `SourceInner<T>` as `Any`, for downcasting
+    node: Rc<dyn Any>,
+    /// The same node as a graph source
+    source: Rc<dyn AnySource>,
+    type_id: TypeId,
+    type_name: &'static str,
+    read: ReadFn,
+    write: WriteFn,
+}
+
+fn read_as<T: Clone + 'static>(node: &Rc<dyn Any>) -> Box<dyn Any> {
+    let inner = node
+        .clone()
+        .downcast::<SourceInner<T>>()
+        .expect("AnySignal node matches its type id");
+    Box::new(Signal::from_inner(inner).get())
+}
+
+fn write_as<T: Clone + 'static>(node: &Rc<dyn Any>, value: &dyn Any) -> Option<bool> {
+    let value = value.downcast_ref::<T>()?.clone();
+    let inner = node
+        .clone()
+        .downcast::<SourceInner<T>>()
+        .expect("AnySignal node matches its type id");
+    Some(Signal::from_inner(inner).set(value))
+}
+
+impl AnySignal {
+    /// Erase a signal.
+    pub fn new<T: Clone + 'static>(signal: &Signal<T>) -> Self {
+        let inner = signal.inner().clone();
+        Self {
+            node: inner.clone(),
+            source: inner,
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            read: read_as::<T>,
+            write: write_as::<T>,
+        }
+    }
+
+    /// The typed signal, if this holds a `Signal<T>`.
+    pub fn downcast<T: 'static>(&self) -> Option<Signal<T>> {
+        self.node
+            .clone()
+            .downcast::<SourceInner<T>>()
+            .ok()
+            .map(Signal::from_inner)
+    }
+
+    /// True if this holds a `Signal<T>`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// `TypeId` of the value type.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Name of the value type, for display.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// A clone of the current value, boxed (tracked).
+    pub fn get_any(&self) -> Box<dyn Any> {
+        (self.read)(&self.node)
+    }
+
+    /// A clone of the current value, boxed, without tracking.
+    pub fn peek_any(&self) -> Box<dyn Any> {
+        untrack(|| self.get_any())
+    }
+
+    /// Set the signal to a clone of `value` if it holds a `T`, returning
+    /// whether the value changed; None (and no write) for any other type.
+    pub fn set_any(&self, value: &dyn Any) -> Option<bool> {
+        (self.write)(&self.node, value)
+    }
+
+    /// The graph node, for APIs taking `Rc<dyn AnySource>`.
+    pub fn as_any_source(&self) -> Rc<dyn AnySource> {
+        self.source.clone()
+    }
+
+    /// True if both handles erase the same signal.
+    pub fn ptr_eq(&self, other: &AnySignal) -> bool {
+        Rc::ptr_eq(&self.source, &other.source)
+    }
+}
+
+impl<T: Clone + 'static> From<Signal<T>> for AnySignal {
+    fn from(signal: Signal<T>) -> Self {
+        Self::new(&signal)
+    }
+}
+
+impl<T: Clone + 'static> From<&Signal<T>> for AnySignal {
+    fn from(signal: &Signal<T>) -> Self {
+        Self::new(signal)
+    }
+}
+
+impl AnySourceRef for AnySignal {
+    fn any_source(&self) -> Rc<dyn AnySource> {
+        self.source.clone()
+    }
+}
+
+impl std::fmt::Debug for AnySignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnySignal")
+            .field("type", &self.type_name)
+            .field("write_version", &self.source.write_version())
+            .field("reactions", &self.source.reaction_count())
+            .finish()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use std::cell::Cell;
+
+    #[test]
+    fn erased_reads_track_and_downcasts_share_the_signal() {
+        let name = signal(String::from("a"));
+        let erased = AnySignal::from(&name);
+        assert_eq!(erased.type_name(), "alloc::string::String");
+        assert!(erased.downcast::<i32>().is_none());
+
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (erased, runs) = (erased.clone(), runs.clone());
+            move || {
+                erased.get_any();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        erased.downcast::<String>().unwrap().set(String::from("b"));
+        assert_eq!(runs.get(), 2);
+        assert_eq!(name.get(), "b");
+
+        assert_eq!(erased.set_any(&7), None);
+        assert_eq!(erased.set_any(&String::from("c")), Some(true));
+        assert_eq!(runs.get(), 3);
+        assert!(erased.ptr_eq(&AnySignal::new(&name)));
+        assert!(format!("{erased:?}").contains("String"));
+    }
+}
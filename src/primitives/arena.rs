@@ -0,0 +1,397 @@
+// ============================================================================
+// spark-signals - Signal Arena
+// Slab storage for many signals of one type, addressed by index handles
+// ============================================================================
+//
+// Every `signal()` is its own `Rc` allocation. For ECS-sized workloads
+// (tens of thousands of components) that's a lot of small heaps:
+//
+//     let positions = SignalArena::with_capacity(10_000);
+//     let ids: Vec<_> = (0..10_000).map(|i| positions.alloc(i as f32)).collect();
+//     positions.set(ids[42], 1.5);
+//
+// Values live in one `Vec<T>`. A slot only gets a graph node (an
+// `Rc<dyn AnySource>`) the first time a reaction reads it, or when asked
+// for one with `source()`, so slots nobody observes cost one `T` each.
+// Writes to a slot without a node have no one to notify.
+// ============================================================================
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::core::context::with_context;
+use crate::core::types::{default_equals, AnySource, EqualsFn, SourceInner};
+use crate::reactivity::middleware::{has_write_middleware, run_write_middleware};
+use crate::reactivity::tracking::{notify_write, track_read};
+
+/// A slot in a [`SignalArena`]. Plain index, `Copy`.
+///
+/// A handle must only be used with the arena that allocated it.
+pub struct ArenaSignal<T> {
+    index: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ArenaSignal<T> {
+    /// Position of the slot in its arena.
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+impl<T> Clone for ArenaSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArenaSignal<T> {}
+
+impl<T> PartialEq for ArenaSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for ArenaSignal<T> {}
+
+impl<T> Hash for ArenaSignal<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for ArenaSignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArenaSignal").field(&self.index).finish()
+    }
+}
+
+struct ArenaInner<T> {
+    values: RefCell<Vec<T>>,
+    /// Graph node of each slot, created on first tracked read
+    nodes: RefCell<Vec<Option<Rc<SourceInner<()>>>>>,
+    equals: EqualsFn<T>,
+}
+
+impl<T> ArenaInner<T> {
+    fn values(&self) -> Ref<'_, Vec<T>> {
+        self.values
+            .try_borrow()
+            .expect("SignalArena read from inside its own update()")
+    }
+
+    fn values_mut(&self) -> RefMut<'_, Vec<T>> {
+        self.values
+            .try_borrow_mut()
+            .expect("SignalArena written from inside its own with() or update()")
+    }
+}
+
+/// Signals of one type stored in a single slab.
+///
+/// Reads and writes go through the arena with an [`ArenaSignal`] handle and
+/// track and notify like a [`Signal`](crate::Signal). Clones share the
+/// arena. Slots are never freed individually; drop the arena instead.
+///
+/// Writes are not staged by `transaction()`: they apply immediately.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, SignalArena};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let healths = SignalArena::with_capacity(10_000);
+/// let ids: Vec<_> = (0..10_000).map(|_| healths.alloc(100)).collect();
+///
+/// let seen = Rc::new(Cell::new(0));
+/// let _watch = effect_sync({
+///     let (healths, seen, player) = (healths.clone(), seen.clone(), ids[7]);
+///     move || seen.set(healths.get(player))
+/// });
+/// assert_eq!(healths.node_count(), 1); // only the observed slot
+///
+/// healths.set(ids[7], 80);
+/// assert_eq!(seen.get(), 80);
+/// ```
+pub struct SignalArena<T> {
+    inner: Rc<ArenaInner<T>>,
+}
+
+impl<T: PartialEq + 'static> SignalArena<T> {
+    /// An empty arena.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// An empty arena with room for `capacity` slots.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_equals(capacity, default_equals)
+    }
+}
+
+impl<T: PartialEq + 'static> Default for SignalArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> SignalArena<T> {
+    /// An empty arena with room for `capacity` slots, comparing writes with
+    /// `equals`.
+    pub fn with_capacity_and_equals(capacity: usize, equals: EqualsFn<T>) -> Self {
+        Self {
+            inner: Rc::new(ArenaInner {
+                values: RefCell::new(Vec::with_capacity(capacity)),
+                nodes: RefCell::new(Vec::with_capacity(capacity)),
+                equals,
+            }),
+        }
+    }
+
+    /// Add a slot holding `value`.
+    ///
+    /// # Panics
+    ///
+    /// If the arena already holds `u32::MAX` slots.
+    pub fn alloc(&self, value: T) -> ArenaSignal<T> {
+        let mut values = self.inner.values_mut();
+        let index = u32::try_from(values.len()).expect("signal arena is full");
+        values.push(value);
+        self.inner.nodes.borrow_mut().push(None);
+        ArenaSignal {
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of slots.
+    pub fn len(&self) -> usize {
+        self.inner.values().len()
+    }
+
+    /// True if no slot was allocated.
+    pub fn is_empty(&self) -> bool {
+        self.inner.values().is_empty()
+    }
+
+    /// Slots the arena can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.values().capacity()
+    }
+
+    /// Number of slots with a graph node (read by a reaction, or passed to
+    /// `source()`).
+    pub fn node_count(&self) -> usize {
+        self.inner.nodes.borrow().iter().flatten().count()
+    }
+
+    /// Read a slot (tracked).
+    ///
+    /// # Panics
+    ///
+    /// If `slot` is out of range.
+    pub fn get(&self, slot: ArenaSignal<T>) -> T
+    where
+        T: Clone,
+    {
+        self.with(slot, T::clone)
+    }
+
+    /// Access a slot's value with a closure (tracked, avoids cloning).
+    ///
+    /// # Panics
+    ///
+    /// If `slot` is out of range, or if `f` writes to or allocates in this
+    /// arena: its values stay borrowed while `f` runs. Reading other slots
+    /// is fine; to write based on a value, `get()` it first.
+    pub fn with<R>(&self, slot: ArenaSignal<T>, f: impl FnOnce(&T) -> R) -> R {
+        let tracking = with_context(|ctx| ctx.has_active_reaction() && !ctx.is_untracking());
+        if tracking {
+            track_read(self.source(slot));
+        }
+        f(&self.inner.values()[slot.index()])
+    }
+
+    /// Write a slot. Returns true if the value changed.
+    ///
+    /// # Panics
+    ///
+    /// If `slot` is out of range, or if write middleware writes to this
+    /// arena while checking the write.
+    pub fn set(&self, slot: ArenaSignal<T>, mut value: T) -> bool {
+        if let Some(source) = self.middleware_source(slot) {
+            let values = self.inner.values();
+            if !run_write_middleware(&source, Some(&values[slot.index()]), &mut value) {
+                return false;
+            }
+        }
+        {
+            let mut values = self.inner.values_mut();
+            let current = &mut values[slot.index()];
+            if (self.inner.equals)(current, &value) {
+                return false;
+            }
+            *current = value;
+        }
+        self.notify(slot);
+        true
+    }
+
+    /// Mutate a slot in place, notifying its readers.
+    ///
+    /// # Panics
+    ///
+    /// If `slot` is out of range, or if `f` or write middleware checking
+    /// the write touches this arena.
+    pub fn update(&self, slot: ArenaSignal<T>, f: impl FnOnce(&mut T))
+    where
+        T: Clone,
+    {
+        if let Some(source) = self.middleware_source(slot) {
+            // Middleware needs the old and new values side by side
+            let mut value = self.inner.values()[slot.index()].clone();
+            f(&mut value);
+            let values = self.inner.values();
+            if !run_write_middleware(&source, Some(&values[slot.index()]), &mut value) {
+                return;
+            }
+            drop(values);
+            self.inner.values_mut()[slot.index()] = value;
+        } else {
+            f(&mut self.inner.values_mut()[slot.index()]);
+        }
+        self.notify(slot);
+    }
+
+    /// The slot's graph node, created if needed, for `track_read` and
+    /// `notify_write`.
+    pub fn source(&self, slot: ArenaSignal<T>) -> Rc<dyn AnySource> {
+        let mut nodes = self.inner.nodes.borrow_mut();
+        nodes[slot.index()]
+            .get_or_insert_with(|| Rc::new(SourceInner::new(())))
+            .clone()
+    }
+
+    /// The slot's graph node, if a write of it now passes through write
+    /// middleware.
+    fn middleware_source(&self, slot: ArenaSignal<T>) -> Option<Rc<dyn AnySource>> {
+        let node = self.inner.nodes.borrow()[slot.index()].clone();
+        // A slot without a node can't be the one middleware is checking
+        let checked = match &node {
+            Some(node) => has_write_middleware(node),
+            None => has_write_middleware(&self.inner),
+        };
+        checked.then(|| match node {
+            Some(node) => node,
+            None => self.source(slot),
+        })
+    }
+
+    fn notify(&self, slot: ArenaSignal<T>) {
+        let node = self.inner.nodes.borrow()[slot.index()].clone();
+        if let Some(node) = node {
+            with_context(|ctx| node.set_write_version(ctx.increment_write_version()));
+            notify_write(node);
+        }
+    }
+}
+
+impl<T> Clone for SignalArena<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SignalArena<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalArena")
+            .field("len", &self.inner.values().len())
+            .finish_non_exhaustive()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::derived::derived;
+    use crate::primitives::effect::effect_sync;
+    use crate::reactivity::batching::batch;
+    use std::cell::Cell;
+
+    #[test]
+    fn arena_slots_track_and_notify_individually() {
+        let arena = SignalArena::with_capacity(1000);
+        let slots: Vec<_> = (0..1000).map(|i| arena.alloc(i)).collect();
+        assert_eq!((arena.len(), arena.node_count()), (1000, 0));
+
+        let pair = derived({
+            let (arena, a, b) = (arena.clone(), slots[1], slots[2]);
+            move || arena.get(a) + arena.get(b)
+        });
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (pair, runs) = (pair.clone(), runs.clone());
+            move || {
+                pair.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(arena.node_count(), 2);
+
+        // Unobserved slot: no node, no run
+        assert!(arena.set(slots[500], -1));
+        assert!(!arena.set(slots[1], 1));
+        assert_eq!((runs.get(), arena.node_count()), (1, 2));
+
+        batch(|| {
+            arena.set(slots[1], 10);
+            arena.update(slots[2], |v| *v += 10);
+        });
+        assert_eq!((runs.get(), pair.get()), (2, 22));
+
+        // Handles work with the plain tracking API
+        let manual = Rc::new(Cell::new(0));
+        let _manual = effect_sync({
+            let (arena, manual, slot) = (arena.clone(), manual.clone(), slots[3]);
+            move || {
+                track_read(arena.source(slot));
+                manual.set(manual.get() + 1);
+            }
+        });
+        notify_write(arena.source(slots[3]));
+        assert_eq!(manual.get(), 2);
+    }
+
+    #[test]
+    fn with_may_read_other_slots() {
+        let arena = SignalArena::new();
+        let (a, b) = (arena.alloc(vec![1, 2]), arena.alloc(vec![3]));
+        let total = arena.with(a, |a| a.iter().sum::<i32>() + arena.with(b, |b| b[0]));
+        assert_eq!(total, 6);
+
+        // Write from a copy taken first
+        let first = arena.get(a)[0];
+        arena.update(b, |b| b.push(first));
+        assert_eq!(arena.get(b), [3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "SignalArena written from inside its own with() or update()")]
+    fn writing_inside_with_panics() {
+        let arena = SignalArena::new();
+        let (a, b) = (arena.alloc(1), arena.alloc(2));
+        arena.with(a, |&a| arena.set(b, a));
+    }
+}
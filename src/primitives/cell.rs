@@ -0,0 +1,296 @@
+// ============================================================================
+// spark-signals - Signal Cells
+// Signals for small Copy values: a Cell instead of a RefCell, bit equality
+// ============================================================================
+//
+// `Signal<T>` keeps its value in a `RefCell` and compares with `PartialEq`,
+// so every read pays for a borrow flag and a clone. For hot numeric paths a
+// `SignalCell` holds the value in a `Cell` and copies it out:
+//
+//     let x = signal_cell(0.0_f32);
+//     x.update(|x| *x += 1.5);
+//     assert_eq!(x.get(), 1.5);
+//
+// Writes compare bits (`BitEq`), so for floats NaN equals NaN and 0.0 does
+// not equal -0.0.
+// ============================================================================
+
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use crate::core::context::{with_context, Owner};
+use crate::core::types::{AnyReaction, AnySource, AnySourceRef, SourceInner};
+use crate::reactivity::middleware::{middleware_source, run_write_middleware};
+use crate::reactivity::tracking::{notify_write, track_read};
+
+// =============================================================================
+// BIT EQUALITY
+// =============================================================================
+
+/// Equality of representation, used by [`SignalCell`] writes.
+///
+/// Implemented for the primitive numbers, `bool`, `char`, `()`, and tuples
+/// and arrays of them. For a `Copy` type of your own, comparing fields with
+/// `==` is usually what you want.
+pub trait BitEq: Copy + 'static {
+    /// True if `self` and `other` have the same bits.
+    fn bit_eq(&self, other: &Self) -> bool;
+}
+
+macro_rules! bit_eq_by_value {
+    ($($t:ty),*) => {
+        $(impl BitEq for $t {
+            fn bit_eq(&self, other: &Self) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+bit_eq_by_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char, ());
+
+impl BitEq for f32 {
+    fn bit_eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl BitEq for f64 {
+    fn bit_eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl<T: BitEq, const N: usize> BitEq for [T; N] {
+    fn bit_eq(&self, other: &Self) -> bool {
+        self.iter().zip(other).all(|(a, b)| a.bit_eq(b))
+    }
+}
+
+macro_rules! bit_eq_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: BitEq),+> BitEq for ($($name,)+) {
+            fn bit_eq(&self, other: &Self) -> bool {
+                $(self.$idx.bit_eq(&other.$idx))&&+
+            }
+        }
+    };
+}
+
+bit_eq_tuple!(A 0);
+bit_eq_tuple!(A 0, B 1);
+bit_eq_tuple!(A 0, B 1, C 2);
+bit_eq_tuple!(A 0, B 1, C 2, D 3);
+
+// =============================================================================
+// CELL INNER
+// =============================================================================
+
+/// Graph node of a `SignalCell`: a value-less source plus the value.
+struct CellInner<T> {
+    node: SourceInner<()>,
+    value: Cell<T>,
+}
+
+impl<T: 'static> AnySource for CellInner<T> {
+    fn flags(&self) -> u32 {
+        self.node.flags()
+    }
+
+    fn set_flags(&self, flags: u32) {
+        self.node.set_flags(flags);
+    }
+
+    fn write_version(&self) -> u32 {
+        self.node.write_version()
+    }
+
+    fn set_write_version(&self, version: u32) {
+        self.node.set_write_version(version);
+    }
+
+    fn read_version(&self) -> u32 {
+        self.node.read_version()
+    }
+
+    fn set_read_version(&self, version: u32) {
+        self.node.set_read_version(version);
+    }
+
+    fn reaction_count(&self) -> usize {
+        self.node.reaction_count()
+    }
+
+    fn add_reaction(&self, reaction: Weak<dyn AnyReaction>) {
+        self.node.add_reaction(reaction);
+    }
+
+    fn cleanup_dead_reactions(&self) {
+        self.node.cleanup_dead_reactions();
+    }
+
+    fn for_each_reaction(&self, f: &mut dyn FnMut(Rc<dyn AnyReaction>) -> bool) {
+        self.node.for_each_reaction(f);
+    }
+
+    fn remove_reaction(&self, reaction: &Rc<dyn AnyReaction>) {
+        self.node.remove_reaction(reaction);
+    }
+
+    fn clear_reactions(&self) {
+        self.node.clear_reactions();
+    }
+
+    fn dead_reaction_count(&self) -> usize {
+        self.node.dead_reaction_count()
+    }
+
+    fn owner(&self) -> Option<&Owner> {
+        self.node.owner()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// =============================================================================
+// SIGNAL CELL
+// =============================================================================
+
+/// A signal for `Copy` values, stored in a `Cell`.
+///
+/// Reads copy the value out instead of borrowing and cloning; writes are
+/// skipped when the new value has the same bits. Created with
+/// [`signal_cell()`].
+///
+/// Writes are not staged by `transaction()`: they apply immediately.
+pub struct SignalCell<T> {
+    inner: Rc<CellInner<T>>,
+}
+
+impl<T: BitEq> SignalCell<T> {
+    /// Read the value (tracked).
+    pub fn get(&self) -> T {
+        track_read(self.inner.clone() as Rc<dyn AnySource>);
+        self.inner.value.get()
+    }
+
+    /// Write the value. Returns true if its bits changed (false if write
+    /// middleware vetoed it).
+    pub fn set(&self, mut value: T) -> bool {
+        if let Some(source) = middleware_source(&self.inner) {
+            let old = self.inner.value.get();
+            if !run_write_middleware(&source, Some(&old), &mut value) {
+                return false;
+            }
+        }
+        if self.inner.value.get().bit_eq(&value) {
+            return false;
+        }
+        self.inner.value.set(value);
+        with_context(|ctx| {
+            let wv = ctx.increment_write_version();
+            self.inner.set_write_version(wv);
+        });
+        notify_write(self.inner.clone() as Rc<dyn AnySource>);
+        true
+    }
+
+    /// Modify a copy of the value and write it back. Readers are notified
+    /// only if it changed.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.inner.value.get();
+        f(&mut value);
+        self.set(value);
+    }
+
+    /// Get the inner source as a type-erased AnySource.
+    pub fn as_any_source(&self) -> Rc<dyn AnySource> {
+        self.inner.clone()
+    }
+}
+
+impl<T> Clone for SignalCell<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: BitEq + fmt::Debug> fmt::Debug for SignalCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalCell")
+            .field("value", &self.inner.value.get())
+            .finish()
+    }
+}
+
+impl<T: BitEq> AnySourceRef for SignalCell<T> {
+    fn any_source(&self) -> Rc<dyn AnySource> {
+        self.as_any_source()
+    }
+}
+
+/// Create a [`SignalCell`] holding `value`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived, signal_cell};
+///
+/// let speed = signal_cell(f64::NAN);
+/// let doubled = derived({
+///     let speed = speed.clone();
+///     move || speed.get() * 2.0
+/// });
+///
+/// assert!(!speed.set(f64::NAN)); // same bits
+/// speed.update(|s| *s = 3.0);
+/// assert_eq!(doubled.get(), 6.0);
+/// ```
+pub fn signal_cell<T: BitEq>(value: T) -> SignalCell<T> {
+    SignalCell {
+        inner: Rc::new(CellInner {
+            node: SourceInner::new(()),
+            value: Cell::new(value),
+        }),
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+
+    #[test]
+    fn cells_notify_on_bit_changes_only() {
+        let pos = signal_cell((0.0_f32, 0.0_f32));
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (pos, runs) = (pos.clone(), runs.clone());
+            move || {
+                pos.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        pos.update(|(x, _)| *x += 1.0);
+        pos.update(|_| {});
+        assert!(!pos.set((1.0, 0.0)));
+        assert_eq!((runs.get(), pos.get()), (2, (1.0, 0.0)));
+
+        // Bits, not IEEE equality
+        assert!(pos.set((1.0, -0.0)));
+        assert!(pos.set((f32::NAN, -0.0)));
+        assert!(!pos.set((f32::NAN, -0.0)));
+        assert_eq!(runs.get(), 4);
+    }
+}
@@ -0,0 +1,426 @@
+// ============================================================================
+// spark-signals - Combinators
+// map / zip / filter / debounce_frames on Signal and Derived
+// ============================================================================
+//
+// Shorthand for the most common deriveds. Instead of
+//
+//     let doubled = derived({
+//         let count = count.clone();
+//         move || count.get() * 2
+//     });
+//
+// write `count.map(|n| n * 2)`. The receiver is cloned into the new derived,
+// so the original handle stays usable.
+//
+// `Signal<Option<T>>` (alias `OptionSignal<T>`) gets helpers for optional
+// state: `is_some()`, `unwrap_or_signal()`, `map_some()` and `take()`.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::core::types::AnySource;
+use crate::primitives::derived::{derived, Derived};
+use crate::primitives::props::UnwrapProp;
+use crate::primitives::signal::{signal, Signal};
+use crate::reactivity::batching::untrack;
+use crate::reactivity::scheduling::on_flush_end;
+
+// =============================================================================
+// SIGNAL
+// =============================================================================
+
+impl<T: Clone + PartialEq + 'static> Signal<T> {
+    /// Derive a value from this signal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::signal;
+    ///
+    /// let count = signal(2);
+    /// let doubled = count.map(|n| n * 2);
+    /// assert_eq!(doubled.get(), 4);
+    ///
+    /// count.set(5);
+    /// assert_eq!(doubled.get(), 10);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> Derived<U>
+    where
+        U: Clone + PartialEq + 'static,
+        F: Fn(T) -> U + 'static,
+    {
+        let this = self.clone();
+        derived(move || f(this.get()))
+    }
+
+    /// Pair this signal with another signal or derived.
+    pub fn zip<U, O>(&self, other: &O) -> Derived<(T, U)>
+    where
+        U: Clone + PartialEq + 'static,
+        O: UnwrapProp<U> + Clone + 'static,
+    {
+        let (this, other) = (self.clone(), other.clone());
+        derived(move || (this.get(), other.unwrap_value()))
+    }
+
+    /// `Some(value)` while `predicate` accepts the value, `None` otherwise.
+    pub fn filter<P>(&self, predicate: P) -> Derived<Option<T>>
+    where
+        P: Fn(&T) -> bool + 'static,
+    {
+        let this = self.clone();
+        derived(move || Some(this.get()).filter(|v| predicate(v)))
+    }
+
+    /// Follow this signal once it has held still for `frames` frames.
+    ///
+    /// See [`Derived::debounce_frames`].
+    pub fn debounce_frames(&self, frames: u32) -> Derived<T> {
+        let this = self.clone();
+        debounce(move || this.get(), self.as_any_source(), frames)
+    }
+}
+
+// =============================================================================
+// DERIVED
+// =============================================================================
+
+impl<T: Clone + PartialEq + 'static> Derived<T> {
+    /// Derive a value from this derived.
+    pub fn map<U, F>(&self, f: F) -> Derived<U>
+    where
+        U: Clone + PartialEq + 'static,
+        F: Fn(T) -> U + 'static,
+    {
+        let this = self.clone();
+        derived(move || f(this.get()))
+    }
+
+    /// Pair this derived with another signal or derived.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::signal;
+    ///
+    /// let width = signal(4);
+    /// let height = signal(3);
+    /// let area = width.map(|w| w * 10).zip(&height).map(|(w, h)| w * h);
+    /// assert_eq!(area.get(), 120);
+    ///
+    /// height.set(1);
+    /// assert_eq!(area.get(), 40);
+    /// ```
+    pub fn zip<U, O>(&self, other: &O) -> Derived<(T, U)>
+    where
+        U: Clone + PartialEq + 'static,
+        O: UnwrapProp<U> + Clone + 'static,
+    {
+        let (this, other) = (self.clone(), other.clone());
+        derived(move || (this.get(), other.unwrap_value()))
+    }
+
+    /// `Some(value)` while `predicate` accepts the value, `None` otherwise.
+    pub fn filter<P>(&self, predicate: P) -> Derived<Option<T>>
+    where
+        P: Fn(&T) -> bool + 'static,
+    {
+        let this = self.clone();
+        derived(move || Some(this.get()).filter(|v| predicate(v)))
+    }
+
+    /// Follow this derived once it has held still for `frames` frames.
+    ///
+    /// A frame is one outermost flush - typically the `tick()` an event loop
+    /// calls once per frame. The result starts at the current value; after a
+    /// change it keeps the old value until `frames` more flushes have passed
+    /// without another change. `0` follows at the end of the flush that saw
+    /// the change.
+    ///
+    /// The flush hook is removed the first flush after every clone of the
+    /// result has been dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{derived, signal, tick};
+    ///
+    /// let query = signal(String::new());
+    /// let trimmed = derived({
+    ///     let query = query.clone();
+    ///     move || query.get().trim().to_string()
+    /// });
+    /// let settled = trimmed.debounce_frames(2);
+    ///
+    /// query.set("ab ".into());
+    /// tick();
+    /// tick();
+    /// assert_eq!(settled.get(), "");
+    /// tick();
+    /// assert_eq!(settled.get(), "ab");
+    /// ```
+    pub fn debounce_frames(&self, frames: u32) -> Derived<T> {
+        let this = self.clone();
+        debounce(move || this.get(), self.as_any_source(), frames)
+    }
+}
+
+// =============================================================================
+// OPTION SIGNAL
+// =============================================================================
+
+/// A signal holding optional state, with the `Signal<Option<T>>` helpers
+/// below.
+pub type OptionSignal<T> = Signal<Option<T>>;
+
+impl<T: Clone + PartialEq + 'static> Signal<Option<T>> {
+    /// True while the signal holds a value.
+    ///
+    /// Reads the signal without cloning the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{signal, OptionSignal};
+    ///
+    /// let selected: OptionSignal<String> = signal(None);
+    /// let has_selection = selected.is_some();
+    /// let title = selected.unwrap_or_signal(&signal(String::from("Untitled")));
+    /// assert!(!has_selection.get());
+    /// assert_eq!(title.get(), "Untitled");
+    ///
+    /// selected.set(Some("notes.txt".into()));
+    /// assert!(has_selection.get());
+    /// assert_eq!(title.get(), "notes.txt");
+    ///
+    /// assert_eq!(selected.take().as_deref(), Some("notes.txt"));
+    /// assert!(!has_selection.get());
+    /// ```
+    pub fn is_some(&self) -> Derived<bool> {
+        let this = self.clone();
+        derived(move || this.with(Option::is_some))
+    }
+
+    /// True while the signal holds no value.
+    pub fn is_none(&self) -> Derived<bool> {
+        let this = self.clone();
+        derived(move || this.with(Option::is_none))
+    }
+
+    /// The value, or the current value of `default` while there is none.
+    ///
+    /// `default` is only tracked while the signal is `None`.
+    pub fn unwrap_or_signal<O>(&self, default: &O) -> Derived<T>
+    where
+        O: UnwrapProp<T> + Clone + 'static,
+    {
+        let (this, default) = (self.clone(), default.clone());
+        derived(move || this.get().unwrap_or_else(|| default.unwrap_value()))
+    }
+
+    /// Derive from the value while there is one; `None` otherwise.
+    pub fn map_some<U, F>(&self, f: F) -> Derived<Option<U>>
+    where
+        U: Clone + PartialEq + 'static,
+        F: Fn(T) -> U + 'static,
+    {
+        let this = self.clone();
+        derived(move || this.get().map(&f))
+    }
+
+    /// Take the value out, leaving `None` (untracked).
+    ///
+    /// Notifies only if there was a value.
+    pub fn take(&self) -> Option<T> {
+        if !untrack(|| self.with(Option::is_some)) {
+            return None;
+        }
+        let mut taken = None;
+        self.update(|value| taken = value.take());
+        taken
+    }
+}
+
+// =============================================================================
+// DEBOUNCE
+// =============================================================================
+
+/// Per-result debounce bookkeeping, owned by the flush hook.
+struct Debounce<T> {
+    /// `write_version` of the source when last seen
+    seen_version: Cell<u32>,
+    /// Changed value waiting to be let through
+    pending: RefCell<Option<T>>,
+    /// Flushes since the last change
+    quiet: Cell<u32>,
+    /// Set while the hook writes the output (its own flush is not a frame)
+    writing: Cell<bool>,
+    /// Unregisters the hook once the output is gone
+    remove: RefCell<Option<Box<dyn FnOnce()>>>,
+}
+
+fn debounce<T, R>(read: R, source: Rc<dyn AnySource>, frames: u32) -> Derived<T>
+where
+    T: Clone + PartialEq + 'static,
+    R: Fn() -> T + 'static,
+{
+    let output = signal(untrack(&read));
+    let state = Rc::new(Debounce {
+        seen_version: Cell::new(source.write_version()),
+        pending: RefCell::new(None),
+        quiet: Cell::new(0),
+        writing: Cell::new(false),
+        remove: RefCell::new(None),
+    });
+
+    let remove = on_flush_end({
+        let state = state.clone();
+        let output = Rc::downgrade(output.inner());
+        move || {
+            if state.writing.get() {
+                return;
+            }
+            let Some(output) = output.upgrade() else {
+                if let Some(remove) = state.remove.borrow_mut().take() {
+                    remove();
+                }
+                return;
+            };
+
+            // Reading first brings a derived source up to date
+            let value = untrack(&read);
+            let version = source.write_version();
+            if version != state.seen_version.get() {
+                state.seen_version.set(version);
+                *state.pending.borrow_mut() = Some(value);
+                state.quiet.set(0);
+            } else {
+                state.quiet.set(state.quiet.get().saturating_add(1));
+            }
+
+            if state.quiet.get() >= frames {
+                if let Some(value) = state.pending.borrow_mut().take() {
+                    state.writing.set(true);
+                    Signal::from_inner(output).set(value);
+                    state.writing.set(false);
+                }
+            }
+        }
+    });
+    *state.remove.borrow_mut() = Some(Box::new(remove));
+
+    derived(move || output.get())
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::reactivity::scheduling::flush_sync;
+
+    #[test]
+    fn map_zip_filter_track_their_sources() {
+        let a = signal(1);
+        let b = signal(10);
+        let sum = a.zip(&b).map(|(a, b)| a + b);
+        let even = sum.filter(|n| n % 2 == 0);
+
+        assert_eq!(sum.get(), 11);
+        assert_eq!(even.get(), None);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (even, seen) = (even.clone(), seen.clone());
+            move || seen.borrow_mut().push(even.get())
+        });
+
+        a.set(2);
+        b.set(20);
+        assert_eq!(*seen.borrow(), vec![None, Some(12), Some(22)]);
+    }
+
+    #[test]
+    fn option_helpers_follow_the_signal() {
+        let user: OptionSignal<&str> = signal(Some("ada"));
+        let fallback = signal("guest");
+        let name = user.unwrap_or_signal(&fallback);
+        let length = user.map_some(|name| name.len());
+
+        assert_eq!(name.get(), "ada");
+        assert_eq!(length.get(), Some(3));
+        assert!(!user.is_none().get());
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (user, seen) = (user.clone(), seen.clone());
+            move || seen.borrow_mut().push(user.get())
+        });
+
+        assert_eq!(user.take(), Some("ada"));
+        assert_eq!(user.take(), None);
+        assert_eq!(*seen.borrow(), vec![Some("ada"), None]);
+
+        assert_eq!(name.get(), "guest");
+        fallback.set("visitor");
+        assert_eq!(name.get(), "visitor");
+        assert_eq!(length.get(), None);
+    }
+
+    #[test]
+    fn debounce_waits_for_quiet_frames() {
+        let source = signal(0);
+        let settled = source.debounce_frames(2);
+        assert_eq!(settled.get(), 0);
+
+        source.set(1);
+        flush_sync();
+        flush_sync();
+        // A change before the window closes restarts it
+        source.set(2);
+        flush_sync();
+        flush_sync();
+        assert_eq!(settled.get(), 0);
+
+        flush_sync();
+        assert_eq!(settled.get(), 2);
+
+        // Going back to the old value is a change too
+        source.set(0);
+        flush_sync();
+        assert_eq!(settled.get(), 2);
+        flush_sync();
+        flush_sync();
+        assert_eq!(settled.get(), 0);
+    }
+
+    #[test]
+    fn debounce_notifies_effects_once_settled() {
+        let source = signal(0);
+        let settled = source.debounce_frames(0);
+        let runs = Rc::new(Cell::new(0));
+
+        let _dispose = effect_sync({
+            let (settled, runs) = (settled.clone(), runs.clone());
+            move || {
+                settled.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        source.set(5);
+        assert_eq!(settled.get(), 0);
+
+        flush_sync();
+        assert_eq!(settled.get(), 5);
+        assert_eq!(runs.get(), 2);
+
+        // Unchanged frames don't re-run anything
+        flush_sync();
+        assert_eq!(runs.get(), 2);
+    }
+}
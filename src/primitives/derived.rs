@@ -10,11 +10,15 @@
 
 use std::any::Any;
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
 
 use crate::core::constants::*;
-use crate::core::context::with_context;
-use crate::core::types::{default_equals, AnyReaction, AnySource, EqualsFn};
+use crate::core::context::{with_context, Owner};
+use crate::core::types::{
+    default_equals, AnyReaction, AnySource, AnySourceRef, DepList, EqualsFn, History,
+};
 use crate::reactivity::tracking::{install_dependencies, set_source_status, track_read};
 
 // =============================================================================
@@ -57,6 +61,22 @@ pub struct DerivedInner<T> {
     /// Self-reference for as_derived_source()
     /// Set immediately during construction in new_with_equals()
     self_ref: RefCell<Option<Weak<DerivedInner<T>>>>,
+
+    /// Number of times the computation ran (for `stats()`)
+    recomputes: Cell<u64>,
+
+    /// Wall-clock time of the last computation (for `stats()`)
+    last_compute: Cell<Duration>,
+
+    /// Global write version when the computation last ran, whether or not
+    /// the value changed
+    verified_version: Cell<u32>,
+
+    /// Previous value, for `derived_with_history()`
+    history: Option<Box<History<T>>>,
+
+    /// The context this derived was created in
+    owner: Owner,
 }
 
 impl<T> DerivedInner<T> {
@@ -71,6 +91,13 @@ impl<T> DerivedInner<T> {
 
     /// Create a new derived with a custom equality function
     pub fn new_with_equals<F>(fn_: F, equals: EqualsFn<T>) -> Rc<Self>
+    where
+        F: Fn() -> T + 'static,
+    {
+        Self::build(fn_, equals, None)
+    }
+
+    fn build<F>(fn_: F, equals: EqualsFn<T>, history: Option<Box<History<T>>>) -> Rc<Self>
     where
         F: Fn() -> T + 'static,
     {
@@ -84,11 +111,21 @@ impl<T> DerivedInner<T> {
             reactions: RefCell::new(Vec::new()),
             deps: RefCell::new(Vec::new()),
             self_ref: RefCell::new(None),
+            recomputes: Cell::new(0),
+            last_compute: Cell::new(Duration::ZERO),
+            verified_version: Cell::new(0),
+            history,
+            owner: Owner::current(),
         });
 
         // Store weak self-reference for as_derived_source()
         *inner.self_ref.borrow_mut() = Some(Rc::downgrade(&inner));
 
+        with_context(|ctx| {
+            ctx.source_created();
+            ctx.reaction_created();
+        });
+
         inner
     }
 
@@ -105,6 +142,12 @@ impl<T> DerivedInner<T> {
         self.value.borrow().is_some()
     }
 
+    /// The value before the most recent change, if this derived keeps
+    /// history and has changed.
+    pub fn previous(&self) -> Option<T> {
+        self.history.as_ref().and_then(|history| history.get())
+    }
+
     /// Execute the computation and update the cached value.
     /// Returns true if the value changed.
     pub fn compute(&self) -> bool
@@ -114,8 +157,14 @@ impl<T> DerivedInner<T> {
         let fn_ref = self.fn_.borrow();
         let fn_ = fn_ref.as_ref().expect("derived fn disposed");
 
-        // Run the computation
+        // Run the computation, timing it (there is no clock on wasm32)
+        let start = (!cfg!(target_arch = "wasm32")).then(Instant::now);
         let new_value = fn_();
+        let elapsed = start.map_or(Duration::ZERO, |start| start.elapsed());
+        self.recomputes.set(self.recomputes.get() + 1);
+        self.last_compute.set(elapsed);
+        #[cfg(feature = "profiling")]
+        crate::profiling::record_duration(self as *const Self as *const () as usize, elapsed);
 
         // Check if value changed
         let changed = {
@@ -127,12 +176,16 @@ impl<T> DerivedInner<T> {
         };
 
         if changed {
-            *self.value.borrow_mut() = Some(new_value);
+            let old = self.value.borrow_mut().replace(new_value);
+            if let (Some(history), Some(old)) = (&self.history, old) {
+                history.record(old);
+            }
             // Increment write version when value changes
             with_context(|ctx| {
                 self.write_version.set(ctx.increment_write_version());
             });
         }
+        self.verified_version.set(with_context(|ctx| ctx.get_write_version()));
 
         changed
     }
@@ -141,6 +194,37 @@ impl<T> DerivedInner<T> {
     pub fn equals_fn(&self) -> EqualsFn<T> {
         self.equals
     }
+
+    /// Recompute count, last compute time and dependency count.
+    pub fn stats(&self) -> DerivedStats {
+        DerivedStats {
+            recomputes: self.recomputes.get(),
+            last_compute: self.last_compute.get(),
+            dependencies: self.deps.borrow().len(),
+        }
+    }
+}
+
+/// Per-derived counters reported by [`Derived::stats()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DerivedStats {
+    /// Times the computation has run
+    pub recomputes: u64,
+    /// Wall-clock time of the last computation (zero on wasm32)
+    pub last_compute: Duration,
+    /// Sources read by the last computation
+    pub dependencies: usize,
+}
+
+impl<T> Drop for DerivedInner<T> {
+    fn drop(&mut self) {
+        self.owner.try_with(|ctx| {
+            #[cfg(feature = "profiling")]
+            crate::profiling::forget(ctx, self as *const Self as *const () as usize);
+            ctx.source_dropped();
+            ctx.reaction_dropped();
+        });
+    }
 }
 
 // =============================================================================
@@ -211,6 +295,18 @@ impl<T: 'static + Clone> AnySource for DerivedInner<T> {
         self.reactions.borrow_mut().clear();
     }
 
+    fn owner(&self) -> Option<&Owner> {
+        Some(&self.owner)
+    }
+
+    fn dead_reaction_count(&self) -> usize {
+        self.reactions
+            .borrow()
+            .iter()
+            .filter(|w| w.strong_count() == 0)
+            .count()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -275,6 +371,10 @@ impl<T: 'static + Clone> AnyReaction for DerivedInner<T> {
         self.compute()
     }
 
+    fn verified_version(&self) -> Option<u32> {
+        Some(self.verified_version.get())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -334,6 +434,16 @@ impl<T: 'static + Clone> Derived<T> {
         self.inner.get_value()
     }
 
+    /// The value before the most recent change, tracked like `get()`.
+    ///
+    /// None until the value first changes, and always None unless the derived
+    /// was created with [`derived_with_history()`].
+    pub fn get_previous(&self) -> Option<T> {
+        update_derived_chain(self.inner.clone() as Rc<dyn AnySource>);
+        track_read(self.inner.clone() as Rc<dyn AnySource>);
+        self.inner.previous()
+    }
+
     /// Get access to the inner for graph operations
     pub fn inner(&self) -> &Rc<DerivedInner<T>> {
         &self.inner
@@ -348,6 +458,58 @@ impl<T: 'static + Clone> Derived<T> {
     pub fn as_any_reaction(&self) -> Rc<dyn AnyReaction> {
         self.inner.clone() as Rc<dyn AnyReaction>
     }
+
+    /// Name this derived for debugging (`debug::dependency_graph()`).
+    ///
+    /// Also makes it a starting point for `dependency_graph()`.
+    pub fn set_debug_name(&self, name: impl Into<String>) {
+        crate::debug::register_source(&self.as_any_source(), Some(name.into()));
+    }
+
+    /// The name given with `set_debug_name()`, if any.
+    pub fn debug_name(&self) -> Option<String> {
+        crate::debug::debug_name_of(&self.inner)
+    }
+
+    /// The sources this derived read on its last computation, for devtools.
+    pub fn dependencies(&self) -> Vec<crate::debug::DebugSourceInfo> {
+        crate::debug::dependencies_of(&*self.inner)
+    }
+
+    /// The live reactions reading this derived, for devtools.
+    pub fn subscribers(&self) -> Vec<crate::debug::DebugReactionInfo> {
+        crate::debug::subscribers_of(&*self.inner)
+    }
+
+    /// Profiling counters for this derived. Doesn't recompute or track.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{derived, signal};
+    ///
+    /// let (a, b) = (signal(1), signal(2));
+    /// let sum = derived({
+    ///     let (a, b) = (a.clone(), b.clone());
+    ///     move || a.get() + b.get()
+    /// });
+    /// assert_eq!(sum.stats().recomputes, 0);
+    ///
+    /// sum.get();
+    /// a.set(5);
+    /// sum.get();
+    /// let stats = sum.stats();
+    /// assert_eq!((stats.recomputes, stats.dependencies), (2, 2));
+    /// ```
+    pub fn stats(&self) -> DerivedStats {
+        self.inner.stats()
+    }
+}
+
+impl<T: 'static + Clone> AnySourceRef for Derived<T> {
+    fn any_source(&self) -> Rc<dyn AnySource> {
+        self.as_any_source()
+    }
 }
 
 // =============================================================================
@@ -372,7 +534,7 @@ where
     T: 'static + Clone + PartialEq,
     F: Fn() -> T + 'static,
 {
-    Derived::from_inner(DerivedInner::new(fn_))
+    derived_with_equals(fn_, default_equals)
 }
 
 /// Create a derived signal with a custom equality function.
@@ -381,7 +543,43 @@ where
     T: 'static + Clone,
     F: Fn() -> T + 'static,
 {
-    Derived::from_inner(DerivedInner::new_with_equals(fn_, equals))
+    let derived = Derived::from_inner(DerivedInner::new_with_equals(fn_, equals));
+    #[cfg(feature = "debug-deps")]
+    crate::debug::register_source(&derived.as_any_source(), None);
+    derived
+}
+
+/// Create a derived that remembers its value from before the most recent
+/// change (see [`Derived::get_previous()`]).
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived_with_history, signal};
+///
+/// let count = signal(1);
+/// let doubled = derived_with_history({
+///     let count = count.clone();
+///     move || count.get() * 2
+/// });
+/// assert_eq!((doubled.get_previous(), doubled.get()), (None, 2));
+///
+/// count.set(5);
+/// assert_eq!((doubled.get_previous(), doubled.get()), (Some(2), 10));
+/// ```
+pub fn derived_with_history<T, F>(fn_: F) -> Derived<T>
+where
+    T: 'static + Clone + PartialEq,
+    F: Fn() -> T + 'static,
+{
+    let derived = Derived::from_inner(DerivedInner::build(
+        fn_,
+        default_equals,
+        Some(History::new()),
+    ));
+    #[cfg(feature = "debug-deps")]
+    crate::debug::register_source(&derived.as_any_source(), None);
+    derived
 }
 
 // =============================================================================
@@ -391,59 +589,37 @@ where
 /// Update a derived and all its dirty dependencies iteratively.
 ///
 /// This is the key algorithm for the MAYBE_DIRTY optimization:
-/// 1. Collect all dirty/maybe-dirty deriveds in the dependency chain
-/// 2. Process from deepest (sources) to shallowest (target)
+/// 1. Collect all dirty/maybe-dirty deriveds the target reads, directly or
+///    through other deriveds, and give each a height: one more than the
+///    highest collected derived it reads
+/// 2. Process them by increasing height, so a derived is only looked at
+///    once everything it reads is final
 /// 3. For DIRTY: always update
 /// 4. For MAYBE_DIRTY: check if any dep's write_version > self.write_version
 ///
+/// Ordering by height is what keeps diamonds glitch-free: in `t = a + b`
+/// with `b = a + 1`, `b` is checked after `a` recomputed, never against
+/// `a`'s old version (which would leave `b` cached and stale).
+///
 /// Uses iterative approach to avoid stack overflow on deep chains.
 pub fn update_derived_chain(target: Rc<dyn AnySource>) {
     // Quick check: if clean, nothing to do
-    let flags = target.flags();
-    if (flags & (DIRTY | MAYBE_DIRTY)) == 0 {
+    if !is_stale(&target) {
         return;
     }
 
-    // Collect all deriveds that need checking
-    // Walk from target toward sources, collecting dirty/maybe-dirty deriveds
-    let mut chain: Vec<Rc<dyn AnySource>> = vec![target.clone()];
-    let mut visited: Vec<*const ()> = vec![Rc::as_ptr(&target) as *const ()];
-    let mut idx = 0;
-
-    while idx < chain.len() {
-        let current = chain[idx].clone();
-        idx += 1;
-
-        // Skip if already clean
-        let flags = current.flags();
-        if (flags & (DIRTY | MAYBE_DIRTY)) == 0 {
-            continue;
-        }
-
-        // For deriveds, check their dependencies
-        // Use as_derived_reaction() to get the reaction side
-        if let Some(reaction) = current.as_derived_reaction() {
-            // Collect deps that are derived and dirty/maybe-dirty
-            let mut deps_to_add = Vec::new();
-            reaction.for_each_dep(&mut |dep| {
-                let dep_flags = dep.flags();
-                if (dep_flags & DERIVED) != 0 && (dep_flags & (DIRTY | MAYBE_DIRTY)) != 0 {
-                    let dep_ptr = Rc::as_ptr(dep) as *const ();
-                    if !visited.contains(&dep_ptr) {
-                        deps_to_add.push(dep.clone());
-                        visited.push(dep_ptr);
-                    }
-                }
-                true // continue
-            });
-            chain.extend(deps_to_add);
+    // Compute in the derived's own context, so its reads track there
+    match target.owner() {
+        Some(owner) if !with_context(|ctx| owner.accepts(ctx)) => {
+            let owner = owner.clone();
+            owner.enter(|| update_stale(target));
         }
+        _ => update_stale(target),
     }
+}
 
-    // Update from deepest (end) to shallowest (start)
-    for i in (0..chain.len()).rev() {
-        let current = &chain[i];
-
+fn update_stale(target: Rc<dyn AnySource>) {
+    for current in stale_by_height(target) {
         // Skip if already clean (might have been cleaned by a previous iteration)
         let flags = current.flags();
         if (flags & (DIRTY | MAYBE_DIRTY)) == 0 {
@@ -452,26 +628,82 @@ pub fn update_derived_chain(target: Rc<dyn AnySource>) {
 
         if (flags & DIRTY) != 0 {
             // Definitely dirty - must update
-            update_derived(current);
+            update_derived(&current);
         } else {
             // MAYBE_DIRTY - check if any dep actually changed
-            let needs_update = check_deps_changed(current);
+            let needs_update = check_deps_changed(&current);
 
             if needs_update {
-                update_derived(current);
+                update_derived(&current);
             } else {
                 // All deps are clean and unchanged - mark as clean
-                set_source_status(&**current, CLEAN);
+                set_source_status(&*current, CLEAN);
             }
         }
     }
 }
 
-/// Check if any dependency has a newer write_version than the derived.
-fn check_deps_changed(source: &Rc<dyn AnySource>) -> bool {
-    let self_wv = source.write_version();
+fn is_stale(source: &Rc<dyn AnySource>) -> bool {
+    (source.flags() & (DIRTY | MAYBE_DIRTY)) != 0
+}
+
+/// The stale deriveds `target` depends on, `target` included, lowest
+/// height first.
+///
+/// Depth-first with an explicit stack: a node is pushed again after its
+/// dependencies and gets its height once they all have theirs.
+fn stale_by_height(target: Rc<dyn AnySource>) -> Vec<Rc<dyn AnySource>> {
+    fn key(source: &Rc<dyn AnySource>) -> *const () {
+        Rc::as_ptr(source) as *const ()
+    }
+
+    // None while a node's dependencies are being visited
+    let mut heights: HashMap<*const (), Option<usize>> = HashMap::new();
+    let mut ordered: Vec<(usize, Rc<dyn AnySource>)> = Vec::new();
+    let mut stack: Vec<(Rc<dyn AnySource>, bool)> = vec![(target, false)];
 
+    while let Some((current, deps_done)) = stack.pop() {
+        let Some(reaction) = current.as_derived_reaction() else {
+            continue;
+        };
+
+        if deps_done {
+            let mut height = 0;
+            reaction.for_each_dep(&mut |dep| {
+                if let Some(Some(dep_height)) = heights.get(&key(dep)) {
+                    height = height.max(dep_height + 1);
+                }
+                true
+            });
+            heights.insert(key(&current), Some(height));
+            ordered.push((height, current));
+            continue;
+        }
+
+        if heights.contains_key(&key(&current)) {
+            continue;
+        }
+        heights.insert(key(&current), None);
+        stack.push((current.clone(), true));
+        reaction.for_each_dep(&mut |dep| {
+            if (dep.flags() & DERIVED) != 0 && is_stale(dep) && !heights.contains_key(&key(dep)) {
+                stack.push((dep.clone(), false));
+            }
+            true
+        });
+    }
+
+    // Stable: equal heights keep discovery order
+    ordered.sort_by_key(|(height, _)| *height);
+    ordered.into_iter().map(|(_, source)| source).collect()
+}
+
+/// Check if any dependency was written after the derived last computed.
+fn check_deps_changed(source: &Rc<dyn AnySource>) -> bool {
     if let Some(reaction) = source.as_derived_reaction() {
+        // Its own write version only moves when the value changes, so it
+        // would keep reporting deps that changed while its value didn't
+        let self_wv = reaction.verified_version().unwrap_or(source.write_version());
         let mut changed = false;
         reaction.for_each_dep(&mut |dep| {
             if dep.write_version() > self_wv {
@@ -496,14 +728,26 @@ fn check_deps_changed(source: &Rc<dyn AnySource>) -> bool {
 /// 4. Marks the derived as clean
 fn update_derived(source: &Rc<dyn AnySource>) {
     if let Some(reaction) = source.as_derived_reaction() {
+        #[cfg(feature = "profiling")]
+        crate::profiling::record(crate::profiling::NodeKind::Derived, crate::profiling::node_id(source));
+        with_context(|ctx| ctx.derived_recomputed(Rc::as_ptr(source) as *const () as usize));
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+            "derived",
+            name = crate::debug::debug_name_of(source).as_deref().unwrap_or("")
+        )
+        .entered();
+
         // Save previous tracking state
         let prev_reaction = with_context(|ctx| ctx.get_active_reaction());
-        let prev_new_deps = with_context(|ctx| ctx.swap_new_deps(Vec::new()));
+        let prev_new_deps = with_context(|ctx| ctx.swap_new_deps(DepList::new()));
 
-        // Set up tracking for this derived
-        with_context(|ctx| {
+        // Set up tracking for this derived (an enclosing untrack() doesn't
+        // apply to its own reads)
+        let prev_untracking = with_context(|ctx| {
             ctx.set_active_reaction(Some(Rc::downgrade(&reaction)));
             ctx.increment_read_version();
+            ctx.set_untracking(false)
         });
 
         // Mark as updating
@@ -529,6 +773,7 @@ fn update_derived(source: &Rc<dyn AnySource>) {
         with_context(|ctx| {
             ctx.set_active_reaction(prev_reaction);
             ctx.swap_new_deps(prev_new_deps);
+            ctx.set_untracking(prev_untracking);
         });
     }
 }
@@ -782,6 +1027,19 @@ mod tests {
         assert!(AnySource::is_clean(&**c_inner));
     }
 
+    #[test]
+    fn first_read_inside_untrack_still_collects_deps() {
+        let a = signal(1);
+        let doubled = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+
+        assert_eq!(crate::reactivity::batching::untrack(|| doubled.get()), 2);
+        a.set(4);
+        assert_eq!(doubled.get(), 8);
+    }
+
     #[test]
     fn derived_heterogeneous_storage() {
         // Test that deriveds can be stored in Vec<Rc<dyn AnySource>>
@@ -0,0 +1,237 @@
+// ============================================================================
+// spark-signals - Dirty Tracker
+// The set of modified indices behind TrackedSlot / TrackedSlotArray
+// ============================================================================
+//
+// Tracked slots mark their index on every write; a consumer (layout pass,
+// ECS system) drains the marks once per frame. The tracker keeps the marked
+// indices in a Vec, in marking order, next to a membership structure:
+//
+//     Sparse - HashSet, for ids spread over a large range
+//     Dense  - bitset, for indices packed into 0..n (slot arrays)
+//
+// Draining or clearing only unmarks what was marked and keeps every
+// allocation, so a steady-state frame loop allocates nothing.
+// ============================================================================
+
+use std::collections::HashSet;
+
+/// How a [`DirtyTracker`] answers "is this index marked?".
+#[derive(Debug, Clone)]
+enum Marks {
+    Sparse(HashSet<usize>),
+    Dense(Vec<u64>),
+}
+
+/// A set of dirty indices with allocation-free drain and clear.
+///
+/// Iteration yields indices in the order they were first marked. The
+/// [`generation()`](Self::generation) counter advances whenever the set
+/// changes, so consumers can tell cheaply whether anything happened since
+/// they last looked.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{tracked_slot_array, DirtyTracker};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let dirty = Rc::new(RefCell::new(DirtyTracker::dense(1024)));
+/// let positions = tracked_slot_array::<f32>(Some(0.0), dirty.clone());
+///
+/// positions.set_value(3, 1.5);
+/// positions.set_value(1, 2.0);
+/// positions.set_value(3, 4.0);
+///
+/// let seen = dirty.borrow().generation();
+/// let changed: Vec<usize> = dirty.borrow_mut().drain().collect();
+/// assert_eq!(changed, [3, 1]);
+/// assert!(dirty.borrow().is_empty());
+/// assert_ne!(dirty.borrow().generation(), seen);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirtyTracker {
+    /// Marked indices, in marking order
+    order: Vec<usize>,
+    marks: Marks,
+    generation: u64,
+}
+
+impl Default for DirtyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DirtyTracker {
+    /// An empty tracker backed by a hash set.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// An empty hash-set tracker with room for `capacity` marks before it
+    /// allocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            order: Vec::with_capacity(capacity),
+            marks: Marks::Sparse(HashSet::with_capacity(capacity)),
+            generation: 0,
+        }
+    }
+
+    /// An empty tracker backed by a bitset sized for indices `0..len`.
+    ///
+    /// Faster than the hash set for slot arrays and other packed indices.
+    /// Larger indices still work; the bitset grows to fit them.
+    pub fn dense(len: usize) -> Self {
+        Self {
+            order: Vec::with_capacity(len),
+            marks: Marks::Dense(vec![0; len.div_ceil(64)]),
+            generation: 0,
+        }
+    }
+
+    /// True if backed by a bitset.
+    pub fn is_dense(&self) -> bool {
+        matches!(self.marks, Marks::Dense(_))
+    }
+
+    /// Mark an index. Returns true if it wasn't marked already.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let added = match &mut self.marks {
+            Marks::Sparse(set) => set.insert(index),
+            Marks::Dense(words) => {
+                let (word, bit) = (index / 64, 1u64 << (index % 64));
+                if word >= words.len() {
+                    words.resize(word + 1, 0);
+                }
+                let added = words[word] & bit == 0;
+                words[word] |= bit;
+                added
+            }
+        };
+        if added {
+            self.order.push(index);
+            self.generation += 1;
+        }
+        added
+    }
+
+    /// True if the index is marked.
+    pub fn contains(&self, index: &usize) -> bool {
+        match &self.marks {
+            Marks::Sparse(set) => set.contains(index),
+            Marks::Dense(words) => words
+                .get(index / 64)
+                .is_some_and(|word| word & (1u64 << (index % 64)) != 0),
+        }
+    }
+
+    /// Number of marked indices.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// True if nothing is marked.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Marked indices, in marking order.
+    pub fn iter(&self) -> std::slice::Iter<'_, usize> {
+        self.order.iter()
+    }
+
+    /// Counter that advances on every change to the set.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Unmark everything, keeping allocations.
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+
+    /// Unmark everything, yielding the marked indices in marking order.
+    ///
+    /// The set is empty as soon as this returns, even if the iterator isn't
+    /// consumed.
+    pub fn drain(&mut self) -> std::vec::Drain<'_, usize> {
+        if !self.order.is_empty() {
+            match &mut self.marks {
+                Marks::Sparse(set) => set.clear(),
+                Marks::Dense(words) => {
+                    for &index in &self.order {
+                        words[index / 64] = 0;
+                    }
+                }
+            }
+            self.generation += 1;
+        }
+        self.order.drain(..)
+    }
+}
+
+impl Extend<usize> for DirtyTracker {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, indices: I) {
+        for index in indices {
+            self.insert(index);
+        }
+    }
+}
+
+impl<'a> Extend<&'a usize> for DirtyTracker {
+    fn extend<I: IntoIterator<Item = &'a usize>>(&mut self, indices: I) {
+        self.extend(indices.into_iter().copied());
+    }
+}
+
+impl<'a> IntoIterator for &'a DirtyTracker {
+    type Item = &'a usize;
+    type IntoIter = std::slice::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dense_and_sparse_agree() {
+        for mut tracker in [DirtyTracker::new(), DirtyTracker::dense(8)] {
+            assert!(tracker.insert(5));
+            assert!(tracker.insert(200));
+            assert!(!tracker.insert(5));
+            assert_eq!(tracker.generation(), 2);
+            assert!(tracker.contains(&200));
+            assert!(!tracker.contains(&6));
+
+            assert_eq!(tracker.drain().collect::<Vec<_>>(), [5, 200]);
+            assert!(!tracker.contains(&5));
+            assert_eq!(tracker.generation(), 3);
+
+            // Draining an empty tracker is not a change
+            tracker.clear();
+            assert_eq!(tracker.generation(), 3);
+            assert!(tracker.insert(5));
+            assert_eq!(tracker.iter().copied().collect::<Vec<_>>(), [5]);
+        }
+    }
+
+    #[test]
+    fn extends_from_borrowed_indices() {
+        let mut tracker = DirtyTracker::new();
+        let other: std::collections::HashSet<usize> = [3].into();
+        tracker.extend(&other);
+        tracker.extend(&[1, 3]);
+        assert_eq!(tracker.iter().copied().collect::<Vec<_>>(), [3, 1]);
+    }
+}
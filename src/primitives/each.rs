@@ -0,0 +1,364 @@
+// ============================================================================
+// spark-signals - Keyed List Projection
+// Map a reactive list item by item, reusing work for unchanged keys
+// Based on Solid's mapArray
+// ============================================================================
+//
+// Rendering a list naively rebuilds every row whenever the list changes.
+// `keyed_each` keeps one row per key instead:
+//
+//     let rows = keyed_each(todos.clone(), |t| t.id, |todo, index| {
+//         build_row(todo, index)
+//     });
+//
+// - A key that stays in the list keeps its row. The row's item and index
+//   signals are updated in place, so only what reads them re-runs.
+// - A new key builds a row with `map_fn`, inside its own effect scope.
+// - A key that leaves the list has its scope stopped, disposing every effect
+//   the row created.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
+
+use crate::core::context::with_context;
+use crate::core::types::AnyReaction;
+use crate::primitives::effect::effect_sync;
+use crate::primitives::props::UnwrapProp;
+use crate::primitives::scope::{effect_scope, EffectScope};
+use crate::primitives::signal::{mutable_source, signal, Signal};
+use crate::reactivity::batching::untrack;
+
+/// One mapped item.
+struct Row<T, U> {
+    item: Signal<T>,
+    index: Signal<usize>,
+    output: U,
+    scope: EffectScope,
+}
+
+/// Live rows in list order, with their keys.
+type Rows<K, T, U> = Rc<RefCell<Vec<(K, Row<T, U>)>>>;
+
+/// Run `f` outside the current effect, so effects it creates belong to the
+/// active scope only (not to the effect, which would dispose them on re-run).
+fn outside_effect<R>(f: impl FnOnce() -> R) -> R {
+    // Restore the effect and reaction even if `f` panics
+    struct OutsideGuard {
+        prev_effect: Option<Weak<dyn AnyReaction>>,
+        prev_reaction: Option<Weak<dyn AnyReaction>>,
+    }
+
+    impl Drop for OutsideGuard {
+        fn drop(&mut self) {
+            with_context(|ctx| {
+                ctx.set_active_effect(self.prev_effect.take());
+                ctx.set_active_reaction(self.prev_reaction.take());
+            });
+        }
+    }
+
+    let (prev_effect, prev_reaction) =
+        with_context(|ctx| (ctx.set_active_effect(None), ctx.set_active_reaction(None)));
+    let _guard = OutsideGuard { prev_effect, prev_reaction };
+    f()
+}
+
+// =============================================================================
+// KEYED EACH
+// =============================================================================
+
+/// A reactive list of mapped rows. Created with [`keyed_each()`].
+///
+/// Every row's scope is stopped when the last clone is dropped.
+pub struct KeyedEach<U> {
+    output: Signal<Vec<U>>,
+    _dispose: Rc<dyn Fn()>,
+}
+
+impl<U> Drop for KeyedEach<U> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self._dispose) == 1 {
+            (self._dispose)();
+        }
+    }
+}
+
+impl<U: Clone + 'static> KeyedEach<U> {
+    /// The mapped rows, in list order.
+    pub fn get(&self) -> Vec<U> {
+        self.output.get()
+    }
+
+    /// Access the mapped rows with a closure (avoids cloning).
+    pub fn with<R>(&self, f: impl FnOnce(&[U]) -> R) -> R {
+        self.output.with(|rows| f(rows))
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.with(<[U]>::len)
+    }
+
+    /// True if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<U: Clone> Clone for KeyedEach<U> {
+    fn clone(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<U: std::fmt::Debug + Clone + 'static> std::fmt::Debug for KeyedEach<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyedEach")
+            .field("rows", &self.output.inner().get())
+            .finish()
+    }
+}
+
+/// Map a reactive list item by item, keeping one row per key.
+///
+/// `source` is a `Signal<Vec<T>>`, `Derived<Vec<T>>`, prop, or a shared
+/// `Rc<RefCell<ReactiveVec<T>>>`. `map_fn` runs once per new key and gets the
+/// item and its position as signals, which follow later changes. Effects it
+/// creates live in the row's own scope and are disposed with the row.
+///
+/// Keys should be unique; duplicates each get their own row, matched up in
+/// order.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{keyed_each, signal};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let todos = signal(vec![(1, "write"), (2, "test")]);
+/// let built = Rc::new(Cell::new(0));
+///
+/// let rows = keyed_each(todos.clone(), |todo| todo.0, {
+///     let built = built.clone();
+///     move |todo, _index| {
+///         built.set(built.get() + 1);
+///         todo
+///     }
+/// });
+/// assert_eq!(built.get(), 2);
+///
+/// // Reordering and editing reuse the existing rows
+/// todos.set(vec![(2, "test"), (1, "write docs")]);
+/// assert_eq!(built.get(), 2);
+/// let labels: Vec<_> = rows.get().iter().map(|row| row.get().1).collect();
+/// assert_eq!(labels, ["test", "write docs"]);
+///
+/// // Only new keys are built
+/// todos.set(vec![(1, "write docs"), (3, "ship")]);
+/// assert_eq!(built.get(), 3);
+/// ```
+pub fn keyed_each<T, K, U, S, KF, MF>(source: S, key_fn: KF, map_fn: MF) -> KeyedEach<U>
+where
+    T: Clone + PartialEq + 'static,
+    K: Eq + Hash + 'static,
+    U: Clone + 'static,
+    S: UnwrapProp<Vec<T>> + 'static,
+    KF: Fn(&T) -> K + 'static,
+    MF: Fn(Signal<T>, Signal<usize>) -> U + 'static,
+{
+    let output = mutable_source(Vec::new());
+    let rows: Rows<K, T, U> = Rc::default();
+
+    let dispose = effect_sync({
+        let (output, rows) = (output.clone(), rows.clone());
+        move || {
+            let items = source.unwrap_value();
+
+            untrack(|| {
+                // Previous rows by key; reversed so `pop` hands out the first
+                let mut previous: HashMap<K, Vec<Row<T, U>>> = HashMap::new();
+                for (key, row) in rows.borrow_mut().drain(..).rev() {
+                    previous.entry(key).or_default().push(row);
+                }
+
+                let mut next = Vec::with_capacity(items.len());
+                for (i, item) in items.into_iter().enumerate() {
+                    let key = key_fn(&item);
+                    let reused = previous.get_mut(&key).and_then(Vec::pop);
+                    let row = match reused {
+                        Some(row) => {
+                            row.item.set(item);
+                            row.index.set(i);
+                            row
+                        }
+                        None => {
+                            let (item, index) = (signal(item), signal(i));
+                            let scope = effect_scope(true);
+                            let mapped = outside_effect(|| {
+                                scope.run(|| map_fn(item.clone(), index.clone()))
+                            });
+                            Row {
+                                item,
+                                index,
+                                output: mapped.expect("a fresh scope is active"),
+                                scope,
+                            }
+                        }
+                    };
+                    next.push((key, row));
+                }
+
+                for row in previous.into_values().flatten() {
+                    row.scope.stop();
+                }
+
+                output.set(next.iter().map(|(_, row)| row.output.clone()).collect());
+                *rows.borrow_mut() = next;
+            });
+        }
+    });
+
+    let dispose = std::cell::Cell::new(Some(dispose));
+    KeyedEach {
+        output,
+        _dispose: Rc::new(move || {
+            if let Some(dispose) = dispose.take() {
+                dispose();
+            }
+            for (_, row) in rows.borrow_mut().drain(..) {
+                row.scope.stop();
+            }
+        }),
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::ReactiveVec;
+    use crate::primitives::scope::on_scope_dispose;
+    use crate::reactivity::batching::batch;
+    use std::cell::Cell;
+
+    #[test]
+    fn removed_rows_are_disposed() {
+        let list = signal(vec![1, 2, 3]);
+        let disposed = Rc::new(RefCell::new(Vec::new()));
+        let rows = keyed_each(list.clone(), |n| *n, {
+            let disposed = disposed.clone();
+            move |item, _| {
+                let n = item.inner().get();
+                let disposed = disposed.clone();
+                on_scope_dispose(move || disposed.borrow_mut().push(n));
+                n * 10
+            }
+        });
+        assert_eq!(rows.get(), vec![10, 20, 30]);
+
+        list.set(vec![3, 1]);
+        assert_eq!(rows.get(), vec![30, 10]);
+        assert_eq!(*disposed.borrow(), vec![2]);
+
+        drop(rows);
+        disposed.borrow_mut().sort();
+        assert_eq!(*disposed.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn row_effects_follow_item_and_index() {
+        let list = signal(vec![("a", 1), ("b", 2)]);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let row_runs = Rc::new(Cell::new(0));
+
+        let _rows = keyed_each(list.clone(), |(name, _)| *name, {
+            let (seen, row_runs) = (seen.clone(), row_runs.clone());
+            move |item, index| {
+                let (seen, row_runs) = (seen.clone(), row_runs.clone());
+                // Row effects survive the list effect re-running
+                let _effect = effect_sync(move || {
+                    row_runs.set(row_runs.get() + 1);
+                    seen.borrow_mut().push((item.get().0, item.get().1, index.get()));
+                });
+            }
+        });
+        assert_eq!(row_runs.get(), 2);
+
+        // Only the edited row re-runs
+        list.set(vec![("a", 1), ("b", 5)]);
+        assert_eq!(row_runs.get(), 3);
+        assert_eq!(seen.borrow().last(), Some(&("b", 5, 1)));
+
+        // Moving a row updates its index
+        list.set(vec![("b", 5), ("a", 1)]);
+        assert!(seen.borrow().contains(&("a", 1, 1)));
+        assert!(seen.borrow().contains(&("b", 5, 0)));
+    }
+
+    #[test]
+    fn duplicate_keys_get_their_own_rows() {
+        let list = signal(vec![1, 1, 2]);
+        let built = Rc::new(Cell::new(0));
+        let rows = keyed_each(list.clone(), |n| *n, {
+            let built = built.clone();
+            move |item, index| {
+                built.set(built.get() + 1);
+                (item, index)
+            }
+        });
+        assert_eq!(built.get(), 3);
+
+        list.set(vec![2, 1]);
+        assert_eq!(built.get(), 3);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows.with(|r| r[1].1.get()), 1);
+    }
+
+    #[test]
+    fn reactive_vec_source() {
+        let items = Rc::new(RefCell::new(ReactiveVec::from_iter(["x", "y"])));
+        let rows = keyed_each(items.clone(), |s| *s, |item, _| item);
+        assert_eq!(rows.len(), 2);
+
+        batch(|| items.borrow_mut().push("z"));
+        assert_eq!(rows.len(), 3);
+
+        batch(|| {
+            items.borrow_mut().set(0, "w");
+        });
+        let values: Vec<_> = rows.get().iter().map(|s| s.get()).collect();
+        assert_eq!(values, ["w", "y", "z"]);
+    }
+
+    #[test]
+    fn outside_effect_restores_the_effect_after_a_panic() {
+        let after = signal(0);
+        let runs = Rc::new(Cell::new(0));
+
+        let _dispose = effect_sync({
+            let (after, runs) = (after.clone(), runs.clone());
+            move || {
+                runs.set(runs.get() + 1);
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    outside_effect(|| panic!("mapper failed"))
+                }));
+                assert!(result.is_err());
+                // Still tracked by the running effect
+                after.get();
+            }
+        });
+
+        after.set(1);
+        assert_eq!(runs.get(), 2);
+    }
+}
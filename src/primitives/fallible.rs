@@ -0,0 +1,134 @@
+// ============================================================================
+// spark-signals - Fallible Deriveds
+// Deriveds whose computation returns Result<T, E>
+// ============================================================================
+//
+// `derived_result(|| ...)` is a derived caching a `Result`. The helpers read
+// one side of it (`ok_value()`, `error()`), and downstream deriveds
+// short-circuit on an upstream error either with `?`:
+//
+//     let total = derived_result(move || Ok(price.get()? * qty.get()));
+//
+// or with `map_ok()`, which skips its closure while the upstream is an `Err`.
+// ============================================================================
+
+use crate::primitives::derived::{derived, Derived};
+
+/// Create a derived whose computation can fail.
+///
+/// The `Result` is cached like any derived value: readers are notified when
+/// it changes, including between `Ok` and `Err`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived_result, signal};
+///
+/// let input = signal(String::from("42"));
+/// let parsed = derived_result({
+///     let input = input.clone();
+///     move || input.get().parse::<i32>().map_err(|e| e.to_string())
+/// });
+/// let doubled = parsed.map_ok(|n| n * 2);
+/// assert_eq!(doubled.ok_value(), Some(84));
+///
+/// input.set("forty".into());
+/// assert_eq!(doubled.error().as_deref(), Some("invalid digit found in string"));
+/// ```
+pub fn derived_result<T, E, F>(fn_: F) -> Derived<Result<T, E>>
+where
+    T: Clone + PartialEq + 'static,
+    E: Clone + PartialEq + 'static,
+    F: Fn() -> Result<T, E> + 'static,
+{
+    derived(fn_)
+}
+
+impl<T, E> Derived<Result<T, E>>
+where
+    T: Clone + PartialEq + 'static,
+    E: Clone + PartialEq + 'static,
+{
+    /// The value if the computation succeeded (tracked).
+    pub fn ok_value(&self) -> Option<T> {
+        self.get().ok()
+    }
+
+    /// The error if the computation failed (tracked).
+    pub fn error(&self) -> Option<E> {
+        self.get().err()
+    }
+
+    /// True if the computation succeeded (tracked).
+    pub fn is_ok(&self) -> bool {
+        self.get().is_ok()
+    }
+
+    /// True if the computation failed (tracked).
+    pub fn is_err(&self) -> bool {
+        self.get().is_err()
+    }
+
+    /// Derive from the value, passing an upstream error through.
+    ///
+    /// `f` doesn't run while this derived holds an `Err`.
+    pub fn map_ok<U, F>(&self, f: F) -> Derived<Result<U, E>>
+    where
+        U: Clone + PartialEq + 'static,
+        F: Fn(T) -> U + 'static,
+    {
+        let this = self.clone();
+        derived(move || this.get().map(&f))
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::signal::signal;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn errors_short_circuit_downstream() {
+        let divisor = signal(2);
+        let quotient = derived_result({
+            let divisor = divisor.clone();
+            move || match divisor.get() {
+                0 => Err("division by zero"),
+                d => Ok(10 / d),
+            }
+        });
+
+        let calls = Rc::new(Cell::new(0));
+        let label = quotient.map_ok({
+            let calls = calls.clone();
+            move |q| {
+                calls.set(calls.get() + 1);
+                format!("= {q}")
+            }
+        });
+        let plus_one = derived_result({
+            let quotient = quotient.clone();
+            move || Ok(quotient.get()? + 1)
+        });
+
+        assert_eq!(label.ok_value().as_deref(), Some("= 5"));
+        assert_eq!(plus_one.get(), Ok(6));
+
+        divisor.set(0);
+        assert!(quotient.is_err());
+        assert_eq!(label.error(), Some("division by zero"));
+        assert_eq!(plus_one.get(), Err("division by zero"));
+        assert_eq!(calls.get(), 1);
+
+        divisor.set(5);
+        assert!(label.is_ok());
+        assert_eq!(plus_one.ok_value(), Some(3));
+        assert_eq!(calls.get(), 2);
+    }
+}
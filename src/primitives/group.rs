@@ -0,0 +1,423 @@
+// ============================================================================
+// spark-signals - Signal Group
+// All-or-nothing updates across several signals
+// ============================================================================
+//
+// A SignalGroup registers existing signals as fields. `group.update()`
+// stages writes in a view instead of touching the signals, runs the group's
+// validators against the staged values, and then either commits every
+// staged write in one batch (one notification wave) or discards them all.
+// ============================================================================
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::batch;
+
+/// A group-level validation function.
+pub type GroupValidator = Rc<dyn Fn(&GroupView) -> Result<(), String>>;
+
+// =============================================================================
+// GROUP ERROR
+// =============================================================================
+
+/// Error returned when a group update is rolled back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupError {
+    /// A validator rejected the staged values
+    Invalid(String),
+}
+
+impl std::fmt::Display for GroupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupError::Invalid(reason) => write!(f, "Group update rejected: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {}
+
+// =============================================================================
+// MEMBERS
+// =============================================================================
+
+/// Type-erased group member.
+trait GroupMember {
+    /// Current value, untracked
+    fn current(&self) -> Box<dyn Any>;
+
+    /// Write a staged value
+    fn commit(&self, value: Box<dyn Any>);
+
+    /// The member as `&dyn Any` (a `Signal<T>`)
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Clone + 'static> GroupMember for Signal<T> {
+    fn current(&self) -> Box<dyn Any> {
+        Box::new(self.inner().get())
+    }
+
+    fn commit(&self, value: Box<dyn Any>) {
+        if let Ok(value) = value.downcast::<T>() {
+            self.set(*value);
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Typed handle to a signal registered in a `SignalGroup`.
+pub struct GroupField<T> {
+    index: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for GroupField<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GroupField<T> {}
+
+// =============================================================================
+// GROUP VIEW
+// =============================================================================
+
+/// Staging area passed to `SignalGroup::update()` and to validators.
+///
+/// Reads return the staged value if there is one, otherwise the signal's
+/// current value. Reads are untracked. Writes only touch the stage.
+pub struct GroupView<'a> {
+    members: &'a RefCell<Vec<Rc<dyn GroupMember>>>,
+    staged: RefCell<HashMap<usize, Box<dyn Any>>>,
+}
+
+impl GroupView<'_> {
+    /// Read a field (staged value, or current value if not staged).
+    pub fn get<T: Clone + 'static>(&self, field: GroupField<T>) -> T {
+        if let Some(value) = self.staged.borrow().get(&field.index) {
+            return value
+                .downcast_ref::<T>()
+                .expect("GroupField type mismatch")
+                .clone();
+        }
+
+        // Cloned out: `f` may add fields while the view is alive
+        let member = self.members.borrow()[field.index].clone();
+        *member
+            .current()
+            .downcast::<T>()
+            .expect("GroupField type mismatch")
+    }
+
+    /// Stage a new value for a field.
+    pub fn set<T: 'static>(&self, field: GroupField<T>, value: T) {
+        self.staged.borrow_mut().insert(field.index, Box::new(value));
+    }
+
+    /// Stage an in-place update of a field.
+    pub fn update<T: Clone + 'static>(&self, field: GroupField<T>, f: impl FnOnce(&mut T)) {
+        let mut value = self.get(field);
+        f(&mut value);
+        self.set(field, value);
+    }
+
+    /// True if the field has a staged value.
+    pub fn is_staged<T>(&self, field: GroupField<T>) -> bool {
+        self.staged.borrow().contains_key(&field.index)
+    }
+}
+
+// =============================================================================
+// SIGNAL GROUP
+// =============================================================================
+
+#[derive(Default)]
+struct GroupInner {
+    members: RefCell<Vec<Rc<dyn GroupMember>>>,
+    validators: RefCell<Vec<GroupValidator>>,
+}
+
+/// A set of signals updated together, with validation.
+///
+/// Cloning shares the group.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{signal, GroupError, SignalGroup};
+///
+/// let min = signal(0);
+/// let max = signal(10);
+///
+/// let group = SignalGroup::new();
+/// let min_f = group.add(&min);
+/// let max_f = group.add(&max);
+/// group.validate(move |view| {
+///     if view.get(min_f) <= view.get(max_f) {
+///         Ok(())
+///     } else {
+///         Err("min must not exceed max".into())
+///     }
+/// });
+///
+/// // Both fields move together
+/// group.update(|view| {
+///     view.set(min_f, 20);
+///     view.set(max_f, 30);
+/// })
+/// .unwrap();
+/// assert_eq!((min.get(), max.get()), (20, 30));
+///
+/// // Invalid staged state: nothing is written
+/// let result = group.update(|view| view.set(min_f, 50));
+/// assert!(matches!(result, Err(GroupError::Invalid(_))));
+/// assert_eq!(min.get(), 20);
+/// ```
+#[derive(Clone, Default)]
+pub struct SignalGroup {
+    inner: Rc<GroupInner>,
+}
+
+impl SignalGroup {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a signal as a field of this group.
+    pub fn add<T: Clone + 'static>(&self, signal: &Signal<T>) -> GroupField<T> {
+        let mut members = self.inner.members.borrow_mut();
+        members.push(Rc::new(signal.clone()));
+        GroupField {
+            index: members.len() - 1,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Add a validator. Every validator must accept the staged values for an
+    /// update to commit.
+    pub fn validate<F>(&self, validator: F)
+    where
+        F: Fn(&GroupView) -> Result<(), String> + 'static,
+    {
+        self.inner.validators.borrow_mut().push(Rc::new(validator));
+    }
+
+    /// The signal registered for a field.
+    pub fn signal<T: Clone + 'static>(&self, field: GroupField<T>) -> Signal<T> {
+        self.inner.members.borrow()[field.index]
+            .as_any()
+            .downcast_ref::<Signal<T>>()
+            .expect("GroupField type mismatch")
+            .clone()
+    }
+
+    /// Read a field's signal (tracked).
+    pub fn get<T: Clone + 'static>(&self, field: GroupField<T>) -> T {
+        self.signal(field).get()
+    }
+
+    /// Number of registered signals.
+    pub fn len(&self) -> usize {
+        self.inner.members.borrow().len()
+    }
+
+    /// True if no signals are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stage writes, validate, then commit all or nothing.
+    ///
+    /// `f` stages writes through the view. If every validator accepts the
+    /// staged values, all staged writes are applied in a single batch, so
+    /// dependents see one consistent change. Otherwise no signal is written
+    /// and the first validator error is returned. A panic in `f` also
+    /// discards the stage.
+    pub fn update<R>(&self, f: impl FnOnce(&GroupView) -> R) -> Result<R, GroupError> {
+        // Nothing stays borrowed while `f` and the validators run, so they
+        // can extend the group
+        let validators = self.inner.validators.borrow().clone();
+
+        let view = GroupView {
+            members: &self.inner.members,
+            staged: RefCell::new(HashMap::new()),
+        };
+
+        let result = f(&view);
+
+        for validator in &validators {
+            validator(&view).map_err(GroupError::Invalid)?;
+        }
+
+        let mut staged: Vec<(usize, Box<dyn Any>)> = view.staged.into_inner().into_iter().collect();
+        staged.sort_by_key(|(index, _)| *index);
+        let members = self.inner.members.borrow().clone();
+
+        batch(|| {
+            for (index, value) in staged {
+                members[index].commit(value);
+            }
+        });
+
+        Ok(result)
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use std::cell::Cell;
+
+    fn range_group() -> (SignalGroup, Signal<i32>, Signal<i32>, GroupField<i32>, GroupField<i32>) {
+        let min = signal(0);
+        let max = signal(10);
+        let group = SignalGroup::new();
+        let min_f = group.add(&min);
+        let max_f = group.add(&max);
+        group.validate(move |view| {
+            if view.get(min_f) <= view.get(max_f) {
+                Ok(())
+            } else {
+                Err(format!("{} > {}", view.get(min_f), view.get(max_f)))
+            }
+        });
+        (group, min, max, min_f, max_f)
+    }
+
+    #[test]
+    fn commit_is_one_notification_wave() {
+        let (group, min, max, min_f, max_f) = range_group();
+
+        let runs = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let runs = runs.clone();
+            move || {
+                min.get();
+                max.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        group
+            .update(|view| {
+                view.set(min_f, 5);
+                view.set(max_f, 50);
+            })
+            .unwrap();
+
+        assert_eq!(runs.get(), 2);
+        assert_eq!(group.get(min_f), 5);
+        assert_eq!(group.get(max_f), 50);
+    }
+
+    #[test]
+    fn fields_can_be_added_inside_update() {
+        let (group, _min, _max, min_f, _max_f) = range_group();
+        let step = signal(1);
+
+        let step_f = group
+            .update(|view| {
+                let step_f = group.add(&step);
+                view.set(step_f, 5);
+                view.set(min_f, view.get(step_f));
+                step_f
+            })
+            .unwrap();
+
+        assert_eq!((group.len(), group.get(step_f), group.get(min_f)), (3, 5, 5));
+        assert_eq!(step.get(), 5);
+    }
+
+    #[test]
+    fn rejected_update_writes_nothing() {
+        let (group, min, max, min_f, max_f) = range_group();
+
+        let result = group.update(|view| {
+            view.set(max_f, 3);
+            view.set(min_f, 4);
+        });
+
+        assert_eq!(result, Err(GroupError::Invalid("4 > 3".into())));
+        assert_eq!(min.get(), 0);
+        assert_eq!(max.get(), 10);
+    }
+
+    #[test]
+    fn view_reads_staged_values() {
+        let (group, min, _max, min_f, _max_f) = range_group();
+
+        let seen = group
+            .update(|view| {
+                assert!(!view.is_staged(min_f));
+                view.update(min_f, |v| *v += 2);
+                assert!(view.is_staged(min_f));
+                // Signal itself is untouched until commit
+                assert_eq!(min.get(), 0);
+                view.get(min_f)
+            })
+            .unwrap();
+
+        assert_eq!(seen, 2);
+        assert_eq!(min.get(), 2);
+    }
+
+    #[test]
+    fn panic_in_update_discards_stage() {
+        let (group, min, _max, min_f, _max_f) = range_group();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = group.update(|view| {
+                view.set(min_f, 7);
+                panic!("boom");
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(min.get(), 0);
+    }
+
+    #[test]
+    fn heterogeneous_fields() {
+        let name = signal(String::from("a"));
+        let enabled = signal(false);
+        let group = SignalGroup::new();
+        let name_f = group.add(&name);
+        let enabled_f = group.add(&enabled);
+        group.validate(move |view| {
+            if view.get(enabled_f) && view.get(name_f).is_empty() {
+                Err("name required".into())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(group
+            .update(|view| {
+                view.set(name_f, String::new());
+                view.set(enabled_f, true);
+            })
+            .is_err());
+
+        group.update(|view| view.set(enabled_f, true)).unwrap();
+        assert!(enabled.get());
+        assert_eq!(group.len(), 2);
+        assert!(group.signal(name_f).get() == "a");
+    }
+}
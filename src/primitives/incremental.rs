@@ -0,0 +1,450 @@
+// ============================================================================
+// spark-signals - Incremental Derived
+// Time-sliced computations that make progress across frames
+// ============================================================================
+//
+// A normal derived computes its whole value inside the flush that reads it,
+// so a large layout or pathfinding pass blocks the frame. An incremental
+// derived splits the work into steps:
+//
+// - `start` runs inside a tracking effect. It reads the inputs and returns
+//   the initial work state. When an input changes, the work restarts.
+// - `step` runs untracked, one slice at a time, and returns either
+//   `Step::Continue(state)` or `Step::Done(value)`.
+//
+// Steps are driven by `advance_incremental(budget)` (e.g. once per frame),
+// never by the flush itself. While work proceeds, `get()` keeps returning
+// the last complete value and `is_computing()` is true.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+use crate::core::context::with_context;
+use crate::primitives::effect::effect_sync;
+use crate::primitives::signal::{signal, signal_with_equals, Signal};
+use crate::reactivity::batching::{batch, untrack};
+
+type DisposeCell = RefCell<Option<Box<dyn FnOnce()>>>;
+
+// =============================================================================
+// STEP
+// =============================================================================
+
+/// Result of one slice of an incremental computation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step<S, T> {
+    /// More work remains; resume from this state on the next slice.
+    Continue(S),
+    /// The computation finished with this value.
+    Done(T),
+}
+
+// =============================================================================
+// PENDING REGISTRY
+// =============================================================================
+
+/// Type-erased handle to an in-progress computation.
+pub(crate) trait IncrementalTask {
+    /// Run one step. Returns true if more work remains.
+    fn step(&self) -> bool;
+}
+
+fn schedule_task(task: Weak<dyn IncrementalTask>) {
+    with_context(|ctx| {
+        let mut pending = ctx.incremental.borrow_mut();
+        if !pending.iter().any(|t| Weak::ptr_eq(t, &task)) {
+            pending.push(task);
+        }
+    });
+}
+
+/// Run pending incremental computations for up to `budget`.
+///
+/// Pending computations are stepped round-robin, so one long computation
+/// can't starve the others. At least one round of steps always runs, even
+/// with a zero budget. All completions are applied in a single batch.
+///
+/// Returns true if any computation still has work left. Call this once per
+/// frame from your event loop.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{advance_incremental, derived_incremental, signal, Step};
+/// use std::time::Duration;
+///
+/// let n = signal(3u64);
+/// let sum = derived_incremental(
+///     {
+///         let n = n.clone();
+///         move || (n.get(), 0u64)
+///     },
+///     |(left, acc)| {
+///         if left == 0 {
+///             Step::Done(acc)
+///         } else {
+///             Step::Continue((left - 1, acc + left))
+///         }
+///     },
+/// );
+///
+/// assert_eq!(sum.get(), None);
+/// while advance_incremental(Duration::ZERO) {}
+/// assert_eq!(sum.get(), Some(6));
+/// ```
+pub fn advance_incremental(budget: Duration) -> bool {
+    let deadline = Instant::now() + budget;
+
+    batch(|| loop {
+        let tasks: Vec<Rc<dyn IncrementalTask>> = with_context(|ctx| {
+            let mut pending = ctx.incremental.borrow_mut();
+            pending.retain(|t| t.strong_count() > 0);
+            pending.iter().filter_map(|t| t.upgrade()).collect()
+        });
+
+        if tasks.is_empty() {
+            return false;
+        }
+
+        for task in &tasks {
+            if !task.step() {
+                let done = Rc::downgrade(task);
+                with_context(|ctx| {
+                    ctx.incremental.borrow_mut().retain(|t| !Weak::ptr_eq(t, &done));
+                });
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return with_context(|ctx| !ctx.incremental.borrow().is_empty());
+        }
+    })
+}
+
+// =============================================================================
+// INCREMENTAL INNER
+// =============================================================================
+
+struct IncrementalInner<S, T> {
+    /// Work state for the current run (None when idle)
+    state: RefCell<Option<S>>,
+
+    /// One slice of work
+    step: Box<dyn Fn(S) -> Step<S, T>>,
+
+    /// Last complete value
+    value: Signal<Option<T>>,
+
+    /// True while work is in progress
+    computing: Signal<bool>,
+
+    /// Disposes the tracking effect
+    dispose: DisposeCell,
+}
+
+impl<S: 'static, T: Clone + 'static> IncrementalTask for IncrementalInner<S, T> {
+    fn step(&self) -> bool {
+        let Some(state) = self.state.borrow_mut().take() else {
+            return false;
+        };
+
+        match untrack(|| (self.step)(state)) {
+            Step::Continue(next) => {
+                // A restart during the step already installed a fresh state
+                let mut slot = self.state.borrow_mut();
+                if slot.is_none() {
+                    *slot = Some(next);
+                }
+                true
+            }
+            Step::Done(value) => {
+                if self.state.borrow().is_some() {
+                    return true;
+                }
+                self.value.set(Some(value));
+                self.computing.set(false);
+                false
+            }
+        }
+    }
+}
+
+impl<S, T> Drop for IncrementalInner<S, T> {
+    fn drop(&mut self) {
+        if let Some(dispose) = self.dispose.borrow_mut().take() {
+            dispose();
+        }
+    }
+}
+
+/// Object-safe view used by `Incremental<T>`, hiding the state type.
+trait IncrementalValue<T> {
+    fn value(&self) -> &Signal<Option<T>>;
+    fn computing(&self) -> &Signal<bool>;
+    fn step(&self) -> bool;
+}
+
+impl<S: 'static, T: Clone + 'static> IncrementalValue<T> for IncrementalInner<S, T> {
+    fn value(&self) -> &Signal<Option<T>> {
+        &self.value
+    }
+
+    fn computing(&self) -> &Signal<bool> {
+        &self.computing
+    }
+
+    fn step(&self) -> bool {
+        IncrementalTask::step(self)
+    }
+}
+
+// =============================================================================
+// INCREMENTAL
+// =============================================================================
+
+/// A derived value computed in slices across frames.
+///
+/// Created by `derived_incremental()`. Cloning shares the computation.
+pub struct Incremental<T> {
+    inner: Rc<dyn IncrementalValue<T>>,
+}
+
+impl<T> Clone for Incremental<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Clone + 'static> Incremental<T> {
+    /// The last complete value (tracked). None until the first run finishes.
+    pub fn get(&self) -> Option<T> {
+        self.inner.value().get()
+    }
+
+    /// True while a run is in progress (tracked).
+    pub fn is_computing(&self) -> bool {
+        self.inner.computing().get()
+    }
+
+    /// Run a single step of this computation only.
+    ///
+    /// Returns true if more work remains.
+    pub fn advance(&self) -> bool {
+        batch(|| self.inner.step())
+    }
+
+    /// Run the current computation to completion and return its value.
+    pub fn finish(&self) -> Option<T> {
+        batch(|| while self.inner.step() {});
+        self.inner.value().get()
+    }
+}
+
+// =============================================================================
+// DERIVED INCREMENTAL
+// =============================================================================
+
+/// Create a derived value that is computed in slices.
+///
+/// `start` reads the inputs (tracked) and returns the initial work state;
+/// it re-runs whenever an input changes, restarting the work. `step`
+/// (untracked) advances the state by one slice and returns
+/// `Step::Continue(state)` or `Step::Done(value)`.
+///
+/// No step runs during the flush. Drive the work with
+/// `advance_incremental(budget)`, or `advance()` / `finish()` on the handle.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived_incremental, signal, Step};
+///
+/// let target = signal(1000u32);
+/// let count = derived_incremental(
+///     {
+///         let target = target.clone();
+///         move || (0u32, target.get())
+///     },
+///     |(i, target)| {
+///         // 100 units of work per slice
+///         let next = (i + 100).min(target);
+///         if next == target {
+///             Step::Done(next)
+///         } else {
+///             Step::Continue((next, target))
+///         }
+///     },
+/// );
+///
+/// assert!(count.is_computing());
+/// assert!(count.advance());
+/// assert_eq!(count.get(), None); // still working
+///
+/// assert_eq!(count.finish(), Some(1000));
+/// assert!(!count.is_computing());
+/// ```
+pub fn derived_incremental<S, T, F, G>(start: F, step: G) -> Incremental<T>
+where
+    S: 'static,
+    T: Clone + 'static,
+    F: Fn() -> S + 'static,
+    G: Fn(S) -> Step<S, T> + 'static,
+{
+    let inner = Rc::new(IncrementalInner {
+        state: RefCell::new(None),
+        step: Box::new(step),
+        value: signal_with_equals(None, |_, _| false),
+        computing: signal(false),
+        dispose: RefCell::new(None),
+    });
+
+    let dispose = effect_sync({
+        let weak = Rc::downgrade(&inner);
+        move || {
+            let state = start();
+
+            if let Some(inner) = weak.upgrade() {
+                *inner.state.borrow_mut() = Some(state);
+                untrack(|| inner.computing.set(true));
+                schedule_task(Rc::downgrade(&(inner as Rc<dyn IncrementalTask>)));
+            }
+        }
+    });
+    *inner.dispose.borrow_mut() = Some(Box::new(dispose));
+
+    Incremental { inner }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use std::cell::Cell;
+
+    fn countdown(n: &Signal<u32>, steps: &Rc<Cell<u32>>) -> Incremental<u32> {
+        let steps = steps.clone();
+        derived_incremental(
+            {
+                let n = n.clone();
+                move || (n.get(), 0u32)
+            },
+            move |(left, acc)| {
+                steps.set(steps.get() + 1);
+                if left == 0 {
+                    Step::Done(acc)
+                } else {
+                    Step::Continue((left - 1, acc + left))
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn no_work_happens_during_flush() {
+        let n = signal(3);
+        let steps = Rc::new(Cell::new(0));
+        let sum = countdown(&n, &steps);
+
+        assert!(sum.is_computing());
+        assert_eq!(sum.get(), None);
+        assert_eq!(steps.get(), 0);
+    }
+
+    #[test]
+    fn advance_makes_progress_one_slice_at_a_time() {
+        let n = signal(3);
+        let steps = Rc::new(Cell::new(0));
+        let sum = countdown(&n, &steps);
+
+        assert!(sum.advance());
+        assert!(sum.advance());
+        assert!(sum.advance());
+        assert_eq!(sum.get(), None);
+        assert!(!sum.advance());
+        assert_eq!(sum.get(), Some(6));
+        assert!(!sum.is_computing());
+        assert_eq!(steps.get(), 4);
+    }
+
+    #[test]
+    fn keeps_last_value_while_recomputing() {
+        let n = signal(2);
+        let steps = Rc::new(Cell::new(0));
+        let sum = countdown(&n, &steps);
+        assert_eq!(sum.finish(), Some(3));
+
+        n.set(4);
+        assert!(sum.is_computing());
+        assert!(sum.advance());
+        assert_eq!(sum.get(), Some(3));
+
+        assert_eq!(sum.finish(), Some(10));
+    }
+
+    #[test]
+    fn input_change_restarts_work() {
+        let n = signal(100);
+        let steps = Rc::new(Cell::new(0));
+        let sum = countdown(&n, &steps);
+
+        sum.advance();
+        sum.advance();
+        n.set(1);
+
+        assert_eq!(sum.finish(), Some(1));
+        // 2 steps of the abandoned run + 2 steps of the new one
+        assert_eq!(steps.get(), 4);
+    }
+
+    #[test]
+    fn advance_incremental_steps_all_pending_round_robin() {
+        let a = signal(2);
+        let b = signal(5);
+        let steps = Rc::new(Cell::new(0));
+        let sum_a = countdown(&a, &steps);
+        let sum_b = countdown(&b, &steps);
+
+        // Zero budget: exactly one round
+        assert!(advance_incremental(Duration::ZERO));
+        assert_eq!(steps.get(), 2);
+
+        while advance_incremental(Duration::ZERO) {}
+        assert_eq!(sum_a.get(), Some(3));
+        assert_eq!(sum_b.get(), Some(15));
+    }
+
+    #[test]
+    fn completion_notifies_readers() {
+        let n = signal(1);
+        let steps = Rc::new(Cell::new(0));
+        let sum = countdown(&n, &steps);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let sum = sum.clone();
+            let seen = seen.clone();
+            move || seen.borrow_mut().push((sum.get(), sum.is_computing()))
+        });
+
+        sum.finish();
+        assert_eq!(*seen.borrow(), vec![(None, true), (Some(1), false)]);
+    }
+
+    #[test]
+    fn dropped_computation_leaves_queue() {
+        let n = signal(3);
+        let steps = Rc::new(Cell::new(0));
+        drop(countdown(&n, &steps));
+
+        assert!(!advance_incremental(Duration::ZERO));
+        assert_eq!(steps.get(), 0);
+    }
+}
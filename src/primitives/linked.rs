@@ -13,11 +13,15 @@ use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use crate::core::types::EqualsFn;
+use crate::primitives::bind::{bind_getter, ReadonlyBinding};
 use crate::primitives::derived::derived;
 use crate::primitives::effect::effect_sync;
 use crate::primitives::signal::{signal, signal_with_equals, Signal};
 use crate::reactivity::batching::untrack;
 
+/// Shared, take-once dispose closure for the internal sync effect.
+type DisposeCell = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+
 // =============================================================================
 // TYPES
 // =============================================================================
@@ -39,7 +43,13 @@ where
     pub equal: Option<EqualsFn<D>>,
 }
 
-/// Previous value context passed to the computation function.
+/// Previous value context passed to the computation function, and returned
+/// by [`LinkedSignal::previous()`].
+///
+/// Both fields describe the moment just before the latest source change:
+/// the source as it was, and the value as it was (including any manual
+/// override).
+#[derive(Debug, Clone, PartialEq)]
 pub struct PreviousValue<S, D> {
     /// The previous source value.
     pub source: S,
@@ -47,6 +57,61 @@ pub struct PreviousValue<S, D> {
     pub value: D,
 }
 
+/// `ResetPolicy::KeepOverrideWhile` predicate: (new source, current value).
+pub type KeepFn<T> = Rc<dyn Fn(&T, &T) -> bool>;
+
+/// `ResetPolicy::MergeWith` function: (new source, current value) -> value.
+pub type MergeFn<T> = Rc<dyn Fn(T, T) -> T>;
+
+/// What a linked signal does with a manual override when its source changes.
+///
+/// Without an override the value always follows the source; the policy only
+/// decides the fate of values set with `set()` / `update()`.
+#[derive(Default)]
+pub enum ResetPolicy<T> {
+    /// Drop the override and take the new source value (the default).
+    #[default]
+    ResetOnSourceChange,
+    /// Keep the override while `pred(&new_source, &current)` holds;
+    /// otherwise reset.
+    KeepOverrideWhile(KeepFn<T>),
+    /// Replace the value with `merge(new_source, current)`. The result still
+    /// counts as an override, so later source changes merge again.
+    MergeWith(MergeFn<T>),
+}
+
+impl<T> ResetPolicy<T> {
+    /// `ResetPolicy::KeepOverrideWhile` from a closure.
+    pub fn keep_override_while(pred: impl Fn(&T, &T) -> bool + 'static) -> Self {
+        ResetPolicy::KeepOverrideWhile(Rc::new(pred))
+    }
+
+    /// `ResetPolicy::MergeWith` from a closure.
+    pub fn merge_with(merge: impl Fn(T, T) -> T + 'static) -> Self {
+        ResetPolicy::MergeWith(Rc::new(merge))
+    }
+}
+
+impl<T> Clone for ResetPolicy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            ResetPolicy::ResetOnSourceChange => ResetPolicy::ResetOnSourceChange,
+            ResetPolicy::KeepOverrideWhile(pred) => ResetPolicy::KeepOverrideWhile(pred.clone()),
+            ResetPolicy::MergeWith(merge) => ResetPolicy::MergeWith(merge.clone()),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ResetPolicy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ResetPolicy::ResetOnSourceChange => "ResetOnSourceChange",
+            ResetPolicy::KeepOverrideWhile(_) => "KeepOverrideWhile(..)",
+            ResetPolicy::MergeWith(_) => "MergeWith(..)",
+        })
+    }
+}
+
 // =============================================================================
 // LINKED SIGNAL
 // =============================================================================
@@ -57,10 +122,16 @@ pub struct PreviousValue<S, D> {
 /// This is inspired by Angular's linkedSignal - it solves the common UI pattern
 /// where form state should reset when parent data changes, but the user can
 /// also manually edit the value.
-pub struct LinkedSignal<T> {
+///
+/// `S` is the source type; it differs from `T` only for
+/// [`linked_signal_full()`].
+pub struct LinkedSignal<T, S = T> {
     /// The internal value signal.
     value_signal: Signal<T>,
 
+    /// Source and value just before the latest source change.
+    previous: Rc<RefCell<Option<PreviousValue<S, T>>>>,
+
     /// Track if user manually overrode the value.
     #[allow(dead_code)]
     manual_override: Rc<Cell<bool>>,
@@ -69,7 +140,7 @@ pub struct LinkedSignal<T> {
     _dispose: Rc<dyn Fn()>,
 }
 
-impl<T> Drop for LinkedSignal<T> {
+impl<T, S> Drop for LinkedSignal<T, S> {
     fn drop(&mut self) {
         // Only run dispose if this is the last strong reference
         // (shared ownership via Rc)
@@ -79,7 +150,7 @@ impl<T> Drop for LinkedSignal<T> {
     }
 }
 
-impl<T: Clone + PartialEq + 'static> LinkedSignal<T> {
+impl<T: Clone + PartialEq + 'static, S: 'static> LinkedSignal<T, S> {
     /// Get the current value.
     ///
     /// In a reactive context, this creates a dependency on the underlying signal.
@@ -110,19 +181,41 @@ impl<T: Clone + PartialEq + 'static> LinkedSignal<T> {
     pub fn peek(&self) -> T {
         untrack(|| self.value_signal.get())
     }
+
+    /// A read-only view of the value.
+    ///
+    /// The view keeps the linked signal (and its source tracking) alive.
+    pub fn readonly(&self) -> ReadonlyBinding<T> {
+        let this = self.clone();
+        bind_getter(move || this.get())
+    }
+
+    /// The source and value just before the latest source change, or None if
+    /// the source hasn't changed yet (not tracked).
+    ///
+    /// `value` includes any manual override that the change replaced.
+    pub fn previous(&self) -> Option<PreviousValue<S, T>>
+    where
+        S: Clone,
+    {
+        self.previous.borrow().clone()
+    }
 }
 
-impl<T: Clone> Clone for LinkedSignal<T> {
+impl<T: Clone, S> Clone for LinkedSignal<T, S> {
     fn clone(&self) -> Self {
         Self {
             value_signal: self.value_signal.clone(),
+            previous: self.previous.clone(),
             manual_override: self.manual_override.clone(),
             _dispose: self._dispose.clone(),
         }
     }
 }
 
-impl<T: std::fmt::Debug + Clone + PartialEq + 'static> std::fmt::Debug for LinkedSignal<T> {
+impl<T: std::fmt::Debug + Clone + PartialEq + 'static, S: 'static> std::fmt::Debug
+    for LinkedSignal<T, S>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LinkedSignal")
             .field("value", &self.get())
@@ -168,6 +261,7 @@ where
     linked_signal_with_options(LinkedSignalOptionsSimple {
         source: getter,
         equal: None,
+        reset: ResetPolicy::default(),
     })
 }
 
@@ -178,9 +272,41 @@ where
 {
     pub source: F,
     pub equal: Option<EqualsFn<T>>,
+    /// What happens to a manual override when the source changes.
+    pub reset: ResetPolicy<T>,
 }
 
 /// Create a linked signal with simple options (source only, no computation).
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{linked_signal_with_options, signal};
+/// use spark_signals::{LinkedSignalOptionsSimple, ResetPolicy};
+///
+/// // A form field that keeps the user's edit unless the server value
+/// // catches up with it
+/// let server = signal(String::from("draft"));
+/// let field = linked_signal_with_options(LinkedSignalOptionsSimple {
+///     source: {
+///         let server = server.clone();
+///         move || server.get()
+///     },
+///     equal: None,
+///     reset: ResetPolicy::keep_override_while(|server, edit| server != edit),
+/// });
+///
+/// field.set(String::from("final"));
+/// server.set(String::from("saving"));
+/// assert_eq!(field.get(), "final");
+///
+/// let previous = field.previous().unwrap();
+/// assert_eq!((previous.source.as_str(), previous.value.as_str()), ("draft", "final"));
+///
+/// server.set(String::from("final"));
+/// server.set(String::from("reverted"));
+/// assert_eq!(field.get(), "reverted");
+/// ```
 pub fn linked_signal_with_options<T, F>(options: LinkedSignalOptionsSimple<T, F>) -> LinkedSignal<T>
 where
     T: Clone + PartialEq + 'static,
@@ -188,11 +314,13 @@ where
 {
     let source_fn = Rc::new(options.source);
     let equal = options.equal;
+    let reset = options.reset;
 
     // State
     let initialized = Rc::new(Cell::new(false));
     let manual_override = Rc::new(Cell::new(false));
     let last_known_source: Rc<RefCell<Option<T>>> = Rc::new(RefCell::new(None));
+    let previous: Rc<RefCell<Option<PreviousValue<T, T>>>> = Rc::new(RefCell::new(None));
 
     // Create the value signal with initial undefined value
     // We'll set it properly in the first effect run
@@ -218,6 +346,7 @@ where
         let initialized = initialized.clone();
         let last_known_source = last_known_source.clone();
         let manual_override_inner = manual_override.clone();
+        let previous = previous.clone();
 
         move || {
             let current_source = source_tracker.get();
@@ -230,23 +359,48 @@ where
 
             if !initialized.get() || source_changed {
                 // Source changed or first init - update the value
-                *last_known_source.borrow_mut() = Some(current_source.clone());
+                let last_source = last_known_source.replace(Some(current_source.clone()));
                 initialized.set(true);
+
+                let current = untrack(|| value_signal.get());
+                if let Some(source) = last_source {
+                    *previous.borrow_mut() = Some(PreviousValue {
+                        source,
+                        value: current.clone(),
+                    });
+                }
+
+                let next = match &reset {
+                    _ if !manual_override_inner.get() => current_source,
+                    ResetPolicy::ResetOnSourceChange => current_source,
+                    ResetPolicy::KeepOverrideWhile(keep) => {
+                        if keep(&current_source, &current) {
+                            return;
+                        }
+                        current_source
+                    }
+                    ResetPolicy::MergeWith(merge) => {
+                        let merged = merge(current_source, current);
+                        untrack(|| value_signal.set(merged));
+                        return;
+                    }
+                };
                 manual_override_inner.set(false);
 
                 // Update the value signal without tracking to avoid loops
                 untrack(|| {
-                    value_signal.set(current_source);
+                    value_signal.set(next);
                 });
             }
         }
     });
 
     // Wrap dispose in Rc for cloning
-    let dispose_fn: Rc<RefCell<Option<Box<dyn FnOnce()>>>> = Rc::new(RefCell::new(Some(Box::new(dispose))));
+    let dispose_fn: DisposeCell = Rc::new(RefCell::new(Some(Box::new(dispose))));
 
     LinkedSignal {
         value_signal,
+        previous,
         manual_override,
         _dispose: Rc::new({
             let dispose_fn = dispose_fn.clone();
@@ -300,6 +454,7 @@ where
 /// // Options change but "b" is still valid - keep it!
 /// options.set(vec!["x", "b", "z"]);
 /// assert_eq!(selected.get(), "b");
+/// assert_eq!(selected.previous().unwrap().source, ["a", "b", "c"]);
 ///
 /// // Options change and "b" is gone - reset to first
 /// options.set(vec!["x", "y", "z"]);
@@ -309,7 +464,7 @@ pub fn linked_signal_full<S, D, F, C>(
     source: F,
     computation: C,
     equal: Option<EqualsFn<D>>,
-) -> LinkedSignal<D>
+) -> LinkedSignal<D, S>
 where
     S: Clone + PartialEq + 'static,
     D: Clone + PartialEq + 'static,
@@ -324,6 +479,7 @@ where
     let manual_override = Rc::new(Cell::new(false));
     let prev_source: Rc<RefCell<Option<S>>> = Rc::new(RefCell::new(None));
     let prev_value: Rc<RefCell<Option<D>>> = Rc::new(RefCell::new(None));
+    let previous: Rc<RefCell<Option<PreviousValue<S, D>>>> = Rc::new(RefCell::new(None));
 
     // Create value signal - we need an initial value
     let initial_source = (source_fn)();
@@ -355,6 +511,7 @@ where
         let prev_source = prev_source.clone();
         let prev_value = prev_value.clone();
         let manual_override_inner = manual_override.clone();
+        let previous_out = previous.clone();
 
         move || {
             let current_source = source_tracker.get();
@@ -381,6 +538,7 @@ where
                 };
 
                 // Compute new value
+                previous_out.borrow_mut().clone_from(&previous);
                 let new_value = (computation_fn)(current_source.clone(), previous);
 
                 // Update state
@@ -397,10 +555,11 @@ where
     });
 
     // Wrap dispose in Rc for cloning
-    let dispose_fn: Rc<RefCell<Option<Box<dyn FnOnce()>>>> = Rc::new(RefCell::new(Some(Box::new(dispose))));
+    let dispose_fn: DisposeCell = Rc::new(RefCell::new(Some(Box::new(dispose))));
 
     LinkedSignal {
         value_signal,
+        previous,
         manual_override,
         _dispose: Rc::new({
             let dispose_fn = dispose_fn.clone();
@@ -420,7 +579,7 @@ where
 /// Marker trait to identify LinkedSignal types.
 pub trait IsLinkedSignal {}
 
-impl<T> IsLinkedSignal for LinkedSignal<T> {}
+impl<T, S> IsLinkedSignal for LinkedSignal<T, S> {}
 
 /// Check if a value is a LinkedSignal.
 pub fn is_linked_signal<T: IsLinkedSignal>(_value: &T) -> bool {
@@ -654,4 +813,50 @@ mod tests {
 
         assert!(is_linked_signal(&linked));
     }
+
+    #[test]
+    fn linked_signal_readonly_outlives_handle() {
+        let source = signal(1);
+        let linked = linked_signal({
+            let source = source.clone();
+            move || source.get() * 10
+        });
+        let view = linked.readonly();
+        drop(linked);
+
+        assert_eq!(view.get(), 10);
+        source.set(2);
+        assert_eq!(view.get(), 20);
+    }
+
+    #[test]
+    fn merge_policy_reapplies_to_overrides() {
+        let server = signal(vec![1, 2]);
+        let edited = linked_signal_with_options(LinkedSignalOptionsSimple {
+            source: {
+                let server = server.clone();
+                move || server.get()
+            },
+            equal: None,
+            reset: ResetPolicy::merge_with(|mut server: Vec<i32>, local: Vec<i32>| {
+                server.extend(local.into_iter().filter(|n| *n > 100));
+                server
+            }),
+        });
+        assert!(edited.previous().is_none());
+
+        // Without an override the source wins
+        server.set(vec![3]);
+        assert_eq!(edited.get(), [3]);
+
+        edited.update(|v| v.push(500));
+        server.set(vec![4]);
+        assert_eq!(edited.get(), [4, 500]);
+        server.set(vec![5]);
+        assert_eq!(edited.get(), [5, 500]);
+
+        let previous = edited.previous().unwrap();
+        assert_eq!(previous.source, [4]);
+        assert_eq!(previous.value, [4, 500]);
+    }
 }
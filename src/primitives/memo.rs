@@ -0,0 +1,827 @@
+// ============================================================================
+// spark-signals - Structural Memo
+// Crate-wide deduplication of identical derived computations
+// ============================================================================
+//
+// Component trees tend to instantiate the same derived (e.g. "format this
+// price") hundreds of times over the same inputs. `memo_structural(key, f)`
+// shares one computation between all of them:
+//
+// - Deriveds are grouped by (key, value type)
+// - Within a group, a new memo reuses an existing one when both read exactly
+//   the same set of sources on their first computation
+// - Handles are ref-counted; when the last reader drops its handle the
+//   shared derived (and its subscriptions) is torn down
+//
+// The dependency set is captured on the first computation. A memo with
+// dynamic dependencies keeps the identity it was registered with.
+//
+// `pure_memo(f)` covers the other common case: a plain helper function
+// (e.g. currency formatting that reads a locale signal) called with the same
+// arguments from many effects in one flush. Results are cached per argument
+// for the duration of the flush, and a cache hit still tracks the sources
+// the original call read, so callers stay subscribed.
+//
+// `memo_keyed(keys, f)` keeps one derived per key of a reactive key set
+// (e.g. the keys of a `ReactiveMap`), created on first read. When a key
+// leaves the set its derived is dropped.
+// ============================================================================
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
+
+use crate::core::constants::DERIVED;
+use crate::core::context::{with_context, Owner};
+use crate::core::types::AnySource;
+use crate::primitives::derived::{derived, derived_with_equals, update_derived_chain, Derived};
+use crate::primitives::effect::effect_sync;
+use crate::primitives::signal::{signal, Signal};
+use crate::reactivity::batching::untrack;
+use crate::reactivity::scheduling::current_flush;
+use crate::reactivity::tracking::track_read;
+
+// =============================================================================
+// REGISTRY
+// =============================================================================
+
+/// Registry key: user key + value type (so different T never collide).
+pub(crate) type MemoKey = (String, TypeId);
+
+/// A registered shared memo, identified by its dependency set.
+pub(crate) struct MemoEntry {
+    /// Sorted source addresses read by the first computation
+    deps: Vec<usize>,
+
+    /// The shared state (`MemoShared<T>`), type-erased
+    shared: Weak<dyn Any>,
+}
+
+/// State shared by every handle to the same memo.
+struct MemoShared<T: Clone + 'static> {
+    derived: Derived<T>,
+    key: MemoKey,
+    /// The context whose registry lists this memo
+    owner: Owner,
+}
+
+impl<T: Clone + 'static> Drop for MemoShared<T> {
+    fn drop(&mut self) {
+        // The registry may already be gone during thread teardown
+        self.owner.try_with(|ctx| {
+            let mut registry = ctx.memos.borrow_mut();
+            if let Some(entries) = registry.get_mut(&self.key) {
+                entries.retain(|e| e.shared.strong_count() > 0);
+                if entries.is_empty() {
+                    registry.remove(&self.key);
+                }
+            }
+        });
+    }
+}
+
+/// Collect the sorted, deduplicated source addresses a derived depends on.
+fn dependency_set<T: Clone + 'static>(d: &Derived<T>) -> Vec<usize> {
+    let mut deps = Vec::new();
+    d.as_any_reaction().for_each_dep(&mut |dep| {
+        deps.push(Rc::as_ptr(dep) as *const () as usize);
+        true
+    });
+    deps.sort_unstable();
+    deps.dedup();
+    deps
+}
+
+// =============================================================================
+// STRUCTURAL MEMO HANDLE
+// =============================================================================
+
+/// A handle to a derived computation shared across the crate.
+///
+/// Cloning a handle adds a reader; dropping the last handle tears down the
+/// shared derived.
+pub struct StructuralMemo<T: Clone + 'static> {
+    shared: Rc<MemoShared<T>>,
+}
+
+impl<T: Clone + 'static> Clone for StructuralMemo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: Clone + 'static> StructuralMemo<T> {
+    /// Get the memo's value (tracks like `Derived::get`).
+    pub fn get(&self) -> T {
+        self.shared.derived.get()
+    }
+
+    /// The underlying shared derived.
+    pub fn derived(&self) -> &Derived<T> {
+        &self.shared.derived
+    }
+
+    /// Number of live handles to this memo.
+    pub fn reader_count(&self) -> usize {
+        Rc::strong_count(&self.shared)
+    }
+
+    /// True if both handles share the same computation.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+// =============================================================================
+// PUBLIC API
+// =============================================================================
+
+/// Create a derived that is deduplicated crate-wide by key and dependency set.
+///
+/// The computation runs once up front to discover which sources it reads.
+/// If a live memo with the same key and value type already reads exactly
+/// those sources, its handle is returned and the probe is discarded.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{memo_structural, signal};
+///
+/// let price = signal(1999);
+///
+/// let a = memo_structural("format_price", {
+///     let price = price.clone();
+///     move || format!("${:.2}", price.get() as f64 / 100.0)
+/// });
+/// let b = memo_structural("format_price", {
+///     let price = price.clone();
+///     move || format!("${:.2}", price.get() as f64 / 100.0)
+/// });
+///
+/// assert!(a.ptr_eq(&b));
+/// assert_eq!(a.reader_count(), 2);
+/// assert_eq!(b.get(), "$19.99");
+/// ```
+pub fn memo_structural<T, F>(key: impl Into<String>, f: F) -> StructuralMemo<T>
+where
+    T: Clone + PartialEq + 'static,
+    F: Fn() -> T + 'static,
+{
+    let key: MemoKey = (key.into(), TypeId::of::<T>());
+
+    // Probe: compute once (untracked) to learn the dependency set
+    let probe = derived(f);
+    update_derived_chain(probe.as_any_source());
+    let deps = dependency_set(&probe);
+
+    let existing = with_context(|ctx| {
+        let registry = ctx.memos.borrow();
+        registry.get(&key).and_then(|entries| {
+            entries
+                .iter()
+                .filter(|e| e.deps == deps)
+                .find_map(|e| e.shared.upgrade())
+        })
+    });
+
+    if let Some(shared) = existing {
+        if let Ok(shared) = Rc::downcast::<MemoShared<T>>(shared) {
+            return StructuralMemo { shared };
+        }
+    }
+
+    let shared = Rc::new(MemoShared {
+        derived: probe,
+        key: key.clone(),
+        owner: Owner::current(),
+    });
+    let erased: Rc<dyn Any> = shared.clone();
+
+    with_context(|ctx| {
+        ctx.memos.borrow_mut().entry(key).or_default().push(MemoEntry {
+            deps,
+            shared: Rc::downgrade(&erased),
+        });
+    });
+
+    StructuralMemo { shared }
+}
+
+// =============================================================================
+// PURE MEMO
+// =============================================================================
+
+/// A source read by a cached call, with its write version at the time.
+type PureDep = (Rc<dyn AnySource>, u32);
+
+/// A cached result and the sources it read.
+struct PureEntry<T> {
+    value: T,
+    deps: Vec<PureDep>,
+}
+
+/// Results cached for one flush.
+struct PureCache<A, T> {
+    /// The flush these entries belong to
+    flush: u64,
+    entries: HashMap<A, PureEntry<T>>,
+}
+
+/// A pure function whose results are shared by every call in a flush.
+///
+/// Cloning shares the cache.
+pub struct PureMemo<A, T> {
+    f: Rc<dyn Fn(A) -> T>,
+    cache: Rc<RefCell<PureCache<A, T>>>,
+}
+
+impl<A, T> Clone for PureMemo<A, T> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<A, T> PureMemo<A, T>
+where
+    A: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    /// Call the function, serving equal arguments from the flush's cache.
+    ///
+    /// Outside a flush this simply calls the function. Inside one, the
+    /// first call for `args` computes and records the sources it reads;
+    /// later calls with equal `args` return the cached value as long as
+    /// none of those sources changed. Either way the caller tracks those
+    /// sources, exactly as if it had run the function itself.
+    pub fn call(&self, args: A) -> T {
+        let Some(flush) = current_flush() else {
+            return (self.f)(args);
+        };
+
+        let cached = {
+            let mut cache = self.cache.borrow_mut();
+            if cache.flush != flush {
+                cache.flush = flush;
+                cache.entries.clear();
+            }
+            cache
+                .entries
+                .get(&args)
+                .map(|entry| (entry.value.clone(), entry.deps.clone()))
+        };
+
+        if let Some((value, deps)) = cached {
+            if deps_unchanged(&deps) {
+                track_deps(&deps);
+                return value;
+            }
+        }
+
+        let (value, deps) = self.compute(args.clone());
+        track_deps(&deps);
+        self.cache.borrow_mut().entries.insert(
+            args,
+            PureEntry {
+                value: value.clone(),
+                deps,
+            },
+        );
+        value
+    }
+
+    /// Number of results cached for the current flush.
+    pub fn cached_len(&self) -> usize {
+        let cache = self.cache.borrow();
+        if current_flush() == Some(cache.flush) {
+            cache.entries.len()
+        } else {
+            0
+        }
+    }
+
+    /// Run the function in a throwaway derived to learn what it reads.
+    fn compute(&self, args: A) -> (T, Vec<PureDep>) {
+        let f = self.f.clone();
+        let probe = derived_with_equals(move || f(args.clone()), |_, _| false);
+        update_derived_chain(probe.as_any_source());
+
+        let mut deps = Vec::new();
+        probe.as_any_reaction().for_each_dep(&mut |dep| {
+            deps.push((dep.clone(), dep.write_version()));
+            true
+        });
+
+        (probe.inner().get_value(), deps)
+    }
+}
+
+/// True if no source read by a cached call has been written since.
+fn deps_unchanged(deps: &[PureDep]) -> bool {
+    deps.iter().all(|(dep, version)| {
+        // A dirty derived hasn't bumped its version yet
+        if dep.flags() & DERIVED != 0 {
+            update_derived_chain(dep.clone());
+        }
+        dep.write_version() == *version
+    })
+}
+
+/// Register the sources read by a cached call with the active reaction.
+fn track_deps(deps: &[PureDep]) {
+    // The probe may have stamped these sources with the current read
+    // version; start a fresh one so the caller doesn't skip them
+    with_context(|ctx| ctx.increment_read_version());
+    for (dep, _) in deps {
+        track_read(dep.clone());
+    }
+}
+
+/// Wrap a pure function so calls with equal arguments within a flush share
+/// one computation.
+///
+/// Meant for helpers that many effects call with the same arguments, such
+/// as formatting that reads a locale signal. The function may read signals;
+/// callers are subscribed to them either way.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, pure_memo, signal};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let locale = signal("en");
+/// let runs = Rc::new(Cell::new(0));
+///
+/// let format_price = pure_memo({
+///     let locale = locale.clone();
+///     let runs = runs.clone();
+///     move |cents: u64| {
+///         runs.set(runs.get() + 1);
+///         match locale.get() {
+///             "de" => format!("{},{:02} €", cents / 100, cents % 100),
+///             _ => format!("${}.{:02}", cents / 100, cents % 100),
+///         }
+///     }
+/// });
+///
+/// let labels: Vec<_> = (0..3)
+///     .map(|_| {
+///         let format_price = format_price.clone();
+///         effect_sync(move || {
+///             format_price.call(1999);
+///         })
+///     })
+///     .collect();
+///
+/// runs.set(0);
+/// locale.set("de");
+///
+/// // Three effects re-ran, the formatter ran once
+/// assert_eq!(runs.get(), 1);
+/// # drop(labels);
+/// ```
+pub fn pure_memo<A, T, F>(f: F) -> PureMemo<A, T>
+where
+    A: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+    F: Fn(A) -> T + 'static,
+{
+    PureMemo {
+        f: Rc::new(f),
+        cache: Rc::new(RefCell::new(PureCache {
+            flush: 0,
+            entries: HashMap::new(),
+        })),
+    }
+}
+
+// =============================================================================
+// KEYED MEMO
+// =============================================================================
+
+/// State shared by the clones of a `KeyedMemo`.
+struct KeyedState<K, V> {
+    /// Keys currently in the source set
+    keys: Signal<HashSet<K>>,
+    /// Deriveds created so far, for live keys only
+    entries: RefCell<HashMap<K, Derived<V>>>,
+    compute: Rc<dyn Fn(&K) -> V>,
+}
+
+/// One derived per key of a reactive key set. Created with [`memo_keyed()`].
+///
+/// Cloning shares the entries. The key set stops being followed when the
+/// last clone is dropped.
+pub struct KeyedMemo<K, V> {
+    state: Rc<KeyedState<K, V>>,
+    _dispose: Rc<dyn Fn()>,
+}
+
+impl<K, V> Drop for KeyedMemo<K, V> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self._dispose) == 1 {
+            (self._dispose)();
+        }
+    }
+}
+
+impl<K, V> Clone for KeyedMemo<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<K, V> KeyedMemo<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + PartialEq + 'static,
+{
+    /// The value for `key`, or None if the key isn't in the set.
+    ///
+    /// Tracks the key's presence and, if present, its derived.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.derived(key).map(|derived| derived.get())
+    }
+
+    /// The derived for `key`, created on first use. None if the key isn't
+    /// in the set (tracked).
+    pub fn derived(&self, key: &K) -> Option<Derived<V>> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let mut entries = self.state.entries.borrow_mut();
+        let derived = entries.entry(key.clone()).or_insert_with(|| {
+            let (compute, key) = (self.state.compute.clone(), key.clone());
+            derived(move || compute(&key))
+        });
+        Some(derived.clone())
+    }
+
+    /// True if `key` is in the set (tracked).
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.state.keys.with(|keys| keys.contains(key))
+    }
+
+    /// Number of keys in the set (tracked).
+    pub fn len(&self) -> usize {
+        self.state.keys.with(HashSet::len)
+    }
+
+    /// True if the key set is empty (tracked).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of deriveds created so far. Doesn't track.
+    pub fn cached_len(&self) -> usize {
+        self.state.entries.borrow().len()
+    }
+}
+
+/// Keep one derived per key: `compute(&key)` for every key `keys()`
+/// returns.
+///
+/// `keys` is re-run whenever what it reads changes. A key's derived is
+/// created the first time it is read, and dropped as soon as the key is no
+/// longer returned.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{batch, memo_keyed, ReactiveMap};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let prices = Rc::new(RefCell::new(ReactiveMap::new()));
+/// prices.borrow_mut().insert("apple", 120);
+/// prices.borrow_mut().insert("pear", 95);
+///
+/// let labels = memo_keyed(
+///     {
+///         let prices = prices.clone();
+///         move || prices.borrow().keys().copied().collect::<Vec<_>>()
+///     },
+///     {
+///         let prices = prices.clone();
+///         move |fruit: &&str| {
+///             let cents = prices.borrow().get(fruit).copied().unwrap_or(0);
+///             format!("{fruit}: ${}.{:02}", cents / 100, cents % 100)
+///         }
+///     },
+/// );
+///
+/// assert_eq!(labels.get(&"apple").as_deref(), Some("apple: $1.20"));
+/// assert_eq!(labels.cached_len(), 1); // only what was read
+///
+/// // Batched, so the key set is re-read after the borrow ends
+/// batch(|| {
+///     prices.borrow_mut().remove(&"apple");
+/// });
+/// assert_eq!(labels.get(&"apple"), None);
+/// assert_eq!(labels.cached_len(), 0);
+/// ```
+pub fn memo_keyed<K, V, I, KF, F>(keys: KF, compute: F) -> KeyedMemo<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + PartialEq + 'static,
+    I: IntoIterator<Item = K>,
+    KF: Fn() -> I + 'static,
+    F: Fn(&K) -> V + 'static,
+{
+    let state = Rc::new(KeyedState {
+        keys: signal(HashSet::new()),
+        entries: RefCell::new(HashMap::new()),
+        compute: Rc::new(compute),
+    });
+
+    let dispose = effect_sync({
+        let state = state.clone();
+        move || {
+            let live: HashSet<K> = keys().into_iter().collect();
+            untrack(|| {
+                state
+                    .entries
+                    .borrow_mut()
+                    .retain(|key, _| live.contains(key));
+                state.keys.set(live);
+            });
+        }
+    });
+
+    let dispose = std::cell::Cell::new(Some(dispose));
+    KeyedMemo {
+        state,
+        _dispose: Rc::new(move || {
+            if let Some(dispose) = dispose.take() {
+                dispose();
+            }
+        }),
+    }
+}
+
+/// Number of live shared memos across all keys (for tests and diagnostics).
+#[cfg(test)]
+fn registered_memo_count() -> usize {
+    with_context(|ctx| ctx.memos.borrow().values().map(|v| v.len()).sum())
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn same_key_same_deps_shares_computation() {
+        let count = signal(2);
+        let runs = Rc::new(Cell::new(0));
+
+        let make = || {
+            let count = count.clone();
+            let runs = runs.clone();
+            memo_structural("double", move || {
+                runs.set(runs.get() + 1);
+                count.get() * 2
+            })
+        };
+
+        let a = make();
+        let b = make();
+        assert!(a.ptr_eq(&b));
+        assert_eq!(a.reader_count(), 2);
+
+        // Two probes ran; the shared derived is already clean
+        let probe_runs = runs.get();
+        assert_eq!(a.get(), 4);
+        assert_eq!(b.get(), 4);
+        assert_eq!(runs.get(), probe_runs);
+
+        count.set(5);
+        assert_eq!(a.get(), 10);
+        assert_eq!(b.get(), 10);
+        assert_eq!(runs.get(), probe_runs + 1);
+    }
+
+    #[test]
+    fn different_deps_are_not_shared() {
+        let x = signal(1);
+        let y = signal(1);
+
+        let a = memo_structural("read", {
+            let x = x.clone();
+            move || x.get()
+        });
+        let b = memo_structural("read", {
+            let y = y.clone();
+            move || y.get()
+        });
+
+        assert!(!a.ptr_eq(&b));
+    }
+
+    #[test]
+    fn different_keys_or_types_are_not_shared() {
+        let x = signal(1);
+
+        let a = memo_structural("k1", {
+            let x = x.clone();
+            move || x.get()
+        });
+        let b = memo_structural("k2", {
+            let x = x.clone();
+            move || x.get()
+        });
+        let c = memo_structural("k1", {
+            let x = x.clone();
+            move || x.get() as i64
+        });
+
+        assert!(!a.ptr_eq(&b));
+        assert_eq!(c.get(), 1i64);
+    }
+
+    #[test]
+    fn last_reader_tears_down_shared_derived() {
+        let x = signal(1);
+        let before = registered_memo_count();
+
+        let a = memo_structural("teardown", {
+            let x = x.clone();
+            move || x.get() + 1
+        });
+        let b = a.clone();
+        assert_eq!(registered_memo_count(), before + 1);
+
+        drop(a);
+        assert_eq!(b.reader_count(), 1);
+        assert_eq!(registered_memo_count(), before + 1);
+
+        let weak = Rc::downgrade(b.derived().inner());
+        drop(b);
+        assert_eq!(registered_memo_count(), before);
+        assert!(weak.upgrade().is_none());
+
+        // A new memo with the same key starts fresh
+        let c = memo_structural("teardown", {
+            let x = x.clone();
+            move || x.get() + 1
+        });
+        assert_eq!(c.reader_count(), 1);
+    }
+
+    #[test]
+    fn pure_memo_shares_calls_within_a_flush() {
+        let locale = signal(1u32);
+        let runs = Rc::new(Cell::new(0));
+
+        let scaled = pure_memo({
+            let locale = locale.clone();
+            let runs = runs.clone();
+            move |n: u32| {
+                runs.set(runs.get() + 1);
+                n * locale.get()
+            }
+        });
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let effects: Vec<_> = [5, 5, 7]
+            .into_iter()
+            .map(|n| {
+                let scaled = scaled.clone();
+                let seen = seen.clone();
+                effect_sync(move || seen.borrow_mut().push(scaled.call(n)))
+            })
+            .collect();
+
+        // Created outside a flush: each effect computed its own call
+        assert_eq!(runs.get(), 3);
+        assert_eq!(scaled.cached_len(), 0);
+
+        runs.set(0);
+        seen.borrow_mut().clear();
+        locale.set(10);
+
+        // One computation per distinct argument, and cache hits kept
+        // every effect subscribed to the locale
+        assert_eq!(runs.get(), 2);
+        assert_eq!(*seen.borrow(), vec![50, 50, 70]);
+
+        locale.set(2);
+        assert_eq!(*seen.borrow(), vec![50, 50, 70, 10, 10, 14]);
+        drop(effects);
+    }
+
+    #[test]
+    fn pure_memo_recomputes_when_a_read_changes_mid_flush() {
+        let factor = signal(1);
+        let trigger = signal(0);
+        let results = Rc::new(RefCell::new(Vec::new()));
+
+        let times = pure_memo({
+            let factor = factor.clone();
+            move |n: i32| n * factor.get()
+        });
+
+        let _first = effect_sync({
+            let (times, trigger, factor) = (times.clone(), trigger.clone(), factor.clone());
+            let results = results.clone();
+            move || {
+                if trigger.get() > 0 {
+                    results.borrow_mut().push(times.call(3));
+                    // Written inside the same flush
+                    factor.set(100);
+                }
+            }
+        });
+        let _second = effect_sync({
+            let (times, trigger) = (times.clone(), trigger.clone());
+            let results = results.clone();
+            move || {
+                if trigger.get() > 0 {
+                    results.borrow_mut().push(times.call(3));
+                }
+            }
+        });
+
+        trigger.set(1);
+        assert_eq!(results.borrow()[..2], [3, 300]);
+    }
+
+    #[test]
+    fn shared_memo_drives_effects() {
+        let x = signal(1);
+        let seen = Rc::new(Cell::new(0));
+
+        let m = memo_structural("effect", {
+            let x = x.clone();
+            move || x.get() * 10
+        });
+
+        let _dispose = effect_sync({
+            let m = m.clone();
+            let seen = seen.clone();
+            move || seen.set(m.get())
+        });
+        assert_eq!(seen.get(), 10);
+
+        x.set(3);
+        assert_eq!(seen.get(), 30);
+    }
+
+    #[test]
+    fn keyed_memo_follows_the_key_set() {
+        let keys = signal(vec![1, 2, 3]);
+        let factor = signal(10);
+        let runs = Rc::new(Cell::new(0));
+        let scaled = memo_keyed(
+            {
+                let keys = keys.clone();
+                move || keys.get()
+            },
+            {
+                let (factor, runs) = (factor.clone(), runs.clone());
+                move |n: &i32| {
+                    runs.set(runs.get() + 1);
+                    n * factor.get()
+                }
+            },
+        );
+        assert_eq!((scaled.len(), scaled.cached_len()), (3, 0));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (scaled, seen) = (scaled.clone(), seen.clone());
+            move || seen.borrow_mut().push(scaled.get(&4))
+        });
+        assert_eq!(scaled.get(&2), Some(20));
+        assert_eq!(scaled.get(&2), Some(20));
+        assert_eq!((runs.get(), scaled.cached_len()), (1, 1));
+
+        // A key that appears notifies readers waiting for it
+        keys.set(vec![2, 4]);
+        assert_eq!(*seen.borrow(), [None, Some(40)]);
+
+        factor.set(2);
+        assert_eq!(*seen.borrow(), [None, Some(40), Some(8)]);
+
+        keys.set(vec![4]);
+        assert_eq!(scaled.get(&2), None);
+        assert_eq!(scaled.cached_len(), 1);
+    }
+}
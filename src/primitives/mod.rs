@@ -3,37 +3,93 @@
 // Core reactive primitives: signal, derived, effect, bind, linked, scope
 // ============================================================================
 
+pub mod any_signal;
+pub mod arena;
 pub mod bind;
+pub mod cell;
+pub mod combinators;
 pub mod derived;
+pub mod dirty;
+pub mod each;
 pub mod effect;
+pub mod fallible;
+pub mod group;
+pub mod incremental;
 pub mod linked;
+pub mod memo;
+pub mod persisted;
+pub mod pipe;
 pub mod props;
 pub mod repeater;
+pub mod resource;
 pub mod scope;
 pub mod selector;
 pub mod signal;
 pub mod slot;
+pub mod snapshot;
+pub mod store;
+pub mod subscribe;
+pub mod time;
+pub mod watch;
+pub mod writable;
 
 // Re-export for convenience
+pub use any_signal::AnySignal;
+pub use arena::{ArenaSignal, SignalArena};
 pub use bind::{
     bind, bind_chain, bind_getter, bind_readonly, bind_readonly_from, bind_readonly_static,
     bind_static, bind_value, binding_has_internal_source, disconnect_binding, disconnect_source,
     is_binding, unwrap_binding, unwrap_readonly, Binding, IsBinding, ReadonlyBinding,
 };
-pub use derived::{derived, derived_with_equals, Derived, DerivedInner};
+pub use cell::{signal_cell, BitEq, SignalCell};
+pub use combinators::OptionSignal;
+pub use derived::{
+    derived, derived_with_equals, derived_with_history, Derived, DerivedInner, DerivedStats,
+};
+pub use dirty::DirtyTracker;
+pub use each::{keyed_each, KeyedEach};
 pub use effect::{
-    destroy_effect, update_effect, CleanupFn, DisposeFn, Effect, EffectFn, EffectInner,
+    destroy_effect, effect_async, effect_in_lane, effect_lazy, effect_once, effect_times,
+    effect_with_deps, on_cleanup, on_effect_error, set_effect_name, set_max_children_per_effect, set_max_effect_depth,
+    update_effect, CleanupFn, DisposeFn, Effect, EffectError, EffectErrorHook, EffectFn,
+    EffectInner, LazyEffect, LocalBoxFuture, PanicPolicy,
 };
+#[cfg(feature = "debug-deps")]
+pub use effect::{source_id, DepChange};
+pub use fallible::derived_result;
+pub use group::{GroupError, GroupField, GroupValidator, GroupView, SignalGroup};
+pub use incremental::{advance_incremental, derived_incremental, Incremental, Step};
 pub use linked::{
     is_linked_signal, linked_signal, linked_signal_full, linked_signal_with_options,
-    IsLinkedSignal, LinkedSignal, LinkedSignalOptionsSimple, PreviousValue,
+    IsLinkedSignal, LinkedSignal, LinkedSignalOptionsSimple, PreviousValue, ResetPolicy,
+};
+pub use memo::{
+    memo_keyed, memo_structural, pure_memo, KeyedMemo, PureMemo, StructuralMemo,
+};
+pub use persisted::{
+    signal_persisted, signal_persisted_with, FileStorage, MemoryStorage, PersistedSignal,
+    StorageBackend,
 };
+pub use pipe::{pipe, Pipe};
+pub use resource::{resource, Resource};
 pub use scope::{
-    effect_scope, get_current_scope, on_scope_dispose, register_effect_with_scope, EffectScope,
-    ScopeCleanupFn,
+    effect_scope, effect_scope_detached, get_current_scope, on_scope_dispose,
+    register_effect_with_scope, EffectScope, ScopeCleanupFn,
+};
+pub use signal::{
+    signal, signal_shared, signal_with_equals, signal_with_history, source, split_signal,
+    ReadSignal, Signal, SourceOptions, WeakSignal, WriteSignal,
 };
-pub use signal::{signal, signal_with_equals, source, Signal, SourceOptions};
 pub use slot::{
-    is_slot, slot, slot_array, slot_with_value, tracked_slot, IsSlot, Slot, SlotArray,
-    SlotWriteError, TrackedSlot,
+    dirty_set_dense, is_slot, slot, slot_array, slot_with_value, tracked_slot, IsSlot, Slot,
+    SlotArray, SlotWriteError, TrackedSlot,
+};
+pub use snapshot::{capture, restore, Snapshot};
+pub use store::{store, Store, StoreField};
+pub use subscribe::Subscription;
+pub use time::{
+    debounced, run_timers, set_clock, signal_interval, signal_timeout, throttled,
+    until_next_timer, Clock, ManualClock, SystemClock,
 };
+pub use watch::{watch, WatchOptions, WatchSource};
+pub use writable::{derived_writable, WritableDerived};
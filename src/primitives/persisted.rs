@@ -0,0 +1,408 @@
+// ============================================================================
+// spark-signals - Persisted Signal
+// A signal that loads from and saves to a key-value store
+// ============================================================================
+//
+// `signal_persisted(key, default, backend)` starts from the stored value (or
+// `default` if there is none / it doesn't parse) and writes the value back
+// whenever it changes:
+//
+//     let theme = signal_persisted("theme", Theme::Light, FileStorage::new(dir));
+//
+// Saves are debounced by flush: every change in a flush (or a batch) ends
+// in one backend write when the flush ends. `debounce_frames(n)` waits for
+// `n` more quiet flushes on top, for values that change every frame. A
+// pending save is written out when the last handle is dropped.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::primitives::signal::{signal, Signal};
+use crate::primitives::subscribe::Subscription;
+use crate::reactivity::batching::untrack;
+use crate::reactivity::scheduling::on_flush_end;
+
+// =============================================================================
+// STORAGE BACKEND
+// =============================================================================
+
+/// A string key-value store for [`signal_persisted()`].
+///
+/// Backends report no errors: a failed read is a missing value (the default
+/// is used), and a failed write is up to the backend to log or retry.
+pub trait StorageBackend {
+    /// The stored value for `key`, if any.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Store `value` under `key`.
+    fn set(&self, key: &str, value: &str);
+}
+
+/// An in-memory [`StorageBackend`]. Clones share the same storage.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStorage {
+    entries: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl MemoryStorage {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.entries.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.entries
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+    }
+}
+
+/// A [`StorageBackend`] keeping one file per key in a directory.
+///
+/// Keys are used as file names as-is; stick to names that are valid paths.
+#[derive(Clone, Debug)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Store files in `dir` (created on the first write).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.dir.join(key)).ok()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        // Best effort (see the trait docs)
+        let _ = std::fs::create_dir_all(&self.dir)
+            .and_then(|()| std::fs::write(self.dir.join(key), value));
+    }
+}
+
+// =============================================================================
+// PERSISTED SIGNAL
+// =============================================================================
+
+/// Save bookkeeping, shared by the handles and the flush hook.
+struct PersistState<T> {
+    key: String,
+    backend: Box<dyn StorageBackend>,
+    encode: Box<dyn Fn(&T) -> String>,
+    /// A change not yet written
+    pending: Cell<bool>,
+    /// Flushes since the last change
+    quiet: Cell<u32>,
+    /// Quiet flushes to wait for before writing
+    frames: Cell<u32>,
+}
+
+impl<T> PersistState<T> {
+    fn save(&self, value: &Signal<T>) {
+        if self.pending.replace(false) {
+            let text = value.inner().with(|v| (self.encode)(v));
+            self.backend.set(&self.key, &text);
+        }
+    }
+}
+
+/// A signal backed by a [`StorageBackend`]. Created with [`signal_persisted()`].
+///
+/// The value is saved when the last clone is dropped if a save is pending.
+pub struct PersistedSignal<T> {
+    value: Signal<T>,
+    state: Rc<PersistState<T>>,
+    _dispose: Rc<dyn Fn()>,
+}
+
+impl<T> Drop for PersistedSignal<T> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self._dispose) == 1 {
+            (self._dispose)();
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> PersistedSignal<T> {
+    /// Get the current value (tracked).
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Access the current value with a closure (tracked).
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.value.with(f)
+    }
+
+    /// Set the value; saved when the flush ends.
+    pub fn set(&self, value: T) -> bool {
+        self.value.set(value)
+    }
+
+    /// Update the value in place; saved when the flush ends.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        self.value.update(f);
+    }
+
+    /// Peek at the value without creating a dependency.
+    pub fn peek(&self) -> T {
+        untrack(|| self.value.get())
+    }
+
+    /// The underlying signal. Writes through it are saved too.
+    pub fn signal(&self) -> &Signal<T> {
+        &self.value
+    }
+
+    /// The storage key.
+    pub fn key(&self) -> &str {
+        &self.state.key
+    }
+
+    /// Wait for `frames` quiet flushes after a change before saving.
+    pub fn debounce_frames(self, frames: u32) -> Self {
+        self.state.frames.set(frames);
+        self
+    }
+
+    /// Write a pending change to the backend now.
+    pub fn save(&self) {
+        self.state.save(&self.value);
+    }
+
+    /// True if a change hasn't been written yet.
+    pub fn has_pending_save(&self) -> bool {
+        self.state.pending.get()
+    }
+}
+
+impl<T: Clone> Clone for PersistedSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            state: self.state.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug + Clone + PartialEq + 'static> std::fmt::Debug for PersistedSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistedSignal")
+            .field("key", &self.state.key)
+            .field("value", &self.peek())
+            .finish()
+    }
+}
+
+/// Create a signal that loads from `backend` and saves back to it.
+///
+/// Values are stored as text with `Display` / `FromStr`; use
+/// [`signal_persisted_with()`] for another encoding (e.g. JSON). A stored
+/// value that doesn't parse is ignored in favor of `default`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{batch, signal_persisted, MemoryStorage, StorageBackend};
+///
+/// let storage = MemoryStorage::new();
+/// storage.set("volume", "30");
+///
+/// let volume = signal_persisted("volume", 50, storage.clone());
+/// assert_eq!(volume.get(), 30);
+///
+/// // One write for the whole batch
+/// batch(|| {
+///     volume.set(40);
+///     volume.set(45);
+/// });
+/// assert_eq!(storage.get("volume").as_deref(), Some("45"));
+/// ```
+pub fn signal_persisted<T, B>(key: impl Into<String>, default: T, backend: B) -> PersistedSignal<T>
+where
+    T: Clone + PartialEq + Display + FromStr + 'static,
+    B: StorageBackend + 'static,
+{
+    signal_persisted_with(key, default, backend, T::to_string, |text| {
+        text.parse().ok()
+    })
+}
+
+/// Like [`signal_persisted()`], with custom `encode` / `decode` functions.
+///
+/// `decode` returning `None` falls back to `default`.
+pub fn signal_persisted_with<T, B, E, D>(
+    key: impl Into<String>,
+    default: T,
+    backend: B,
+    encode: E,
+    decode: D,
+) -> PersistedSignal<T>
+where
+    T: Clone + PartialEq + 'static,
+    B: StorageBackend + 'static,
+    E: Fn(&T) -> String + 'static,
+    D: Fn(&str) -> Option<T>,
+{
+    let key = key.into();
+    let initial = backend
+        .get(&key)
+        .and_then(|text| decode(&text))
+        .unwrap_or(default);
+    let value = signal(initial);
+
+    let state = Rc::new(PersistState {
+        key,
+        backend: Box::new(backend),
+        encode: Box::new(encode),
+        pending: Cell::new(false),
+        quiet: Cell::new(0),
+        frames: Cell::new(0),
+    });
+
+    // Changes mark a save as pending; the flush hook writes it out
+    let subscription: Subscription = value.subscribe({
+        let state = state.clone();
+        move |_, _| {
+            state.pending.set(true);
+            state.quiet.set(0);
+        }
+    });
+    let remove_hook = on_flush_end({
+        let state = Rc::downgrade(&state);
+        let value = Rc::downgrade(value.inner());
+        move || {
+            let (Some(state), Some(value)) = (state.upgrade(), value.upgrade()) else {
+                return;
+            };
+            if !state.pending.get() {
+                return;
+            }
+            if state.quiet.get() >= state.frames.get() {
+                state.save(&Signal::from_inner(value));
+            } else {
+                state.quiet.set(state.quiet.get() + 1);
+            }
+        }
+    });
+
+    let dispose = RefCell::new(Some((subscription, remove_hook)));
+    PersistedSignal {
+        _dispose: Rc::new({
+            let (state, value) = (state.clone(), value.clone());
+            move || {
+                if let Some((subscription, remove_hook)) = dispose.borrow_mut().take() {
+                    drop(subscription);
+                    remove_hook();
+                    state.save(&value);
+                }
+            }
+        }),
+        value,
+        state,
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactivity::scheduling::flush_sync;
+
+    /// Counts backend writes.
+    #[derive(Clone, Default)]
+    struct CountingStorage {
+        inner: MemoryStorage,
+        writes: Rc<Cell<u32>>,
+    }
+
+    impl StorageBackend for CountingStorage {
+        fn get(&self, key: &str) -> Option<String> {
+            self.inner.get(key)
+        }
+
+        fn set(&self, key: &str, value: &str) {
+            self.writes.set(self.writes.get() + 1);
+            self.inner.set(key, value);
+        }
+    }
+
+    #[test]
+    fn loads_saves_and_falls_back_to_default() {
+        let storage = CountingStorage::default();
+        storage.inner.set("count", "not a number");
+
+        let count = signal_persisted("count", 7, storage.clone());
+        assert_eq!(count.get(), 7);
+        assert_eq!(storage.writes.get(), 0);
+
+        count.update(|n| *n += 1);
+        assert_eq!(storage.get("count").as_deref(), Some("8"));
+        assert_eq!(storage.writes.get(), 1);
+
+        // Unchanged value: nothing to save
+        count.set(8);
+        assert_eq!(storage.writes.get(), 1);
+
+        let reloaded = signal_persisted("count", 0, storage.clone());
+        assert_eq!(reloaded.get(), 8);
+    }
+
+    #[test]
+    fn debounced_saves_wait_for_quiet_flushes() {
+        let storage = CountingStorage::default();
+        let position = signal_persisted("x", 0, storage.clone()).debounce_frames(2);
+
+        for x in 1..=5 {
+            position.set(x);
+        }
+        assert_eq!(storage.writes.get(), 0);
+        assert!(position.has_pending_save());
+
+        flush_sync();
+        assert_eq!(storage.writes.get(), 0);
+        flush_sync();
+        assert_eq!(storage.get("x").as_deref(), Some("5"));
+        assert_eq!(storage.writes.get(), 1);
+    }
+
+    #[test]
+    fn dropping_the_last_handle_saves_pending_changes() {
+        let storage = CountingStorage::default();
+        let name = signal_persisted_with(
+            "name",
+            String::from("ada"),
+            storage.clone(),
+            |s| format!("\"{s}\""),
+            |text| text.strip_prefix('"')?.strip_suffix('"').map(String::from),
+        )
+        .debounce_frames(10);
+
+        let other = name.clone();
+        name.set("grace".into());
+        drop(name);
+        assert_eq!(storage.writes.get(), 0);
+
+        drop(other);
+        assert_eq!(storage.get("name").as_deref(), Some("\"grace\""));
+    }
+}
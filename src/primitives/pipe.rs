@@ -0,0 +1,228 @@
+// ============================================================================
+// spark-signals - Pipelines
+// map / filter / fold chains compiled into a single derived
+// ============================================================================
+//
+// Chaining `.map()` on deriveds creates one graph node per stage: N caches,
+// N equality checks and N hops for every change. A pipeline composes the
+// stages into one closure instead:
+//
+//     let total = pipe(prices.clone())
+//         .map(|p| p * 2)
+//         .filter(|p| *p > 0)
+//         .fold(0, |sum, p| sum + p)
+//         .build();
+//
+// Stages pass `Option<T>` along - `None` means a filter dropped the value and
+// later stages skip it. The built derived holds the last value that made it
+// through the whole chain.
+// ============================================================================
+
+use std::cell::RefCell;
+
+use crate::primitives::derived::{derived, Derived};
+use crate::primitives::props::UnwrapProp;
+
+/// A composed stage: `None` = filtered out this time.
+type Stage<T> = Box<dyn Fn() -> Option<T>>;
+
+// =============================================================================
+// PIPE
+// =============================================================================
+
+/// A pipeline under construction. Finish it with `build()` or `build_or()`.
+pub struct Pipe<T> {
+    stage: Stage<T>,
+}
+
+/// Start a pipeline from a signal, derived or prop.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{pipe, signal};
+///
+/// let reading = signal(3);
+/// let even_total = pipe(reading.clone())
+///     .filter(|n| n % 2 == 0)
+///     .fold(0, |sum, n| sum + n)
+///     .build_or(0);
+///
+/// assert_eq!(even_total.get(), 0);
+/// reading.set(4);
+/// assert_eq!(even_total.get(), 4);
+/// reading.set(5);
+/// assert_eq!(even_total.get(), 4);
+/// reading.set(6);
+/// assert_eq!(even_total.get(), 10);
+/// ```
+pub fn pipe<T, S>(source: S) -> Pipe<T>
+where
+    T: 'static,
+    S: UnwrapProp<T> + 'static,
+{
+    Pipe {
+        stage: Box::new(move || Some(source.unwrap_value())),
+    }
+}
+
+impl<T: 'static> Pipe<T> {
+    /// Transform each value.
+    pub fn map<U, F>(self, f: F) -> Pipe<U>
+    where
+        U: 'static,
+        F: Fn(T) -> U + 'static,
+    {
+        let prev = self.stage;
+        Pipe {
+            stage: Box::new(move || prev().map(&f)),
+        }
+    }
+
+    /// Drop values `predicate` rejects.
+    pub fn filter<P>(self, predicate: P) -> Pipe<T>
+    where
+        P: Fn(&T) -> bool + 'static,
+    {
+        let prev = self.stage;
+        Pipe {
+            stage: Box::new(move || prev().filter(|v| predicate(v))),
+        }
+    }
+
+    /// Accumulate values into a running state, starting from `init`.
+    ///
+    /// Each recomputation that gets a value through the earlier stages folds
+    /// it in once. Like any derived, the pipeline is lazy: values written
+    /// between two reads with nothing subscribed are not seen.
+    pub fn fold<A, F>(self, init: A, f: F) -> Pipe<A>
+    where
+        A: Clone + 'static,
+        F: Fn(A, T) -> A + 'static,
+    {
+        let prev = self.stage;
+        let acc = RefCell::new(init);
+        Pipe {
+            stage: Box::new(move || {
+                if let Some(value) = prev() {
+                    let next = f(acc.borrow().clone(), value);
+                    *acc.borrow_mut() = next;
+                }
+                Some(acc.borrow().clone())
+            }),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Pipe<T> {
+    /// Compile the pipeline into one derived.
+    ///
+    /// `None` until a value makes it through every stage; afterwards the
+    /// last value that did.
+    pub fn build(self) -> Derived<Option<T>> {
+        let stage = self.stage;
+        let last = RefCell::new(None);
+        derived(move || {
+            if let Some(value) = stage() {
+                *last.borrow_mut() = Some(value);
+            }
+            last.borrow().clone()
+        })
+    }
+
+    /// Compile the pipeline into one derived that starts at `initial`.
+    pub fn build_or(self, initial: T) -> Derived<T> {
+        let stage = self.stage;
+        let last = RefCell::new(initial);
+        derived(move || {
+            if let Some(value) = stage() {
+                *last.borrow_mut() = value;
+            }
+            last.borrow().clone()
+        })
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn stages_compose_into_one_node() {
+        let source = signal(1);
+        let computes = Rc::new(Cell::new(0));
+        let labels = pipe(source.clone())
+            .map({
+                let computes = computes.clone();
+                move |n| {
+                    computes.set(computes.get() + 1);
+                    n * 10
+                }
+            })
+            .filter(|n| *n != 20)
+            .map(|n| format!("#{n}"))
+            .build();
+
+        let _dispose = effect_sync({
+            let labels = labels.clone();
+            move || {
+                labels.get();
+            }
+        });
+
+        // One derived between the source and the effect
+        assert_eq!(source.as_any_source().reaction_count(), 1);
+        assert_eq!(labels.get(), Some("#10".to_string()));
+
+        // Filtered out: output holds
+        source.set(2);
+        assert_eq!(labels.get(), Some("#10".to_string()));
+
+        source.set(3);
+        assert_eq!(labels.get(), Some("#30".to_string()));
+        // Each write ran the chain once
+        assert_eq!(computes.get(), 3);
+    }
+
+    #[test]
+    fn build_is_none_until_a_value_passes() {
+        let source = signal(-1);
+        let positive = pipe(source.clone()).filter(|n| *n > 0).build();
+
+        assert_eq!(positive.get(), None);
+        source.set(7);
+        assert_eq!(positive.get(), Some(7));
+        source.set(-3);
+        assert_eq!(positive.get(), Some(7));
+    }
+
+    #[test]
+    fn fold_sees_each_subscribed_change() {
+        let source = signal(1);
+        let history = pipe(source.clone())
+            .fold(Vec::new(), |mut seen, n| {
+                seen.push(n);
+                seen
+            })
+            .build_or(Vec::new());
+
+        let _dispose = effect_sync({
+            let history = history.clone();
+            move || {
+                history.get();
+            }
+        });
+
+        source.set(2);
+        source.set(3);
+        assert_eq!(history.get(), vec![1, 2, 3]);
+    }
+}
@@ -4,9 +4,11 @@
 // Based on @rlabs-inc/signals primitives/props.ts
 // ============================================================================
 
+use std::rc::Rc;
 
 use crate::primitives::derived::{derived, Derived};
-use crate::primitives::signal::Signal;
+use crate::primitives::signal::{ReadSignal, Signal};
+use crate::reactivity::batching::untrack;
 
 // =============================================================================
 // PROP VALUE - A value that can be static, getter, or signal
@@ -76,6 +78,257 @@ impl<T: Clone + PartialEq + 'static> PropValue<T> {
     }
 }
 
+// =============================================================================
+// INTO PROP VALUE - One setter for values, signals and closures
+// =============================================================================
+
+/// Conversion into a [`PropValue`], for setters that accept a value, a
+/// signal or a closure alike (like the builders generated by
+/// `#[derive(Props)]`).
+///
+/// `M` is a marker that keeps the value and closure impls from
+/// overlapping; callers never name it.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{signal, IntoPropValue, PropValue};
+///
+/// fn width<M>(value: impl IntoPropValue<u32, M>) -> PropValue<u32> {
+///     value.into_prop_value()
+/// }
+///
+/// let base = signal(10);
+/// assert_eq!(width(5).peek(), 5);
+/// assert_eq!(width(&base).peek(), 10);
+/// assert_eq!(width(move || base.get() * 2).peek(), 20);
+/// ```
+pub trait IntoPropValue<T: Clone + PartialEq + 'static, M> {
+    /// Convert into a PropValue.
+    fn into_prop_value(self) -> PropValue<T>;
+}
+
+/// Markers for [`IntoPropValue`] impls.
+pub mod prop_marker {
+    /// A plain value
+    pub struct Value;
+    /// A `Signal`, `ReadSignal` or `Derived`
+    pub struct Reactive;
+    /// A getter closure
+    pub struct Getter;
+    /// Already a `PropValue`
+    pub struct Prop;
+}
+
+impl<T: Clone + PartialEq + 'static> IntoPropValue<T, prop_marker::Value> for T {
+    fn into_prop_value(self) -> PropValue<T> {
+        PropValue::Static(self)
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> IntoPropValue<T, prop_marker::Reactive> for Signal<T> {
+    fn into_prop_value(self) -> PropValue<T> {
+        PropValue::Signal(self)
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> IntoPropValue<T, prop_marker::Reactive> for &Signal<T> {
+    fn into_prop_value(self) -> PropValue<T> {
+        PropValue::Signal(self.clone())
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> IntoPropValue<T, prop_marker::Reactive> for ReadSignal<T> {
+    fn into_prop_value(self) -> PropValue<T> {
+        PropValue::Getter(Box::new(move || self.get()))
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> IntoPropValue<T, prop_marker::Reactive> for Derived<T> {
+    fn into_prop_value(self) -> PropValue<T> {
+        PropValue::Getter(Box::new(move || self.get()))
+    }
+}
+
+impl<T, F> IntoPropValue<T, prop_marker::Getter> for F
+where
+    T: Clone + PartialEq + 'static,
+    F: Fn() -> T + 'static,
+{
+    fn into_prop_value(self) -> PropValue<T> {
+        PropValue::Getter(Box::new(self))
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> IntoPropValue<T, prop_marker::Prop> for PropValue<T> {
+    fn into_prop_value(self) -> PropValue<T> {
+        self
+    }
+}
+
+// =============================================================================
+// MAYBE SIGNAL - Accept any reactive or static value via Into
+// =============================================================================
+
+/// A value that may or may not be reactive: a static value, a signal's read
+/// half, a derived, or a getter closure.
+///
+/// Every form converts with `Into`, so a component-style function can take
+/// `impl Into<MaybeSignal<T>>` and callers pass whatever they have. Closures
+/// go through [`MaybeSignal::getter()`], since a blanket `From<F>` would
+/// overlap with `From<T>`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived, signal, MaybeSignal};
+///
+/// fn label(count: impl Into<MaybeSignal<i32>>) -> String {
+///     format!("count: {}", count.into().get())
+/// }
+///
+/// let count = signal(2);
+/// let doubled = derived({
+///     let count = count.clone();
+///     move || count.get() * 2
+/// });
+/// assert_eq!(label(1), "count: 1");
+/// assert_eq!(label(count.clone()), "count: 2");
+/// assert_eq!(label(doubled), "count: 4");
+/// assert_eq!(label(MaybeSignal::getter(move || count.get() + 1)), "count: 3");
+/// ```
+pub enum MaybeSignal<T> {
+    /// A static (non-reactive) value
+    Static(T),
+
+    /// The read half of a signal
+    Signal(ReadSignal<T>),
+
+    /// A derived
+    Derived(Derived<T>),
+
+    /// A getter closure, tracking whatever it reads
+    Getter(Rc<dyn Fn() -> T>),
+}
+
+impl<T: Clone + PartialEq + 'static> MaybeSignal<T> {
+    /// Create a getter MaybeSignal from a closure.
+    pub fn getter<F: Fn() -> T + 'static>(f: F) -> Self {
+        MaybeSignal::Getter(Rc::new(f))
+    }
+
+    /// Get the current value. In a reactive context, this tracks the
+    /// underlying signal, derived or whatever the getter reads.
+    pub fn get(&self) -> T {
+        match self {
+            MaybeSignal::Static(v) => v.clone(),
+            MaybeSignal::Signal(s) => s.get(),
+            MaybeSignal::Derived(d) => d.get(),
+            MaybeSignal::Getter(f) => f(),
+        }
+    }
+
+    /// Get the current value without creating a reactive dependency.
+    pub fn peek(&self) -> T {
+        untrack(|| self.get())
+    }
+
+    /// True for a static value, which never changes.
+    pub fn is_static(&self) -> bool {
+        matches!(self, MaybeSignal::Static(_))
+    }
+
+    /// Convert to a Derived for uniform reactive access. A derived is
+    /// returned as is.
+    pub fn into_derived(self) -> Derived<T> {
+        match self {
+            MaybeSignal::Derived(d) => d,
+            other => derived(move || other.get()),
+        }
+    }
+}
+
+impl<T: Clone> Clone for MaybeSignal<T> {
+    fn clone(&self) -> Self {
+        match self {
+            MaybeSignal::Static(v) => MaybeSignal::Static(v.clone()),
+            MaybeSignal::Signal(s) => MaybeSignal::Signal(s.clone()),
+            MaybeSignal::Derived(d) => MaybeSignal::Derived(d.clone()),
+            MaybeSignal::Getter(f) => MaybeSignal::Getter(f.clone()),
+        }
+    }
+}
+
+impl<T: Default> Default for MaybeSignal<T> {
+    fn default() -> Self {
+        MaybeSignal::Static(T::default())
+    }
+}
+
+impl<T: std::fmt::Debug + Clone + PartialEq + 'static> std::fmt::Debug for MaybeSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            MaybeSignal::Static(_) => "Static",
+            MaybeSignal::Signal(_) => "Signal",
+            MaybeSignal::Derived(_) => "Derived",
+            MaybeSignal::Getter(_) => "Getter",
+        };
+        f.debug_tuple(kind).field(&self.peek()).finish()
+    }
+}
+
+impl<T> From<T> for MaybeSignal<T> {
+    fn from(value: T) -> Self {
+        MaybeSignal::Static(value)
+    }
+}
+
+impl From<&str> for MaybeSignal<String> {
+    fn from(value: &str) -> Self {
+        MaybeSignal::Static(value.to_string())
+    }
+}
+
+impl<T> From<Signal<T>> for MaybeSignal<T> {
+    fn from(signal: Signal<T>) -> Self {
+        MaybeSignal::Signal(signal.split().0)
+    }
+}
+
+impl<T> From<&Signal<T>> for MaybeSignal<T> {
+    fn from(signal: &Signal<T>) -> Self {
+        MaybeSignal::Signal(Signal::from_inner(signal.inner().clone()).split().0)
+    }
+}
+
+impl<T> From<ReadSignal<T>> for MaybeSignal<T> {
+    fn from(signal: ReadSignal<T>) -> Self {
+        MaybeSignal::Signal(signal)
+    }
+}
+
+impl<T> From<Derived<T>> for MaybeSignal<T> {
+    fn from(derived: Derived<T>) -> Self {
+        MaybeSignal::Derived(derived)
+    }
+}
+
+impl<T: Clone> From<&Derived<T>> for MaybeSignal<T> {
+    fn from(derived: &Derived<T>) -> Self {
+        MaybeSignal::Derived(derived.clone())
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> From<PropValue<T>> for MaybeSignal<T> {
+    fn from(prop: PropValue<T>) -> Self {
+        match prop {
+            PropValue::Static(v) => MaybeSignal::Static(v),
+            PropValue::Getter(f) => MaybeSignal::Getter(Rc::from(f)),
+            PropValue::Signal(s) => s.into(),
+        }
+    }
+}
+
 // =============================================================================
 // REACTIVE PROP - Convert PropValue to Derived
 // =============================================================================
@@ -125,7 +378,7 @@ pub fn reactive_prop<T: Clone + PartialEq + 'static>(prop: PropValue<T>) -> Deri
         }
         PropValue::Getter(f) => {
             // For getters, the derived calls the getter (tracking any signals inside)
-            derived(move || f())
+            derived(f)
         }
         PropValue::Signal(s) => {
             // For signals, the derived reads from the signal (creating dependency)
@@ -139,7 +392,7 @@ pub fn reactive_prop<T: Clone + PartialEq + 'static>(prop: PropValue<T>) -> Deri
 // =============================================================================
 
 /// A trait for types that can be unwrapped to their inner value.
-/// Implemented for PropValue, Signal, Derived, and raw values.
+/// Implemented for PropValue, MaybeSignal, Signal, ReadSignal and Derived.
 pub trait UnwrapProp<T> {
     /// Get the current value, potentially creating reactive dependencies.
     fn unwrap_value(&self) -> T;
@@ -167,6 +420,18 @@ impl<T: Clone + PartialEq + 'static> UnwrapProp<T> for Derived<T> {
     }
 }
 
+impl<T: Clone + PartialEq + 'static> UnwrapProp<T> for ReadSignal<T> {
+    fn unwrap_value(&self) -> T {
+        self.get()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> UnwrapProp<T> for MaybeSignal<T> {
+    fn unwrap_value(&self) -> T {
+        self.get()
+    }
+}
+
 // Note: We don't implement UnwrapProp<T> for T directly because it creates
 // ambiguity with Signal<T> and Derived<T> which are also T. Instead, use
 // PropValue::Static for static values.
@@ -244,6 +509,9 @@ where
 ///
 /// use_button(props);
 /// ```
+///
+/// With the `macros` feature, `#[derive(Props)]` generates these `with_*`
+/// setters (as a separate builder type) for you.
 pub struct PropsBuilder<T> {
     _marker: std::marker::PhantomData<T>,
 }
@@ -426,4 +694,29 @@ mod tests {
         count_signal.set(10);
         assert_eq!(count.get(), 10);
     }
+
+    #[test]
+    fn maybe_signal_from_every_form() {
+        fn doubled(value: impl Into<MaybeSignal<i32>>) -> Derived<i32> {
+            let value = value.into();
+            derived(move || value.get() * 2)
+        }
+
+        let sig = signal(1);
+        let from_static = doubled(5);
+        let from_signal = doubled(&sig);
+        let from_derived = doubled(from_signal.clone());
+        let from_prop = doubled(PropValue::from_signal(&sig));
+        assert!(MaybeSignal::from(5).is_static());
+        assert_eq!(from_static.get(), 10);
+        assert_eq!(from_derived.get(), 4);
+
+        sig.set(3);
+        assert_eq!(from_signal.get(), 6);
+        assert_eq!(from_derived.get(), 12);
+        assert_eq!(from_prop.get(), 6);
+
+        let name: MaybeSignal<String> = "static".into();
+        assert_eq!(format!("{name:?}"), r#"Static("static")"#);
+    }
 }
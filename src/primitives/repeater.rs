@@ -9,6 +9,27 @@
 // Zero scheduling overhead.
 //
 // This is Layer 2 of the Cross-Language Reactive Shared Memory architecture.
+//
+// Ordering contract:
+// - Inline (default): a repeater forwards during the write that changed its
+//   source, before `set()` returns. Repeaters reached by one write forward
+//   in graph order (direct reactions in subscription order, then through
+//   deriveds), and always before any effect scheduled by that write runs.
+//   Inside a batch, every write forwards, so the target sees intermediate
+//   values and deriveds read mid-batch.
+// - Deferred: the write only queues the repeater (once, however many times
+//   its source changes). Queued repeaters forward at the start of the next
+//   flush, in the order they were first queued, before any effect in that
+//   flush runs. Writes outside a batch flush immediately, so `set()` still
+//   returns with the target updated.
+//
+// Either way, an effect never observes a repeater target that lags behind
+// the values the effect itself reads.
+//
+// `repeat()` takes a raw source and a forwarding closure. The typed API on
+// top - `repeater()`, `repeater_map()`, `repeater_when()` - reads a signal or
+// derived and writes a `RepeatTarget`: another signal, a closure, or one
+// position of a `SharedSlotBuffer` / `MutableSharedArray`.
 // ============================================================================
 
 use std::any::Any;
@@ -16,7 +37,28 @@ use std::cell::{Cell, RefCell};
 use std::rc::{Rc, Weak};
 
 use crate::core::constants::*;
+use crate::core::context::{try_with_context, with_context};
 use crate::core::types::{AnyReaction, AnySource};
+use crate::primitives::derived::Derived;
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::untrack;
+use crate::reactivity::tracking::{link_reaction, set_signal_status};
+use crate::shared::shared_slot_buffer::SharedSlotBuffer;
+use crate::shared::MutableSharedArray;
+
+// =============================================================================
+// REPEAT MODE
+// =============================================================================
+
+/// When a repeater forwards relative to the flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    /// Forward inline during the write (lowest latency)
+    #[default]
+    Inline,
+    /// Forward once at the start of the next flush (consistent snapshots)
+    Deferred,
+}
 
 // =============================================================================
 // REPEATER INNER
@@ -33,6 +75,9 @@ pub struct RepeaterInner {
     /// The function to read the current value and write it to the target.
     /// Encapsulates both the read and the write in a single closure.
     forward_fn: Box<dyn Fn()>,
+    mode: RepeatMode,
+    /// Weak reference to self, for queueing deferred forwards
+    self_weak: Weak<RepeaterInner>,
 }
 
 impl RepeaterInner {
@@ -41,14 +86,26 @@ impl RepeaterInner {
     /// `source` — the reactive source to watch (will be stored as a dep)
     /// `forward_fn` — called inline during mark_reactions to read source + write target
     pub fn new(source: Rc<dyn AnySource>, forward_fn: impl Fn() + 'static) -> Rc<Self> {
-        let inner = Rc::new(Self {
+        Self::new_with_mode(source, RepeatMode::Inline, forward_fn)
+    }
+
+    /// Create a new repeater with an explicit forwarding mode.
+    pub fn new_with_mode(
+        source: Rc<dyn AnySource>,
+        mode: RepeatMode,
+        forward_fn: impl Fn() + 'static,
+    ) -> Rc<Self> {
+        let inner = Rc::new_cyclic(|self_weak| Self {
             flags: Cell::new(REPEATER | CLEAN),
             deps: RefCell::new(vec![source.clone()]),
             forward_fn: Box::new(forward_fn),
+            mode,
+            self_weak: self_weak.clone(),
         });
 
         // Register with source's reactions
-        source.add_reaction(Rc::downgrade(&inner) as Weak<dyn AnyReaction>);
+        link_reaction(&source, Rc::downgrade(&inner) as Weak<dyn AnyReaction>);
+        with_context(|ctx| ctx.reaction_created());
 
         inner
     }
@@ -61,6 +118,69 @@ impl RepeaterInner {
         }
         (self.forward_fn)();
     }
+
+    /// The forwarding mode of this repeater.
+    pub fn mode(&self) -> RepeatMode {
+        self.mode
+    }
+
+    /// React to a source change, as seen by mark_reactions.
+    ///
+    /// Only called while the repeater is not DIRTY. Inline repeaters forward
+    /// now. Deferred repeaters are queued for the next flush and stay DIRTY
+    /// until then, so repeated writes queue them once.
+    /// Returns true if the repeater was queued.
+    pub(crate) fn notify(&self) -> bool {
+        match self.mode {
+            RepeatMode::Inline => {
+                self.forward();
+                set_signal_status(self, CLEAN);
+                false
+            }
+            RepeatMode::Deferred => {
+                set_signal_status(self, DIRTY);
+                with_context(|ctx| {
+                    ctx.deferred_repeaters.borrow_mut().push(self.self_weak.clone());
+                });
+                true
+            }
+        }
+    }
+}
+
+// =============================================================================
+// DEFERRED QUEUE
+// =============================================================================
+
+/// Forward every queued deferred repeater, in the order they were queued.
+///
+/// Called at the start of each flush pass, before effects run. Forwarding
+/// may queue more repeaters; those run in the same call. Returns the number
+/// of repeaters forwarded.
+pub fn flush_deferred_repeaters() -> usize {
+    let mut count = 0;
+
+    loop {
+        let queued = with_context(|ctx| ctx.deferred_repeaters.take());
+        if queued.is_empty() {
+            return count;
+        }
+
+        for weak in queued {
+            if let Some(repeater) = weak.upgrade() {
+                // CLEAN first so writes made while forwarding can re-queue it
+                set_signal_status(&*repeater, CLEAN);
+                repeater.forward();
+                count += 1;
+            }
+        }
+    }
+}
+
+impl Drop for RepeaterInner {
+    fn drop(&mut self) {
+        try_with_context(|ctx| ctx.reaction_dropped());
+    }
 }
 
 impl AnyReaction for RepeaterInner {
@@ -145,7 +265,42 @@ pub fn repeat(
     source: Rc<dyn AnySource>,
     forward_fn: impl Fn() + 'static,
 ) -> Box<dyn FnOnce()> {
-    let inner = RepeaterInner::new(source.clone(), forward_fn);
+    repeat_with_mode(source, RepeatMode::Inline, forward_fn)
+}
+
+/// Create a repeater with an explicit forwarding mode.
+///
+/// `RepeatMode::Deferred` forwards once per flush, before effects, reading
+/// the settled value instead of every intermediate write in a batch.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{batch, repeat_with_mode, signal, RepeatMode};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let source = signal(0);
+/// let target = Rc::new(RefCell::new(Vec::new()));
+///
+/// let _dispose = repeat_with_mode(source.as_any_source(), RepeatMode::Deferred, {
+///     let source = source.clone();
+///     let target = target.clone();
+///     move || target.borrow_mut().push(source.get())
+/// });
+///
+/// batch(|| {
+///     source.set(1);
+///     source.set(2);
+/// });
+/// assert_eq!(*target.borrow(), vec![2]); // one forward with the final value
+/// ```
+pub fn repeat_with_mode(
+    source: Rc<dyn AnySource>,
+    mode: RepeatMode,
+    forward_fn: impl Fn() + 'static,
+) -> Box<dyn FnOnce()> {
+    let inner = RepeaterInner::new_with_mode(source.clone(), mode, forward_fn);
 
     // Return dispose function
     let weak = Rc::downgrade(&inner);
@@ -160,6 +315,143 @@ pub fn repeat(
     })
 }
 
+// =============================================================================
+// TYPED REPEATERS
+// =============================================================================
+
+/// A reactive value a [`repeater()`] can read: a `Signal` or `Derived`.
+pub trait RepeatSource<T>: 'static {
+    /// The graph node the repeater subscribes to.
+    fn repeat_source(&self) -> Rc<dyn AnySource>;
+
+    /// The current value.
+    fn read(&self) -> T;
+}
+
+impl<T: Clone + 'static> RepeatSource<T> for Signal<T> {
+    fn repeat_source(&self) -> Rc<dyn AnySource> {
+        self.as_any_source()
+    }
+
+    fn read(&self) -> T {
+        self.get()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> RepeatSource<T> for Derived<T> {
+    fn repeat_source(&self) -> Rc<dyn AnySource> {
+        self.as_any_source()
+    }
+
+    fn read(&self) -> T {
+        self.get()
+    }
+}
+
+/// Where a [`repeater()`] writes.
+///
+/// Implemented for signals, closures, and `(buffer, index)` pairs naming one
+/// position of a `SharedSlotBuffer` or `MutableSharedArray`.
+pub trait RepeatTarget<T>: 'static {
+    /// Write a forwarded value.
+    fn write(&self, value: T);
+}
+
+impl<T: PartialEq + 'static> RepeatTarget<T> for Signal<T> {
+    fn write(&self, value: T) {
+        self.set(value);
+    }
+}
+
+impl<T, F> RepeatTarget<T> for F
+where
+    F: Fn(T) + 'static,
+{
+    fn write(&self, value: T) {
+        self(value)
+    }
+}
+
+impl<T: Copy + PartialEq + 'static> RepeatTarget<T> for (Rc<SharedSlotBuffer<T>>, usize) {
+    fn write(&self, value: T) {
+        self.0.set(self.1, value);
+    }
+}
+
+impl<T: Copy + 'static> RepeatTarget<T> for (Rc<MutableSharedArray<T>>, usize) {
+    fn write(&self, value: T) {
+        self.0.set(self.1, value);
+    }
+}
+
+/// Mirror `source` into `target`, forwarding inline on every change.
+///
+/// The current value is written once on creation. Returns a dispose
+/// function, like [`repeat()`].
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{repeater, repeater_map, repeater_when, signal, MutableSharedArray};
+/// use std::rc::Rc;
+///
+/// let mut memory = vec![0.0f32; 2];
+/// let output = Rc::new(unsafe { MutableSharedArray::new(memory.as_mut_ptr(), 2) });
+///
+/// let width = signal(100.0f32);
+/// let _mirror = repeater(&width, (output.clone(), 0));
+/// let _half = repeater_map(&width, (output.clone(), 1), |w| w / 2.0);
+///
+/// width.set(300.0);
+/// assert_eq!((output.get(0), output.get(1)), (300.0, 150.0));
+///
+/// // Only forward positive widths
+/// let last_valid = signal(0.0f32);
+/// let _valid = repeater_when(&width, last_valid.clone(), |w| *w > 0.0);
+/// width.set(-1.0);
+/// assert_eq!(last_valid.get(), 300.0);
+/// ```
+pub fn repeater<T, S, D>(source: &S, target: D) -> Box<dyn FnOnce()>
+where
+    T: 'static,
+    S: RepeatSource<T> + Clone,
+    D: RepeatTarget<T>,
+{
+    repeater_map(source, target, |value| value)
+}
+
+/// Like [`repeater()`], writing `f(value)` instead of the value.
+pub fn repeater_map<T, U, S, D, F>(source: &S, target: D, f: F) -> Box<dyn FnOnce()>
+where
+    T: 'static,
+    S: RepeatSource<T> + Clone,
+    D: RepeatTarget<U>,
+    F: Fn(T) -> U + 'static,
+{
+    let read = source.clone();
+    // Untracked: a write inside an effect must not subscribe the effect
+    let forward = move || target.write(f(untrack(|| read.read())));
+    forward();
+    repeat(source.repeat_source(), forward)
+}
+
+/// Like [`repeater()`], forwarding only values `predicate` accepts. The
+/// target keeps the last accepted value.
+pub fn repeater_when<T, S, D, P>(source: &S, target: D, predicate: P) -> Box<dyn FnOnce()>
+where
+    T: 'static,
+    S: RepeatSource<T> + Clone,
+    D: RepeatTarget<T>,
+    P: Fn(&T) -> bool + 'static,
+{
+    let forward_accepted = move |value: T| {
+        if predicate(&value) {
+            target.write(value);
+        }
+    };
+    repeater_map(source, forward_accepted, |value| value)
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -251,4 +543,141 @@ mod tests {
 
         assert!(forwarded.get(), "Repeater should have been forwarded inline during mark_reactions");
     }
+
+    // =========================================================================
+    // Ordering contract
+    // =========================================================================
+
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use crate::reactivity::batching::batch;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    /// A repeater and an effect on the same source, both appending to a log.
+    fn logging_repeater(mode: RepeatMode) -> (crate::Signal<i32>, Log, Box<dyn FnOnce()>) {
+        let source = signal(0);
+        let target = Rc::new(StdCell::new(0));
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let dispose_repeater = repeat_with_mode(source.as_any_source(), mode, {
+            let source = source.clone();
+            let target = target.clone();
+            let log = log.clone();
+            move || {
+                target.set(source.get());
+                log.borrow_mut().push(format!("forward {}", source.get()));
+            }
+        });
+
+        let dispose_effect = effect_sync({
+            let source = source.clone();
+            let log = log.clone();
+            move || {
+                let value = source.get();
+                // The target never lags behind what the effect reads
+                assert_eq!(target.get(), value);
+                log.borrow_mut().push(format!("effect {}", value));
+            }
+        });
+        let dispose = Box::new(move || {
+            dispose_effect();
+            dispose_repeater();
+        });
+
+        (source, log, dispose)
+    }
+
+    #[test]
+    fn inline_forwards_before_effects() {
+        let (source, log, _dispose) = logging_repeater(RepeatMode::Inline);
+        source.set(1);
+        assert_eq!(*log.borrow(), vec!["effect 0", "forward 1", "effect 1"]);
+    }
+
+    #[test]
+    fn inline_forwards_every_write_in_batch() {
+        let (source, log, _dispose) = logging_repeater(RepeatMode::Inline);
+        batch(|| {
+            source.set(1);
+            source.set(2);
+        });
+        assert_eq!(*log.borrow(), vec!["effect 0", "forward 1", "forward 2", "effect 2"]);
+    }
+
+    #[test]
+    fn deferred_forwards_once_before_effects() {
+        let (source, log, _dispose) = logging_repeater(RepeatMode::Deferred);
+        batch(|| {
+            source.set(1);
+            source.set(2);
+        });
+        assert_eq!(*log.borrow(), vec!["effect 0", "forward 2", "effect 2"]);
+    }
+
+    #[test]
+    fn deferred_unbatched_write_forwards_before_set_returns() {
+        let source = signal(0);
+        let target = Rc::new(StdCell::new(0));
+        let _dispose = repeat_with_mode(source.as_any_source(), RepeatMode::Deferred, {
+            let source = source.clone();
+            let target = target.clone();
+            move || target.set(source.get())
+        });
+
+        // No effects involved: the write still flushes the queue
+        source.set(7);
+        assert_eq!(target.get(), 7);
+        assert_eq!(flush_deferred_repeaters(), 0);
+    }
+
+    #[test]
+    fn typed_repeaters_forward_through_deriveds() {
+        use crate::primitives::derived::derived;
+
+        let count = signal(1);
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        let label = signal(String::new());
+        let even = Rc::new(RefCell::new(Vec::new()));
+
+        let _label = repeater_map(&doubled, label.clone(), |n| format!("x{n}"));
+        let record = {
+            let even = even.clone();
+            move |n| even.borrow_mut().push(n)
+        };
+        let _even = repeater_when(&count, record, |n| n % 2 == 0);
+        assert_eq!(label.get(), "x2");
+
+        for n in 2..=4 {
+            count.set(n);
+        }
+        assert_eq!(label.get(), "x8");
+        assert_eq!(*even.borrow(), vec![2, 4]);
+    }
+
+    #[test]
+    fn deferred_forwards_in_queue_order() {
+        let a = signal(0);
+        let b = signal(0);
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let _da = repeat_with_mode(a.as_any_source(), RepeatMode::Deferred, {
+            let order = order.clone();
+            move || order.borrow_mut().push("a")
+        });
+        let _db = repeat_with_mode(b.as_any_source(), RepeatMode::Deferred, {
+            let order = order.clone();
+            move || order.borrow_mut().push("b")
+        });
+
+        batch(|| {
+            b.set(1);
+            a.set(1);
+            b.set(2);
+        });
+        assert_eq!(*order.borrow(), vec!["b", "a"]);
+    }
 }
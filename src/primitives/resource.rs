@@ -0,0 +1,307 @@
+// ============================================================================
+// spark-signals - Resource
+// Async data fetching driven by a reactive source
+// ============================================================================
+//
+// A resource pairs a reactive source (e.g. a user id signal) with an async
+// fetcher. Whenever the source changes, the fetcher runs again; the result
+// lands in reactive `value()` / `error()` / `loading()` reads.
+//
+// Built on `effect_async`: a new fetch cancels the one in flight, so a slow
+// response for an old source value can never overwrite a newer one. Like any
+// effect, the fetching effect belongs to the surrounding effect scope.
+// ============================================================================
+
+use std::future::Future;
+use std::rc::Rc;
+
+use crate::primitives::effect::{effect_async, LocalBoxFuture};
+use crate::primitives::props::UnwrapProp;
+use crate::primitives::signal::{signal, signal_with_equals, Signal};
+use crate::reactivity::batching::{batch, untrack};
+
+/// Errors are rarely `PartialEq`; only "no error" equals "no error".
+fn both_none<E>(a: &Option<E>, b: &Option<E>) -> bool {
+    a.is_none() && b.is_none()
+}
+
+// =============================================================================
+// RESOURCE
+// =============================================================================
+
+/// Reactive state of an async fetch. Created with [`resource()`].
+///
+/// The fetching effect is disposed when the last clone is dropped (or when
+/// its effect scope stops).
+pub struct Resource<T, E> {
+    value: Signal<Option<T>>,
+    error: Signal<Option<E>>,
+    loading: Signal<bool>,
+    /// Bumped by `refetch()`; read by the fetching effect
+    refetch: Signal<u64>,
+    _dispose: Rc<dyn Fn()>,
+}
+
+impl<T, E> Drop for Resource<T, E> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self._dispose) == 1 {
+            (self._dispose)();
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static, E: Clone + 'static> Resource<T, E> {
+    /// The latest fetched value (`None` before the first success).
+    ///
+    /// Keeps the previous value while a refetch is loading or after it fails.
+    pub fn value(&self) -> Option<T> {
+        self.value.get()
+    }
+
+    /// The error from the latest fetch, cleared by the next success.
+    pub fn error(&self) -> Option<E> {
+        self.error.get()
+    }
+
+    /// True while a fetch is in flight.
+    pub fn loading(&self) -> bool {
+        self.loading.get()
+    }
+
+    /// Fetch again with the current source value.
+    pub fn refetch(&self) {
+        self.refetch.update(|n| *n += 1);
+    }
+
+    /// Overwrite the value locally (e.g. an optimistic update).
+    ///
+    /// An in-flight fetch still lands when it completes.
+    pub fn mutate(&self, value: Option<T>) {
+        self.value.set(value);
+    }
+}
+
+impl<T: Clone, E: Clone> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            error: self.error.clone(),
+            loading: self.loading.clone(),
+            refetch: self.refetch.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<T, E> std::fmt::Debug for Resource<T, E>
+where
+    T: std::fmt::Debug + Clone + PartialEq + 'static,
+    E: std::fmt::Debug + Clone + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        untrack(|| {
+            f.debug_struct("Resource")
+                .field("value", &self.value())
+                .field("error", &self.error())
+                .field("loading", &self.loading())
+                .finish()
+        })
+    }
+}
+
+/// Fetch data asynchronously whenever `source` changes.
+///
+/// `fetcher` gets the source value and returns a future; `spawn` hands that
+/// future to your executor (see `effect_async`). The first fetch starts
+/// immediately.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{resource, signal, LocalBoxFuture};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use std::task::{Context, Waker};
+///
+/// let tasks: Rc<RefCell<Vec<LocalBoxFuture>>> = Rc::default();
+/// let user_id = signal(1);
+///
+/// let user = resource(
+///     {
+///         let tasks = tasks.clone();
+///         move |task| tasks.borrow_mut().push(task)
+///     },
+///     user_id.clone(),
+///     |id: i32| async move { Ok::<_, String>(format!("user #{id}")) },
+/// );
+/// assert!(user.loading());
+///
+/// // Drive the executor
+/// let mut cx = Context::from_waker(Waker::noop());
+/// for task in tasks.borrow_mut().iter_mut() {
+///     let _ = task.as_mut().poll(&mut cx);
+/// }
+/// assert!(!user.loading());
+/// assert_eq!(user.value(), Some("user #1".to_string()));
+/// ```
+pub fn resource<S, T, E, Src, Sp, F, Fut>(spawn: Sp, source: Src, fetcher: F) -> Resource<T, E>
+where
+    S: 'static,
+    T: Clone + PartialEq + 'static,
+    E: Clone + 'static,
+    Src: UnwrapProp<S> + 'static,
+    Sp: Fn(LocalBoxFuture) + 'static,
+    F: Fn(S) -> Fut + 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    let value = signal(None);
+    let error = signal_with_equals(None, both_none::<E>);
+    let loading = signal(false);
+    let refetch = signal(0u64);
+
+    let dispose = effect_async(spawn, {
+        let (value, error, loading, refetch) =
+            (value.clone(), error.clone(), loading.clone(), refetch.clone());
+        move || {
+            let input = source.unwrap_value();
+            refetch.get();
+            loading.set(true);
+
+            let pending = fetcher(input);
+            let (value, error, loading) = (value.clone(), error.clone(), loading.clone());
+            async move {
+                let result = pending.await;
+                batch(|| {
+                    match result {
+                        Ok(v) => {
+                            value.set(Some(v));
+                            error.set(None);
+                        }
+                        Err(e) => {
+                            error.set(Some(e));
+                        }
+                    }
+                    loading.set(false);
+                });
+            }
+        }
+    });
+
+    let dispose = std::cell::Cell::new(Some(dispose));
+    Resource {
+        value,
+        error,
+        loading,
+        refetch,
+        _dispose: Rc::new(move || {
+            if let Some(dispose) = dispose.take() {
+                dispose();
+            }
+        }),
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::task::{Context, Poll, Waker};
+
+    type Tasks = Rc<RefCell<Vec<LocalBoxFuture>>>;
+    type Replies = Rc<RefCell<Vec<(i32, Result<String, String>)>>>;
+
+    fn spawner(tasks: &Tasks) -> impl Fn(LocalBoxFuture) + 'static {
+        let tasks = tasks.clone();
+        move |task| tasks.borrow_mut().push(task)
+    }
+
+    /// Poll every spawned task once; drop the finished ones.
+    fn run(tasks: &Tasks) {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut pending = std::mem::take(&mut *tasks.borrow_mut());
+        pending.retain_mut(|task| task.as_mut().poll(&mut cx).is_pending());
+        tasks.borrow_mut().extend(pending);
+    }
+
+    /// A fetcher whose replies the test supplies per id.
+    fn fetcher(replies: &Replies) -> impl Fn(i32) -> LocalFetch + 'static {
+        let replies = replies.clone();
+        move |id| {
+            let replies = replies.clone();
+            Box::pin(std::future::poll_fn(move |_| {
+                let mut replies = replies.borrow_mut();
+                match replies.iter().position(|(reply_id, _)| *reply_id == id) {
+                    Some(index) => Poll::Ready(replies.remove(index).1),
+                    None => Poll::Pending,
+                }
+            }))
+        }
+    }
+
+    type LocalFetch = std::pin::Pin<Box<dyn Future<Output = Result<String, String>>>>;
+
+    #[test]
+    fn refetches_when_source_changes_and_drops_stale_results() {
+        let tasks: Tasks = Rc::default();
+        let replies: Replies = Rc::default();
+        let id = signal(1);
+        let user = resource(spawner(&tasks), id.clone(), fetcher(&replies));
+
+        assert!(user.loading());
+        id.set(2);
+
+        // The reply for id 1 arrives late, after id 2 was requested
+        replies.borrow_mut().push((1, Ok("one".into())));
+        run(&tasks);
+        assert_eq!(user.value(), None);
+        assert!(user.loading());
+
+        replies.borrow_mut().push((2, Ok("two".into())));
+        run(&tasks);
+        assert_eq!(user.value(), Some("two".to_string()));
+        assert!(!user.loading());
+        assert!(tasks.borrow().is_empty());
+    }
+
+    #[test]
+    fn errors_keep_last_value_until_next_success() {
+        let tasks: Tasks = Rc::default();
+        let replies: Replies = Rc::default();
+        let id = signal(1);
+        let user = resource(spawner(&tasks), id.clone(), fetcher(&replies));
+
+        replies.borrow_mut().push((1, Ok("one".into())));
+        run(&tasks);
+
+        user.refetch();
+        assert!(user.loading());
+        replies.borrow_mut().push((1, Err("offline".into())));
+        run(&tasks);
+        assert_eq!(user.error(), Some("offline".to_string()));
+        assert_eq!(user.value(), Some("one".to_string()));
+
+        user.refetch();
+        replies.borrow_mut().push((1, Ok("back".into())));
+        run(&tasks);
+        assert_eq!(user.error(), None);
+        assert_eq!(user.value(), Some("back".to_string()));
+    }
+
+    #[test]
+    fn dropping_the_resource_stops_fetching() {
+        let tasks: Tasks = Rc::default();
+        let replies: Replies = Rc::default();
+        let id = signal(1);
+        let user = resource(spawner(&tasks), id.clone(), fetcher(&replies));
+        drop(user);
+
+        // The in-flight fetch was cancelled and no new one starts
+        run(&tasks);
+        id.set(2);
+        assert!(tasks.borrow().is_empty());
+    }
+}
@@ -15,37 +15,30 @@
 // - pause()/resume() - Temporarily disable effects
 // - Nested scopes (child scopes auto-disposed with parent)
 // - Detached scopes (opt out of parent collection)
+// - Introspection (parent, children, effect_count)
 // ============================================================================
 
 use std::cell::{Cell, RefCell};
 use std::rc::{Rc, Weak};
 
 use crate::core::constants::*;
+use crate::core::context::with_context;
 use crate::core::types::AnyReaction;
 use crate::primitives::effect::{destroy_effect, EffectInner};
 use crate::reactivity::scheduling::{flush_sync, schedule_effect_inner};
 
 // =============================================================================
-// THREAD-LOCAL SCOPE STATE
+// ACTIVE SCOPE (kept in the reactive context)
 // =============================================================================
 
-thread_local! {
-    /// Currently active scope (if any)
-    static ACTIVE_SCOPE: RefCell<Option<Rc<EffectScopeInner>>> = const { RefCell::new(None) };
-}
-
 /// Get the currently active scope
 fn get_active_scope() -> Option<Rc<EffectScopeInner>> {
-    ACTIVE_SCOPE.with(|s| s.borrow().clone())
+    with_context(|ctx| ctx.active_scope.borrow().clone())
 }
 
 /// Set the active scope, returning the previous one
 fn set_active_scope(scope: Option<Rc<EffectScopeInner>>) -> Option<Rc<EffectScopeInner>> {
-    ACTIVE_SCOPE.with(|s| {
-        let prev = s.borrow().clone();
-        *s.borrow_mut() = scope;
-        prev
-    })
+    with_context(|ctx| ctx.active_scope.replace(scope))
 }
 
 // =============================================================================
@@ -144,6 +137,12 @@ impl EffectScopeInner {
         // Flush any pending effects first to ensure clean state
         flush_sync();
 
+        // Stop child scopes first, newest first (like nested destructors)
+        let child_scopes: Vec<_> = self.scopes.borrow_mut().drain(..).collect();
+        for child in child_scopes.into_iter().rev() {
+            child.stop();
+        }
+
         // Dispose all effects
         let effects: Vec<_> = self.effects.borrow_mut().drain(..).collect();
         for effect in effects {
@@ -157,12 +156,6 @@ impl EffectScopeInner {
             let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(cleanup));
         }
 
-        // Stop child scopes
-        let child_scopes: Vec<_> = self.scopes.borrow_mut().drain(..).collect();
-        for child in child_scopes {
-            child.stop();
-        }
-
         // Remove from parent's scope list
         if let Some(parent) = self.parent.borrow().as_ref().and_then(|w| w.upgrade()) {
             if let Some(self_rc) = self.self_weak.borrow().upgrade() {
@@ -181,10 +174,10 @@ impl EffectScopeInner {
 
         self.paused.set(true);
 
-        // Mark all effects as inert (paused)
+        // Mark all effects as inert (paused), with the child effects they
+        // created since the scope ran
         for effect in self.effects.borrow().iter() {
-            let flags = effect.flags();
-            effect.set_flags(flags | INERT);
+            set_tree_inert(effect, true, &mut Vec::new());
         }
 
         // Pause child scopes
@@ -201,15 +194,14 @@ impl EffectScopeInner {
 
         self.paused.set(false);
 
-        // Unmark effects and reschedule dirty ones
+        // Unmark effects, then reschedule the ones a write reached while
+        // paused (MAYBE_DIRTY: through a derived)
+        let mut dirty = Vec::new();
         for effect in self.effects.borrow().iter() {
-            let flags = effect.flags();
-            effect.set_flags(flags & !INERT);
-
-            // If effect is dirty, reschedule it
-            if (flags & DIRTY) != 0 {
-                schedule_effect_inner(effect.clone());
-            }
+            set_tree_inert(effect, false, &mut dirty);
+        }
+        for effect in dirty {
+            schedule_effect_inner(effect);
         }
 
         // Resume child scopes
@@ -218,6 +210,25 @@ impl EffectScopeInner {
         }
     }
 
+    /// Parent scope, if this scope is nested and not detached
+    pub fn parent(&self) -> Option<Rc<EffectScopeInner>> {
+        self.parent.borrow().as_ref().and_then(|w| w.upgrade())
+    }
+
+    /// Child scopes that haven't been stopped, in creation order
+    pub fn child_scopes(&self) -> Vec<Rc<EffectScopeInner>> {
+        self.scopes.borrow().clone()
+    }
+
+    /// Number of tracked effects that haven't been destroyed
+    pub fn effect_count(&self) -> usize {
+        self.effects
+            .borrow()
+            .iter()
+            .filter(|effect| (effect.flags() & DESTROYED) == 0)
+            .count()
+    }
+
     /// Add an effect to this scope
     pub fn add_effect(&self, effect: Rc<EffectInner>) {
         self.effects.borrow_mut().push(effect);
@@ -229,6 +240,26 @@ impl EffectScopeInner {
     }
 }
 
+/// Set or clear INERT on an effect and its child effects. When clearing,
+/// collects the effects left dirty (parents before children).
+fn set_tree_inert(effect: &Rc<EffectInner>, inert: bool, dirty: &mut Vec<Rc<EffectInner>>) {
+    let flags = effect.flags();
+    if inert {
+        effect.set_flags(flags | INERT);
+    } else {
+        effect.set_flags(flags & !INERT);
+        if (flags & (DIRTY | MAYBE_DIRTY)) != 0 {
+            dirty.push(effect.clone());
+        }
+    }
+
+    let mut child = effect.first_child();
+    while let Some(current) = child {
+        set_tree_inert(&current, inert, dirty);
+        child = current.next_sibling();
+    }
+}
+
 impl Drop for EffectScopeInner {
     fn drop(&mut self) {
         // Stop the scope if it's still active
@@ -306,27 +337,52 @@ impl EffectScope {
 
     /// Stop the scope, disposing all tracked effects.
     ///
-    /// - All effects are destroyed
-    /// - All cleanup callbacks are run (in reverse order)
-    /// - All child scopes are stopped
+    /// In this order:
+    /// 1. Child scopes are stopped, newest first (each one in this order)
+    /// 2. All effects are destroyed (running their teardowns)
+    /// 3. All cleanup callbacks are run, in reverse registration order
     ///
-    /// After stopping, `run()` will return None.
+    /// So a scope's `on_scope_dispose` callbacks always run after everything
+    /// nested inside it has been disposed. After stopping, `run()` will
+    /// return None.
     pub fn stop(&self) {
         self.inner.stop();
     }
 
     /// Pause all effects in this scope.
     ///
-    /// Paused effects won't run when their dependencies change.
-    /// Changes are accumulated and effects will run when resumed.
+    /// Paused effects (including child effects they created) won't run when
+    /// their dependencies change. Changes are accumulated and effects will
+    /// run when resumed.
     pub fn pause(&self) {
         self.inner.pause();
     }
 
+    /// The scope this one was created in, unless it is detached or a
+    /// top-level scope.
+    pub fn parent(&self) -> Option<EffectScope> {
+        self.inner.parent().map(EffectScope::from_inner)
+    }
+
+    /// Nested (non-detached) scopes that are still active, in creation order.
+    pub fn children(&self) -> Vec<EffectScope> {
+        self.inner
+            .child_scopes()
+            .into_iter()
+            .map(EffectScope::from_inner)
+            .collect()
+    }
+
+    /// Number of live effects created in this scope (not counting child
+    /// scopes, or effects created by effects outside `run()`).
+    pub fn effect_count(&self) -> usize {
+        self.inner.effect_count()
+    }
+
     /// Resume all paused effects in this scope.
     ///
-    /// Dirty effects (those whose dependencies changed while paused)
-    /// will be scheduled for execution.
+    /// Dirty effects (those whose dependencies changed while paused,
+    /// directly or through a derived) will be scheduled for execution.
     pub fn resume(&self) {
         self.inner.resume();
     }
@@ -407,6 +463,33 @@ pub fn effect_scope(detached: bool) -> EffectScope {
     EffectScope::from_inner(EffectScopeInner::new(detached))
 }
 
+/// Create a scope that isn't owned by the current scope.
+///
+/// Same as `effect_scope(true)`: stopping the enclosing scope leaves it
+/// running, and it stops when it is stopped explicitly or its last handle is
+/// dropped.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_scope, effect_scope_detached};
+///
+/// let app = effect_scope(false);
+/// let (panel, popup) = app
+///     .run(|| (effect_scope(false), effect_scope_detached()))
+///     .unwrap();
+///
+/// assert_eq!(app.children().len(), 1);
+/// assert!(popup.parent().is_none());
+///
+/// app.stop();
+/// assert!(!panel.active());
+/// assert!(popup.active());
+/// ```
+pub fn effect_scope_detached() -> EffectScope {
+    effect_scope(true)
+}
+
 /// Get the currently active scope, if any.
 ///
 /// Returns None if not inside a scope's `run()` call.
@@ -427,7 +510,9 @@ pub fn get_current_scope() -> Option<EffectScope> {
 
 /// Register a cleanup function on the current scope.
 ///
-/// Will be called when the scope is stopped.
+/// Will be called when the scope is stopped: after its child scopes and
+/// effects are disposed, in reverse registration order (see
+/// [`EffectScope::stop()`]).
 /// Does nothing if called outside of a scope context (with a warning).
 ///
 /// # Example
@@ -661,6 +746,41 @@ mod tests {
         assert!(detached_cleanup.get(), "Detached cleanup should run now");
     }
 
+    #[test]
+    fn stop_disposes_children_before_own_cleanups() {
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let log = |name: &'static str| {
+            let order = order.clone();
+            move || order.borrow_mut().push(name)
+        };
+
+        let parent = effect_scope(false);
+        parent.run(|| {
+            on_scope_dispose(log("parent"));
+            let _ = crate::primitives::effect::effect_sync_with_cleanup({
+                let teardown = log("parent effect");
+                move || Some(Box::new(teardown.clone()) as Box<dyn FnOnce()>)
+            });
+            for name in ["first child", "second child"] {
+                effect_scope(false).run(|| on_scope_dispose(log(name)));
+            }
+        });
+
+        assert_eq!(parent.children().len(), 2);
+        assert_eq!(parent.effect_count(), 1);
+        assert!(Rc::ptr_eq(
+            &parent.children()[0].parent().unwrap().inner,
+            &parent.inner
+        ));
+
+        parent.stop();
+        assert_eq!(
+            *order.borrow(),
+            ["second child", "first child", "parent effect", "parent"]
+        );
+        assert_eq!(parent.effect_count(), 0);
+    }
+
     #[test]
     fn scope_pause_resume() {
         let effect_runs = Rc::new(Cell::new(0));
@@ -694,6 +814,48 @@ mod tests {
         assert_eq!(effect_runs.get(), 2, "Effect should run on resume");
     }
 
+    #[test]
+    fn pause_covers_derived_deps_and_child_effects() {
+        use crate::primitives::derived::derived;
+
+        let count = signal(0);
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        let outer_seen = Rc::new(Cell::new(0));
+        let inner_seen = Rc::new(Cell::new(0));
+
+        let scope = effect_scope(false);
+        scope.run(|| {
+            let (doubled, outer_seen, inner_seen, count) =
+                (doubled.clone(), outer_seen.clone(), inner_seen.clone(), count.clone());
+            let _ = effect_sync(move || {
+                outer_seen.set(doubled.get());
+                // Child effect, created on every run of the parent
+                let (inner_seen, count) = (inner_seen.clone(), count.clone());
+                let _ = effect_sync(move || inner_seen.set(count.get()));
+            });
+        });
+
+        scope.pause();
+        count.set(1);
+        count.set(2);
+        assert_eq!(outer_seen.get(), 0);
+        assert_eq!(inner_seen.get(), 0);
+
+        scope.resume();
+        assert_eq!(outer_seen.get(), 4);
+        assert_eq!(inner_seen.get(), 2);
+
+        // The child was recreated outside scope.run(); still paused with it
+        scope.pause();
+        count.set(3);
+        assert_eq!(inner_seen.get(), 2);
+        scope.resume();
+        assert_eq!(inner_seen.get(), 3);
+    }
+
     #[test]
     fn multiple_cleanups_run_in_reverse_order() {
         let order = Rc::new(RefCell::new(Vec::new()));
@@ -17,6 +17,15 @@ use crate::core::types::AnyReaction;
 use crate::primitives::effect::effect_sync;
 use crate::reactivity::tracking::set_signal_status;
 
+/// Key -> subscribed reactions registry shared with the internal effect.
+type SubscriberMap<K> = Rc<RefCell<HashMap<K, HashSet<SubscriberEntry>>>>;
+
+/// Key/value selection predicate.
+type CompareFn<K, T> = Rc<dyn Fn(&K, &T) -> bool>;
+
+/// Shared, take-once dispose closure for the internal effect.
+type DisposeCell = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+
 // =============================================================================
 // SELECTOR
 // =============================================================================
@@ -37,14 +46,14 @@ where
     initialized: Rc<Cell<bool>>,
 
     /// Map of keys to their subscribed reactions
-    subscribers: Rc<RefCell<HashMap<K, HashSet<SubscriberEntry>>>>,
+    subscribers: SubscriberMap<K>,
 
     /// The comparison function
-    compare: Rc<dyn Fn(&K, &T) -> bool>,
+    compare: CompareFn<K, T>,
 
     /// Dispose function for the internal effect (stored as boxed closure)
     /// We use RefCell<Option<...>> so we can take it once for disposal
-    _dispose: Rc<RefCell<Option<Box<dyn FnOnce()>>>>,
+    _dispose: DisposeCell,
 }
 
 impl<T, K> Drop for Selector<T, K>
@@ -220,11 +229,10 @@ where
 {
     let current_value: Rc<RefCell<Option<T>>> = Rc::new(RefCell::new(None));
     let initialized = Rc::new(Cell::new(false));
-    let subscribers: Rc<RefCell<HashMap<K, HashSet<SubscriberEntry>>>> =
-        Rc::new(RefCell::new(HashMap::new()));
+    let subscribers: SubscriberMap<K> = Rc::new(RefCell::new(HashMap::new()));
 
     // Default comparison: equality
-    let compare: Rc<dyn Fn(&K, &T) -> bool> = match compare {
+    let compare: CompareFn<K, T> = match compare {
         Some(f) => Rc::new(f),
         None => Rc::new(|k: &K, v: &T| {
             // This only works if K and T are the same type
@@ -252,8 +260,7 @@ where
 
         // Only notify if value actually changed and we're initialized
         let prev_value = current_value_clone.borrow().clone();
-        if initialized_clone.get() {
-            if prev_value.as_ref() != Some(&value) {
+        if initialized_clone.get() && prev_value.as_ref() != Some(&value) {
                 // Find keys whose selection state changed
                 let subscribers_snapshot: Vec<(K, HashSet<SubscriberEntry>)> = {
                     let subs = subscribers_clone.borrow();
@@ -331,7 +338,6 @@ where
                     });
                 }
             }
-        }
 
         *current_value_clone.borrow_mut() = Some(value);
         initialized_clone.set(true);
@@ -377,6 +383,402 @@ where
     create_selector(source, Some(|k: &T, v: &T| k == v))
 }
 
+/// Create a selector whose source is a struct, selected by an extracted key.
+///
+/// `is_selected(&k)` is true when `key_fn(&source()) == k`, so the source
+/// can be the whole selected row while list items check their id.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{create_selector_by, signal};
+///
+/// #[derive(Clone, PartialEq)]
+/// struct Row {
+///     id: u32,
+///     label: String,
+/// }
+///
+/// let selected = signal(Row { id: 1, label: "one".into() });
+/// let selector = create_selector_by(
+///     {
+///         let selected = selected.clone();
+///         move || selected.get()
+///     },
+///     |row: &Row| row.id,
+/// );
+///
+/// assert!(selector.is_selected(&1));
+/// selected.set(Row { id: 2, label: "two".into() });
+/// assert!(selector.is_selected(&2));
+/// assert!(!selector.is_selected(&1));
+/// ```
+pub fn create_selector_by<T, K, F, KF>(source: F, key_fn: KF) -> Selector<T, K>
+where
+    T: Clone + PartialEq + 'static,
+    K: Clone + Eq + Hash + 'static,
+    F: Fn() -> T + 'static,
+    KF: Fn(&T) -> K + 'static,
+{
+    create_selector(source, Some(move |k: &K, v: &T| key_fn(v) == *k))
+}
+
+// =============================================================================
+// MULTI SELECTOR
+// =============================================================================
+
+/// Per-key subscriber lists of a `MultiSelector`.
+type MultiSubscribers<K> = Rc<RefCell<HashMap<K, Vec<Weak<dyn AnyReaction>>>>>;
+
+/// A selector over a set of selected keys (multi-row selection).
+///
+/// `is_selected(&k)` checks membership. When the set changes, only effects
+/// of keys that entered or left it re-run.
+pub struct MultiSelector<K>
+where
+    K: Clone + Eq + Hash + 'static,
+{
+    /// Current selection
+    current: Rc<RefCell<HashSet<K>>>,
+
+    /// Subscribed reactions, by key
+    subscribers: MultiSubscribers<K>,
+
+    /// Dispose function for the internal effect
+    _dispose: DisposeCell,
+}
+
+impl<K> MultiSelector<K>
+where
+    K: Clone + Eq + Hash + 'static,
+{
+    /// Check if a key is in the selection.
+    ///
+    /// Inside a reactive context, subscribes the current reaction to
+    /// selection changes of this key only.
+    pub fn is_selected(&self, key: &K) -> bool {
+        let reaction = with_context(|ctx| ctx.get_active_reaction());
+        if let Some(reaction) = reaction {
+            let mut subscribers = self.subscribers.borrow_mut();
+            let slot = subscribers.entry(key.clone()).or_default();
+            if !slot.iter().any(|existing| Weak::ptr_eq(existing, &reaction)) {
+                slot.push(reaction);
+            }
+        }
+
+        self.current.borrow().contains(key)
+    }
+
+    /// The selected keys (untracked).
+    pub fn selected(&self) -> HashSet<K> {
+        self.current.borrow().clone()
+    }
+
+    /// Number of selected keys (untracked).
+    pub fn len(&self) -> usize {
+        self.current.borrow().len()
+    }
+
+    /// True if nothing is selected (untracked).
+    pub fn is_empty(&self) -> bool {
+        self.current.borrow().is_empty()
+    }
+}
+
+impl<K> Clone for MultiSelector<K>
+where
+    K: Clone + Eq + Hash + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+            subscribers: self.subscribers.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl<K> Drop for MultiSelector<K>
+where
+    K: Clone + Eq + Hash + 'static,
+{
+    fn drop(&mut self) {
+        // Dispose the internal effect only if this is the last reference
+        if Rc::strong_count(&self._dispose) == 1 {
+            if let Some(dispose) = self._dispose.borrow_mut().take() {
+                dispose();
+            }
+        }
+    }
+}
+
+/// Create a selector over a set of selected keys.
+///
+/// `source` returns the selected set. A change re-runs only the effects of
+/// keys added to or removed from it.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{create_multi_selector, effect_sync, signal};
+/// use std::cell::Cell;
+/// use std::collections::HashSet;
+/// use std::rc::Rc;
+///
+/// let selected = signal(HashSet::from([0]));
+/// let selector = create_multi_selector({
+///     let selected = selected.clone();
+///     move || selected.get()
+/// });
+///
+/// let runs: Vec<Rc<Cell<u32>>> = (0..4).map(|_| Rc::new(Cell::new(0))).collect();
+/// let _effects: Vec<_> = (0..4)
+///     .map(|i| {
+///         let selector = selector.clone();
+///         let runs = runs[i].clone();
+///         effect_sync(move || {
+///             selector.is_selected(&i);
+///             runs.set(runs.get() + 1);
+///         })
+///     })
+///     .collect();
+///
+/// // 0 stays selected, 2 is added: only item 2 re-runs
+/// selected.set(HashSet::from([0, 2]));
+/// assert_eq!(runs.iter().map(|r| r.get()).collect::<Vec<_>>(), vec![1, 1, 2, 1]);
+/// ```
+pub fn create_multi_selector<K, F>(source: F) -> MultiSelector<K>
+where
+    K: Clone + Eq + Hash + 'static,
+    F: Fn() -> HashSet<K> + 'static,
+{
+    let current: Rc<RefCell<HashSet<K>>> = Rc::new(RefCell::new(HashSet::new()));
+    let subscribers: MultiSubscribers<K> = Rc::new(RefCell::new(HashMap::new()));
+    let initialized = Rc::new(Cell::new(false));
+
+    let dispose = effect_sync({
+        let current = current.clone();
+        let subscribers = subscribers.clone();
+        move || {
+            let value = source();
+            let prev = current.replace(value.clone());
+
+            if !initialized.replace(true) || prev == value {
+                return;
+            }
+
+            // Only keys that entered or left the set changed selection state
+            let mut dirty_reactions: Vec<Rc<dyn AnyReaction>> = Vec::new();
+            {
+                let mut subs = subscribers.borrow_mut();
+                for key in prev.symmetric_difference(&value) {
+                    // Subscribers re-subscribe when they re-run
+                    if let Some(slot) = subs.remove(key) {
+                        for weak in slot {
+                            if let Some(reaction) = weak.upgrade() {
+                                if (reaction.flags() & DESTROYED) == 0 {
+                                    dirty_reactions.push(reaction);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Mark dirty and queue; the outer flush loop runs them
+            if !dirty_reactions.is_empty() {
+                with_context(|ctx| {
+                    for reaction in &dirty_reactions {
+                        set_signal_status(&**reaction, DIRTY);
+                        ctx.add_pending_reaction(Rc::downgrade(reaction));
+                    }
+                });
+            }
+        }
+    });
+
+    MultiSelector {
+        current,
+        subscribers,
+        _dispose: Rc::new(RefCell::new(Some(Box::new(dispose)))),
+    }
+}
+
+// =============================================================================
+// DENSE SELECTOR
+// =============================================================================
+
+/// Per-key subscriber lists, indexed directly by key.
+type DenseSubscribers = Rc<RefCell<Vec<Vec<Weak<dyn AnyReaction>>>>>;
+
+/// A selector specialized for dense integer keys (e.g. entity ids `0..N`).
+///
+/// Same semantics as `Selector`, but subscribers live in a `Vec` indexed by
+/// key instead of a `HashMap`, so `is_selected` does no hashing and a
+/// selection change touches exactly two slots instead of scanning every
+/// subscribed key.
+pub struct DenseSelector {
+    /// Current selection
+    current: Rc<Cell<Option<usize>>>,
+
+    /// Subscribed reactions, indexed by key
+    subscribers: DenseSubscribers,
+
+    /// Dispose function for the internal effect
+    _dispose: DisposeCell,
+}
+
+impl DenseSelector {
+    /// Check if a key is currently selected.
+    ///
+    /// Inside a reactive context, subscribes the current reaction to
+    /// selection changes of this key only.
+    #[inline]
+    pub fn is_selected(&self, key: usize) -> bool {
+        let reaction = with_context(|ctx| ctx.get_active_reaction());
+        if let Some(reaction) = reaction {
+            self.subscribe(key, reaction);
+        }
+
+        self.current.get() == Some(key)
+    }
+
+    /// The currently selected key (untracked).
+    pub fn selected(&self) -> Option<usize> {
+        self.current.get()
+    }
+
+    /// Number of key slots allocated.
+    pub fn capacity(&self) -> usize {
+        self.subscribers.borrow().len()
+    }
+
+    fn subscribe(&self, key: usize, reaction: Weak<dyn AnyReaction>) {
+        let mut subscribers = self.subscribers.borrow_mut();
+        if key >= subscribers.len() {
+            subscribers.resize_with(key + 1, Vec::new);
+        }
+
+        let slot = &mut subscribers[key];
+        if !slot.iter().any(|existing| Weak::ptr_eq(existing, &reaction)) {
+            slot.push(reaction);
+        }
+    }
+}
+
+impl Clone for DenseSelector {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+            subscribers: self.subscribers.clone(),
+            _dispose: self._dispose.clone(),
+        }
+    }
+}
+
+impl Drop for DenseSelector {
+    fn drop(&mut self) {
+        // Dispose the internal effect only if this is the last reference
+        if Rc::strong_count(&self._dispose) == 1 {
+            if let Some(dispose) = self._dispose.borrow_mut().take() {
+                dispose();
+            }
+        }
+    }
+}
+
+/// Create a selector for dense integer keys.
+///
+/// `capacity` pre-allocates key slots (keys beyond it still work; the table
+/// grows on demand). `source` returns the selected key, or `None`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{create_dense_selector, effect_sync, signal};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let selected = signal(Some(1usize));
+/// let selector = create_dense_selector(100, {
+///     let selected = selected.clone();
+///     move || selected.get()
+/// });
+///
+/// let runs: Vec<Rc<Cell<u32>>> = (0..3).map(|_| Rc::new(Cell::new(0))).collect();
+/// let _effects: Vec<_> = (0..3)
+///     .map(|i| {
+///         let selector = selector.clone();
+///         let runs = runs[i].clone();
+///         effect_sync(move || {
+///             selector.is_selected(i);
+///             runs.set(runs.get() + 1);
+///         })
+///     })
+///     .collect();
+///
+/// // Only the previously and newly selected items re-run
+/// selected.set(Some(2));
+/// assert_eq!(runs.iter().map(|r| r.get()).collect::<Vec<_>>(), vec![1, 2, 2]);
+/// ```
+pub fn create_dense_selector<F>(capacity: usize, source: F) -> DenseSelector
+where
+    F: Fn() -> Option<usize> + 'static,
+{
+    let current: Rc<Cell<Option<usize>>> = Rc::new(Cell::new(None));
+    let subscribers: DenseSubscribers = Rc::new(RefCell::new(
+        std::iter::repeat_with(Vec::new).take(capacity).collect(),
+    ));
+    let initialized = Rc::new(Cell::new(false));
+
+    let dispose = effect_sync({
+        let current = current.clone();
+        let subscribers = subscribers.clone();
+        move || {
+            let value = source();
+            let prev = current.replace(value);
+
+            if !initialized.replace(true) || prev == value {
+                return;
+            }
+
+            // Only the old and new keys changed selection state
+            let mut dirty_reactions: Vec<Rc<dyn AnyReaction>> = Vec::new();
+            {
+                let mut subs = subscribers.borrow_mut();
+                for key in [prev, value].into_iter().flatten() {
+                    if let Some(slot) = subs.get_mut(key) {
+                        // Subscribers re-subscribe when they re-run
+                        for weak in slot.drain(..) {
+                            if let Some(reaction) = weak.upgrade() {
+                                if (reaction.flags() & DESTROYED) == 0 {
+                                    dirty_reactions.push(reaction);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Mark dirty and queue; the outer flush loop runs them
+            if !dirty_reactions.is_empty() {
+                with_context(|ctx| {
+                    for reaction in &dirty_reactions {
+                        set_signal_status(&**reaction, DIRTY);
+                        ctx.add_pending_reaction(Rc::downgrade(reaction));
+                    }
+                });
+            }
+        }
+    });
+
+    DenseSelector {
+        current,
+        subscribers,
+        _dispose: Rc::new(RefCell::new(Some(Box::new(dispose)))),
+    }
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -671,4 +1073,146 @@ mod tests {
         selected.set(1);
         assert!(is_selected_1.get());
     }
+
+    #[test]
+    fn dense_selector_basic() {
+        let selected = signal(Some(1usize));
+        let selector = create_dense_selector(4, {
+            let selected = selected.clone();
+            move || selected.get()
+        });
+
+        assert!(selector.is_selected(1));
+        assert!(!selector.is_selected(2));
+        assert_eq!(selector.selected(), Some(1));
+
+        selected.set(None);
+        assert!(!selector.is_selected(1));
+        assert_eq!(selector.selected(), None);
+    }
+
+    #[test]
+    fn dense_selector_o2_updates() {
+        let selected = signal(Some(0usize));
+        let selector = create_dense_selector(10, {
+            let selected = selected.clone();
+            move || selected.get()
+        });
+
+        let runs: Vec<Rc<Cell<u32>>> = (0..10).map(|_| Rc::new(Cell::new(0))).collect();
+        let _effects: Vec<_> = (0..10)
+            .map(|i| {
+                let selector = selector.clone();
+                let runs = runs[i].clone();
+                effect_sync(move || {
+                    // Repeated reads subscribe once
+                    selector.is_selected(i);
+                    selector.is_selected(i);
+                    runs.set(runs.get() + 1);
+                })
+            })
+            .collect();
+
+        selected.set(Some(5));
+        selected.set(Some(7));
+        selected.set(None);
+
+        let counts: Vec<u32> = runs.iter().map(|r| r.get()).collect();
+        assert_eq!(counts, vec![2, 1, 1, 1, 1, 3, 1, 3, 1, 1]);
+        assert_eq!(selector.subscribers.borrow()[7].len(), 1);
+    }
+
+    #[test]
+    fn dense_selector_grows_beyond_capacity() {
+        let selected = signal(Some(0usize));
+        let selector = create_dense_selector(2, {
+            let selected = selected.clone();
+            move || selected.get()
+        });
+
+        let runs = Rc::new(Cell::new(0));
+        let _effect = effect_sync({
+            let selector = selector.clone();
+            let runs = runs.clone();
+            move || {
+                selector.is_selected(100);
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(selector.capacity(), 101);
+
+        selected.set(Some(100));
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn selector_by_extracted_key() {
+        #[derive(Clone, PartialEq)]
+        struct Row {
+            id: u32,
+            label: &'static str,
+        }
+
+        let selected = signal(Row { id: 1, label: "a" });
+        let selector = create_selector_by(
+            {
+                let selected = selected.clone();
+                move || selected.get()
+            },
+            |row: &Row| row.id,
+        );
+
+        let runs = Rc::new(Cell::new(0));
+        let _effect = effect_sync({
+            let selector = selector.clone();
+            let runs = runs.clone();
+            move || {
+                selector.is_selected(&1);
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        // Same key, different row: row 1 is still selected
+        selected.set(Row { id: 1, label: "b" });
+        assert_eq!(runs.get(), 1);
+        assert_eq!(selected.get().label, "b");
+
+        selected.set(Row { id: 2, label: "b" });
+        assert_eq!(runs.get(), 2);
+        assert!(!selector.is_selected(&1));
+    }
+
+    #[test]
+    fn multi_selector_reruns_changed_keys_only() {
+        let selected = signal(HashSet::from([1usize, 2]));
+        let selector = create_multi_selector({
+            let selected = selected.clone();
+            move || selected.get()
+        });
+        assert_eq!(selector.len(), 2);
+
+        let runs: Vec<Rc<Cell<u32>>> = (0..5).map(|_| Rc::new(Cell::new(0))).collect();
+        let _effects: Vec<_> = (0..5)
+            .map(|i| {
+                let selector = selector.clone();
+                let runs = runs[i].clone();
+                effect_sync(move || {
+                    selector.is_selected(&i);
+                    runs.set(runs.get() + 1);
+                })
+            })
+            .collect();
+
+        // 1 leaves, 3 and 4 join
+        selected.set(HashSet::from([2, 3, 4]));
+        let counts: Vec<u32> = runs.iter().map(|r| r.get()).collect();
+        assert_eq!(counts, vec![1, 2, 1, 2, 2]);
+        assert!(selector.is_selected(&3));
+        assert!(!selector.is_selected(&1));
+
+        selected.set(HashSet::new());
+        let counts: Vec<u32> = runs.iter().map(|r| r.get()).collect();
+        assert_eq!(counts, vec![1, 2, 2, 3, 3]);
+        assert!(selector.is_empty());
+    }
 }
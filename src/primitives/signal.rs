@@ -3,10 +3,18 @@
 // The core writable reactive signal
 // ============================================================================
 
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
 
 use crate::core::context::with_context;
-use crate::core::types::{AnySource, EqualsFn, SourceInner};
+use crate::core::types::{AnySource, AnySourceRef, EqualsFn, SourceInner};
+use crate::primitives::subscribe::has_subscribers;
+use crate::reactivity::batching::{in_transaction, stage_set, stage_update};
+use crate::reactivity::equality::{
+    default_float_equals, equals_epsilon, equals_epsilon_f32, safe_equals_f32, safe_equals_f64,
+    FloatEquals,
+};
+use crate::reactivity::middleware::{has_write_middleware, run_write_middleware};
 use crate::reactivity::tracking::{notify_write, track_read};
 
 // =============================================================================
@@ -40,9 +48,7 @@ impl<T> Signal<T> {
     where
         T: PartialEq + 'static,
     {
-        Self {
-            inner: Rc::new(SourceInner::new(value)),
-        }
+        Self::new_with_equals(value, crate::core::types::default_equals)
     }
 
     /// Create a new signal with a custom equality function.
@@ -50,9 +56,12 @@ impl<T> Signal<T> {
     where
         T: 'static,
     {
-        Self {
+        let signal = Self {
             inner: Rc::new(SourceInner::new_with_equals(value, equals)),
-        }
+        };
+        #[cfg(feature = "debug-deps")]
+        crate::debug::register_source(&signal.as_any_source(), None);
+        signal
     }
 
     /// Get the current value (cloning).
@@ -103,11 +112,18 @@ impl<T> Signal<T> {
     /// Set the signal's value.
     ///
     /// Returns true if the value changed (based on equality check).
-    /// If the value didn't change, no notifications are sent.
-    pub fn set(&self, value: T) -> bool
+    /// If the value didn't change, or write middleware vetoed it, no
+    /// notifications are sent.
+    pub fn set(&self, mut value: T) -> bool
     where
         T: 'static,
     {
+        if !self.pass_middleware(&mut value) {
+            return false;
+        }
+        if in_transaction() {
+            return stage_set(&self.inner, value);
+        }
         let changed = self.inner.set(value);
         if changed {
             // Update write version in context and notify reactions
@@ -135,8 +151,41 @@ impl<T> Signal<T> {
     where
         T: Clone + 'static,
     {
+        if has_write_middleware(&self.inner) {
+            // Middleware needs the old and new values side by side
+            let mut value = self.inner.get();
+            f(&mut value);
+            if self.pass_middleware(&mut value) {
+                self.update_notify(|current| *current = value);
+            }
+            return;
+        }
+        self.update_notify(f);
+    }
+
+    /// Run write middleware over a proposed replacement of the value.
+    /// Returns false if it was vetoed.
+    fn pass_middleware(&self, value: &mut T) -> bool
+    where
+        T: 'static,
+    {
+        if !has_write_middleware(&self.inner) {
+            return true;
+        }
+        let source = self.inner.clone() as Rc<dyn AnySource>;
+        self.inner.with(|old| run_write_middleware(&source, Some(old), value))
+    }
+
+    /// `update()` after middleware: stage or apply, then notify.
+    fn update_notify(&self, f: impl FnOnce(&mut T))
+    where
+        T: Clone + 'static,
+    {
+        if in_transaction() {
+            return stage_update(&self.inner, f);
+        }
         let had_reactions = self.inner.update(f);
-        if had_reactions {
+        if had_reactions || has_subscribers(&self.inner) {
             // Update write version and notify reactions
             with_context(|ctx| {
                 let wv = ctx.increment_write_version();
@@ -146,11 +195,132 @@ impl<T> Signal<T> {
         }
     }
 
+    /// Replace the value without bumping its version or notifying anyone.
+    ///
+    /// For bulk initialization: nothing downstream learns of the change
+    /// (cached deriveds keep their old value) until you call `notify()`.
+    /// Write middleware still sees the write, and may veto it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{effect_sync, signal};
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// let count = signal(0);
+    /// let seen = Rc::new(Cell::new(-1));
+    /// let _dispose = effect_sync({
+    ///     let (count, seen) = (count.clone(), seen.clone());
+    ///     move || seen.set(count.get())
+    /// });
+    ///
+    /// count.set_untracked(5);
+    /// assert_eq!(seen.get(), 0);
+    ///
+    /// count.notify();
+    /// assert_eq!(seen.get(), 5);
+    /// ```
+    pub fn set_untracked(&self, mut value: T)
+    where
+        T: 'static,
+    {
+        if self.pass_middleware(&mut value) {
+            self.inner.set_untracked(value);
+        }
+    }
+
+    /// Mutate the value in place without bumping its version or notifying
+    /// anyone (see `set_untracked()`).
+    pub fn update_untracked(&self, f: impl FnOnce(&mut T))
+    where
+        T: Clone + 'static,
+    {
+        if has_write_middleware(&self.inner) {
+            let mut value = self.inner.get();
+            f(&mut value);
+            if self.pass_middleware(&mut value) {
+                self.inner.set_untracked(value);
+            }
+            return;
+        }
+        self.inner.update_untracked(f);
+    }
+
+    /// Notify dependents after untracked writes.
+    ///
+    /// Bumps the signal's write version, so cached deriveds recompute, and
+    /// propagates as if the value had just been set.
+    pub fn notify(&self)
+    where
+        T: 'static,
+    {
+        with_context(|ctx| {
+            let wv = ctx.increment_write_version();
+            self.inner.set_write_version(wv);
+        });
+        notify_write(self.inner.clone() as Rc<dyn AnySource>);
+    }
+
+    /// The value before the most recent change, tracked like `get()`.
+    ///
+    /// None until the first change, and always None unless the signal was
+    /// created with [`signal_with_history()`].
+    pub fn get_previous(&self) -> Option<T>
+    where
+        T: 'static,
+    {
+        track_read(self.inner.clone() as Rc<dyn AnySource>);
+        self.inner.previous()
+    }
+
+    /// Name this signal for debugging (`debug::dependency_graph()`).
+    ///
+    /// Also makes it a starting point for `dependency_graph()`.
+    pub fn set_debug_name(&self, name: impl Into<String>)
+    where
+        T: 'static,
+    {
+        crate::debug::register_source(&self.as_any_source(), Some(name.into()));
+    }
+
+    /// The name given with `set_debug_name()`, if any.
+    pub fn debug_name(&self) -> Option<String>
+    where
+        T: 'static,
+    {
+        crate::debug::debug_name_of(&self.as_any_source())
+    }
+
+    /// The live reactions reading this signal, for devtools.
+    pub fn subscribers(&self) -> Vec<crate::debug::DebugReactionInfo>
+    where
+        T: 'static,
+    {
+        crate::debug::subscribers_of(&*self.inner)
+    }
+
+    /// Wrap an existing inner source.
+    pub(crate) fn from_inner(inner: Rc<SourceInner<T>>) -> Self {
+        Self { inner }
+    }
+
     /// Get a reference to the inner source (for advanced use).
     pub fn inner(&self) -> &Rc<SourceInner<T>> {
         &self.inner
     }
 
+    /// Create a `Send` handle that posts writes to this signal from other threads.
+    ///
+    /// Writes are applied on this thread at its next `flush_sync()` / `tick()`.
+    #[cfg(feature = "sync")]
+    pub fn sender(&self) -> crate::reactivity::inbox::SignalSender<T>
+    where
+        T: 'static,
+    {
+        crate::reactivity::inbox::register_sender(Rc::downgrade(&self.inner))
+    }
+
     /// Get the inner source as a type-erased AnySource.
     ///
     /// This enables storing signals of different types in the same collection.
@@ -160,6 +330,41 @@ impl<T> Signal<T> {
     {
         self.inner.clone()
     }
+
+    /// Split into a read half and a write half.
+    ///
+    /// The halves share this signal, so APIs can hand out read-only access
+    /// that the type system enforces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::signal;
+    ///
+    /// let (count, set_count) = signal(0).split();
+    /// set_count.set(1);
+    /// set_count.update(|n| *n += 1);
+    /// assert_eq!(count.get(), 2);
+    /// ```
+    pub fn split(self) -> (ReadSignal<T>, WriteSignal<T>) {
+        let read = ReadSignal {
+            signal: Self::from_inner(self.inner.clone()),
+        };
+        (read, WriteSignal { signal: self })
+    }
+
+    /// Create a [`WeakSignal`] that doesn't keep this signal alive.
+    pub fn downgrade(&self) -> WeakSignal<T> {
+        WeakSignal {
+            inner: Rc::downgrade(&self.inner),
+        }
+    }
+}
+
+impl<T: 'static> AnySourceRef for Signal<T> {
+    fn any_source(&self) -> Rc<dyn AnySource> {
+        self.as_any_source()
+    }
 }
 
 impl<T: std::fmt::Debug> std::fmt::Debug for Signal<T>
@@ -173,6 +378,220 @@ where
     }
 }
 
+// =============================================================================
+// WEAK SIGNAL
+// =============================================================================
+
+/// A non-owning handle to a [`Signal`], created with [`Signal::downgrade()`].
+///
+/// Lets caches and registries refer to signals without keeping them alive.
+/// The signal lives as long as a `Signal` handle exists, or a derived or
+/// effect that depends on it.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::signal;
+///
+/// let count = signal(1);
+/// let weak = count.downgrade();
+/// assert_eq!(weak.upgrade().map(|s| s.get()), Some(1));
+///
+/// drop(count);
+/// assert!(weak.upgrade().is_none());
+/// ```
+pub struct WeakSignal<T> {
+    inner: Weak<SourceInner<T>>,
+}
+
+impl<T> WeakSignal<T> {
+    /// Get a `Signal` back, if it is still alive.
+    pub fn upgrade(&self) -> Option<Signal<T>> {
+        self.inner.upgrade().map(Signal::from_inner)
+    }
+
+    /// True if the signal hasn't been dropped yet.
+    pub fn is_alive(&self) -> bool {
+        self.inner.strong_count() > 0
+    }
+}
+
+impl<T> Clone for WeakSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for WeakSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeakSignal")
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+// =============================================================================
+// READ / WRITE HALVES
+// =============================================================================
+
+/// The read half of a signal, from [`Signal::split()`].
+///
+/// Has no way to write, so handing one out grants read-only access.
+pub struct ReadSignal<T> {
+    signal: Signal<T>,
+}
+
+impl<T> ReadSignal<T> {
+    /// Get the current value (cloning), tracked like [`Signal::get()`].
+    pub fn get(&self) -> T
+    where
+        T: Clone + 'static,
+    {
+        self.signal.get()
+    }
+
+    /// Try to get the current value (see [`Signal::try_get()`]).
+    pub fn try_get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.signal.try_get()
+    }
+
+    /// Access the current value with a closure, tracked.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R
+    where
+        T: 'static,
+    {
+        self.signal.with(f)
+    }
+
+    /// The value before the most recent change (see [`Signal::get_previous()`]).
+    pub fn get_previous(&self) -> Option<T>
+    where
+        T: 'static,
+    {
+        self.signal.get_previous()
+    }
+
+    /// Get the inner source as a type-erased AnySource.
+    pub fn as_any_source(&self) -> Rc<dyn AnySource>
+    where
+        T: 'static,
+    {
+        self.signal.as_any_source()
+    }
+}
+
+impl<T> Clone for ReadSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: Signal::from_inner(self.signal.inner.clone()),
+        }
+    }
+}
+
+impl<T: 'static> AnySourceRef for ReadSignal<T> {
+    fn any_source(&self) -> Rc<dyn AnySource> {
+        self.as_any_source()
+    }
+}
+
+impl<T: std::fmt::Debug + Clone + 'static> std::fmt::Debug for ReadSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadSignal")
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+/// The write half of a signal, from [`Signal::split()`].
+///
+/// Can write but not read, so a producer can't come to depend on what it
+/// writes.
+pub struct WriteSignal<T> {
+    signal: Signal<T>,
+}
+
+impl<T> WriteSignal<T> {
+    /// Set the value. Returns true if it changed (see [`Signal::set()`]).
+    pub fn set(&self, value: T) -> bool
+    where
+        T: 'static,
+    {
+        self.signal.set(value)
+    }
+
+    /// Update the value in place using a closure.
+    pub fn update(&self, f: impl FnOnce(&mut T))
+    where
+        T: Clone + 'static,
+    {
+        self.signal.update(f)
+    }
+
+    /// Replace the value without notifying anyone
+    /// (see [`Signal::set_untracked()`]).
+    pub fn set_untracked(&self, value: T)
+    where
+        T: 'static,
+    {
+        self.signal.set_untracked(value)
+    }
+
+    /// Mutate the value in place without notifying anyone.
+    pub fn update_untracked(&self, f: impl FnOnce(&mut T))
+    where
+        T: Clone + 'static,
+    {
+        self.signal.update_untracked(f)
+    }
+
+    /// Notify dependents after untracked writes (see [`Signal::notify()`]).
+    pub fn notify(&self)
+    where
+        T: 'static,
+    {
+        self.signal.notify()
+    }
+}
+
+impl<T> Clone for WriteSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: Signal::from_inner(self.signal.inner.clone()),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for WriteSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteSignal").finish_non_exhaustive()
+    }
+}
+
+/// Create a signal and return its read and write halves.
+///
+/// Shorthand for `signal(value).split()`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::split_signal;
+///
+/// let (count, set_count) = split_signal(0);
+/// set_count.set(3);
+/// assert_eq!(count.get(), 3);
+/// ```
+pub fn split_signal<T>(value: T) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: PartialEq + 'static,
+{
+    signal(value).split()
+}
+
 // =============================================================================
 // SIGNAL CREATION FUNCTIONS (TypeScript-like API)
 // =============================================================================
@@ -219,6 +638,36 @@ where
     Signal::new_with_equals(value, equals)
 }
 
+/// Create a signal that remembers its value from before the most recent
+/// change (see [`Signal::get_previous()`]).
+///
+/// Each write moves the old value into the history slot; in-place updates
+/// clone it first.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::signal_with_history;
+///
+/// let count = signal_with_history(1);
+/// assert_eq!(count.get_previous(), None);
+///
+/// count.set(2);
+/// count.update(|n| *n *= 10);
+/// assert_eq!((count.get_previous(), count.get()), (Some(2), 20));
+/// ```
+pub fn signal_with_history<T>(value: T) -> Signal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let signal = Signal {
+        inner: Rc::new(SourceInner::new(value).with_history()),
+    };
+    #[cfg(feature = "debug-deps")]
+    crate::debug::register_source(&signal.as_any_source(), None);
+    signal
+}
+
 // =============================================================================
 // SOURCE (Low-level API)
 // =============================================================================
@@ -280,8 +729,7 @@ where
 
 /// Create a signal for f64 values with safe NaN handling.
 ///
-/// Uses `safe_equals_f64` which treats NaN == NaN as true,
-/// unlike IEEE 754 where NaN != NaN.
+/// NaN == NaN is treated as true, unlike IEEE 754 where NaN != NaN.
 ///
 /// # Example
 ///
@@ -296,13 +744,129 @@ where
 /// // Setting to different value triggers update
 /// assert!(value.set(1.0)); // Returns true (changed)
 /// ```
+///
+/// Values are compared with the current thread's default float equality
+/// (`Config::float_equals`, exact unless changed), captured now.
 pub fn signal_f64(value: f64) -> Signal<f64> {
-    Signal::new_with_equals(value, crate::reactivity::equality::safe_equals_f64)
+    signal_f64_with(value, None)
 }
 
 /// Create a signal for f32 values with safe NaN handling.
+///
+/// Values are compared with the current thread's default float equality,
+/// captured now.
 pub fn signal_f32(value: f32) -> Signal<f32> {
-    Signal::new_with_equals(value, crate::reactivity::equality::safe_equals_f32)
+    signal_f32_with(value, None)
+}
+
+/// Create an f64 signal comparing values with `equals`, or the current
+/// thread's default float equality for `None`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::reactivity::equality::FloatEquals;
+/// use spark_signals::signal_f64_with;
+///
+/// let position = signal_f64_with(0.0, Some(FloatEquals::Epsilon(1e-6)));
+/// assert!(!position.set(1e-9)); // within epsilon
+/// assert!(position.set(0.5));
+/// ```
+pub fn signal_f64_with(value: f64, equals: Option<FloatEquals>) -> Signal<f64> {
+    let equals = equals.unwrap_or_else(default_float_equals);
+    let inner = match equals.epsilon() {
+        None => SourceInner::new_with_equals(value, safe_equals_f64),
+        Some(epsilon) => SourceInner::new_with_equals_closure(value, equals_epsilon(epsilon)),
+    };
+    let signal = Signal {
+        inner: Rc::new(inner),
+    };
+    #[cfg(feature = "debug-deps")]
+    crate::debug::register_source(&signal.as_any_source(), None);
+    signal
+}
+
+/// Create an f32 signal comparing values with `equals`, or the current
+/// thread's default float equality for `None`.
+pub fn signal_f32_with(value: f32, equals: Option<FloatEquals>) -> Signal<f32> {
+    let equals = equals.unwrap_or_else(default_float_equals);
+    let inner = match equals.epsilon() {
+        None => SourceInner::new_with_equals(value, safe_equals_f32),
+        Some(epsilon) => {
+            SourceInner::new_with_equals_closure(value, equals_epsilon_f32(epsilon as f32))
+        }
+    };
+    let signal = Signal {
+        inner: Rc::new(inner),
+    };
+    #[cfg(feature = "debug-deps")]
+    crate::debug::register_source(&signal.as_any_source(), None);
+    signal
+}
+
+// =============================================================================
+// SHARED SIGNALS (copy-on-write Arc<T> storage)
+// =============================================================================
+
+impl<T> Signal<Arc<T>>
+where
+    T: Clone + 'static,
+{
+    /// Update the shared value in place, cloning only if it is shared.
+    ///
+    /// Uses `Arc::make_mut`: if no reader is holding a snapshot from `get()`,
+    /// the value is mutated without any allocation. If a snapshot is alive,
+    /// the value is deep-cloned once and the snapshot keeps the old data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::signal_shared;
+    ///
+    /// let config = signal_shared(vec![1, 2, 3]);
+    /// let snapshot = config.get();
+    ///
+    /// config.update_cow(|v| v.push(4));
+    ///
+    /// assert_eq!(*snapshot, vec![1, 2, 3]);
+    /// assert_eq!(*config.get(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn update_cow(&self, f: impl FnOnce(&mut T)) {
+        self.update(|shared| f(Arc::make_mut(shared)));
+    }
+}
+
+/// Equality for shared values: same allocation, or structurally equal.
+fn shared_equals<T: PartialEq>(a: &Arc<T>, b: &Arc<T>) -> bool {
+    Arc::ptr_eq(a, b) || **a == **b
+}
+
+/// Create a signal whose value is stored behind an `Arc`.
+///
+/// `get()` hands out cheap `Arc` clones instead of deep-cloning the value,
+/// which matters for large config/state structs read by many effects.
+/// Use `update_cow()` to mutate - it only deep-clones when a reader is
+/// still holding a previous snapshot.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::signal_shared;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct Config { width: u32, title: String }
+///
+/// let config = signal_shared(Config { width: 800, title: "app".into() });
+/// assert_eq!(config.get().width, 800);
+///
+/// config.update_cow(|c| c.width = 1024);
+/// assert_eq!(config.get().width, 1024);
+/// ```
+pub fn signal_shared<T>(value: T) -> Signal<Arc<T>>
+where
+    T: PartialEq + 'static,
+{
+    Signal::new_with_equals(Arc::new(value), shared_equals::<T>)
 }
 
 // =============================================================================
@@ -320,6 +884,22 @@ mod tests {
         assert_eq!(s.get(), 42);
     }
 
+    #[test]
+    fn notify_after_untracked_write_recomputes_deriveds() {
+        let count = signal(1);
+        let doubled = crate::primitives::derived::derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        assert_eq!(doubled.get(), 2);
+
+        count.set_untracked(5);
+        assert_eq!(doubled.get(), 2);
+
+        count.notify();
+        assert_eq!(doubled.get(), 10);
+    }
+
     #[test]
     fn signal_set() {
         let s = signal(1);
@@ -457,7 +1037,7 @@ mod tests {
         s.set(String::from("world"));
         assert_eq!(s.get(), "world");
 
-        s.update(|s| s.push_str("!"));
+        s.update(|s| s.push('!'));
         assert_eq!(s.get(), "world!");
     }
 
@@ -531,4 +1111,133 @@ mod tests {
         // But NaN != regular values
         assert!(s.set(1.0)); // Changed
     }
+
+    #[test]
+    fn signal_shared_get_shares_allocation() {
+        let s = signal_shared(vec![1, 2, 3]);
+        let a = s.get();
+        let b = s.get();
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn signal_shared_update_cow_mutates_in_place_when_unique() {
+        let s = signal_shared(vec![1, 2, 3]);
+        let before = Arc::as_ptr(&s.get());
+
+        s.update_cow(|v| v[0] = 10);
+
+        let after = s.get();
+        assert_eq!(*after, vec![10, 2, 3]);
+        assert_eq!(Arc::as_ptr(&after), before);
+    }
+
+    #[test]
+    fn signal_shared_update_cow_clones_when_shared() {
+        let s = signal_shared(vec![1, 2, 3]);
+        let snapshot = s.get();
+
+        s.update_cow(|v| v.push(4));
+
+        assert_eq!(*snapshot, vec![1, 2, 3]);
+        assert_eq!(*s.get(), vec![1, 2, 3, 4]);
+        assert!(!Arc::ptr_eq(&snapshot, &s.get()));
+    }
+
+    #[test]
+    fn signal_shared_set_uses_value_equality() {
+        let s = signal_shared(String::from("a"));
+        assert!(!s.set(Arc::new(String::from("a"))));
+        assert!(s.set(Arc::new(String::from("b"))));
+    }
+
+    #[test]
+    fn signal_shared_update_cow_notifies_effects() {
+        use crate::primitives::effect::effect_sync;
+        use std::cell::Cell;
+
+        let s = signal_shared(vec![0u32; 4]);
+        let runs = Rc::new(Cell::new(0));
+        let seen = Rc::new(Cell::new(0));
+
+        let _dispose = effect_sync({
+            let s = s.clone();
+            let runs = runs.clone();
+            let seen = seen.clone();
+            move || {
+                runs.set(runs.get() + 1);
+                seen.set(s.get()[0]);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        s.update_cow(|v| v[0] = 7);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(seen.get(), 7);
+    }
+
+    #[test]
+    fn weak_signal_does_not_keep_signal_alive() {
+        let s = signal(String::from("a"));
+        let weak = s.downgrade();
+
+        // Upgraded handles write the same signal
+        weak.upgrade().unwrap().set("b".into());
+        assert_eq!(s.get(), "b");
+        assert!(weak.is_alive());
+
+        drop(s);
+        assert!(!weak.is_alive());
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn split_halves_share_one_signal() {
+        use crate::primitives::effect::effect_sync;
+        use std::cell::Cell;
+
+        let (count, set_count) = signal(1).split();
+        let seen = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let (count, seen) = (count.clone(), seen.clone());
+            move || seen.set(count.get())
+        });
+
+        assert!(set_count.set(2));
+        assert!(!set_count.clone().set(2));
+        set_count.update(|n| *n *= 10);
+        assert_eq!(seen.get(), 20);
+        assert_eq!(count.with(|n| *n + 1), 21);
+        assert!(format!("{count:?}").contains("20"));
+    }
+
+    #[test]
+    fn history_tracks_the_previous_value() {
+        use crate::primitives::effect::effect_sync;
+        use std::cell::RefCell;
+
+        let name = signal_with_history(String::from("a"));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (name, seen) = (name.clone(), seen.clone());
+            move || seen.borrow_mut().push((name.get_previous(), name.get()))
+        });
+
+        name.set("a".into()); // unchanged: history kept
+        name.set("b".into());
+        name.update(|s| s.push('!'));
+        assert_eq!(
+            *seen.borrow(),
+            [
+                (None, "a".to_string()),
+                (Some("a".to_string()), "b".to_string()),
+                (Some("b".to_string()), "b!".to_string()),
+            ]
+        );
+
+        // Without history
+        let plain = signal(1);
+        plain.set(2);
+        assert_eq!(plain.get_previous(), None);
+    }
 }
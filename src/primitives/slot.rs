@@ -15,10 +15,17 @@ use std::fmt::Debug;
 use std::rc::Rc;
 
 use crate::core::constants::*;
-use crate::core::types::{AnySource, SourceInner};
-use crate::primitives::signal::Signal;
+use crate::core::context::with_context;
+use crate::core::types::{AnyReaction, AnySource, DepList, SourceInner};
+use crate::primitives::bind::{bind_getter, ReadonlyBinding};
+use crate::primitives::derived::{derived, update_derived_chain, Derived};
+use crate::primitives::dirty::DirtyTracker;
+use crate::primitives::effect::effect_sync;
+use crate::primitives::signal::{signal, ReadSignal, Signal};
 use crate::primitives::props::PropValue;
-use crate::reactivity::tracking::{mark_reactions, notify_write, track_read};
+use crate::reactivity::batching::{batch, untrack};
+use crate::reactivity::middleware::{has_write_middleware, run_write_middleware};
+use crate::reactivity::tracking::{link_reaction, mark_reactions, notify_write, track_read};
 
 // =============================================================================
 // SOURCE TYPE CONSTANTS
@@ -27,6 +34,42 @@ use crate::reactivity::tracking::{mark_reactions, notify_write, track_read};
 const SOURCE_STATIC: u8 = 0; // Holds a static value
 const SOURCE_SIGNAL: u8 = 1; // Points to a signal/source
 const SOURCE_GETTER: u8 = 2; // Points to a getter function
+const SOURCE_CHAIN: u8 = 3; // Points to another slot, a derived or a read-only binding
+
+// =============================================================================
+// CHAINED SOURCES
+// =============================================================================
+
+/// What a chained slot points to.
+#[derive(Clone)]
+enum Chain<T: Clone + PartialEq + 'static> {
+    /// Another slot: reads and writes go through to it
+    Slot(Slot<T>),
+    /// A derived (read-only)
+    Derived(Derived<T>),
+    /// A read-only binding
+    Readonly(ReadonlyBinding<T>),
+}
+
+impl<T: Clone + PartialEq + 'static> Chain<T> {
+    /// Read the target, tracking it
+    fn read(&self) -> Option<T> {
+        match self {
+            Chain::Slot(slot) => slot.get(),
+            Chain::Derived(derived) => Some(derived.get()),
+            Chain::Readonly(binding) => Some(binding.get()),
+        }
+    }
+
+    /// Write through to the target, if it is writable
+    fn write(&self, value: T, untracked: bool) -> Result<(), SlotWriteError> {
+        match self {
+            Chain::Slot(slot) if untracked => slot.set_untracked(value),
+            Chain::Slot(slot) => slot.set(value),
+            Chain::Derived(_) | Chain::Readonly(_) => Err(SlotWriteError::ReadOnlySource),
+        }
+    }
+}
 
 // =============================================================================
 // SLOT INNER
@@ -48,7 +91,10 @@ struct SlotInner<T: Clone + PartialEq + 'static> {
     signal_ref: RefCell<Option<Signal<T>>>,
 
     /// Getter function
-    getter: RefCell<Option<Box<dyn Fn() -> T>>>,
+    getter: RefCell<Option<Rc<dyn Fn() -> T>>>,
+
+    /// Chained slot, derived or read-only binding
+    chain: RefCell<Option<Chain<T>>>,
 }
 
 impl<T: Clone + PartialEq + 'static> SlotInner<T> {
@@ -59,6 +105,7 @@ impl<T: Clone + PartialEq + 'static> SlotInner<T> {
             source_type: Cell::new(SOURCE_STATIC),
             signal_ref: RefCell::new(None),
             getter: RefCell::new(None),
+            chain: RefCell::new(None),
         }
     }
 
@@ -86,6 +133,13 @@ impl<T: Clone + PartialEq + 'static> SlotInner<T> {
                     self.source.get()
                 }
             }
+            SOURCE_CHAIN => {
+                // The target tracks itself (a slot: its version and its source)
+                match *self.chain.borrow() {
+                    Some(ref chain) => chain.read(),
+                    None => self.source.get(),
+                }
+            }
             _ => self.source.get(),
         }
     }
@@ -109,48 +163,144 @@ impl<T: Clone + PartialEq + 'static> SlotInner<T> {
                     self.source.get()
                 }
             }
+            SOURCE_CHAIN => match *self.chain.borrow() {
+                Some(ref chain) => untrack(|| chain.read()),
+                None => self.source.get(),
+            },
             _ => self.source.get(),
         }
     }
 
-    /// Set a static value as the source
-    fn set_static(&self, value: T) {
+    /// Set a static value as the source.
+    ///
+    /// Dependents are only notified if the slot's value actually changes
+    /// (unless `force` is set).
+    fn set_static(&self, value: T, force: bool) {
+        let changed = force || untrack(|| self.peek()).as_ref() != Some(&value);
+        let old_sources = if changed { DepList::new() } else { self.bound_sources() };
+
         self.source_type.set(SOURCE_STATIC);
         *self.signal_ref.borrow_mut() = None;
         *self.getter.borrow_mut() = None;
+        *self.chain.borrow_mut() = None;
         self.source.set(Some(value));
 
-        // Notify dependents
-        notify_write(self.source.clone() as Rc<dyn AnySource>);
+        if changed {
+            self.notify_value_changed();
+        } else {
+            self.rebind_readers(&old_sources, &[]);
+        }
     }
 
-    /// Set a signal as the source
+    /// Set a signal as the source.
+    ///
+    /// If the signal's value equals the slot's current value, dependents are
+    /// not re-run; instead they are subscribed to the new signal directly so
+    /// its future changes still reach them.
     fn set_signal(&self, signal: Signal<T>) {
+        let same_signal = self.source_type.get() == SOURCE_SIGNAL
+            && matches!(*self.signal_ref.borrow(), Some(ref s) if Rc::ptr_eq(s.inner(), signal.inner()));
+        if same_signal {
+            return;
+        }
+
+        let old = untrack(|| self.peek());
+        let new = signal.inner().get();
+        let changed = old.as_ref() != Some(&new);
+        let old_sources = if changed { DepList::new() } else { self.bound_sources() };
+        let signal_source = signal.as_any_source();
+
         self.source_type.set(SOURCE_SIGNAL);
         *self.signal_ref.borrow_mut() = Some(signal);
         *self.getter.borrow_mut() = None;
+        *self.chain.borrow_mut() = None;
 
-        // Notify dependents that source changed
-        self.notify_source_changed();
+        if changed {
+            self.notify_source_changed();
+        } else {
+            self.rebind_readers(&old_sources, &[signal_source]);
+        }
     }
 
-    /// Set a getter function as the source
-    fn set_getter(&self, getter: Box<dyn Fn() -> T>) {
+    /// Set a getter function as the source.
+    ///
+    /// While nothing reads the slot, the getter first runs on the next read.
+    /// Otherwise it runs once up front (untracked) to learn its value and
+    /// the sources it reads: if the value is unchanged, dependents are moved
+    /// over to those sources instead of being re-run.
+    fn set_getter(&self, getter: Rc<dyn Fn() -> T>) {
+        if self.readers().is_empty() {
+            self.source_type.set(SOURCE_GETTER);
+            *self.signal_ref.borrow_mut() = None;
+            *self.getter.borrow_mut() = Some(getter);
+            *self.chain.borrow_mut() = None;
+            self.notify_source_changed();
+            return;
+        }
+
+        let old = untrack(|| self.peek());
+        let (new, sources) = {
+            let getter = getter.clone();
+            Self::probe(move || Some(getter()))
+        };
+        let old_sources = if old != new { DepList::new() } else { self.bound_sources() };
+
         self.source_type.set(SOURCE_GETTER);
         *self.signal_ref.borrow_mut() = None;
         *self.getter.borrow_mut() = Some(getter);
+        *self.chain.borrow_mut() = None;
+
+        if old != new {
+            self.notify_source_changed();
+        } else {
+            self.rebind_readers(&old_sources, &sources);
+        }
+    }
+
+    /// Point the slot at another slot, a derived or a read-only binding.
+    ///
+    /// Like `set_getter()`: dependents re-run only if the value differs,
+    /// otherwise they are subscribed to what the target reads.
+    fn set_chain(&self, chain: Chain<T>) {
+        let old = untrack(|| self.peek());
+        let (new, sources) = {
+            let chain = chain.clone();
+            Self::probe(move || chain.read())
+        };
+        let old_sources = if old != new { DepList::new() } else { self.bound_sources() };
+
+        self.source_type.set(SOURCE_CHAIN);
+        *self.signal_ref.borrow_mut() = None;
+        *self.getter.borrow_mut() = None;
+        *self.chain.borrow_mut() = Some(chain);
+
+        if old != new {
+            self.notify_source_changed();
+        } else {
+            self.rebind_readers(&old_sources, &sources);
+        }
+    }
 
-        // Notify dependents that source changed
-        self.notify_source_changed();
+    /// Run `read` once, untracked, returning its value and the sources it read.
+    fn probe(read: impl Fn() -> Option<T> + 'static) -> (Option<T>, DepList) {
+        let probe = derived(read);
+        update_derived_chain(probe.as_any_source());
+        let value = probe.inner().get_value();
+        let mut sources = DepList::new();
+        probe.as_any_reaction().for_each_dep(&mut |dep| {
+            sources.push(dep.clone());
+            true
+        });
+        (value, sources)
     }
 
     /// Write a value (writes through if pointing to writable source)
-    fn set(&self, value: T) -> Result<(), SlotWriteError> {
+    fn set(&self, mut value: T) -> Result<(), SlotWriteError> {
         match self.source_type.get() {
             SOURCE_STATIC => {
                 // Update static value
-                if self.source.set(Some(value)) {
-                    notify_write(self.source.clone() as Rc<dyn AnySource>);
+                if self.pass_middleware(&mut value) && self.source.set(Some(value)) {
+                    self.notify_value_changed();
                 }
                 Ok(())
             }
@@ -167,25 +317,216 @@ impl<T: Clone + PartialEq + 'static> SlotInner<T> {
                 // Can't write to getter
                 Err(SlotWriteError::ReadOnlyGetter)
             }
+            SOURCE_CHAIN => match *self.chain.borrow() {
+                Some(ref chain) => chain.write(value, false),
+                None => Err(SlotWriteError::NoSource),
+            },
             _ => Err(SlotWriteError::NoSource),
         }
     }
 
+    /// Write without notifying dependents.
+    fn set_untracked(&self, mut value: T) -> Result<(), SlotWriteError> {
+        match self.source_type.get() {
+            SOURCE_STATIC => {
+                if self.pass_middleware(&mut value) {
+                    self.source.set_untracked(Some(value));
+                }
+                Ok(())
+            }
+            SOURCE_SIGNAL => match *self.signal_ref.borrow() {
+                Some(ref sig) => {
+                    sig.set_untracked(value);
+                    Ok(())
+                }
+                None => Err(SlotWriteError::NoSource),
+            },
+            SOURCE_GETTER => Err(SlotWriteError::ReadOnlyGetter),
+            SOURCE_CHAIN => match *self.chain.borrow() {
+                Some(ref chain) => chain.write(value, true),
+                None => Err(SlotWriteError::NoSource),
+            },
+            _ => Err(SlotWriteError::NoSource),
+        }
+    }
+
+    /// Mutate the value in place without notifying dependents.
+    fn update_untracked(&self, f: impl FnOnce(&mut T)) -> Result<(), SlotWriteError> {
+        match self.source_type.get() {
+            SOURCE_STATIC => {
+                if has_write_middleware(&self.source) {
+                    // Middleware needs the old and new values side by side
+                    let Some(mut value) = self.source.get() else {
+                        return Err(SlotWriteError::NoSource);
+                    };
+                    f(&mut value);
+                    if self.pass_middleware(&mut value) {
+                        self.source.set_untracked(Some(value));
+                    }
+                    return Ok(());
+                }
+                if self.source.with(Option::is_none) {
+                    return Err(SlotWriteError::NoSource);
+                }
+                self.source.update_untracked(|value| {
+                    if let Some(value) = value {
+                        f(value);
+                    }
+                });
+                Ok(())
+            }
+            SOURCE_SIGNAL => match *self.signal_ref.borrow() {
+                Some(ref sig) => {
+                    sig.update_untracked(f);
+                    Ok(())
+                }
+                None => Err(SlotWriteError::NoSource),
+            },
+            SOURCE_GETTER => Err(SlotWriteError::ReadOnlyGetter),
+            SOURCE_CHAIN => match *self.chain.borrow() {
+                Some(Chain::Slot(ref slot)) => slot.update_untracked(f),
+                Some(_) => Err(SlotWriteError::ReadOnlySource),
+                None => Err(SlotWriteError::NoSource),
+            },
+            _ => Err(SlotWriteError::NoSource),
+        }
+    }
+
+    /// Run write middleware over a proposed static value. Returns false if
+    /// it was vetoed.
+    fn pass_middleware(&self, value: &mut T) -> bool {
+        if !has_write_middleware(&self.source) {
+            return true;
+        }
+        let old = self.source.get();
+        let source = self.source.clone() as Rc<dyn AnySource>;
+        run_write_middleware(&source, old.as_ref(), value)
+    }
+
+    /// Notify dependents of whatever the slot writes to, after untracked writes.
+    fn notify(&self) {
+        match self.source_type.get() {
+            SOURCE_STATIC => self.notify_value_changed(),
+            SOURCE_SIGNAL => {
+                let signal = self.signal_ref.borrow().clone();
+                if let Some(signal) = signal {
+                    signal.notify();
+                }
+            }
+            SOURCE_CHAIN => {
+                let target = match *self.chain.borrow() {
+                    Some(Chain::Slot(ref slot)) => Some(slot.clone()),
+                    _ => None,
+                };
+                if let Some(target) = target {
+                    target.notify();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Notify dependents that the slot's static value changed
+    fn notify_value_changed(&self) {
+        with_context(|ctx| {
+            let wv = ctx.increment_write_version();
+            self.source.set_write_version(wv);
+        });
+        notify_write(self.source.clone() as Rc<dyn AnySource>);
+    }
+
     /// Notify dependents that the slot's source reference changed
     fn notify_source_changed(&self) {
         // Increment write version and mark reactions dirty
-        let new_version = self.source.write_version() + 1;
-        self.source.set_write_version(new_version);
+        with_context(|ctx| {
+            let wv = ctx.increment_write_version();
+            self.source.set_write_version(wv);
+        });
         mark_reactions(self.source.clone() as Rc<dyn AnySource>, DIRTY);
     }
 
+    /// Reactions currently reading the slot.
+    fn readers(&self) -> Vec<Rc<dyn AnyReaction>> {
+        let mut readers: Vec<Rc<dyn AnyReaction>> = Vec::new();
+        self.source.for_each_reaction(&mut |reaction| {
+            readers.push(reaction);
+            true
+        });
+        readers
+    }
+
+    /// The sources a read of the slot tracks through its current binding,
+    /// besides the slot itself. Empty while nothing reads the slot.
+    fn bound_sources(&self) -> DepList {
+        if self.readers().is_empty() {
+            return DepList::new();
+        }
+        match self.source_type.get() {
+            SOURCE_SIGNAL => self.signal_ref.borrow().iter().map(Signal::as_any_source).collect(),
+            SOURCE_GETTER => match self.getter.borrow().clone() {
+                Some(getter) => Self::probe(move || Some(getter())).1,
+                None => DepList::new(),
+            },
+            SOURCE_CHAIN => match self.chain.borrow().clone() {
+                Some(chain) => Self::probe(move || chain.read()).1,
+                None => DepList::new(),
+            },
+            _ => DepList::new(),
+        }
+    }
+
+    /// Move the slot's current readers from the sources of the old binding
+    /// to those of the new one, without re-running them.
+    ///
+    /// Used when a source switch leaves the value unchanged: readers would
+    /// otherwise keep waking up for the old source, and only discover the
+    /// new one on their next run.
+    fn rebind_readers(&self, old: &[Rc<dyn AnySource>], sources: &[Rc<dyn AnySource>]) {
+        fn key(source: &Rc<dyn AnySource>) -> *const () {
+            Rc::as_ptr(source) as *const ()
+        }
+
+        for reader in self.readers() {
+            if reader.is_destroyed() {
+                continue;
+            }
+            for source in old {
+                if !sources.iter().any(|new| key(new) == key(source)) {
+                    reader.remove_source(source);
+                    source.remove_reaction(&reader);
+                }
+            }
+            for source in sources {
+                let source_ptr = key(source);
+                let mut already = false;
+                reader.for_each_dep(&mut |dep| {
+                    already = Rc::as_ptr(dep) as *const () == source_ptr;
+                    !already
+                });
+                if !already {
+                    reader.add_dep(source.clone());
+                    link_reaction(source, Rc::downgrade(&reader));
+                }
+            }
+        }
+    }
+
     /// Clear the slot
     fn clear(&self) {
+        let changed = untrack(|| self.peek()).is_some();
+        let old_sources = if changed { DepList::new() } else { self.bound_sources() };
+
         self.source_type.set(SOURCE_STATIC);
         *self.signal_ref.borrow_mut() = None;
         *self.getter.borrow_mut() = None;
+        *self.chain.borrow_mut() = None;
         self.source.set(None);
-        notify_write(self.source.clone() as Rc<dyn AnySource>);
+
+        if changed {
+            self.notify_value_changed();
+        } else {
+            self.rebind_readers(&old_sources, &[]);
+        }
     }
 }
 
@@ -198,6 +539,8 @@ impl<T: Clone + PartialEq + 'static> SlotInner<T> {
 pub enum SlotWriteError {
     /// Slot is pointing to a getter function (read-only)
     ReadOnlyGetter,
+    /// Slot is pointing to a derived or read-only binding
+    ReadOnlySource,
     /// Slot has no source configured
     NoSource,
 }
@@ -208,6 +551,9 @@ impl std::fmt::Display for SlotWriteError {
             SlotWriteError::ReadOnlyGetter => {
                 write!(f, "Cannot write to a slot pointing to a getter function")
             }
+            SlotWriteError::ReadOnlySource => {
+                write!(f, "Cannot write to a slot pointing to a read-only source")
+            }
             SlotWriteError::NoSource => write!(f, "Slot has no source configured"),
         }
     }
@@ -272,14 +618,23 @@ impl<T: Clone + PartialEq + 'static> Slot<T> {
     /// Set a static value as the slot's source.
     ///
     /// This clears any signal or getter reference and stores the value directly.
+    /// Dependents are only notified if the slot's value actually changes.
     pub fn set_value(&self, value: T) {
-        self.inner.set_static(value);
+        self.inner.set_static(value, false);
+    }
+
+    /// Set a static value and always notify dependents, even if it is equal
+    /// to the current value.
+    pub fn force_set_value(&self, value: T) {
+        self.inner.set_static(value, true);
     }
 
     /// Point the slot to a signal.
     ///
     /// Reading the slot will read through to the signal.
     /// Writing to the slot will write through to the signal.
+    /// Dependents only re-run if the signal's value differs from the slot's
+    /// current value.
     pub fn set_signal(&self, signal: &Signal<T>) {
         self.inner.set_signal(signal.clone());
     }
@@ -288,8 +643,77 @@ impl<T: Clone + PartialEq + 'static> Slot<T> {
     ///
     /// Reading the slot will call the getter (with dependency tracking inside).
     /// Writing to the slot will fail with `SlotWriteError::ReadOnlyGetter`.
+    /// While the slot has readers, the getter runs once immediately and they
+    /// only re-run if its value differs from the slot's current value;
+    /// otherwise it first runs on the next read.
     pub fn set_getter<F: Fn() -> T + 'static>(&self, getter: F) {
-        self.inner.set_getter(Box::new(getter));
+        self.inner.set_getter(Rc::new(getter));
+    }
+
+    /// Point the slot to a derived.
+    ///
+    /// Reading the slot reads the derived (tracking both); writing fails
+    /// with `SlotWriteError::ReadOnlySource`. Dependents only re-run if the
+    /// derived's value differs from the slot's current value.
+    pub fn set_derived(&self, derived: &Derived<T>) {
+        self.inner.set_chain(Chain::Derived(derived.clone()));
+    }
+
+    /// Point the slot to another slot.
+    ///
+    /// Reads follow the target wherever it points, now or later; writes go
+    /// through to it (and on to its signal, if it has one). Dependents only
+    /// re-run if the target's value differs from the slot's current value.
+    ///
+    /// # Panics
+    ///
+    /// If the target is this slot or chains back to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{derived, signal, slot, SlotWriteError};
+    ///
+    /// let theme = slot(Some(1));
+    /// let button = slot(None);
+    /// button.set_slot(&theme);
+    ///
+    /// // Re-pointing the target is seen through the chain
+    /// let accent = signal(7);
+    /// theme.set_signal(&accent);
+    /// assert_eq!(button.get(), Some(7));
+    ///
+    /// // Writes go through both slots to the signal
+    /// button.set(8).unwrap();
+    /// assert_eq!(accent.get(), 8);
+    ///
+    /// // Read-only targets refuse writes
+    /// let doubled = derived(move || accent.get() * 2);
+    /// theme.set_derived(&doubled);
+    /// assert_eq!(button.get(), Some(16));
+    /// assert_eq!(button.set(1), Err(SlotWriteError::ReadOnlySource));
+    /// ```
+    pub fn set_slot(&self, target: &Slot<T>) {
+        let mut next = Some(target.clone());
+        while let Some(slot) = next {
+            assert!(
+                !Rc::ptr_eq(&slot.inner, &self.inner),
+                "slot chain would form a cycle"
+            );
+            next = match *slot.inner.chain.borrow() {
+                Some(Chain::Slot(ref further)) => Some(further.clone()),
+                _ => None,
+            };
+        }
+        self.inner.set_chain(Chain::Slot(target.clone()));
+    }
+
+    /// Point the slot to a read-only binding.
+    ///
+    /// Like `set_derived()`: reads go through, writes fail with
+    /// `SlotWriteError::ReadOnlySource`.
+    pub fn set_readonly_binding(&self, binding: &ReadonlyBinding<T>) {
+        self.inner.set_chain(Chain::Readonly(binding.clone()));
     }
 
     /// Bind a PropValue to the slot.
@@ -300,7 +724,7 @@ impl<T: Clone + PartialEq + 'static> Slot<T> {
         match prop {
             PropValue::Static(v) => self.set_value(v),
             PropValue::Signal(s) => self.set_signal(&s),
-            PropValue::Getter(g) => self.set_getter(move || g()),
+            PropValue::Getter(g) => self.set_getter(g),
         }
     }
 
@@ -313,6 +737,31 @@ impl<T: Clone + PartialEq + 'static> Slot<T> {
         self.inner.set(value)
     }
 
+    /// Write like `set()`, without bumping versions or notifying dependents.
+    ///
+    /// A signal source is written with `Signal::set_untracked()`. Call
+    /// `notify()` afterwards to propagate.
+    pub fn set_untracked(&self, value: T) -> Result<(), SlotWriteError> {
+        self.inner.set_untracked(value)
+    }
+
+    /// Mutate the value in place without bumping versions or notifying
+    /// dependents (see `set_untracked()`).
+    ///
+    /// An empty slot has nothing to mutate: `Err(SlotWriteError::NoSource)`.
+    pub fn update_untracked(&self, f: impl FnOnce(&mut T)) -> Result<(), SlotWriteError> {
+        self.inner.update_untracked(f)
+    }
+
+    /// Notify dependents after untracked writes.
+    ///
+    /// Propagates from whatever the slot writes to: its static value, the
+    /// signal it points to, or the slot it is chained to. Does nothing for
+    /// getters and read-only sources.
+    pub fn notify(&self) {
+        self.inner.notify();
+    }
+
     /// Clear the slot (reset to None for static value).
     pub fn clear(&self) {
         self.inner.clear();
@@ -332,6 +781,34 @@ impl<T: Clone + PartialEq + 'static> Slot<T> {
     pub fn is_static(&self) -> bool {
         self.inner.source_type.get() == SOURCE_STATIC
     }
+
+    /// Check if the slot is pointing to another slot, a derived or a
+    /// read-only binding
+    pub fn is_chained(&self) -> bool {
+        self.inner.source_type.get() == SOURCE_CHAIN
+    }
+
+    /// A read-only view of the slot.
+    ///
+    /// Reads follow the slot wherever it is pointed later; the view itself
+    /// can't write to or re-point it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{signal, slot};
+    ///
+    /// let width = slot(Some(10));
+    /// let exposed = width.readonly();
+    ///
+    /// let theme_width = signal(40);
+    /// width.set_signal(&theme_width);
+    /// assert_eq!(exposed.get(), Some(40));
+    /// ```
+    pub fn readonly(&self) -> ReadonlyBinding<Option<T>> {
+        let this = self.clone();
+        bind_getter(move || this.get())
+    }
 }
 
 impl<T: Clone + PartialEq + 'static> Clone for Slot<T> {
@@ -445,6 +922,12 @@ impl<T: Clone + PartialEq + 'static> TrackedSlot<T> {
         self.dirty.borrow_mut().insert(self.id);
     }
 
+    /// Set a static value, always notifying dependents (marks id as dirty).
+    pub fn force_set_value(&self, value: T) {
+        self.inner.force_set_value(value);
+        self.dirty.borrow_mut().insert(self.id);
+    }
+
     /// Point to a signal (marks id as dirty).
     pub fn set_signal(&self, signal: &Signal<T>) {
         self.inner.set_signal(signal);
@@ -457,6 +940,24 @@ impl<T: Clone + PartialEq + 'static> TrackedSlot<T> {
         self.dirty.borrow_mut().insert(self.id);
     }
 
+    /// Point to a derived (marks id as dirty).
+    pub fn set_derived(&self, derived: &Derived<T>) {
+        self.inner.set_derived(derived);
+        self.dirty.borrow_mut().insert(self.id);
+    }
+
+    /// Point to another slot (marks id as dirty).
+    pub fn set_slot(&self, target: &Slot<T>) {
+        self.inner.set_slot(target);
+        self.dirty.borrow_mut().insert(self.id);
+    }
+
+    /// Point to a read-only binding (marks id as dirty).
+    pub fn set_readonly_binding(&self, binding: &ReadonlyBinding<T>) {
+        self.inner.set_readonly_binding(binding);
+        self.dirty.borrow_mut().insert(self.id);
+    }
+
     /// Bind a PropValue (marks id as dirty).
     pub fn bind(&self, prop: PropValue<T>) {
         self.inner.bind(prop);
@@ -477,6 +978,11 @@ impl<T: Clone + PartialEq + 'static> TrackedSlot<T> {
         self.inner.clear();
         self.dirty.borrow_mut().insert(self.id);
     }
+
+    /// A read-only view of the slot (see `Slot::readonly()`).
+    pub fn readonly(&self) -> ReadonlyBinding<Option<T>> {
+        self.inner.readonly()
+    }
 }
 
 impl<T: Clone + PartialEq + 'static> Clone for TrackedSlot<T> {
@@ -584,6 +1090,12 @@ impl<T: Clone + PartialEq + 'static> SlotArray<T> {
         self.slots.borrow()[index].set_value(value);
     }
 
+    /// Set a static value at index, always notifying dependents
+    pub fn force_set_value(&self, index: usize, value: T) {
+        self.ensure_capacity(index + 1);
+        self.slots.borrow()[index].force_set_value(value);
+    }
+
     /// Point slot at index to a signal
     pub fn set_signal(&self, index: usize, signal: &Signal<T>) {
         self.ensure_capacity(index + 1);
@@ -602,6 +1114,30 @@ impl<T: Clone + PartialEq + 'static> SlotArray<T> {
         self.slots.borrow()[index].set(value)
     }
 
+    /// Write through to slot at index without notifying dependents
+    pub fn set_untracked(&self, index: usize, value: T) -> Result<(), SlotWriteError> {
+        self.ensure_capacity(index + 1);
+        self.slots.borrow()[index].set_untracked(value)
+    }
+
+    /// Mutate the slot at index in place without notifying dependents
+    pub fn update_untracked(
+        &self,
+        index: usize,
+        f: impl FnOnce(&mut T),
+    ) -> Result<(), SlotWriteError> {
+        self.ensure_capacity(index + 1);
+        let slot = self.slots.borrow()[index].clone();
+        slot.update_untracked(f)
+    }
+
+    /// Notify dependents of the slot at index after untracked writes
+    pub fn notify(&self, index: usize) {
+        self.ensure_capacity(index + 1);
+        let slot = self.slots.borrow()[index].clone();
+        slot.notify();
+    }
+
     /// Get the raw slot at index
     pub fn slot(&self, index: usize) -> Slot<T> {
         self.ensure_capacity(index + 1);
@@ -630,6 +1166,53 @@ impl<T: Clone + PartialEq + 'static> SlotArray<T> {
         self.ensure_capacity(index + 1);
         self.slots.borrow()[index].bind(prop);
     }
+
+    /// Bind `props[i]` to slot `i` for every prop, in one batch.
+    ///
+    /// Dependents re-run once for the whole call, not once per slot. Slots
+    /// past the end of `props` are left as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{signal, slot_array, PropValue};
+    ///
+    /// let width = signal(100);
+    /// let sizes = slot_array::<i32>(None);
+    /// sizes.bind_all(vec![PropValue::Static(10), PropValue::Signal(width.clone())]);
+    ///
+    /// assert_eq!(sizes.snapshot(), [Some(10), Some(100)]);
+    /// width.set(120);
+    /// assert_eq!(sizes.get(1), Some(120));
+    /// ```
+    pub fn bind_all(&self, props: Vec<PropValue<T>>) {
+        self.ensure_capacity(props.len());
+        batch(|| {
+            let slots = self.slots.borrow();
+            for (slot, prop) in slots.iter().zip(props) {
+                slot.bind(prop);
+            }
+        });
+    }
+
+    /// Set static values `values[i]` on slot `i`, in one batch.
+    ///
+    /// Like `bind_all()` with static props.
+    pub fn set_values(&self, values: &[T]) {
+        self.ensure_capacity(values.len());
+        batch(|| {
+            let slots = self.slots.borrow();
+            for (slot, value) in slots.iter().zip(values) {
+                slot.set_value(value.clone());
+            }
+        });
+    }
+
+    /// Current value of every slot, without tracking.
+    pub fn snapshot(&self) -> Vec<Option<T>> {
+        let slots = self.slots.borrow();
+        slots.iter().map(Slot::peek).collect()
+    }
 }
 
 impl<T: Clone + PartialEq + Debug + 'static> Debug for SlotArray<T> {
@@ -678,14 +1261,19 @@ pub fn slot_array<T: Clone + PartialEq + 'static>(default_value: Option<T>) -> S
 // TRACKED SLOT ARRAY
 // =============================================================================
 
-use std::collections::HashSet;
-
 /// Shared dirty set for TrackedSlotArray - uses interior mutability for sharing.
-pub type DirtySet = Rc<RefCell<HashSet<usize>>>;
+pub type DirtySet = Rc<RefCell<DirtyTracker>>;
 
-/// Create a new shared dirty set.
+/// Create a new shared dirty set (hash-set backed).
 pub fn dirty_set() -> DirtySet {
-    Rc::new(RefCell::new(HashSet::new()))
+    Rc::new(RefCell::new(DirtyTracker::new()))
+}
+
+/// Create a new shared dirty set backed by a bitset sized for `0..len`.
+///
+/// Prefer this for slot arrays, whose indices are packed from zero.
+pub fn dirty_set_dense(len: usize) -> DirtySet {
+    Rc::new(RefCell::new(DirtyTracker::dense(len)))
 }
 
 /// A SlotArray that automatically tracks which indices have been modified.
@@ -716,6 +1304,8 @@ pub fn dirty_set() -> DirtySet {
 pub struct TrackedSlotArray<T: Clone + PartialEq + 'static> {
     inner: SlotArray<T>,
     dirty: DirtySet,
+    /// Bumped by every marking write
+    dirty_version: Signal<u64>,
 }
 
 impl<T: Clone + PartialEq + 'static> TrackedSlotArray<T> {
@@ -747,26 +1337,32 @@ impl<T: Clone + PartialEq + 'static> TrackedSlotArray<T> {
     /// Set a static value at index (marks index as dirty)
     pub fn set_value(&self, index: usize, value: T) {
         self.inner.set_value(index, value);
-        self.dirty.borrow_mut().insert(index);
+        self.mark([index]);
+    }
+
+    /// Set a static value at index, always notifying (marks index as dirty)
+    pub fn force_set_value(&self, index: usize, value: T) {
+        self.inner.force_set_value(index, value);
+        self.mark([index]);
     }
 
     /// Point slot at index to a signal (marks index as dirty)
     pub fn set_signal(&self, index: usize, signal: &Signal<T>) {
         self.inner.set_signal(index, signal);
-        self.dirty.borrow_mut().insert(index);
+        self.mark([index]);
     }
 
     /// Point slot at index to a getter (marks index as dirty)
     pub fn set_getter<F: Fn() -> T + 'static>(&self, index: usize, getter: F) {
         self.inner.set_getter(index, getter);
-        self.dirty.borrow_mut().insert(index);
+        self.mark([index]);
     }
 
     /// Write through to slot at index (marks index as dirty)
     pub fn set(&self, index: usize, value: T) -> Result<(), SlotWriteError> {
         let result = self.inner.set(index, value);
         if result.is_ok() {
-            self.dirty.borrow_mut().insert(index);
+            self.mark([index]);
         }
         result
     }
@@ -781,7 +1377,7 @@ impl<T: Clone + PartialEq + 'static> TrackedSlotArray<T> {
         let was_present = index < self.len();
         self.inner.clear(index);
         if was_present {
-            self.dirty.borrow_mut().insert(index);
+            self.mark([index]);
         }
     }
 
@@ -793,7 +1389,26 @@ impl<T: Clone + PartialEq + 'static> TrackedSlotArray<T> {
     /// Bind a PropValue to the slot at the given index (marks index as dirty).
     pub fn bind(&self, index: usize, prop: PropValue<T>) {
         self.inner.bind(index, prop);
-        self.dirty.borrow_mut().insert(index);
+        self.mark([index]);
+    }
+
+    /// Bind `props[i]` to slot `i` in one batch (marks each index as dirty).
+    pub fn bind_all(&self, props: Vec<PropValue<T>>) {
+        let count = props.len();
+        self.inner.bind_all(props);
+        self.mark(0..count);
+    }
+
+    /// Set static values on slots `0..values.len()` in one batch (marks each
+    /// index as dirty).
+    pub fn set_values(&self, values: &[T]) {
+        self.inner.set_values(values);
+        self.mark(0..values.len());
+    }
+
+    /// Current value of every slot, without tracking.
+    pub fn snapshot(&self) -> Vec<Option<T>> {
+        self.inner.snapshot()
     }
 
     /// Get the dirty set for manual inspection/clearing
@@ -801,6 +1416,62 @@ impl<T: Clone + PartialEq + 'static> TrackedSlotArray<T> {
         &self.dirty
     }
 
+    /// A signal bumped once per marking write (once for `bind_all()` and
+    /// `set_values()`), for effects that react to this array getting dirty.
+    ///
+    /// Only writes through this array bump it, not other users of a shared
+    /// dirty set.
+    pub fn dirty_signal(&self) -> ReadSignal<u64> {
+        self.dirty_version.clone().split().0
+    }
+
+    /// Run `callback` with the dirty set after writes mark indices, as an
+    /// effect: writes inside one batch run it once. Also runs at creation if
+    /// indices are already marked. Returns the effect's dispose function.
+    ///
+    /// The callback runs untracked and usually drains the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{batch, dirty_set, tracked_slot_array};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let sizes = tracked_slot_array::<u32>(Some(0), dirty_set());
+    /// let passes = Rc::new(RefCell::new(Vec::new()));
+    /// let _layout = sizes.on_dirty({
+    ///     let passes = passes.clone();
+    ///     move |dirty| passes.borrow_mut().push(dirty.borrow_mut().drain().count())
+    /// });
+    ///
+    /// batch(|| {
+    ///     sizes.set_value(0, 10);
+    ///     sizes.set_value(4, 20);
+    /// });
+    /// sizes.set_value(4, 30);
+    /// assert_eq!(*passes.borrow(), [2, 1]);
+    /// ```
+    pub fn on_dirty<F>(&self, mut callback: F) -> impl FnOnce()
+    where
+        F: FnMut(&DirtySet) + 'static,
+    {
+        let version = self.dirty_version.clone();
+        let dirty = self.dirty.clone();
+        effect_sync(move || {
+            version.get();
+            if !dirty.borrow().is_empty() {
+                untrack(|| callback(&dirty));
+            }
+        })
+    }
+
+    /// Mark indices dirty and bump the dirty signal once
+    fn mark(&self, indices: impl IntoIterator<Item = usize>) {
+        self.dirty.borrow_mut().extend(indices);
+        self.dirty_version.update(|version| *version += 1);
+    }
+
     /// Get the inner SlotArray (for advanced use)
     pub fn inner(&self) -> &SlotArray<T> {
         &self.inner
@@ -856,6 +1527,7 @@ pub fn tracked_slot_array<T: Clone + PartialEq + 'static>(
     TrackedSlotArray {
         inner: slot_array(default_value),
         dirty,
+        dirty_version: signal(0),
     }
 }
 
@@ -1312,8 +1984,364 @@ mod tests {
         arr.set_value(0, 20);
         arr.set_value(0, 30);
 
-        // HashSet deduplicates
+        // The tracker deduplicates
         assert_eq!(dirty.borrow().len(), 1);
         assert!(dirty.borrow().contains(&0));
     }
+
+    // =========================================================================
+    // EQUALITY-AWARE NOTIFICATION
+    // =========================================================================
+
+    fn count_runs<T: Clone + PartialEq + 'static>(s: &Slot<T>) -> (Rc<Cell<u32>>, impl FnOnce()) {
+        let runs = Rc::new(Cell::new(0));
+        let dispose = effect_sync({
+            let s = s.clone();
+            let runs = runs.clone();
+            move || {
+                let _ = s.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        (runs, dispose)
+    }
+
+    #[test]
+    fn slot_set_value_same_value_does_not_notify() {
+        let s = slot(Some(5));
+        let (runs, _dispose) = count_runs(&s);
+        assert_eq!(runs.get(), 1);
+
+        s.set_value(5);
+        assert_eq!(runs.get(), 1);
+
+        s.set_value(6);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn slot_force_set_value_always_notifies() {
+        let s = slot(Some(5));
+        let (runs, _dispose) = count_runs(&s);
+
+        s.force_set_value(5);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn slot_switch_to_equal_signal_skips_rerun_but_tracks_signal() {
+        let s = slot(Some(1));
+        let sig = signal(1);
+        let (runs, _dispose) = count_runs(&s);
+
+        s.set_signal(&sig);
+        assert_eq!(runs.get(), 1);
+
+        // Re-pointing at the same signal is a no-op
+        s.set_signal(&sig);
+        assert_eq!(runs.get(), 1);
+
+        // Reader was subscribed to the signal without re-running
+        sig.set(2);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn slot_switch_to_different_signal_value_notifies() {
+        let s = slot(Some(1));
+        let sig = signal(2);
+        let (runs, _dispose) = count_runs(&s);
+
+        s.set_signal(&sig);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn slot_switch_to_equal_getter_skips_rerun_but_tracks_getter_deps() {
+        let s = slot(Some(10));
+        let base = signal(5);
+        let (runs, _dispose) = count_runs(&s);
+
+        s.set_getter({
+            let base = base.clone();
+            move || base.get() * 2
+        });
+        assert_eq!(runs.get(), 1);
+        assert_eq!(s.peek(), Some(10));
+
+        base.set(6);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(s.peek(), Some(12));
+    }
+
+    #[test]
+    fn slot_signal_to_static_equal_value_does_not_notify() {
+        let sig = signal(3);
+        let s = slot(None);
+        s.set_signal(&sig);
+        let (runs, _dispose) = count_runs(&s);
+
+        s.set_value(3);
+        assert_eq!(runs.get(), 1);
+        assert!(s.is_static());
+    }
+
+    #[test]
+    fn slot_rebind_to_equal_value_unsubscribes_old_source() {
+        let a = signal(1);
+        let b = signal(1);
+        let s = slot(None);
+        s.set_signal(&a);
+        let (runs, _dispose) = count_runs(&s);
+
+        s.set_signal(&b);
+        assert_eq!(runs.get(), 1);
+        assert_eq!(a.as_any_source().reaction_count(), 0);
+
+        a.set(2);
+        assert_eq!(runs.get(), 1);
+        b.set(2);
+        assert_eq!(runs.get(), 2);
+
+        s.set_value(2);
+        assert_eq!(b.as_any_source().reaction_count(), 0);
+        b.set(3);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn slot_getter_runs_lazily_without_readers() {
+        let calls = Rc::new(Cell::new(0));
+        let s = slot(Some(1));
+
+        s.set_getter({
+            let calls = calls.clone();
+            move || {
+                calls.set(calls.get() + 1);
+                7
+            }
+        });
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(s.get(), Some(7));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn slot_clear_empty_does_not_notify() {
+        let s: Slot<i32> = slot(None);
+        let (runs, _dispose) = count_runs(&s);
+
+        s.clear();
+        assert_eq!(runs.get(), 1);
+
+        s.set_value(1);
+        s.clear();
+        assert_eq!(runs.get(), 3);
+    }
+
+    #[test]
+    fn readonly_view_follows_repointed_slot() {
+        let count = Rc::new(Cell::new(0));
+        let width = slot(Some(1));
+        let view = width.readonly();
+
+        let _dispose = effect_sync({
+            let (view, count) = (view.clone(), count.clone());
+            move || {
+                view.get();
+                count.set(count.get() + 1);
+            }
+        });
+
+        let source = signal(5);
+        width.set_signal(&source);
+        assert_eq!(view.get(), Some(5));
+        source.set(6);
+        assert_eq!(view.get(), Some(6));
+        assert_eq!(count.get(), 3);
+
+        let dirty = dirty_set();
+        let tracked = tracked_slot(Some(2), dirty.clone(), 7);
+        assert_eq!(tracked.readonly().get(), Some(2));
+        assert!(dirty.borrow().is_empty());
+    }
+
+    #[test]
+    fn set_untracked_defers_notification() {
+        let array = Rc::new(slot_array(Some(0)));
+        let backing = signal(1);
+        array.set_signal(1, &backing);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (array, seen) = (array.clone(), seen.clone());
+            move || seen.borrow_mut().push((array.get(0), array.get(1)))
+        });
+
+        array.set_untracked(0, 10).unwrap();
+        array.set_untracked(1, 20).unwrap();
+        assert_eq!(seen.borrow().len(), 1);
+        assert_eq!(backing.inner().get(), 20);
+
+        backing.notify();
+        assert_eq!(seen.borrow().last(), Some(&(Some(10), Some(20))));
+
+        array.set_getter(2, || 3);
+        assert_eq!(array.set_untracked(2, 4), Err(SlotWriteError::ReadOnlyGetter));
+    }
+
+    #[test]
+    fn update_untracked_then_notify() {
+        let array = Rc::new(slot_array(Some(vec![1])));
+        let backing = signal(vec![2]);
+        array.set_signal(1, &backing);
+        let chained = slot(None);
+        chained.set_slot(&array.slot(0));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (array, seen) = (array.clone(), seen.clone());
+            move || seen.borrow_mut().push((array.get(0), array.get(1)))
+        });
+
+        chained.update_untracked(|v| v.push(10)).unwrap();
+        array.update_untracked(1, |v| v.push(20)).unwrap();
+        assert_eq!(seen.borrow().len(), 1);
+
+        chained.notify();
+        assert_eq!(seen.borrow().last(), Some(&(Some(vec![1, 10]), Some(vec![2, 20]))));
+        assert_eq!(seen.borrow().len(), 2);
+        array.notify(1);
+        assert_eq!(seen.borrow().len(), 3);
+
+        let empty = slot::<Vec<i32>>(None);
+        assert_eq!(empty.update_untracked(|v| v.clear()), Err(SlotWriteError::NoSource));
+        array.set_getter(2, Vec::new);
+        assert_eq!(array.update_untracked(2, |v| v.clear()), Err(SlotWriteError::ReadOnlyGetter));
+    }
+
+    #[test]
+    fn chained_slots_follow_and_write_through() {
+        let theme = slot(Some(1));
+        let button = Rc::new(slot(None));
+        button.set_slot(&theme);
+        assert!(button.is_chained());
+
+        let runs = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let (button, runs) = (button.clone(), runs.clone());
+            move || {
+                button.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        // Target changes reach the chained slot's dependents
+        let accent = signal(2);
+        theme.set_signal(&accent);
+        assert_eq!(runs.get(), 2);
+        accent.set(3);
+        assert_eq!(runs.get(), 3);
+        assert_eq!(button.get(), Some(3));
+
+        // Re-pointing to an equal value doesn't re-run, but keeps tracking
+        let same = signal(3);
+        button.set_derived(&derived({
+            let same = same.clone();
+            move || same.get()
+        }));
+        assert_eq!(runs.get(), 3);
+        same.set(4);
+        assert_eq!(runs.get(), 4);
+        assert_eq!(button.set(5), Err(SlotWriteError::ReadOnlySource));
+
+        // Writes through a slot chain land on the signal
+        button.set_slot(&theme);
+        button.set(6).unwrap();
+        assert_eq!(accent.get(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle")]
+    fn slot_chain_cycle_panics() {
+        let a = slot(Some(1));
+        let b = slot(None);
+        b.set_slot(&a);
+        a.set_slot(&b);
+    }
+
+    #[test]
+    fn bulk_writes_notify_once() {
+        let array = Rc::new(slot_array::<i32>(None));
+        let runs = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let (array, runs) = (array.clone(), runs.clone());
+            move || {
+                for i in 0..3 {
+                    array.get(i);
+                }
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        array.set_values(&[1, 2, 3]);
+        assert_eq!(runs.get(), 2);
+
+        let source = signal(5);
+        array.bind_all(vec![
+            PropValue::Signal(source.clone()),
+            PropValue::Getter(Box::new(|| 6)),
+            PropValue::Static(7),
+        ]);
+        assert_eq!(runs.get(), 3);
+        assert_eq!(array.snapshot(), [Some(5), Some(6), Some(7)]);
+
+        // snapshot() doesn't track
+        let _peek = effect_sync({
+            let array = array.clone();
+            move || {
+                array.snapshot();
+            }
+        });
+        source.set(8);
+        assert_eq!(runs.get(), 4);
+    }
+
+    #[test]
+    fn dirty_signal_bumps_once_per_marking_write() {
+        let array = tracked_slot_array::<i32>(Some(0), dirty_set());
+        let dirty_signal = array.dirty_signal();
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let runs = runs.clone();
+            move || {
+                dirty_signal.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        array.set_values(&[1, 2, 3]);
+        assert_eq!(runs.get(), 2);
+        array.set_value(1, 5);
+        assert_eq!(runs.get(), 3);
+
+        // Reading doesn't mark
+        array.get(2);
+        assert_eq!(runs.get(), 3);
+
+        // on_dirty sees the pending marks at creation, then each write
+        let drained = Rc::new(RefCell::new(Vec::new()));
+        let dispose = array.on_dirty({
+            let drained = drained.clone();
+            move |dirty| drained.borrow_mut().extend(dirty.borrow_mut().drain())
+        });
+        assert_eq!(*drained.borrow(), [0, 1, 2]);
+        array.set_value(7, 1);
+        assert_eq!(*drained.borrow(), [0, 1, 2, 7]);
+
+        dispose();
+        array.set_value(8, 1);
+        assert_eq!(drained.borrow().len(), 4);
+    }
 }
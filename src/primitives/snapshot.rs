@@ -0,0 +1,162 @@
+// ============================================================================
+// spark-signals - Snapshots
+// Capture the values of a set of signals and write them back later
+// ============================================================================
+//
+//     let saved = capture([AnySignal::from(&draft), (&cursor).into()]);
+//     draft.set(optimistic_edit);
+//     if request_failed {
+//         restore(&saved); // one batch: effects see the old state once
+//     }
+//
+// Signals of different types are passed as `AnySignal`s. Values are cloned
+// without tracking, so capturing inside an effect doesn't subscribe it.
+//
+// There is no "capture a scope": an `EffectScope` owns effects, not signals,
+// and the graph only knows a signal's type through its typed handle. Callers
+// list the signals that make up the state they want to save.
+// ============================================================================
+
+use std::any::Any;
+use std::rc::Rc;
+
+use crate::primitives::any_signal::AnySignal;
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::batch;
+
+/// Values of a set of signals at the moment of [`capture()`].
+///
+/// Restoring doesn't consume it: one snapshot can be restored any number of
+/// times.
+pub struct Snapshot {
+    entries: Vec<(AnySignal, Box<dyn Any>)>,
+}
+
+impl Snapshot {
+    /// Number of captured signals.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if no signals were captured.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The captured value of `signal`, if it is part of the snapshot.
+    pub fn get<T: 'static>(&self, signal: &Signal<T>) -> Option<&T> {
+        let source = signal.as_any_source();
+        self.entries
+            .iter()
+            .find(|(captured, _)| Rc::ptr_eq(&captured.as_any_source(), &source))
+            .and_then(|(_, value)| value.downcast_ref())
+    }
+
+    /// The captured signals.
+    pub fn signals(&self) -> impl Iterator<Item = &AnySignal> {
+        self.entries.iter().map(|(signal, _)| signal)
+    }
+}
+
+impl std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|(signal, _)| signal.type_name()))
+            .finish()
+    }
+}
+
+/// Capture the current values of `signals`, without tracking.
+///
+/// Only the listed signals are captured; capturing everything an
+/// `EffectScope` touches is not supported.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{capture, restore, signal, AnySignal};
+///
+/// let title = signal(String::from("Draft"));
+/// let likes = signal(3);
+/// let saved = capture([AnySignal::from(&title), AnySignal::from(&likes)]);
+///
+/// // Optimistic update...
+/// title.set(String::from("Published"));
+/// likes.set(4);
+///
+/// // ...rolled back
+/// restore(&saved);
+/// assert_eq!(title.get(), "Draft");
+/// assert_eq!(likes.get(), 3);
+/// assert_eq!(saved.get(&likes), Some(&3));
+/// ```
+pub fn capture<I>(signals: I) -> Snapshot
+where
+    I: IntoIterator,
+    I::Item: Into<AnySignal>,
+{
+    let entries = signals
+        .into_iter()
+        .map(|signal| {
+            let signal = signal.into();
+            let value = signal.peek_any();
+            (signal, value)
+        })
+        .collect();
+    Snapshot { entries }
+}
+
+/// Write every captured value back to its signal, in one batch.
+///
+/// Signals whose value is unchanged don't notify. Returns the number of
+/// signals that changed.
+pub fn restore(snapshot: &Snapshot) -> usize {
+    batch(|| {
+        snapshot
+            .entries
+            .iter()
+            .filter(|(signal, value)| signal.set_any(value.as_ref()) == Some(true))
+            .count()
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use std::cell::Cell;
+
+    #[test]
+    fn restore_notifies_once_and_can_repeat() {
+        let a = signal(1);
+        let b = signal(String::from("x"));
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (a, b, runs) = (a.clone(), b.clone(), runs.clone());
+            move || {
+                a.get();
+                b.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        let saved = capture([AnySignal::from(&a), AnySignal::from(&b)]);
+        a.set(2);
+        b.set(String::from("y"));
+        assert_eq!(runs.get(), 3);
+
+        assert_eq!(restore(&saved), 2);
+        assert_eq!(runs.get(), 4);
+        assert_eq!((a.get(), b.get().as_str()), (1, "x"));
+
+        // Nothing changed since: no writes, no runs
+        assert_eq!(restore(&saved), 0);
+        assert_eq!(runs.get(), 4);
+        assert_eq!(saved.len(), 2);
+    }
+}
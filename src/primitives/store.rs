@@ -0,0 +1,313 @@
+// ============================================================================
+// spark-signals - Store
+// A nested struct with per-field reactivity through lenses
+// ============================================================================
+//
+// Splitting a large state struct into one signal per field is tedious. A
+// store keeps the struct whole and hands out field handles instead:
+//
+//     let state = store(AppState { .. });
+//     let name = state.field(|s| &s.user.name);
+//
+// Reading `name.get()` subscribes to that field only. Every `update()` on
+// the store re-reads each live field through its lens and notifies the ones
+// whose value changed, so an effect reading `user.name` doesn't re-run when
+// `user.age` changes.
+//
+// Reading the store as a whole (`get()` / `with()`) subscribes to every
+// update.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use crate::core::types::SourceInner;
+use crate::primitives::signal::{signal, Signal};
+use crate::reactivity::batching::batch;
+
+/// Re-reads one field after an update; false once the field handle is gone.
+type FieldRefresh<T> = Box<dyn Fn(&T) -> bool>;
+
+struct StoreInner<T> {
+    value: RefCell<T>,
+    /// Bumped on every update (whole-store readers)
+    version: Signal<u64>,
+    fields: RefCell<Vec<FieldRefresh<T>>>,
+}
+
+// =============================================================================
+// STORE
+// =============================================================================
+
+/// A struct with fine-grained, per-field reactivity. Created with [`store()`].
+///
+/// Clones share the same state.
+pub struct Store<T> {
+    inner: Rc<StoreInner<T>>,
+}
+
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: 'static> Store<T> {
+    /// A reactive handle to one field, selected by a lens.
+    ///
+    /// Reads of the handle only re-run when the field's value changes. The
+    /// lens may reach anywhere inside the struct (nested fields, elements).
+    /// Create handles once and keep them; each call registers a new one.
+    pub fn field<U, L>(&self, lens: L) -> StoreField<U>
+    where
+        U: Clone + PartialEq + 'static,
+        L: Fn(&T) -> &U + 'static,
+    {
+        let field = signal(lens(&self.inner.value.borrow()).clone());
+        let weak: Weak<SourceInner<U>> = Rc::downgrade(field.inner());
+
+        self.inner.fields.borrow_mut().push(Box::new(move |root: &T| {
+            match weak.upgrade() {
+                Some(inner) => {
+                    Signal::from_inner(inner).set(lens(root).clone());
+                    true
+                }
+                None => false,
+            }
+        }));
+
+        StoreField { signal: field }
+    }
+
+    /// Mutate the struct in place, then notify the fields that changed.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.inner.value.borrow_mut());
+
+        batch(|| {
+            let root = self.inner.value.borrow();
+            self.inner.fields.borrow_mut().retain(|refresh| refresh(&root));
+            drop(root);
+            self.inner.version.update(|v| *v += 1);
+        });
+    }
+
+    /// Replace the whole struct.
+    pub fn set(&self, value: T) {
+        self.update(move |current| *current = value);
+    }
+
+    /// Read the whole struct with a closure, subscribing to every update.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.inner.version.get();
+        f(&self.inner.value.borrow())
+    }
+
+    /// Clone the whole struct, subscribing to every update.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.with(T::clone)
+    }
+
+    /// Read without subscribing.
+    pub fn peek<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.inner.value.borrow())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Store<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Store")
+            .field("value", &*self.inner.value.borrow())
+            .finish()
+    }
+}
+
+/// Wrap a struct in a [`Store`].
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, store};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// #[derive(Clone)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let user = store(User { name: "Ada".into(), age: 36 });
+/// let name = user.field(|u| &u.name);
+///
+/// let runs = Rc::new(Cell::new(0));
+/// let _dispose = effect_sync({
+///     let (name, runs) = (name.clone(), runs.clone());
+///     move || {
+///         name.get();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+///
+/// user.update(|u| u.age += 1); // name unchanged: effect doesn't re-run
+/// assert_eq!(runs.get(), 1);
+///
+/// user.update(|u| u.name = "Grace".into());
+/// assert_eq!(runs.get(), 2);
+/// assert_eq!(name.get(), "Grace");
+/// ```
+pub fn store<T: 'static>(value: T) -> Store<T> {
+    Store {
+        inner: Rc::new(StoreInner {
+            value: RefCell::new(value),
+            version: signal(0),
+            fields: RefCell::new(Vec::new()),
+        }),
+    }
+}
+
+// =============================================================================
+// STORE FIELD
+// =============================================================================
+
+/// A read handle to one field of a [`Store`].
+///
+/// Write through the store (`Store::update`); the handle follows.
+#[derive(Clone)]
+pub struct StoreField<U> {
+    signal: Signal<U>,
+}
+
+impl<U: Clone + PartialEq + 'static> StoreField<U> {
+    /// The field's current value, subscribing to changes of this field only.
+    pub fn get(&self) -> U {
+        self.signal.get()
+    }
+
+    /// Access the field's value with a closure (avoids cloning).
+    pub fn with<R>(&self, f: impl FnOnce(&U) -> R) -> R {
+        self.signal.with(f)
+    }
+
+    /// Read without subscribing.
+    pub fn peek(&self) -> U {
+        self.signal.inner().get()
+    }
+}
+
+impl<U: std::fmt::Debug + Clone + PartialEq + 'static> std::fmt::Debug for StoreField<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoreField")
+            .field("value", &self.peek())
+            .finish()
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use std::cell::Cell;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Position {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        position: Position,
+        items: Vec<&'static str>,
+    }
+
+    fn player() -> Store<Player> {
+        store(Player {
+            name: "p1".into(),
+            position: Position { x: 0, y: 0 },
+            items: vec!["sword"],
+        })
+    }
+
+    fn count_runs(read: impl Fn() + 'static) -> (Rc<Cell<u32>>, impl FnOnce()) {
+        let runs = Rc::new(Cell::new(0));
+        let dispose = effect_sync({
+            let runs = runs.clone();
+            move || {
+                read();
+                runs.set(runs.get() + 1);
+            }
+        });
+        (runs, dispose)
+    }
+
+    #[test]
+    fn nested_fields_notify_independently() {
+        let state = player();
+        let x = state.field(|p| &p.position.x);
+        let first_item = state.field(|p| &p.items[0]);
+
+        let (x_runs, _x) = count_runs({
+            let x = x.clone();
+            move || {
+                x.get();
+            }
+        });
+        let (item_runs, _item) = count_runs({
+            let first_item = first_item.clone();
+            move || {
+                first_item.get();
+            }
+        });
+
+        state.update(|p| p.position.y = 5);
+        assert_eq!((x_runs.get(), item_runs.get()), (1, 1));
+
+        state.update(|p| p.position.x = 3);
+        assert_eq!((x_runs.get(), item_runs.get()), (2, 1));
+        assert_eq!(x.get(), 3);
+
+        state.update(|p| p.items.insert(0, "shield"));
+        assert_eq!((x_runs.get(), item_runs.get()), (2, 2));
+        assert_eq!(first_item.get(), "shield");
+    }
+
+    #[test]
+    fn whole_store_reads_see_every_update() {
+        let state = player();
+        let (runs, _dispose) = count_runs({
+            let state = state.clone();
+            move || {
+                state.with(|p| p.name.len());
+            }
+        });
+
+        state.update(|p| p.position.x += 1);
+        state.set(Player {
+            name: "p2".into(),
+            ..state.peek(Player::clone)
+        });
+        assert_eq!(runs.get(), 3);
+        assert_eq!(state.get().name, "p2");
+    }
+
+    #[test]
+    fn dropped_field_handles_are_pruned() {
+        let state = player();
+        let name = state.field(|p| &p.name);
+        let _kept = state.field(|p| &p.items);
+        drop(name);
+
+        state.update(|p| p.name.push('!'));
+        assert_eq!(state.inner.fields.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,290 @@
+// ============================================================================
+// spark-signals - Subscriptions
+// Plain on-change callbacks, outside the reactive graph
+// ============================================================================
+//
+// An effect is a graph node: it tracks dependencies, gets scheduled, and
+// re-runs through the flush loop. Bridging a signal to non-reactive code
+// (logging, metrics, a channel) needs none of that:
+//
+//     let _sub = count.subscribe(|new, old| println!("{old} -> {new}"));
+//
+// Subscriptions live in a side table keyed by source, not in the graph. Every
+// write that notifies the graph (`notify_write`) also calls the source's
+// subscribers, synchronously and untracked, with the new and previous values.
+// A `HAS_SUBSCRIBERS` flag on the source keeps other writes off the table.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use crate::core::constants::HAS_SUBSCRIBERS;
+use crate::core::context::{with_context, Owner};
+use crate::core::types::{AnySource, SourceInner};
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::untrack;
+
+/// Called after a write; compares against its own copy of the last value.
+type Listener = Rc<RefCell<dyn FnMut()>>;
+
+#[derive(Default)]
+pub(crate) struct Subscribers {
+    next_id: u64,
+    /// Source address -> (subscription id, listener)
+    by_source: HashMap<usize, Vec<(u64, Listener)>>,
+}
+
+impl Subscribers {
+    /// Number of sources with live subscriptions.
+    #[cfg(test)]
+    pub(crate) fn source_count(&self) -> usize {
+        self.by_source.len()
+    }
+}
+
+fn address<T: ?Sized>(node: &Rc<T>) -> usize {
+    Rc::as_ptr(node) as *const () as usize
+}
+
+/// True if `source` has at least one live subscription.
+///
+/// Reads the source's `HAS_SUBSCRIBERS` flag, so writes to sources nobody
+/// subscribed to never touch the subscriber table.
+pub(crate) fn has_subscribers<S: AnySource + ?Sized>(source: &Rc<S>) -> bool {
+    source.flags() & HAS_SUBSCRIBERS != 0
+}
+
+/// Call the subscribers of a source that was just written.
+///
+/// Called by `notify_write()`. A listener that writes its own source doesn't
+/// re-enter itself; it sees the final value on the next write.
+pub(crate) fn notify_subscribers(source: &Rc<dyn AnySource>) {
+    // Collect first: listeners may subscribe, unsubscribe, or write
+    let listeners: Vec<Listener> = with_context(|ctx| {
+        let s = ctx.subscribers.borrow();
+        match s.by_source.get(&address(source)) {
+            Some(entries) => entries.iter().map(|(_, l)| l.clone()).collect(),
+            None => Vec::new(),
+        }
+    });
+    if listeners.is_empty() {
+        return;
+    }
+
+    untrack(|| {
+        for listener in listeners {
+            if let Ok(mut listener) = listener.try_borrow_mut() {
+                listener();
+            }
+        }
+    });
+}
+
+// =============================================================================
+// SUBSCRIPTION
+// =============================================================================
+
+/// A live [`Signal::subscribe()`] callback.
+///
+/// Unsubscribes when dropped; keep it bound (`let _sub = ...`) for as long
+/// as the callback should run.
+#[must_use = "dropping a Subscription unsubscribes immediately"]
+pub struct Subscription {
+    source: usize,
+    /// The source itself, to clear its `HAS_SUBSCRIBERS` flag
+    node: Weak<dyn AnySource>,
+    id: u64,
+    /// The source's context, which holds the listener
+    owner: Owner,
+}
+
+impl Subscription {
+    /// Stop calling the callback. Same as dropping the subscription.
+    pub fn unsubscribe(self) {}
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // Ignore drops during thread teardown
+        self.owner.try_with(|ctx| {
+            let mut s = ctx.subscribers.borrow_mut();
+            if let Some(entries) = s.by_source.get_mut(&self.source) {
+                entries.retain(|(id, _)| *id != self.id);
+                if entries.is_empty() {
+                    s.by_source.remove(&self.source);
+                    if let Some(node) = self.node.upgrade() {
+                        node.set_flags(node.flags() & !HAS_SUBSCRIBERS);
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("source", &format_args!("{:#x}", self.source))
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl<T: Clone + 'static> Signal<T> {
+    /// Call `f(new, old)` after every change to this signal.
+    ///
+    /// Unlike an effect, no graph node is created and nothing is tracked:
+    /// the callback runs synchronously on each notifying write (also inside
+    /// a `batch()`, once per write), untracked. Writes that don't change the
+    /// value (by the signal's equality function) are skipped, as are
+    /// `set_untracked()` writes until they are announced with `Signal::notify()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::signal;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let count = signal(1);
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let sub = count.subscribe({
+    ///     let log = log.clone();
+    ///     move |new, old| log.borrow_mut().push((*old, *new))
+    /// });
+    ///
+    /// count.set(2);
+    /// count.set(2); // unchanged: not reported
+    /// count.update(|n| *n *= 10);
+    /// assert_eq!(*log.borrow(), [(1, 2), (2, 20)]);
+    ///
+    /// sub.unsubscribe();
+    /// count.set(3);
+    /// assert_eq!(log.borrow().len(), 2);
+    /// ```
+    pub fn subscribe<F>(&self, mut f: F) -> Subscription
+    where
+        F: FnMut(&T, &T) + 'static,
+    {
+        let weak: Weak<SourceInner<T>> = Rc::downgrade(self.inner());
+        let mut previous = self.inner().get();
+
+        let listener: Listener = Rc::new(RefCell::new(move || {
+            let Some(inner) = weak.upgrade() else {
+                return;
+            };
+            let current = inner.get();
+            if inner.is_equal(&previous, &current) {
+                return;
+            }
+            let old = std::mem::replace(&mut previous, current);
+            f(&previous, &old);
+        }));
+
+        // Listeners live with the source, where its writes notify
+        let source = address(self.inner());
+        let owner = self.inner().owner().cloned().unwrap_or_else(Owner::current);
+        let id = owner.enter(|| {
+            with_context(|ctx| {
+                let mut s = ctx.subscribers.borrow_mut();
+                let id = s.next_id;
+                s.next_id += 1;
+                s.by_source.entry(source).or_default().push((id, listener));
+                id
+            })
+        });
+        let node = self.as_any_source();
+        node.set_flags(node.flags() | HAS_SUBSCRIBERS);
+        Subscription { source, node: Rc::downgrade(&node), id, owner }
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use crate::reactivity::batching::batch;
+    use std::cell::Cell;
+
+    #[test]
+    fn reports_each_write_without_a_graph_node() {
+        let name = signal(String::from("a"));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _sub = name.subscribe({
+            let seen = seen.clone();
+            move |new: &String, old: &String| seen.borrow_mut().push(format!("{old}>{new}"))
+        });
+
+        assert_eq!(name.inner().reaction_count(), 0);
+        batch(|| {
+            name.set("b".into());
+            name.set("c".into());
+        });
+        assert_eq!(*seen.borrow(), ["a>b", "b>c"]);
+    }
+
+    #[test]
+    fn callbacks_are_untracked() {
+        let source = signal(0);
+        let other = signal(0);
+        let runs = Rc::new(Cell::new(0));
+
+        // Written from inside an effect: the callback's reads must not
+        // become the effect's dependencies
+        let _sub = source.subscribe({
+            let other = other.clone();
+            move |_, _| {
+                other.get();
+            }
+        });
+        let trigger = signal(0);
+        let _dispose = effect_sync({
+            let (source, trigger, runs) = (source.clone(), trigger.clone(), runs.clone());
+            move || {
+                runs.set(runs.get() + 1);
+                source.set(trigger.get());
+            }
+        });
+
+        trigger.set(1);
+        assert_eq!(runs.get(), 2);
+        other.set(1);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn dropping_the_subscription_cleans_up() {
+        let count = signal(0);
+        let calls = Rc::new(Cell::new(0));
+        let sub = count.subscribe({
+            let calls = calls.clone();
+            move |_, _| calls.set(calls.get() + 1)
+        });
+        assert!(has_subscribers(count.inner()));
+
+        count.set(1);
+        drop(sub);
+        count.set(2);
+        assert_eq!(calls.get(), 1);
+        assert!(!has_subscribers(count.inner()));
+    }
+
+    #[test]
+    fn flag_stays_until_the_last_subscription_drops() {
+        let count = signal(0);
+        let first = count.subscribe(|_, _| {});
+        let second = count.subscribe(|_, _| {});
+        assert_ne!(count.as_any_source().flags() & HAS_SUBSCRIBERS, 0);
+
+        drop(first);
+        assert!(has_subscribers(count.inner()));
+        drop(second);
+        assert_eq!(count.as_any_source().flags() & HAS_SUBSCRIBERS, 0);
+    }
+}
@@ -0,0 +1,517 @@
+// ============================================================================
+// spark-signals - Time Sources
+// Interval and timeout signals, debounced and throttled wrappers
+// ============================================================================
+//
+// Timers are kept per thread and fired by `run_timers()`, which compares
+// them against a pluggable clock. The host calls it from its loop
+// (`ReactiveDriver::pump()` does), and tests swap in a `ManualClock`:
+//
+//     let clock = ManualClock::new();
+//     set_clock(clock.clone());
+//     let ticks = signal_interval(Duration::from_millis(100));
+//     clock.advance(Duration::from_millis(250)); // fires due timers
+//     assert_eq!(ticks.get(), 2);
+//
+// Every timer holds its output weakly: dropping the signal or derived a
+// timer feeds ends it.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::core::config::{update_config, with_config};
+use crate::primitives::derived::{derived, Derived};
+use crate::primitives::effect::effect_sync;
+use crate::primitives::props::UnwrapProp;
+use crate::primitives::signal::{signal, ReadSignal, WeakSignal};
+use crate::reactivity::batching::{batch, untrack};
+
+// =============================================================================
+// CLOCKS
+// =============================================================================
+
+/// Where timers get the current time from.
+///
+/// Clocks live in `Config`, which may be shared between threads, so they
+/// must be `Send + Sync`.
+pub trait Clock: Send + Sync {
+    /// Time elapsed since the clock's origin.
+    fn now(&self) -> Duration;
+}
+
+/// Wall-clock time, measured from the clock's creation.
+///
+/// The default clock. Not available on `wasm32-unknown-unknown`; install a
+/// clock reading `performance.now()` there instead.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// A clock starting now.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A clock that only moves when told to, for deterministic tests.
+///
+/// Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    /// A clock at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the clock forward by `by` and fire every timer due by then.
+    /// Returns the number of timers fired.
+    pub fn advance(&self, by: Duration) -> usize {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += by;
+        run_timers()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// =============================================================================
+// TIMER QUEUE
+// =============================================================================
+
+type TimerId = u64;
+
+/// Called with the number of periods elapsed; returns false to stop a
+/// periodic timer.
+type TimerFn = Box<dyn FnMut(u64) -> bool>;
+
+struct Timer {
+    id: TimerId,
+    due: Duration,
+    period: Option<Duration>,
+    fire: TimerFn,
+}
+
+#[derive(Default)]
+struct Timers {
+    /// Fallback clock, started on first use, when `Config::clock` is unset
+    system: Option<SystemClock>,
+    next_id: TimerId,
+    pending: Vec<Timer>,
+}
+
+thread_local! {
+    static TIMERS: RefCell<Timers> = RefCell::new(Timers::default());
+}
+
+/// Use `clock` for this thread's timers from now on.
+///
+/// Shorthand for setting `Config::clock` with `set_thread_config()`.
+/// Timers already pending keep their due time, read from the old clock.
+pub fn set_clock(clock: impl Clock + 'static) {
+    update_config(|c| c.clock = Some(Arc::new(clock)));
+}
+
+fn now() -> Duration {
+    if let Some(clock) = with_config(|c| c.clock.clone()) {
+        return clock.now();
+    }
+    TIMERS.with(|timers| timers.borrow_mut().system.get_or_insert_with(SystemClock::new).now())
+}
+
+fn set_timer(delay: Duration, period: Option<Duration>, fire: TimerFn) -> TimerId {
+    let due = now() + delay;
+    TIMERS.with(|timers| {
+        let mut timers = timers.borrow_mut();
+        let id = timers.next_id;
+        timers.next_id += 1;
+        timers.pending.push(Timer {
+            id,
+            due,
+            period,
+            fire,
+        });
+        id
+    })
+}
+
+fn clear_timer(id: TimerId) {
+    // May run from a Drop during thread teardown
+    let _ = TIMERS.try_with(|timers| timers.borrow_mut().pending.retain(|t| t.id != id));
+}
+
+/// Fire every timer due on this thread's clock, earliest first, in one
+/// batch. Returns the number fired.
+///
+/// A periodic timer that fell several periods behind fires once, catching
+/// up (an interval's tick count jumps).
+pub fn run_timers() -> usize {
+    let now = now();
+    let mut fired = 0;
+    batch(|| {
+        loop {
+            // Take the timer out, so its callback may set or clear timers
+            let next = TIMERS.with(|timers| {
+                let mut timers = timers.borrow_mut();
+                let index = (0..timers.pending.len())
+                    .filter(|&i| timers.pending[i].due <= now)
+                    .min_by_key(|&i| (timers.pending[i].due, timers.pending[i].id))?;
+                Some(timers.pending.swap_remove(index))
+            });
+            let Some(mut timer) = next else {
+                break;
+            };
+
+            let periods = match timer.period {
+                Some(period) => ((now - timer.due).as_nanos() / period.as_nanos()) as u64 + 1,
+                None => 1,
+            };
+            fired += 1;
+            let keep = untrack(|| (timer.fire)(periods));
+            if let (true, Some(period)) = (keep, timer.period) {
+                timer.due += period * u32::try_from(periods).unwrap_or(u32::MAX);
+                TIMERS.with(|timers| timers.borrow_mut().pending.push(timer));
+            }
+        }
+    });
+    fired
+}
+
+/// Time until the next timer is due (zero if one is overdue), or None if
+/// none is pending. For hosts that sleep between `run_timers()` calls.
+pub fn until_next_timer() -> Option<Duration> {
+    let due = TIMERS.with(|timers| timers.borrow().pending.iter().map(|t| t.due).min())?;
+    Some(due.saturating_sub(now()))
+}
+
+// =============================================================================
+// SOURCES
+// =============================================================================
+
+/// Run `f` on the signal if it is still alive; false if it was dropped.
+fn write_weak<T>(weak: &WeakSignal<T>, f: impl FnOnce(&crate::Signal<T>)) -> bool {
+    weak.upgrade().map(|signal| f(&signal)).is_some()
+}
+
+/// A counter that goes up by one every `period`, starting at 0.
+///
+/// # Panics
+///
+/// If `period` is zero.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{set_clock, signal_interval, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// set_clock(clock.clone());
+///
+/// let ticks = signal_interval(Duration::from_secs(1));
+/// clock.advance(Duration::from_millis(2500));
+/// assert_eq!(ticks.get(), 2);
+/// ```
+pub fn signal_interval(period: Duration) -> ReadSignal<u64> {
+    assert!(!period.is_zero(), "signal_interval needs a non-zero period");
+    let ticks = signal(0u64);
+    let weak = ticks.downgrade();
+    set_timer(
+        period,
+        Some(period),
+        Box::new(move |periods| write_weak(&weak, |ticks| ticks.update(|n| *n += periods))),
+    );
+    ticks.split().0
+}
+
+/// False until `delay` has passed, then true.
+pub fn signal_timeout(delay: Duration) -> ReadSignal<bool> {
+    let done = signal(false);
+    let weak = done.downgrade();
+    set_timer(
+        delay,
+        None,
+        Box::new(move |_| {
+            write_weak(&weak, |done| {
+                done.set(true);
+            });
+            false
+        }),
+    );
+    done.split().0
+}
+
+// =============================================================================
+// DEBOUNCE & THROTTLE
+// =============================================================================
+
+/// Keeps a wrapper's effect alive for as long as its derived, and clears
+/// its pending timer with it.
+struct WrapperGuard {
+    dispose: Option<Box<dyn FnOnce()>>,
+    timer: Rc<Cell<Option<TimerId>>>,
+}
+
+impl Drop for WrapperGuard {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer.take() {
+            clear_timer(id);
+        }
+        if let Some(dispose) = self.dispose.take() {
+            dispose();
+        }
+    }
+}
+
+/// Follows `source`, but only once it has stopped changing for `delay`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{debounced, set_clock, signal, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// set_clock(clock.clone());
+///
+/// let query = signal(String::new());
+/// let search = debounced(query.clone(), Duration::from_millis(300));
+///
+/// query.set("r".into());
+/// clock.advance(Duration::from_millis(100));
+/// query.set("rust".into());
+/// clock.advance(Duration::from_millis(200));
+/// assert_eq!(search.get(), ""); // still typing
+///
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(search.get(), "rust");
+/// ```
+pub fn debounced<T, S>(source: S, delay: Duration) -> Derived<T>
+where
+    T: Clone + PartialEq + 'static,
+    S: UnwrapProp<T> + 'static,
+{
+    let output = signal(untrack(|| source.unwrap_value()));
+    let timer: Rc<Cell<Option<TimerId>>> = Rc::default();
+
+    let mut first = true;
+    let dispose = effect_sync({
+        let (output, timer) = (output.downgrade(), timer.clone());
+        move || {
+            let value = source.unwrap_value();
+            if std::mem::take(&mut first) {
+                return;
+            }
+            if let Some(id) = timer.take() {
+                clear_timer(id);
+            }
+            let (output, pending) = (output.clone(), timer.clone());
+            let mut value = Some(value);
+            let id = set_timer(
+                delay,
+                None,
+                Box::new(move |_| {
+                    pending.set(None);
+                    if let Some(value) = value.take() {
+                        write_weak(&output, |output| {
+                            output.set(value);
+                        });
+                    }
+                    false
+                }),
+            );
+            timer.set(Some(id));
+        }
+    });
+
+    let guard = WrapperGuard {
+        dispose: Some(Box::new(dispose)),
+        timer,
+    };
+    derived(move || {
+        let _guard = &guard;
+        output.get()
+    })
+}
+
+/// Follows `source` at most once per `interval`.
+///
+/// The first change goes through at once and starts the interval; the
+/// latest change made during it goes through when it ends, starting the
+/// next.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{set_clock, signal, throttled, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// set_clock(clock.clone());
+///
+/// let scroll = signal(0);
+/// let sampled = throttled(scroll.clone(), Duration::from_millis(100));
+///
+/// scroll.set(10);
+/// scroll.set(20);
+/// scroll.set(30);
+/// assert_eq!(sampled.get(), 10);
+///
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(sampled.get(), 30);
+/// ```
+pub fn throttled<T, S>(source: S, interval: Duration) -> Derived<T>
+where
+    T: Clone + PartialEq + 'static,
+    S: UnwrapProp<T> + 'static,
+{
+    assert!(!interval.is_zero(), "throttled needs a non-zero interval");
+    let output = signal(untrack(|| source.unwrap_value()));
+    let timer: Rc<Cell<Option<TimerId>>> = Rc::default();
+    let trailing: Rc<RefCell<Option<T>>> = Rc::default();
+
+    let mut first = true;
+    let dispose = effect_sync({
+        let (output, timer) = (output.clone(), timer.clone());
+        move || {
+            let value = source.unwrap_value();
+            if std::mem::take(&mut first) {
+                return;
+            }
+            if timer.get().is_some() {
+                *trailing.borrow_mut() = Some(value);
+                return;
+            }
+            output.set(value);
+
+            // Periodic while changes keep arriving, stopped by a quiet one
+            let (weak, running, trailing) = (output.downgrade(), timer.clone(), trailing.clone());
+            let id = set_timer(
+                interval,
+                Some(interval),
+                Box::new(move |_| {
+                    let latest = trailing.borrow_mut().take();
+                    let written = latest.is_some_and(|value| {
+                        write_weak(&weak, |output| {
+                            output.set(value);
+                        })
+                    });
+                    if !written {
+                        running.set(None);
+                    }
+                    written
+                }),
+            );
+            timer.set(Some(id));
+        }
+    });
+
+    let guard = WrapperGuard {
+        dispose: Some(Box::new(dispose)),
+        timer,
+    };
+    derived(move || {
+        let _guard = &guard;
+        output.get()
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::testing::with_isolated_runtime;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn timers_follow_the_manual_clock() {
+        with_isolated_runtime(|| {
+            let clock = ManualClock::new();
+            set_clock(clock.clone());
+
+            let ticks = signal_interval(10 * MS);
+            let done = signal_timeout(25 * MS);
+            assert_eq!(until_next_timer(), Some(10 * MS));
+
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            let _watch = effect_sync({
+                let (ticks, done, seen) = (ticks.clone(), done.clone(), seen.clone());
+                move || seen.borrow_mut().push((ticks.get(), done.get()))
+            });
+
+            assert_eq!(clock.advance(9 * MS), 0);
+            assert_eq!(clock.advance(MS), 1);
+            // Both due at once: one batch, one effect run
+            clock.advance(20 * MS);
+            assert_eq!(*seen.borrow(), [(0, false), (1, false), (3, true)]);
+
+            // Dropping the signal ends its interval
+            drop(_watch);
+            drop(ticks);
+            clock.advance(10 * MS);
+            assert_eq!(until_next_timer(), None);
+        });
+    }
+
+    #[test]
+    fn debounce_and_throttle_wrappers() {
+        with_isolated_runtime(|| {
+            let clock = ManualClock::new();
+            set_clock(clock.clone());
+            let source = signal(0);
+            let settled = debounced(source.clone(), 10 * MS);
+            let sampled = throttled(source.clone(), 10 * MS);
+
+            for n in 1..=3 {
+                source.set(n);
+                clock.advance(3 * MS);
+            }
+            assert_eq!((settled.get(), sampled.get()), (0, 1));
+
+            // 10ms: the throttle lets the latest through and keeps going
+            clock.advance(MS);
+            assert_eq!((settled.get(), sampled.get()), (0, 3));
+
+            // 20ms: debounce settled 10ms after the last write; the
+            // throttle had nothing new and stopped
+            clock.advance(10 * MS);
+            assert_eq!((settled.get(), sampled.get()), (3, 3));
+            assert_eq!(until_next_timer(), None);
+
+            source.set(4);
+            assert_eq!(sampled.get(), 4);
+            drop((settled, sampled));
+            assert_eq!(until_next_timer(), None);
+        });
+    }
+}
@@ -0,0 +1,257 @@
+// ============================================================================
+// spark-signals - Watch
+// Run a callback when explicitly listed sources change
+// Based on Vue's watch()
+// ============================================================================
+//
+// An effect depends on whatever it happens to read, which is sometimes more
+// than intended (a helper reads a signal, a log line formats one). `watch`
+// splits the two halves apart:
+//
+//     let _stop = watch((a.clone(), b.clone()), |new, old| { .. }, WatchOptions::default());
+//
+// - Only `sources` are tracked.
+// - The callback runs untracked, with the new values and the previous ones.
+// - By default the callback first runs on the first change, not on creation
+//   (`immediate` changes that); `once` stops after the first call.
+// ============================================================================
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::primitives::derived::Derived;
+use crate::primitives::effect::effect;
+use crate::primitives::signal::{ReadSignal, Signal};
+use crate::reactivity::batching::untrack;
+
+// =============================================================================
+// WATCH SOURCE
+// =============================================================================
+
+/// Something `watch()` can track: a signal, a derived, or a tuple or `Vec` of
+/// them.
+pub trait WatchSource: 'static {
+    /// The values handed to the callback.
+    type Value: 'static;
+
+    /// Read the current value(s), tracking them.
+    fn read(&self) -> Self::Value;
+}
+
+impl<T: Clone + 'static> WatchSource for Signal<T> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        self.get()
+    }
+}
+
+impl<T: Clone + 'static> WatchSource for ReadSignal<T> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        self.get()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> WatchSource for Derived<T> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        self.get()
+    }
+}
+
+impl<S: WatchSource> WatchSource for Vec<S> {
+    type Value = Vec<S::Value>;
+
+    fn read(&self) -> Self::Value {
+        self.iter().map(WatchSource::read).collect()
+    }
+}
+
+macro_rules! impl_watch_source_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: WatchSource),+> WatchSource for ($($name,)+) {
+            type Value = ($($name::Value,)+);
+
+            fn read(&self) -> Self::Value {
+                ($(self.$index.read(),)+)
+            }
+        }
+    };
+}
+
+impl_watch_source_tuple!(A 0);
+impl_watch_source_tuple!(A 0, B 1);
+impl_watch_source_tuple!(A 0, B 1, C 2);
+impl_watch_source_tuple!(A 0, B 1, C 2, D 3);
+
+// =============================================================================
+// WATCH
+// =============================================================================
+
+/// Options for [`watch()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Also call the callback right away, with no old value.
+    pub immediate: bool,
+    /// Stop watching after the first call.
+    pub once: bool,
+}
+
+/// Call `callback(new, old)` whenever one of `sources` changes.
+///
+/// Only `sources` are dependencies; the callback runs untracked, so reading
+/// other signals inside it never re-triggers the watcher. The callback only
+/// runs when the values differ from the last ones seen, and `old` is `None`
+/// only for the `immediate` call. Like any effect, the watcher belongs to
+/// the surrounding effect scope; the returned closure stops it.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{signal, watch, WatchOptions};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let width = signal(2);
+/// let height = signal(3);
+/// let scale = signal(1); // read in the callback, but not watched
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// let _stop = watch(
+///     (width.clone(), height.clone()),
+///     {
+///         let (log, scale) = (log.clone(), scale.clone());
+///         move |(w, h): &(i32, i32), old: Option<&(i32, i32)>| {
+///             log.borrow_mut().push((w * h * scale.get(), old.copied()));
+///         }
+///     },
+///     WatchOptions::default(),
+/// );
+/// assert!(log.borrow().is_empty());
+///
+/// width.set(4);
+/// assert_eq!(*log.borrow(), [(12, Some((2, 3)))]);
+///
+/// scale.set(10); // not a source: no call
+/// assert_eq!(log.borrow().len(), 1);
+/// ```
+pub fn watch<S, F>(sources: S, mut callback: F, options: WatchOptions) -> impl FnOnce()
+where
+    S: WatchSource,
+    S::Value: PartialEq,
+    F: FnMut(&S::Value, Option<&S::Value>) + 'static,
+{
+    let mut previous: Option<S::Value> = None;
+    let done = Rc::new(Cell::new(false));
+
+    effect(move || {
+        if done.get() {
+            // Reading nothing leaves the effect without deps: it never runs again
+            return;
+        }
+
+        let current = sources.read();
+        if previous.as_ref() == Some(&current) {
+            // Re-run without a real change (e.g. a derived recomputed to the same value)
+            return;
+        }
+        if previous.is_some() || options.immediate {
+            untrack(|| callback(&current, previous.as_ref()));
+            if options.once {
+                done.set(true);
+            }
+        }
+        previous = Some(current);
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::derived::derived;
+    use crate::primitives::signal::signal;
+    use crate::reactivity::batching::batch;
+    use std::cell::RefCell;
+
+    type Log<T> = Rc<RefCell<Vec<(T, Option<T>)>>>;
+
+    fn logger<T: Clone + 'static>(log: &Log<T>) -> impl FnMut(&T, Option<&T>) + 'static {
+        let log = log.clone();
+        move |new, old| log.borrow_mut().push((new.clone(), old.cloned()))
+    }
+
+    #[test]
+    fn immediate_runs_on_creation_without_old_value() {
+        let count = signal(1);
+        let log: Log<i32> = Rc::default();
+        let _stop = watch(
+            count.clone(),
+            logger(&log),
+            WatchOptions {
+                immediate: true,
+                ..WatchOptions::default()
+            },
+        );
+        assert_eq!(*log.borrow(), [(1, None)]);
+
+        count.set(2);
+        assert_eq!(*log.borrow(), [(1, None), (2, Some(1))]);
+    }
+
+    #[test]
+    fn once_stops_after_first_call() {
+        let count = signal(0);
+        let log: Log<i32> = Rc::default();
+        let _stop = watch(
+            count.clone(),
+            logger(&log),
+            WatchOptions {
+                once: true,
+                ..WatchOptions::default()
+            },
+        );
+
+        count.set(1);
+        count.set(2);
+        assert_eq!(*log.borrow(), [(1, Some(0))]);
+    }
+
+    #[test]
+    fn watches_a_list_of_deriveds() {
+        let a = signal(1);
+        let b = signal(2);
+        let sources = vec![
+            derived({
+                let a = a.clone();
+                move || a.get() % 2
+            }),
+            derived({
+                let b = b.clone();
+                move || b.get()
+            }),
+        ];
+        let log: Log<Vec<i32>> = Rc::default();
+        let stop = watch(sources, logger(&log), WatchOptions::default());
+
+        // Derived value unchanged: no call
+        a.set(3);
+        assert!(log.borrow().is_empty());
+
+        batch(|| {
+            a.set(4);
+            b.set(5);
+        });
+        assert_eq!(*log.borrow(), [(vec![0, 5], Some(vec![1, 2]))]);
+
+        stop();
+        b.set(6);
+        assert_eq!(log.borrow().len(), 1);
+    }
+}
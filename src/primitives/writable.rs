@@ -0,0 +1,169 @@
+// ============================================================================
+// spark-signals - Writable Deriveds
+// Computed values with a setter that writes back to their sources
+// ============================================================================
+//
+// A derived is read-only; `linked_signal` can be written but only overrides
+// its value until the source changes. A writable derived maps both ways,
+// like Vue's writable computed:
+//
+//     let fahrenheit = derived_writable(
+//         move || celsius.get() * 9.0 / 5.0 + 32.0,
+//         move |f| celsius.set((f - 32.0) * 5.0 / 9.0),
+//     );
+//     fahrenheit.set(212.0); // celsius is now 100
+//
+// The value is always the getter's: a write only takes effect through what
+// the setter writes to the sources.
+// ============================================================================
+
+use std::rc::Rc;
+
+use crate::primitives::derived::{derived, Derived};
+use crate::primitives::props::UnwrapProp;
+use crate::reactivity::batching::{batch, untrack};
+
+/// A derived whose writes go through a setter.
+/// Created with [`derived_writable()`].
+pub struct WritableDerived<T> {
+    derived: Derived<T>,
+    setter: Rc<dyn Fn(T)>,
+}
+
+impl<T: Clone + PartialEq + 'static> WritableDerived<T> {
+    /// Read the value (tracked), recomputing it if a source changed.
+    pub fn get(&self) -> T {
+        self.derived.get()
+    }
+
+    /// Write through the setter.
+    ///
+    /// The setter runs in a batch, so effects see all of its source writes
+    /// at once.
+    pub fn set(&self, value: T) {
+        batch(|| (self.setter)(value));
+    }
+
+    /// Write `f(current value)` through the setter. The current value is
+    /// read untracked.
+    pub fn update(&self, f: impl FnOnce(T) -> T) {
+        let current = untrack(|| self.derived.get());
+        self.set(f(current));
+    }
+
+    /// The read side, as a plain derived.
+    pub fn derived(&self) -> &Derived<T> {
+        &self.derived
+    }
+}
+
+impl<T: Clone> Clone for WritableDerived<T> {
+    fn clone(&self) -> Self {
+        Self {
+            derived: self.derived.clone(),
+            setter: self.setter.clone(),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug + Clone + PartialEq + 'static> std::fmt::Debug for WritableDerived<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WritableDerived")
+            .field("value", &untrack(|| self.derived.get()))
+            .finish()
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> UnwrapProp<T> for WritableDerived<T> {
+    fn unwrap_value(&self) -> T {
+        self.get()
+    }
+}
+
+/// Create a derived that can be written: reads run `getter` (cached like
+/// any derived), writes call `setter`, which typically writes back to the
+/// getter's sources.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{derived_writable, signal};
+///
+/// let celsius = signal(0.0);
+/// let fahrenheit = derived_writable(
+///     {
+///         let celsius = celsius.clone();
+///         move || celsius.get() * 9.0 / 5.0 + 32.0
+///     },
+///     {
+///         let celsius = celsius.clone();
+///         move |f: f64| {
+///             celsius.set((f - 32.0) * 5.0 / 9.0);
+///         }
+///     },
+/// );
+/// assert_eq!(fahrenheit.get(), 32.0);
+///
+/// fahrenheit.set(212.0);
+/// assert_eq!(celsius.get(), 100.0);
+///
+/// celsius.set(-40.0);
+/// assert_eq!(fahrenheit.get(), -40.0);
+/// ```
+pub fn derived_writable<T, G, S>(getter: G, setter: S) -> WritableDerived<T>
+where
+    T: Clone + PartialEq + 'static,
+    G: Fn() -> T + 'static,
+    S: Fn(T) + 'static,
+{
+    WritableDerived {
+        derived: derived(getter),
+        setter: Rc::new(setter),
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use std::cell::RefCell;
+
+    #[test]
+    fn setter_writes_sources_in_one_batch() {
+        let first = signal(String::from("Ada"));
+        let last = signal(String::from("Lovelace"));
+        let full = derived_writable(
+            {
+                let (first, last) = (first.clone(), last.clone());
+                move || format!("{} {}", first.get(), last.get())
+            },
+            {
+                let (first, last) = (first.clone(), last.clone());
+                move |name: String| {
+                    let (f, l) = name.split_once(' ').unwrap_or((&name, ""));
+                    first.set(f.to_string());
+                    last.set(l.to_string());
+                }
+            },
+        );
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_sync({
+            let (full, seen) = (full.clone(), seen.clone());
+            move || seen.borrow_mut().push(full.get())
+        });
+
+        full.set("Grace Hopper".into());
+        assert_eq!((first.get(), last.get()), ("Grace".into(), "Hopper".into()));
+        assert_eq!(*seen.borrow(), ["Ada Lovelace", "Grace Hopper"]);
+
+        full.update(|name| name.to_uppercase());
+        assert_eq!(full.get(), "GRACE HOPPER");
+        assert_eq!(seen.borrow().len(), 3);
+    }
+}
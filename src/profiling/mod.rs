@@ -0,0 +1,382 @@
+// ============================================================================
+// spark-signals - Profiling
+// Per-node counters for the reactive graph, exportable as OpenMetrics
+// ============================================================================
+//
+// Enabled with the `profiling` feature. When enabled, the runtime counts:
+//
+// - effect runs (update_effect)
+// - derived recomputes (update_derived)
+// - source writes (notify_write)
+// - flush passes and their wall-clock duration
+// - derived compute time, for `top_recomputed()`
+//
+// Counters are per reactive context (the thread's, or an entered Runtime's),
+// like the reactive graph itself. Nodes are keyed
+// by the address of their inner allocation; a node's counters are dropped
+// with it, so a new node reusing the address starts from zero. Give nodes
+// readable names with `set_node_name()` so dashboards don't have to show
+// raw addresses.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::core::context::{with_context, ReactiveContext};
+
+// =============================================================================
+// COUNTERS
+// =============================================================================
+
+/// Kind of graph node a counter belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Signal,
+    Derived,
+    Effect,
+}
+
+#[derive(Default)]
+struct NodeCounters {
+    /// Runs (effects), recomputes (deriveds), or writes (signals), by kind
+    counts: [u64; 3],
+    /// Total compute time (deriveds)
+    duration: Duration,
+    name: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct Profile {
+    nodes: HashMap<usize, NodeCounters>,
+    flush_count: u64,
+    flush_duration: Duration,
+}
+
+fn with_profile<R>(f: impl FnOnce(&RefCell<Profile>) -> R) -> R {
+    with_context(|ctx| f(&ctx.profile))
+}
+
+fn kind_index(kind: NodeKind) -> usize {
+    match kind {
+        NodeKind::Signal => 0,
+        NodeKind::Derived => 1,
+        NodeKind::Effect => 2,
+    }
+}
+
+/// Stable id for a node: the address of its inner allocation.
+pub fn node_id<T: ?Sized>(node: &Rc<T>) -> usize {
+    Rc::as_ptr(node) as *const () as usize
+}
+
+/// Count one run/recompute/write for a node.
+pub(crate) fn record(kind: NodeKind, id: usize) {
+    with_profile(|profile| {
+        profile.borrow_mut().nodes.entry(id).or_default().counts[kind_index(kind)] += 1;
+    });
+}
+
+/// Add one derived computation's duration.
+pub(crate) fn record_duration(id: usize, elapsed: Duration) {
+    with_profile(|profile| {
+        profile.borrow_mut().nodes.entry(id).or_default().duration += elapsed;
+    });
+}
+
+/// Drop a node's counters when the node is freed.
+pub(crate) fn forget(ctx: &ReactiveContext, id: usize) {
+    if let Ok(mut profile) = ctx.profile.try_borrow_mut() {
+        profile.nodes.remove(&id);
+    }
+}
+
+/// Count one flush pass that started at `start`.
+pub(crate) fn record_flush(start: Instant) {
+    let elapsed = start.elapsed();
+    with_profile(|profile| {
+        let mut profile = profile.borrow_mut();
+        profile.flush_count += 1;
+        profile.flush_duration += elapsed;
+    });
+}
+
+/// Label a node in exported metrics.
+///
+/// Accepts any inner `Rc` - `signal.inner()`, `derived.inner()`,
+/// `effect.inner()`, or an `Rc<dyn AnySource>`.
+pub fn set_node_name<T: ?Sized>(node: &Rc<T>, name: impl Into<String>) {
+    let id = node_id(node);
+    with_profile(|profile| {
+        profile.borrow_mut().nodes.entry(id).or_default().name = Some(name.into());
+    });
+}
+
+/// Count of runs/recomputes/writes recorded for a node.
+pub fn node_count<T: ?Sized>(node: &Rc<T>, kind: NodeKind) -> u64 {
+    let id = node_id(node);
+    with_profile(|profile| {
+        profile
+            .borrow()
+            .nodes
+            .get(&id)
+            .map_or(0, |counters| counters.counts[kind_index(kind)])
+    })
+}
+
+/// One derived in the [`top_recomputed()`] ranking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedProfile {
+    /// Node id (see [`node_id()`])
+    pub node: usize,
+    /// Name given with `set_node_name()`, if any
+    pub name: Option<String>,
+    /// Recomputes since the last `reset()`
+    pub recomputes: u64,
+    /// Total compute time since the last `reset()` (zero on wasm32)
+    pub total_time: Duration,
+}
+
+/// The `n` deriveds recomputed most often on this thread, most first.
+///
+/// Ties are broken by total compute time.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::profiling::{reset, set_node_name, top_recomputed};
+/// use spark_signals::{derived, signal};
+///
+/// reset();
+/// let count = signal(0);
+/// let hot = derived({
+///     let count = count.clone();
+///     move || count.get() + 1
+/// });
+/// set_node_name(hot.inner(), "hot");
+/// for n in 1..=3 {
+///     count.set(n);
+///     hot.get();
+/// }
+///
+/// let top = top_recomputed(5);
+/// assert_eq!(top[0].name.as_deref(), Some("hot"));
+/// assert_eq!(top[0].recomputes, 3);
+/// ```
+pub fn top_recomputed(n: usize) -> Vec<DerivedProfile> {
+    let derived = kind_index(NodeKind::Derived);
+    with_profile(|profile| {
+        let mut ranked: Vec<DerivedProfile> = profile
+            .borrow()
+            .nodes
+            .iter()
+            .filter(|(_, counters)| counters.counts[derived] > 0)
+            .map(|(&node, counters)| DerivedProfile {
+                node,
+                name: counters.name.clone(),
+                recomputes: counters.counts[derived],
+                total_time: counters.duration,
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            (b.recomputes, b.total_time, a.node).cmp(&(a.recomputes, a.total_time, b.node))
+        });
+        ranked.truncate(n);
+        ranked
+    })
+}
+
+/// Clear all counters and names on this thread.
+pub fn reset() {
+    with_profile(|profile| *profile.borrow_mut() = Profile::default());
+}
+
+// =============================================================================
+// OPENMETRICS EXPORT
+// =============================================================================
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Render this thread's counters in the OpenMetrics text format.
+///
+/// Emits `spark_signal_writes`, `spark_derived_recomputes`, and
+/// `spark_effect_runs` counters with a `node` label (and `name`, if set),
+/// plus a `spark_flush_duration_seconds` summary. Serve the string from
+/// your metrics endpoint.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, signal};
+/// use spark_signals::profiling::{export_openmetrics, set_node_name};
+///
+/// let count = signal(0);
+/// set_node_name(count.inner(), "count");
+///
+/// let _dispose = effect_sync({
+///     let count = count.clone();
+///     move || { count.get(); }
+/// });
+/// count.set(1);
+///
+/// let text = export_openmetrics();
+/// assert!(text.contains("# TYPE spark_signal_writes counter"));
+/// assert!(text.contains(r#"name="count"} 1"#));
+/// assert!(text.ends_with("# EOF\n"));
+/// ```
+pub fn export_openmetrics() -> String {
+    const FAMILIES: [(NodeKind, &str, &str); 3] = [
+        (NodeKind::Signal, "spark_signal_writes", "Source writes that notified the graph."),
+        (NodeKind::Derived, "spark_derived_recomputes", "Derived recomputations."),
+        (NodeKind::Effect, "spark_effect_runs", "Effect executions."),
+    ];
+
+    with_profile(|profile| {
+        let profile = profile.borrow();
+        let mut ids: Vec<&usize> = profile.nodes.keys().collect();
+        ids.sort();
+
+        let mut out = String::new();
+        for (kind, metric, help) in FAMILIES {
+            let _ = writeln!(out, "# TYPE {metric} counter");
+            let _ = writeln!(out, "# HELP {metric} {help}");
+
+            for id in &ids {
+                let counters = &profile.nodes[id];
+                let count = counters.counts[kind_index(kind)];
+                if count == 0 {
+                    continue;
+                }
+
+                let _ = write!(out, "{metric}_total{{node=\"{:#x}\"", id);
+                if let Some(name) = &counters.name {
+                    let _ = write!(out, ",name=\"{}\"", escape_label(name));
+                }
+                let _ = writeln!(out, "}} {count}");
+            }
+        }
+
+        let metric = "spark_flush_duration_seconds";
+        let _ = writeln!(out, "# TYPE {metric} summary");
+        let _ = writeln!(out, "# UNIT {metric} seconds");
+        let _ = writeln!(out, "# HELP {metric} Wall-clock time spent flushing effects.");
+        let _ = writeln!(out, "{metric}_count {}", profile.flush_count);
+        let _ = writeln!(out, "{metric}_sum {}", profile.flush_duration.as_secs_f64());
+
+        out.push_str("# EOF\n");
+        out
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::derived::derived;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+
+    #[test]
+    fn counts_writes_recomputes_and_runs() {
+        reset();
+        let a = signal(1);
+        let doubled = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+        let _dispose = effect_sync({
+            let doubled = doubled.clone();
+            move || {
+                doubled.get();
+            }
+        });
+
+        a.set(2);
+        a.set(3);
+
+        assert_eq!(node_count(a.inner(), NodeKind::Signal), 2);
+        assert_eq!(node_count(doubled.inner(), NodeKind::Derived), 3);
+        assert!(with_profile(|p| p.borrow().flush_count) >= 2);
+    }
+
+    #[test]
+    fn counters_are_dropped_with_their_node() {
+        reset();
+        let tracked = |id: usize| with_profile(|p| p.borrow().nodes.contains_key(&id));
+        let a = signal(1);
+        let doubled = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+        a.set(2);
+        doubled.get();
+        let (a_id, doubled_id) = (node_id(a.inner()), node_id(doubled.inner()));
+        assert!(tracked(a_id) && tracked(doubled_id));
+
+        drop(doubled);
+        assert!(!tracked(doubled_id));
+        drop(a);
+        assert!(!tracked(a_id));
+    }
+
+    #[test]
+    fn export_labels_named_nodes() {
+        reset();
+        let a = signal(0);
+        set_node_name(a.inner(), "needs \"escaping\"");
+        a.set(1);
+
+        let text = export_openmetrics();
+        let id = node_id(a.inner());
+        assert!(text.contains(&format!(
+            "spark_signal_writes_total{{node=\"{:#x}\",name=\"needs \\\"escaping\\\"\"}} 1",
+            id
+        )));
+        assert!(text.contains("# TYPE spark_effect_runs counter"));
+        assert!(text.contains("spark_flush_duration_seconds_count"));
+    }
+
+    #[test]
+    fn reset_clears_counters() {
+        reset();
+        let a = signal(0);
+        a.set(1);
+        reset();
+        assert_eq!(node_count(a.inner(), NodeKind::Signal), 0);
+        assert!(!export_openmetrics().contains("spark_signal_writes_total"));
+    }
+
+    #[test]
+    fn top_recomputed_ranks_hot_deriveds() {
+        reset();
+        let a = signal(0);
+        let cold = derived({
+            let a = a.clone();
+            move || a.get() > 100
+        });
+        let hot = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+        cold.get();
+        for n in 1..=3 {
+            a.set(n);
+            hot.get();
+        }
+
+        let top = top_recomputed(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!((top[0].node, top[0].recomputes), (node_id(hot.inner()), 3));
+        assert_eq!(top_recomputed(10).len(), 2);
+    }
+}
@@ -6,8 +6,19 @@
 // Ported from @rlabs-inc/signals batching.ts
 // ============================================================================
 
+use std::cell::RefCell;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use crate::core::constants::{DERIVED, DIRTY, MAYBE_DIRTY};
 use crate::core::context::with_context;
-use crate::reactivity::scheduling::flush_pending_reactions;
+use crate::core::types::{AnyReaction, AnySource, SourceInner};
+use crate::reactivity::scheduling::{flush_pending_reactions, is_manual_flush};
+use crate::reactivity::tracking::{notify_write, set_signal_status};
 
 // =============================================================================
 // BATCH
@@ -17,6 +28,11 @@ use crate::reactivity::scheduling::flush_pending_reactions;
 ///
 /// Without batching, each signal update triggers effects immediately.
 /// With batching, effects only run once after all updates complete.
+/// Returns whatever `f` returns.
+///
+/// Batches nest: an inner batch joins the outer one, and pending effects
+/// run once, when the outermost batch ends (also if `f` panics). For code
+/// that isn't closure-shaped, see [`start_batch()`].
 ///
 /// # Example
 ///
@@ -43,33 +59,92 @@ use crate::reactivity::scheduling::flush_pending_reactions;
 ///
 /// // Without batch: would run effect twice (once per update)
 /// // With batch: runs effect once (after both updates)
-/// batch(|| {
+/// let sum = batch(|| {
 ///     a.set(10);
 ///     b.set(20);
+///     a.get() + b.get()
 /// });
 ///
 /// // Effect ran only once more (not twice)
-/// assert_eq!(run_count.get(), 2);
+/// assert_eq!((sum, run_count.get()), (30, 2));
 /// ```
 pub fn batch<T>(f: impl FnOnce() -> T) -> T {
-    with_context(|ctx| ctx.enter_batch());
+    let _guard = start_batch();
+    f()
+}
 
-    // Use a guard pattern to ensure we exit the batch even on panic
-    struct BatchGuard;
+/// An open batch, from [`start_batch()`]. Ends the batch when dropped.
+///
+/// Tied to the thread that opened it.
+#[must_use = "the batch ends as soon as the guard is dropped"]
+pub struct BatchGuard {
+    /// Covers the flush at the end of the outermost batch too
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+    _not_send: PhantomData<Rc<()>>,
+}
 
-    impl Drop for BatchGuard {
-        fn drop(&mut self) {
-            let depth = with_context(|ctx| ctx.exit_batch());
+impl BatchGuard {
+    /// End the batch now. Same as dropping the guard.
+    pub fn end(self) {}
+}
 
-            // When outermost batch completes, flush pending reactions
-            if depth == 0 {
-                flush_pending_reactions();
-            }
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        let depth = with_context(|ctx| ctx.exit_batch());
+
+        // When outermost batch completes, flush pending reactions
+        // (unless a TestScheduler is holding them)
+        if depth == 0 && !is_manual_flush() {
+            flush_pending_reactions();
         }
     }
+}
 
-    let _guard = BatchGuard;
-    f()
+impl std::fmt::Debug for BatchGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchGuard").finish_non_exhaustive()
+    }
+}
+
+/// Open a batch that lasts until the returned guard is dropped.
+///
+/// Like [`batch()`], but for spans of code that aren't a closure, such as
+/// one iteration of an event loop. Guards nest with each other and with
+/// `batch()`; pending effects run when the last one is dropped.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect, signal, start_batch};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let x = signal(0);
+/// let runs = Rc::new(Cell::new(0));
+/// let _log = effect({
+///     let (x, runs) = (x.clone(), runs.clone());
+///     move || {
+///         x.get();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+///
+/// let guard = start_batch();
+/// for event in [1, 2, 3] {
+///     x.set(event);
+/// }
+/// assert_eq!(runs.get(), 1);
+/// drop(guard); // effects run here
+/// assert_eq!(runs.get(), 2);
+/// ```
+pub fn start_batch() -> BatchGuard {
+    let _depth = with_context(|ctx| ctx.enter_batch());
+    BatchGuard {
+        #[cfg(feature = "tracing")]
+        _span: tracing::trace_span!("batch", depth = _depth).entered(),
+        _not_send: PhantomData,
+    }
 }
 
 /// Check if currently inside a batch.
@@ -91,6 +166,200 @@ pub fn is_batching() -> bool {
     with_context(|ctx| ctx.is_batching())
 }
 
+// =============================================================================
+// TRANSACTION
+// =============================================================================
+
+/// Undo entry for one source written inside a transaction.
+pub(crate) struct Staged {
+    source: Rc<dyn AnySource>,
+    rollback: Box<dyn FnOnce()>,
+}
+
+/// The active context's open transaction journals.
+fn with_transactions<R>(f: impl FnOnce(&RefCell<Vec<Vec<Staged>>>) -> R) -> R {
+    with_context(|ctx| f(&ctx.transactions))
+}
+
+/// Check if currently inside a `transaction()`.
+pub(crate) fn in_transaction() -> bool {
+    with_transactions(|t| !t.borrow().is_empty())
+}
+
+/// Record a staged write; `old` is the value before it.
+///
+/// Only the first write to a source per transaction is kept - that's the
+/// value a rollback restores.
+fn journal<T: 'static>(inner: &Rc<SourceInner<T>>, old: T) {
+    with_transactions(|t| {
+        let mut t = t.borrow_mut();
+        let Some(journal) = t.last_mut() else {
+            return;
+        };
+        let source: Rc<dyn AnySource> = inner.clone();
+        if journal.iter().any(|s| Rc::ptr_eq(&s.source, &source)) {
+            return;
+        }
+        let inner = inner.clone();
+        journal.push(Staged {
+            source,
+            rollback: Box::new(move || inner.set_untracked(old)),
+        });
+    });
+}
+
+/// `Signal::set` inside a transaction: write the value, defer notification.
+pub(crate) fn stage_set<T: 'static>(inner: &Rc<SourceInner<T>>, value: T) -> bool {
+    if inner.with(|current| inner.is_equal(current, &value)) {
+        return false;
+    }
+    let mut old = None;
+    inner.update_untracked(|current| old = Some(std::mem::replace(current, value)));
+    if let Some(old) = old {
+        journal(inner, old);
+    }
+    true
+}
+
+/// `Signal::update` inside a transaction: mutate in place, defer notification.
+pub(crate) fn stage_update<T: Clone + 'static>(inner: &Rc<SourceInner<T>>, f: impl FnOnce(&mut T)) {
+    let old = inner.get();
+    inner.update_untracked(f);
+    journal(inner, old);
+}
+
+/// Run `f` as one all-or-nothing unit of signal writes.
+///
+/// Signal writes (`set` / `update`) inside the closure are staged: reading
+/// the signal returns the new value, but nothing downstream is notified yet.
+/// - `Ok`: every written signal is notified at once and effects run once,
+///   as with `batch()`.
+/// - `Err` or a panic: every written signal gets its value from before the
+///   transaction back, and no effect is notified. Deriveds downstream are
+///   marked dirty, since one read inside the transaction cached a staged
+///   value. Panics are resumed after the rollback.
+///
+/// Deriveds computed before the transaction keep their cached value until
+/// it commits.
+/// Only signals are journaled; other writes (slots, collections) apply
+/// immediately and are merely batched.
+///
+/// Nested transactions fold into the enclosing one on success and roll back
+/// only their own writes on failure.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, signal, transaction};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let from = signal(100);
+/// let to = signal(0);
+/// let runs = Rc::new(Cell::new(0));
+/// let _dispose = effect_sync({
+///     let (from, to, runs) = (from.clone(), to.clone(), runs.clone());
+///     move || {
+///         let _ = from.get() + to.get();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+///
+/// let transfer = |amount: i32| {
+///     transaction(|| {
+///         from.update(|v| *v -= amount);
+///         to.update(|v| *v += amount);
+///         if from.get() < 0 { Err("insufficient funds") } else { Ok(()) }
+///     })
+/// };
+///
+/// assert_eq!(transfer(30), Ok(()));
+/// assert_eq!((from.get(), to.get(), runs.get()), (70, 30, 2));
+///
+/// assert_eq!(transfer(500), Err("insufficient funds"));
+/// assert_eq!((from.get(), to.get(), runs.get()), (70, 30, 2));
+/// ```
+pub fn transaction<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    batch(|| {
+        with_transactions(|t| t.borrow_mut().push(Vec::new()));
+        let result = catch_unwind(AssertUnwindSafe(f));
+        let staged = with_transactions(|t| t.borrow_mut().pop()).unwrap_or_default();
+
+        match result {
+            Ok(Ok(value)) => {
+                commit(staged);
+                Ok(value)
+            }
+            Ok(Err(error)) => {
+                rollback(staged);
+                Err(error)
+            }
+            Err(payload) => {
+                rollback(staged);
+                resume_unwind(payload)
+            }
+        }
+    })
+}
+
+/// Hand staged writes to the enclosing transaction, or notify them.
+fn commit(staged: Vec<Staged>) {
+    let mut staged = staged.into_iter();
+    with_transactions(|t| {
+        if let Some(parent) = t.borrow_mut().last_mut() {
+            for entry in staged.by_ref() {
+                // The parent's entry holds the older value
+                if !parent.iter().any(|s| Rc::ptr_eq(&s.source, &entry.source)) {
+                    parent.push(entry);
+                }
+            }
+        }
+    });
+
+    for Staged { source, .. } in staged {
+        with_context(|ctx| {
+            let wv = ctx.increment_write_version();
+            source.set_write_version(wv);
+        });
+        notify_write(source);
+    }
+}
+
+/// Restore pre-transaction values, latest writes first.
+fn rollback(staged: Vec<Staged>) {
+    let mut sources = Vec::with_capacity(staged.len());
+    for Staged { source, rollback } in staged.into_iter().rev() {
+        rollback();
+        sources.push(source);
+    }
+    for source in sources {
+        invalidate_deriveds(source);
+    }
+}
+
+/// Mark the deriveds downstream of a rolled-back source for recomputation,
+/// without scheduling effects: no effect ran on the staged value.
+fn invalidate_deriveds(source: Rc<dyn AnySource>) {
+    let mut stack: Vec<(Rc<dyn AnySource>, u32)> = vec![(source, DIRTY)];
+    while let Some((source, status)) = stack.pop() {
+        let mut deriveds: Vec<Rc<dyn AnyReaction>> = Vec::new();
+        source.for_each_reaction(&mut |reaction| {
+            if (reaction.flags() & DERIVED) != 0 {
+                deriveds.push(reaction);
+            }
+            true
+        });
+        for derived in deriveds {
+            if (derived.flags() & DIRTY) == 0 {
+                set_signal_status(&*derived, status);
+            }
+            if let Some(derived) = derived.as_derived_source() {
+                stack.push((derived, MAYBE_DIRTY));
+            }
+        }
+    }
+}
+
 // =============================================================================
 // UNTRACK
 // =============================================================================
@@ -223,6 +492,83 @@ pub fn tick() {
     crate::reactivity::scheduling::flush_sync();
 }
 
+/// True if nothing is left to run: no batch or flush in progress and no
+/// effect waiting in the queue (e.g. held by a `TestScheduler`).
+pub fn is_settled() -> bool {
+    with_context(|ctx| !ctx.is_batching() && !ctx.is_flushing_sync())
+        && crate::reactivity::scheduling::pending_effect_count() == 0
+}
+
+/// Future returned by [`await_settled()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Settled {
+    last_version: Option<u32>,
+}
+
+impl Future for Settled {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let before = with_context(|ctx| ctx.get_write_version());
+        crate::reactivity::scheduling::flush_sync();
+        let after = with_context(|ctx| ctx.get_write_version());
+
+        // Quiet twice in a row: nothing ran in this flush, and no other task
+        // wrote since the last poll
+        if before == after && self.last_version == Some(after) && is_settled() {
+            return Poll::Ready(());
+        }
+        self.last_version = Some(after);
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Wait until the graph is settled.
+///
+/// Each poll flushes pending effects, then yields so other tasks (e.g.
+/// `effect_async` runs) can make progress. The future resolves once a flush
+/// had nothing to run and nothing was written since the previous poll.
+/// Awaiting it inside a `batch()` never resolves.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{await_settled, effect, is_settled, signal, TestScheduler};
+/// use std::cell::Cell;
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::rc::Rc;
+/// use std::task::{Context, Poll, Waker};
+///
+/// let _scheduler = TestScheduler::new();
+/// let count = signal(0);
+/// let doubled = signal(0);
+/// let seen = Rc::new(Cell::new(0));
+/// let _double = effect({
+///     let (count, doubled) = (count.clone(), doubled.clone());
+///     move || {
+///         doubled.set(count.get() * 2);
+///     }
+/// });
+/// let _log = effect({
+///     let (doubled, seen) = (doubled.clone(), seen.clone());
+///     move || seen.set(doubled.get())
+/// });
+///
+/// count.set(21);
+/// assert!(!is_settled());
+///
+/// let mut settled = pin!(await_settled());
+/// let mut cx = Context::from_waker(Waker::noop());
+/// while settled.as_mut().poll(&mut cx).is_pending() {}
+/// assert!(is_settled());
+/// assert_eq!(seen.get(), 42);
+/// ```
+pub fn await_settled() -> Settled {
+    Settled { last_version: None }
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -311,6 +657,33 @@ mod tests {
         assert_eq!(a.get(), 4);
     }
 
+    #[test]
+    fn batch_guards_nest_with_batch() {
+        let a = signal(0);
+        let run_count = Rc::new(Cell::new(0));
+        let _dispose = effect({
+            let (a, run_count) = (a.clone(), run_count.clone());
+            move || {
+                a.get();
+                run_count.set(run_count.get() + 1);
+            }
+        });
+
+        let outer = start_batch();
+        batch(|| {
+            let inner = start_batch();
+            a.set(1);
+            inner.end();
+            a.set(2);
+        });
+        assert!(is_batching());
+        assert_eq!(run_count.get(), 1);
+
+        outer.end();
+        assert!(!is_batching());
+        assert_eq!(run_count.get(), 2);
+    }
+
     #[test]
     fn is_batching_flag() {
         assert!(!is_batching());
@@ -391,11 +764,11 @@ mod tests {
 
     #[test]
     fn multiple_signals_batch() {
-        let signals: Vec<_> = (0..10).map(|i| signal(i)).collect();
+        let signals: Vec<_> = (0..10).map(signal).collect();
         let total = Rc::new(Cell::new(0));
         let run_count = Rc::new(Cell::new(0));
 
-        let signals_clone: Vec<_> = signals.iter().map(|s| s.clone()).collect();
+        let signals_clone: Vec<_> = signals.to_vec();
         let total_clone = total.clone();
         let run_count_clone = run_count.clone();
         let _dispose = effect(move || {
@@ -607,4 +980,173 @@ mod tests {
         tick();
         assert_eq!(seen.get(), 100);
     }
+
+    #[test]
+    fn transaction_commits_with_one_notification() {
+        let a = signal(1);
+        let b = signal(2);
+        let runs = Rc::new(Cell::new(0));
+
+        let runs_clone = runs.clone();
+        let a_clone = a.clone();
+        let b_clone = b.clone();
+        let _dispose = effect(move || {
+            let _ = a_clone.get() + b_clone.get();
+            runs_clone.set(runs_clone.get() + 1);
+        });
+
+        let result: Result<i32, ()> = transaction(|| {
+            a.set(10);
+            a.set(11);
+            b.update(|v| *v += 1);
+            // Writes are visible inside the transaction
+            Ok(a.get() + b.get())
+        });
+
+        assert_eq!(result, Ok(14));
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn transaction_rolls_back_on_err_and_panic() {
+        let a = signal(1);
+        let doubled = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+        let runs = Rc::new(Cell::new(0));
+
+        let runs_clone = runs.clone();
+        let doubled_clone = doubled.clone();
+        let _dispose = effect(move || {
+            doubled_clone.get();
+            runs_clone.set(runs_clone.get() + 1);
+        });
+
+        let result: Result<(), &str> = transaction(|| {
+            a.set(5);
+            a.set(6);
+            Err("nope")
+        });
+        assert_eq!(result, Err("nope"));
+        assert_eq!((a.get(), doubled.get(), runs.get()), (1, 2, 1));
+
+        let panicked = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let _: Result<(), ()> = transaction(|| {
+                a.update(|v| *v = 7);
+                panic!("boom");
+            });
+        }));
+        assert!(panicked.is_err());
+        assert_eq!((a.get(), doubled.get(), runs.get()), (1, 2, 1));
+        assert!(!in_transaction());
+        assert!(!is_batching());
+    }
+
+    #[test]
+    fn rollback_discards_deriveds_computed_from_staged_values() {
+        let a = signal(1);
+        let tens = derived({
+            let a = a.clone();
+            move || a.get() * 10
+        });
+        let plus_one = derived({
+            let tens = tens.clone();
+            move || tens.get() + 1
+        });
+
+        let result: Result<(), ()> = transaction(|| {
+            a.set(2);
+            assert_eq!(plus_one.get(), 21);
+            Err(())
+        });
+        assert!(result.is_err());
+        assert_eq!((a.get(), tens.get(), plus_one.get()), (1, 10, 11));
+
+        let panicked = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let _: Result<(), ()> = transaction(|| {
+                a.set(3);
+                assert_eq!(plus_one.get(), 31);
+                panic!("boom");
+            });
+        }));
+        assert!(panicked.is_err());
+        assert_eq!((a.get(), tens.get(), plus_one.get()), (1, 10, 11));
+    }
+
+    #[test]
+    fn nested_transaction_rolls_back_only_its_own_writes() {
+        let a = signal(1);
+        let b = signal(1);
+
+        let result: Result<(), ()> = transaction(|| {
+            a.set(2);
+            let inner: Result<(), ()> = transaction(|| {
+                a.set(3);
+                b.set(3);
+                Err(())
+            });
+            assert!(inner.is_err());
+            assert_eq!((a.get(), b.get()), (2, 1));
+
+            let _: Result<(), ()> = transaction(|| {
+                b.set(4);
+                Ok(())
+            });
+            Err(())
+        });
+
+        // The outer rollback also undoes the committed inner write
+        assert!(result.is_err());
+        assert_eq!((a.get(), b.get()), (1, 1));
+    }
+
+    #[test]
+    fn await_settled_waits_for_async_writes() {
+        use crate::primitives::effect::{effect_async, LocalBoxFuture};
+        use std::task::Waker;
+
+        let tasks: Rc<RefCell<Vec<LocalBoxFuture>>> = Rc::default();
+        let query = signal(1);
+        let result = signal(0);
+        let seen = Rc::new(Cell::new(0));
+
+        let _fetch = effect_async(
+            {
+                let tasks = tasks.clone();
+                move |task| tasks.borrow_mut().push(task)
+            },
+            {
+                let (query, result) = (query.clone(), result.clone());
+                move || {
+                    let (q, result) = (query.get(), result.clone());
+                    async move {
+                        result.set(q * 10);
+                    }
+                }
+            },
+        );
+        let _log = effect({
+            let (result, seen) = (result.clone(), seen.clone());
+            move || seen.set(result.get())
+        });
+
+        query.set(2);
+        assert!(is_settled());
+        assert_eq!(seen.get(), 0);
+
+        // A single-threaded executor: the settled future alongside the tasks
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut settled = std::pin::pin!(await_settled());
+        let mut polls = 0;
+        while settled.as_mut().poll(&mut cx).is_pending() {
+            polls += 1;
+            assert!(polls < 10);
+            let mut pending = std::mem::take(&mut *tasks.borrow_mut());
+            pending.retain_mut(|task| task.as_mut().poll(&mut cx).is_pending());
+            tasks.borrow_mut().extend(pending);
+        }
+        assert_eq!(seen.get(), 20);
+        assert!(tasks.borrow().is_empty());
+    }
 }
@@ -3,6 +3,10 @@
 // Based on Svelte 5's / @rlabs-inc/signals equality checking
 // ============================================================================
 
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::core::config::{update_config, with_config};
 use crate::core::types::EqualsFn;
 
 // =============================================================================
@@ -123,6 +127,7 @@ pub fn safe_equals_option_f64(a: &Option<f64>, b: &Option<f64>) -> bool {
 /// assert!(!shallow_equals_vec(&vec![1, 2, 3], &vec![1, 2, 4]));
 /// assert!(!shallow_equals_vec(&vec![1, 2], &vec![1, 2, 3]));
 /// ```
+#[allow(clippy::ptr_arg)] // Signature must match EqualsFn<Vec<T>>
 pub fn shallow_equals_vec<T: PartialEq>(a: &Vec<T>, b: &Vec<T>) -> bool {
     if a.len() != b.len() {
         return false;
@@ -214,6 +219,126 @@ where
     move |a, b| field_fn(a) == field_fn(b)
 }
 
+// =============================================================================
+// POINTER EQUALITY
+// =============================================================================
+
+/// Shared pointers that can be compared by address.
+pub trait PtrEq {
+    /// True if both point to the same allocation.
+    fn ptr_eq(a: &Self, b: &Self) -> bool;
+}
+
+impl<T: ?Sized> PtrEq for Rc<T> {
+    fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+}
+
+impl<T: ?Sized> PtrEq for Arc<T> {
+    fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+}
+
+/// Pointer equality for `Rc`/`Arc` payloads: equal only if both point to
+/// the same allocation. Skips comparing (possibly large) contents, and
+/// works for payloads without `PartialEq`.
+///
+/// # Example
+/// ```
+/// use spark_signals::reactivity::equality::equals_by_ptr;
+/// use spark_signals::signal_with_equals;
+/// use std::rc::Rc;
+///
+/// let rows = Rc::new(vec![1, 2, 3]);
+/// let s = signal_with_equals(rows.clone(), equals_by_ptr);
+///
+/// assert!(!s.set(rows));                   // same allocation
+/// assert!(s.set(Rc::new(vec![1, 2, 3]))); // equal contents, new allocation
+/// ```
+pub fn equals_by_ptr<P: PtrEq>(a: &P, b: &P) -> bool {
+    P::ptr_eq(a, b)
+}
+
+// =============================================================================
+// EPSILON EQUALITY
+// =============================================================================
+
+/// True if `a` and `b` are within `epsilon` of each other (NaN equals NaN).
+fn within_epsilon(a: f64, b: f64, epsilon: f64) -> bool {
+    if a.is_nan() || b.is_nan() {
+        return a.is_nan() && b.is_nan();
+    }
+    // `a == b` first so equal infinities compare equal
+    a == b || (a - b).abs() <= epsilon
+}
+
+/// Equality within `epsilon` for f64 values. NaN equals NaN.
+///
+/// For a float signal, pass the epsilon as `FloatEquals::Epsilon` instead:
+/// signals take a plain `fn` for custom equality, which can't capture it.
+///
+/// # Example
+/// ```
+/// use spark_signals::reactivity::equality::{equals_epsilon, FloatEquals};
+/// use spark_signals::signal_f64_with;
+///
+/// let close = equals_epsilon(1e-3);
+/// assert!(close(&1.0, &1.0005));
+/// assert!(!close(&1.0, &1.01));
+///
+/// let s = signal_f64_with(1.0, Some(FloatEquals::Epsilon(1e-3)));
+/// assert!(!s.set(1.0005));
+/// ```
+pub fn equals_epsilon(epsilon: f64) -> impl Fn(&f64, &f64) -> bool + Copy {
+    move |a, b| within_epsilon(*a, *b, epsilon)
+}
+
+/// Equality within `epsilon` for f32 values. NaN equals NaN.
+pub fn equals_epsilon_f32(epsilon: f32) -> impl Fn(&f32, &f32) -> bool + Copy {
+    move |a, b| within_epsilon(f64::from(*a), f64::from(*b), f64::from(epsilon))
+}
+
+// =============================================================================
+// DEFAULT FLOAT EQUALITY
+// =============================================================================
+
+/// How float signals compare values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FloatEquals {
+    /// Exact comparison, NaN equals NaN (`safe_equals_f64`)
+    #[default]
+    Exact,
+    /// Values within the epsilon are equal
+    Epsilon(f64),
+}
+
+impl FloatEquals {
+    /// The tolerance, or None for exact comparison. `Epsilon(0.0)` is exact.
+    pub(crate) fn epsilon(self) -> Option<f64> {
+        match self {
+            FloatEquals::Epsilon(epsilon) if epsilon != 0.0 => Some(epsilon.abs()),
+            _ => None,
+        }
+    }
+}
+
+/// Set the default equality of `signal_f64` / `signal_f32` (and
+/// `signal_f64_with(value, None)`) on the current thread.
+///
+/// Shorthand for changing `Config::float_equals` with `set_thread_config()`.
+/// Signals capture the mode when created, so ones that already exist keep
+/// comparing the way they did.
+pub fn set_default_float_equals(mode: FloatEquals) {
+    update_config(|c| c.float_equals = mode);
+}
+
+/// The default float equality in effect on the current thread.
+pub fn default_float_equals() -> FloatEquals {
+    with_config(|c| c.float_equals)
+}
+
 // =============================================================================
 // EQUALITY FUNCTION CONSTRUCTORS (for EqualsFn<T>)
 // =============================================================================
@@ -344,6 +469,7 @@ mod tests {
     #[test]
     fn test_by_field() {
         #[derive(Clone)]
+        #[allow(dead_code)]
         struct User {
             id: u32,
             name: String,
@@ -382,4 +508,51 @@ mod tests {
         let always: EqualsFn<i32> = always_equals_fn();
         assert!(always(&42, &43));
     }
+
+    #[test]
+    fn test_equals_by_ptr() {
+        let a = Rc::new(String::from("x"));
+        assert!(equals_by_ptr(&a, &a.clone()));
+        assert!(!equals_by_ptr(&a, &Rc::new(String::from("x"))));
+
+        let b: Arc<[u8]> = Arc::from(&[1u8, 2][..]);
+        assert!(equals_by_ptr(&b, &b.clone()));
+    }
+
+    #[test]
+    fn test_equals_epsilon() {
+        let close = equals_epsilon(0.1);
+        assert!(close(&1.0, &1.05));
+        assert!(!close(&1.0, &1.2));
+        assert!(close(&f64::NAN, &f64::NAN));
+        assert!(!close(&f64::NAN, &1.0));
+        assert!(close(&f64::INFINITY, &f64::INFINITY));
+        assert!(!close(&f64::INFINITY, &f64::NEG_INFINITY));
+
+        assert!(equals_epsilon_f32(0.5)(&1.0, &1.25));
+    }
+
+    #[test]
+    fn test_default_float_equals() {
+        use crate::core::config::clear_thread_config;
+        use crate::primitives::signal::{signal_f32, signal_f64, signal_f64_with};
+
+        let exact = signal_f64(1.0);
+        set_default_float_equals(FloatEquals::Epsilon(0.01));
+        assert_eq!(default_float_equals(), FloatEquals::Epsilon(0.01));
+        let loose = signal_f64(1.0);
+        let loose_f32 = signal_f32(1.0);
+        let explicit = signal_f64_with(1.0, Some(FloatEquals::Exact));
+        clear_thread_config();
+
+        // Each signal keeps the mode it was created with
+        assert_eq!(default_float_equals(), FloatEquals::Exact);
+        assert!(!loose.set(1.005));
+        assert!(!loose_f32.set(1.005));
+        assert!(loose.set(1.5));
+        assert!(exact.set(1.005));
+        assert!(explicit.set(1.005));
+        assert!(!exact.set(1.005));
+        assert!(!signal_f64(f64::NAN).set(f64::NAN));
+    }
 }
@@ -0,0 +1,415 @@
+// ============================================================================
+// spark-signals - Cross-Thread Inbox
+// Lets background threads request work on the reactive thread
+// ============================================================================
+//
+// The reactive graph is thread-local (Rc/RefCell everywhere), so a worker
+// thread can never touch a Signal directly. Instead it posts a `Send`
+// closure into the reactive thread's inbox; the scheduler drains the inbox
+// at the start of every `flush_sync()` / `tick()`, running the closures
+// inside a single batch.
+//
+// Enabled with the `sync` feature.
+// ============================================================================
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Weak;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::ThreadId;
+
+use crate::core::types::SourceInner;
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::batch;
+
+/// A unit of work posted from another thread.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Callback used to wake the reactive thread's loop after a post.
+type WakeFn = Arc<dyn Fn() + Send + Sync>;
+
+// =============================================================================
+// INBOX
+// =============================================================================
+
+struct Inbox {
+    /// Thread that drains this inbox (claimed on first use)
+    owner: OnceLock<ThreadId>,
+    queue: Mutex<Vec<Job>>,
+    waker: Mutex<Option<WakeFn>>,
+}
+
+impl Inbox {
+    fn new() -> Self {
+        Self {
+            owner: OnceLock::new(),
+            queue: Mutex::new(Vec::new()),
+            waker: Mutex::new(None),
+        }
+    }
+
+    fn push(&self, job: Job) {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner()).push(job);
+
+        let waker = self.waker.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some(wake) = waker {
+            wake();
+        }
+    }
+
+    fn take(&self) -> Vec<Job> {
+        std::mem::take(&mut *self.queue.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn len(&self) -> usize {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+/// Process-wide default inbox, used by the free `post()` function.
+///
+/// Created on first use by any thread; owned by the first thread that
+/// touches its thread-local inbox.
+fn default_inbox() -> &'static Arc<Inbox> {
+    static DEFAULT: OnceLock<Arc<Inbox>> = OnceLock::new();
+    DEFAULT.get_or_init(|| Arc::new(Inbox::new()))
+}
+
+thread_local! {
+    /// This thread's inbox: the default inbox if this thread claimed it first,
+    /// otherwise a private one.
+    static THREAD_INBOX: Arc<Inbox> = {
+        let default = default_inbox();
+        let me = std::thread::current().id();
+        if *default.owner.get_or_init(|| me) == me {
+            default.clone()
+        } else {
+            let inbox = Arc::new(Inbox::new());
+            let _ = inbox.owner.set(me);
+            inbox
+        }
+    };
+
+    /// Signals reachable from `SignalSender`s, by id.
+    static SENDER_TARGETS: RefCell<HashMap<u64, SenderTarget>> = RefCell::new(HashMap::new());
+
+    static NEXT_SENDER_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+// =============================================================================
+// REACTIVE THREAD HANDLE
+// =============================================================================
+
+/// A `Send + Sync` handle to a reactive thread's inbox.
+///
+/// Obtain it on the reactive thread with `reactive_thread()`, then hand
+/// clones to worker threads.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{reactive_thread, signal, flush_sync};
+///
+/// let handle = reactive_thread();
+/// let count = signal(0);
+/// let sender = count.sender();
+///
+/// std::thread::spawn(move || {
+///     sender.set(42);
+///     handle.post(|| println!("runs on the reactive thread"));
+/// })
+/// .join()
+/// .unwrap();
+///
+/// flush_sync(); // drains the inbox
+/// assert_eq!(count.get(), 42);
+/// ```
+#[derive(Clone)]
+pub struct ReactiveThread {
+    inbox: Arc<Inbox>,
+}
+
+impl ReactiveThread {
+    /// Queue a closure to run on the reactive thread at its next flush.
+    pub fn post<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.inbox.push(Box::new(f));
+    }
+
+    /// Install a callback invoked (on the posting thread) after every post.
+    ///
+    /// Use this to wake an idle event loop, e.g. an `EventLoopProxy` or a
+    /// condvar, so the reactive thread drains promptly.
+    pub fn set_waker<F>(&self, wake: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        *self.inbox.waker.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(wake));
+    }
+
+    /// Number of posted closures not yet drained.
+    pub fn pending(&self) -> usize {
+        self.inbox.len()
+    }
+}
+
+/// Get a handle to the current thread's inbox.
+pub fn reactive_thread() -> ReactiveThread {
+    ReactiveThread {
+        inbox: THREAD_INBOX.with(|inbox| inbox.clone()),
+    }
+}
+
+/// Post a closure to the default reactive thread.
+///
+/// The default reactive thread is the first thread that used the reactive
+/// inbox (by calling `reactive_thread()`, `drain_posted()`, `flush_sync()`,
+/// or creating a `SignalSender`). For programs with several reactive
+/// threads, post through an explicit `ReactiveThread` handle instead.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{flush_sync, post, reactive_thread};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// let _ = reactive_thread(); // claim the default inbox for this thread
+/// let ran = Arc::new(AtomicBool::new(false));
+///
+/// let r = ran.clone();
+/// std::thread::spawn(move || post(move || r.store(true, Ordering::SeqCst)))
+///     .join()
+///     .unwrap();
+///
+/// flush_sync();
+/// assert!(ran.load(Ordering::SeqCst));
+/// ```
+pub fn post<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    default_inbox().push(Box::new(f));
+}
+
+/// Run every closure posted to this thread's inbox, inside one batch.
+///
+/// Called automatically by `flush_sync()` and `tick()`. Returns the number
+/// of closures that ran. Closures posted while draining run on the next drain.
+pub fn drain_posted() -> usize {
+    let jobs = THREAD_INBOX.with(|inbox| inbox.take());
+    let count = jobs.len();
+
+    if count > 0 {
+        batch(|| {
+            for job in jobs {
+                job();
+            }
+        });
+    }
+
+    count
+}
+
+// =============================================================================
+// SIGNAL SENDER
+// =============================================================================
+
+/// A `Send` handle that posts writes to a specific signal.
+///
+/// Created on the reactive thread with `Signal::sender()`. Writes are applied
+/// at the reactive thread's next flush; if the signal has been dropped by
+/// then, they are ignored.
+pub struct SignalSender<T> {
+    inbox: Arc<Inbox>,
+    id: u64,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for SignalSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inbox: self.inbox.clone(),
+            id: self.id,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + 'static> SignalSender<T> {
+    /// Post `signal.set(value)` to the reactive thread.
+    pub fn set(&self, value: T) {
+        let id = self.id;
+        self.inbox.push(Box::new(move || {
+            if let Some(signal) = sender_target::<T>(id) {
+                signal.set(value);
+            }
+        }));
+    }
+
+    /// Post `signal.update(f)` to the reactive thread.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T) + Send + 'static,
+        T: Clone,
+    {
+        let id = self.id;
+        self.inbox.push(Box::new(move || {
+            if let Some(signal) = sender_target::<T>(id) {
+                signal.update(f);
+            }
+        }));
+    }
+}
+
+/// A type-erased `Weak<SourceInner<T>>` plus a liveness probe for it.
+struct SenderTarget {
+    signal: Box<dyn Any>,
+    is_alive: fn(&dyn Any) -> bool,
+}
+
+fn weak_is_alive<T: 'static>(signal: &dyn Any) -> bool {
+    signal
+        .downcast_ref::<Weak<SourceInner<T>>>()
+        .is_some_and(|weak| weak.strong_count() > 0)
+}
+
+/// Register a signal as a sender target on the current thread.
+pub(crate) fn register_sender<T: 'static>(inner: Weak<SourceInner<T>>) -> SignalSender<T> {
+    let id = NEXT_SENDER_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    });
+
+    SENDER_TARGETS.with(|targets| {
+        let mut targets = targets.borrow_mut();
+        // Prune targets whose signal is gone
+        targets.retain(|_, target| (target.is_alive)(target.signal.as_ref()));
+        targets.insert(
+            id,
+            SenderTarget {
+                signal: Box::new(inner),
+                is_alive: weak_is_alive::<T>,
+            },
+        );
+    });
+
+    SignalSender {
+        inbox: THREAD_INBOX.with(|inbox| inbox.clone()),
+        id,
+        _marker: PhantomData,
+    }
+}
+
+fn sender_target<T: 'static>(id: u64) -> Option<Signal<T>> {
+    SENDER_TARGETS.with(|targets| {
+        targets
+            .borrow()
+            .get(&id)
+            .and_then(|target| target.signal.downcast_ref::<Weak<SourceInner<T>>>())
+            .and_then(|weak| weak.upgrade())
+            .map(Signal::from_inner)
+    })
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+    use crate::reactivity::scheduling::flush_sync;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn posted_closures_run_on_drain() {
+        let handle = reactive_thread();
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        let worker = {
+            let handle = handle.clone();
+            let hits = hits.clone();
+            std::thread::spawn(move || {
+                for _ in 0..3 {
+                    let hits = hits.clone();
+                    handle.post(move || {
+                        hits.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+            })
+        };
+        worker.join().unwrap();
+
+        assert_eq!(handle.pending(), 3);
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        assert_eq!(drain_posted(), 3);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        assert_eq!(handle.pending(), 0);
+    }
+
+    #[test]
+    fn signal_sender_writes_apply_in_one_batch() {
+        let a = signal(0);
+        let runs = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let a = a.clone();
+            let runs = runs.clone();
+            move || {
+                a.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        let sender = a.sender();
+        std::thread::spawn(move || {
+            sender.set(1);
+            sender.set(2);
+            sender.update(|v| *v += 10);
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(a.get(), 0);
+        flush_sync();
+        assert_eq!(a.get(), 12);
+        // Three writes, one batched re-run
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn signal_sender_ignores_dropped_signal() {
+        let a = signal(0);
+        let sender = a.sender();
+        drop(a);
+
+        sender.set(5);
+        assert_eq!(drain_posted(), 1);
+    }
+
+    #[test]
+    fn waker_runs_on_post() {
+        let handle = reactive_thread();
+        let wakes = Arc::new(AtomicUsize::new(0));
+        handle.set_waker({
+            let wakes = wakes.clone();
+            move || {
+                wakes.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        handle.post(|| {});
+        handle.post(|| {});
+        assert_eq!(wakes.load(Ordering::SeqCst), 2);
+        drain_posted();
+    }
+}
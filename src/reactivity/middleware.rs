@@ -0,0 +1,373 @@
+// ============================================================================
+// spark-signals - Write Middleware
+// Context-wide hooks that see, veto or transform every signal write
+// ============================================================================
+//
+// Every write that stores a value passes the old and proposed values through
+// each registered middleware, in registration order, before storing
+// anything: `Signal` writes (tracked, untracked or staged in a transaction),
+// `SignalCell`, `Slot` / `SlotArray` and `SignalArena` writes, and values
+// added to or replaced in the reactive collections:
+//
+//     let remove = add_write_middleware(|info, old, new| {
+//         if let Some(n) = new.downcast_mut::<i32>() {
+//             *n = (*n).clamp(0, 100); // transform
+//         }
+//         WriteAction::Allow
+//     });
+//
+// Values are passed as `&dyn Any`, so one middleware can handle every
+// signal type it knows and ignore the rest. Writes made while middleware is
+// running (a logging layer writing a log signal, say) are applied directly
+// and don't re-enter middleware, so layers can't recurse into each other.
+//
+// Changes that don't store a value (removing or reordering collection
+// elements) and writes through `&mut` handles (`ReactiveVec::get_mut()`,
+// `raw_mut()`, map entries) don't pass through middleware.
+// ============================================================================
+
+use std::any::Any;
+use std::rc::Rc;
+
+use crate::core::context::{try_with_context, with_context};
+use crate::core::types::AnySource;
+use crate::debug::{source_info, DebugSourceInfo};
+
+/// What a write middleware decided about a write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteAction {
+    /// Store the (possibly transformed) new value
+    Allow,
+    /// Drop the write: the signal keeps its value and nobody is notified
+    Veto,
+}
+
+/// A registered write middleware.
+pub(crate) type WriteMiddleware =
+    Rc<dyn Fn(&DebugSourceInfo, Option<&dyn Any>, &mut dyn Any) -> WriteAction>;
+
+/// Register a middleware that runs before every write in the active context.
+///
+/// The callback gets the written source's debug info, the current value
+/// and the proposed value; it can change the proposed value in place
+/// (`new.downcast_mut::<T>()`) and returns whether the write goes ahead.
+/// `old` is None when the write adds a value (a collection push or insert),
+/// and a collection's writes all report the collection's own source.
+/// Middleware runs in registration order and the first `Veto` stops the
+/// rest. Returns a function that unregisters the middleware.
+///
+/// Writes the middleware itself makes skip middleware. It can read the
+/// signal being written, but must not write it (that panics): transform
+/// `new` instead.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{add_write_middleware, signal, WriteAction};
+///
+/// let volume = signal(5);
+/// let remove = add_write_middleware(|_info, _old, new| {
+///     match new.downcast_mut::<i32>() {
+///         Some(n) if *n < 0 => WriteAction::Veto,
+///         Some(n) => {
+///             *n = (*n).min(10);
+///             WriteAction::Allow
+///         }
+///         None => WriteAction::Allow,
+///     }
+/// });
+///
+/// assert!(!volume.set(-1));
+/// assert_eq!(volume.get(), 5);
+/// volume.set(42);
+/// assert_eq!(volume.get(), 10);
+///
+/// remove();
+/// volume.set(42);
+/// assert_eq!(volume.get(), 42);
+/// ```
+pub fn add_write_middleware<F>(f: F) -> impl FnOnce()
+where
+    F: Fn(&DebugSourceInfo, Option<&dyn Any>, &mut dyn Any) -> WriteAction + 'static,
+{
+    let id = with_context(|ctx| {
+        let id = ctx.next_middleware_id.get() + 1;
+        ctx.next_middleware_id.set(id);
+        ctx.write_middleware.borrow_mut().push((id, Rc::new(f)));
+        id
+    });
+
+    move || {
+        try_with_context(|ctx| {
+            ctx.write_middleware
+                .borrow_mut()
+                .retain(|(middleware_id, _)| *middleware_id != id);
+        });
+    }
+}
+
+/// Clears the running flag, even if a middleware panics.
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        try_with_context(|ctx| {
+            ctx.in_write_middleware.set(false);
+            ctx.write_middleware_target.set(0);
+        });
+    }
+}
+
+fn address<T: ?Sized>(node: &Rc<T>) -> usize {
+    Rc::as_ptr(node) as *const () as usize
+}
+
+/// Pass a write of `source` through the registered middleware, returning
+/// false if it was vetoed. `new` may be changed in place.
+///
+/// Callers check `has_write_middleware()` first, and may hold a borrow of
+/// the old value: middleware writing `source` is refused there.
+pub(crate) fn run_write_middleware<T: 'static>(
+    source: &Rc<dyn AnySource>,
+    old: Option<&T>,
+    new: &mut T,
+) -> bool {
+    // Snapshot so middleware can register/unregister middleware while running
+    let middleware: Vec<WriteMiddleware> = with_context(|ctx| {
+        if ctx.in_write_middleware.get() {
+            return Vec::new();
+        }
+        let registered = ctx.write_middleware.borrow();
+        registered.iter().map(|(_, f)| f.clone()).collect()
+    });
+    if middleware.is_empty() {
+        return true;
+    }
+
+    with_context(|ctx| {
+        ctx.in_write_middleware.set(true);
+        ctx.write_middleware_target.set(address(source));
+    });
+    let _running = RunningGuard;
+    let info = source_info(source);
+    let old = old.map(|old| old as &dyn Any);
+    middleware
+        .iter()
+        .all(|f| f(&info, old, new) == WriteAction::Allow)
+}
+
+/// True if a write of `source` now would pass through middleware.
+///
+/// # Panics
+///
+/// If called from middleware checking a write of `source`: the write would
+/// race the one being checked.
+pub(crate) fn has_write_middleware<S: ?Sized>(source: &Rc<S>) -> bool {
+    with_context(|ctx| {
+        if !ctx.in_write_middleware.get() {
+            return !ctx.write_middleware.borrow().is_empty();
+        }
+        assert!(
+            ctx.write_middleware_target.get() != address(source),
+            "write middleware wrote the source whose write it is checking; \
+             transform `new` instead"
+        );
+        false
+    })
+}
+
+/// `source` as the source to report, if a write of it now would pass
+/// through middleware.
+pub(crate) fn middleware_source<S: AnySource + 'static>(
+    source: &Rc<S>,
+) -> Option<Rc<dyn AnySource>> {
+    has_write_middleware(source).then(|| source.clone() as Rc<dyn AnySource>)
+}
+
+/// Pass a value being added (`old` is None) through middleware, returning
+/// it unless vetoed. `source` comes from `middleware_source()`.
+pub(crate) fn admit_added<T: 'static>(
+    source: Option<&Rc<dyn AnySource>>,
+    mut value: T,
+) -> Option<T> {
+    match source {
+        Some(source) => run_write_middleware(source, None, &mut value).then_some(value),
+        None => Some(value),
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::{ReactiveMap, ReactiveSet, ReactiveVec};
+    use crate::primitives::arena::SignalArena;
+    use crate::primitives::cell::signal_cell;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::{signal, signal_shared};
+    use crate::primitives::slot::slot;
+    use crate::reactivity::batching::transaction;
+    use std::cell::Cell;
+    use std::sync::Arc;
+
+    #[test]
+    fn logging_layer_writes_without_recursing() {
+        let count = signal(0);
+        count.set_debug_name("count");
+        let log = signal(Vec::<String>::new());
+        let runs = Rc::new(Cell::new(0));
+        let _watch = effect_sync({
+            let (count, runs) = (count.clone(), runs.clone());
+            move || {
+                count.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        let remove = add_write_middleware({
+            let log = log.clone();
+            move |info, old, new| {
+                let old = old.and_then(|old| old.downcast_ref::<i32>());
+                let (Some(old), Some(new)) = (old, new.downcast_mut::<i32>()) else {
+                    return WriteAction::Allow;
+                };
+                *new *= 2;
+                // Writes from inside middleware skip it
+                log.update(|l| l.push(format!("{:?} {old} -> {new}", info.name)));
+                if *new > 10 { WriteAction::Veto } else { WriteAction::Allow }
+            }
+        });
+
+        count.set(2);
+        count.update(|n| *n += 1);
+        assert!(!count.set(6));
+        assert_eq!((count.get(), runs.get()), (10, 3));
+        assert_eq!(
+            log.get(),
+            [
+                "Some(\"count\") 0 -> 4",
+                "Some(\"count\") 4 -> 10",
+                "Some(\"count\") 10 -> 12",
+            ]
+        );
+
+        remove();
+        count.set(6);
+        assert_eq!(count.get(), 6);
+    }
+
+    /// Doubles every `i32` written, vetoes negative ones.
+    fn doubling_middleware() -> impl FnOnce() {
+        add_write_middleware(|_info, _old, new| match new.downcast_mut::<i32>() {
+            Some(n) if *n < 0 => WriteAction::Veto,
+            Some(n) => {
+                *n *= 2;
+                WriteAction::Allow
+            }
+            None => WriteAction::Allow,
+        })
+    }
+
+    #[test]
+    fn untracked_and_staged_writes_pass_through_middleware() {
+        let count = signal(0);
+        let remove = doubling_middleware();
+
+        count.set_untracked(1);
+        assert_eq!(count.get(), 2);
+        count.update_untracked(|n| *n += 1);
+        assert_eq!(count.get(), 6);
+        count.set_untracked(-1);
+        assert_eq!(count.get(), 6);
+
+        let result: Result<(), ()> = transaction(|| {
+            count.set(4);
+            count.update(|n| *n += 1);
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(count.get(), 18);
+
+        let shared = signal_shared(vec![1]);
+        let seen = Rc::new(Cell::new(0));
+        let _watch = add_write_middleware({
+            let seen = seen.clone();
+            move |_info, old, new| {
+                let old = old.and_then(|old| old.downcast_ref::<Arc<Vec<i32>>>());
+                if let (Some(old), Some(new)) = (old, new.downcast_ref::<Arc<Vec<i32>>>()) {
+                    seen.set(old.len() * 10 + new.len());
+                }
+                WriteAction::Allow
+            }
+        });
+        shared.update_cow(|v| v.push(2));
+        assert_eq!(seen.get(), 12);
+        remove();
+    }
+
+    #[test]
+    fn slot_arena_and_collection_writes_pass_through_middleware() {
+        let remove = doubling_middleware();
+
+        let slot = slot(Some(1));
+        slot.set(2).unwrap();
+        slot.update_untracked(|n| *n += 1).unwrap();
+        assert_eq!(slot.get(), Some(10));
+
+        let arena = SignalArena::new();
+        let id = arena.alloc(1);
+        assert!(arena.set(id, 2));
+        arena.update(id, |n| *n += 1);
+        assert!(!arena.set(id, -1));
+        assert_eq!(arena.get(id), 10);
+
+        let mut vec = ReactiveVec::new();
+        vec.push(1);
+        vec.push(-1);
+        vec.extend([2, -2, 3]);
+        assert_eq!(vec.set(0, -5), -5);
+        assert_eq!(vec.set(0, 5), 2);
+        assert_eq!(vec.as_slice(), &[10, 4, 6]);
+
+        let mut set = ReactiveSet::new();
+        assert!(set.insert(1));
+        assert!(!set.insert(-1));
+        assert!(set.contains(&2));
+
+        let mut map = ReactiveMap::new();
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("a", -1), Some(-1));
+        assert_eq!(map.get("a"), Some(&2));
+        remove();
+    }
+
+    #[test]
+    fn signal_cell_writes_pass_through_middleware() {
+        let cell = signal_cell(1);
+        let remove = doubling_middleware();
+
+        assert!(cell.set(2));
+        cell.update(|n| *n += 1);
+        assert_eq!(cell.get(), 10);
+        assert!(!cell.set(-1));
+        assert_eq!(cell.get(), 10);
+        remove();
+    }
+
+    #[test]
+    #[should_panic(expected = "write middleware wrote the source whose write it is checking")]
+    fn writing_the_checked_signal_from_middleware_panics() {
+        let count = signal(0);
+        let _remove = add_write_middleware({
+            let count = count.clone();
+            move |_info, _old, _new| {
+                count.set(1);
+                WriteAction::Allow
+            }
+        });
+        count.set(2);
+    }
+}
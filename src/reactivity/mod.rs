@@ -5,7 +5,11 @@
 
 pub mod batching;
 pub mod equality;
+#[cfg(feature = "sync")]
+pub mod inbox;
+pub mod middleware;
 pub mod scheduling;
+pub mod test_scheduler;
 pub mod tracking;
 
 // Re-export main tracking functions
@@ -14,7 +18,23 @@ pub use tracking::{
 };
 
 // Re-export scheduling functions
-pub use scheduling::{flush_pending_reactions, flush_sync, schedule_effect_inner};
+pub use scheduling::{
+    flush_idle, flush_pending_reactions, flush_sync, flush_sync_with, has_idle_work, on_flush_end,
+    on_flush_start, schedule_effect_inner, Lane, Scheduler,
+};
+
+// Re-export write middleware
+pub use middleware::{add_write_middleware, WriteAction};
+
+// Re-export the test scheduler
+pub use test_scheduler::TestScheduler;
 
 // Re-export batching functions
-pub use batching::{batch, peek, tick, untrack};
+pub use batching::{
+    await_settled, batch, is_settled, peek, start_batch, tick, transaction, untrack, BatchGuard,
+    Settled,
+};
+
+// Re-export cross-thread inbox
+#[cfg(feature = "sync")]
+pub use inbox::{drain_posted, post, reactive_thread, ReactiveThread, SignalSender};
@@ -11,15 +11,378 @@
 // - schedule_effect: Queue an effect for execution
 // - flush_effects: Run all queued effects
 // - flush_sync: Synchronously flush with loop detection
+// - try_flush: Same, returning UpdateDepthExceeded instead of panicking
+// - flush_idle: Run the idle lane (render and user lanes flush on their own)
+// - on_flush_start / on_flush_end: Hooks around each outermost flush
 // ============================================================================
 
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
+use crate::core::config::{update_config, with_config};
 use crate::core::constants::*;
-use crate::core::context::with_context;
+use crate::core::context::{enter_runtime, try_with_context, with_context, Runtime};
 use crate::core::types::AnyReaction;
 use crate::primitives::effect::EffectInner;
-use crate::reactivity::tracking::is_dirty;
+use crate::primitives::repeater::flush_deferred_repeaters;
+use crate::primitives::derived::update_derived_chain;
+use crate::reactivity::tracking::{is_dirty, set_signal_status};
+
+// =============================================================================
+// FLUSH HOOKS
+// =============================================================================
+
+/// A registered flush boundary callback.
+type FlushHook = (u64, Rc<dyn Fn()>);
+
+/// A context's flush boundary callbacks.
+#[derive(Default)]
+pub(crate) struct FlushHooks {
+    next_id: u64,
+    start: Vec<FlushHook>,
+    end: Vec<FlushHook>,
+}
+
+fn register_flush_hook(at_start: bool, f: Rc<dyn Fn()>) -> impl FnOnce() {
+    let id = with_context(|ctx| {
+        let mut hooks = ctx.flush_hooks.borrow_mut();
+        hooks.next_id += 1;
+        let id = hooks.next_id;
+        if at_start {
+            hooks.start.push((id, f));
+        } else {
+            hooks.end.push((id, f));
+        }
+        id
+    });
+
+    // Unregister from the context it was registered in
+    let runtime = Runtime::current();
+    move || {
+        enter_runtime(runtime.as_ref(), || {
+            try_with_context(|ctx| {
+                let mut hooks = ctx.flush_hooks.borrow_mut();
+                hooks.start.retain(|(hook_id, _)| *hook_id != id);
+                hooks.end.retain(|(hook_id, _)| *hook_id != id);
+            });
+        });
+    }
+}
+
+fn run_flush_hooks(at_start: bool) {
+    // Snapshot so hooks can register/unregister hooks while running
+    let hooks: Vec<Rc<dyn Fn()>> = with_context(|ctx| {
+        let hooks = ctx.flush_hooks.borrow();
+        let list = if at_start { &hooks.start } else { &hooks.end };
+        list.iter().map(|(_, f)| f.clone()).collect()
+    });
+
+    for hook in hooks {
+        hook();
+    }
+}
+
+/// Marks one flush pass; fires the hooks when the outermost pass begins and ends.
+pub(crate) struct FlushScope {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl FlushScope {
+    pub(crate) fn enter() -> Self {
+        let depth = with_context(|ctx| {
+            ctx.flush_depth.set(ctx.flush_depth.get() + 1);
+            if ctx.flush_depth.get() == 1 {
+                ctx.flush_epoch.set(ctx.flush_epoch.get() + 1);
+            }
+            ctx.flush_depth.get()
+        });
+        if depth == 1 {
+            run_flush_hooks(true);
+        }
+        FlushScope {
+            #[cfg(feature = "tracing")]
+            _span: tracing::trace_span!("flush", depth).entered(),
+        }
+    }
+}
+
+impl Drop for FlushScope {
+    fn drop(&mut self) {
+        let depth = with_context(|ctx| {
+            ctx.flush_depth.set(ctx.flush_depth.get() - 1);
+            ctx.flush_depth.get()
+        });
+        if depth == 0 && !std::thread::panicking() {
+            run_flush_hooks(false);
+        }
+    }
+}
+
+/// Marks a flush loop as running for its lifetime, restoring the previous
+/// state on exit, including when an effect panic unwinds through the loop.
+pub(crate) struct SyncFlushGuard {
+    was_flushing: bool,
+}
+
+impl SyncFlushGuard {
+    pub(crate) fn enter() -> Self {
+        let was_flushing = with_context(|ctx| ctx.set_flushing_sync(true));
+        SyncFlushGuard { was_flushing }
+    }
+}
+
+impl Drop for SyncFlushGuard {
+    fn drop(&mut self) {
+        try_with_context(|ctx| {
+            ctx.set_flushing_sync(self.was_flushing);
+        });
+    }
+}
+
+/// When queued effects run. Set in `Config::scheduler`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scheduler {
+    /// As soon as the write that queued them is done (or the outermost
+    /// batch ends)
+    #[default]
+    Immediate,
+    /// Only when the host flushes: `flush_sync()`, `try_flush()` or
+    /// `ReactiveDriver::pump()`, say once per frame
+    Manual,
+}
+
+/// Enter/leave manual flushing (used by `TestScheduler`).
+pub(crate) fn set_manual_flush(enabled: bool) {
+    with_context(|ctx| {
+        let m = &ctx.manual_flush;
+        m.set(if enabled { m.get() + 1 } else { m.get().saturating_sub(1) });
+    });
+}
+
+/// True while a `TestScheduler` or `Scheduler::Manual` is holding effects
+/// back.
+pub(crate) fn is_manual_flush() -> bool {
+    with_context(|ctx| ctx.manual_flush.get()) != 0
+        || with_config(|c| c.scheduler == Scheduler::Manual)
+}
+
+/// Install (or remove) a host callback that is asked to flush whenever
+/// effects are queued without being run. Used with manual flushing by hosts
+/// that flush asynchronously, like the `wasm` microtask scheduler.
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+pub(crate) fn set_flush_request(request: Option<Rc<dyn Fn()>>) {
+    with_context(|ctx| *ctx.flush_request.borrow_mut() = request);
+}
+
+/// True if the current context has a host flush callback installed.
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+pub(crate) fn has_flush_request() -> bool {
+    with_context(|ctx| ctx.flush_request.borrow().is_some())
+}
+
+/// Ask the host to flush the effects just queued, if it wants to be asked.
+pub(crate) fn request_flush() {
+    let request = with_context(|ctx| ctx.flush_request.borrow().clone());
+    if let Some(request) = request {
+        request();
+    }
+}
+
+/// True if scheduled effects should flush right away: not in a batch, not
+/// already flushing, and not flushing manually.
+pub(crate) fn should_auto_flush() -> bool {
+    !is_manual_flush() && with_context(|ctx| !ctx.is_batching() && !ctx.is_flushing_sync())
+}
+
+/// Id of the outermost flush in progress, or `None` outside a flush.
+pub(crate) fn current_flush() -> Option<u64> {
+    with_context(|ctx| (ctx.flush_depth.get() != 0).then(|| ctx.flush_epoch.get()))
+}
+
+/// Register a callback that runs when a flush begins.
+///
+/// Fires once per outermost flush, before any effect (or deferred repeater)
+/// in it runs - nested flushes triggered by effects don't fire it again.
+/// Several callbacks can be registered; they run in registration order.
+///
+/// Returns a function that unregisters the callback.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, on_flush_end, on_flush_start, signal};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let count = signal(0);
+///
+/// let _e = effect_sync({
+///     let count = count.clone();
+///     let log = log.clone();
+///     move || log.borrow_mut().push(format!("effect {}", count.get()))
+/// });
+///
+/// let remove_start = on_flush_start({
+///     let log = log.clone();
+///     move || log.borrow_mut().push("start".into())
+/// });
+/// let remove_end = on_flush_end({
+///     let log = log.clone();
+///     move || log.borrow_mut().push("end".into())
+/// });
+///
+/// count.set(1);
+/// assert_eq!(log.borrow()[1..], ["start", "effect 1", "end"]);
+///
+/// remove_start();
+/// remove_end();
+/// ```
+pub fn on_flush_start<F>(f: F) -> impl FnOnce()
+where
+    F: Fn() + 'static,
+{
+    register_flush_hook(true, Rc::new(f))
+}
+
+/// Register a callback that runs when a flush ends.
+///
+/// Fires once per outermost flush, after every effect in it has run.
+/// Returns a function that unregisters the callback.
+pub fn on_flush_end<F>(f: F) -> impl FnOnce()
+where
+    F: Fn() + 'static,
+{
+    register_flush_hook(false, Rc::new(f))
+}
+
+// =============================================================================
+// PRIORITY LANES
+// =============================================================================
+
+/// Scheduling priority of an effect.
+///
+/// When several effects are pending together (triggered by the same write,
+/// or by a batch), render effects run before user effects. Idle effects
+/// don't run in regular flushes at all; they wait for `flush_idle()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lane {
+    /// Runs first in every flush pass
+    Render,
+    /// The default lane (`effect()`, `effect_sync()`)
+    User,
+    /// Runs only on `flush_idle()`
+    Idle,
+}
+
+impl Lane {
+    /// The lane an effect with these flags is scheduled in.
+    ///
+    /// `effect_sync()` effects carry both RENDER_EFFECT and USER_EFFECT and
+    /// stay in the user lane.
+    pub fn of(flags: u32) -> Lane {
+        if (flags & IDLE_EFFECT) != 0 {
+            Lane::Idle
+        } else if (flags & RENDER_EFFECT) != 0 && (flags & USER_EFFECT) == 0 {
+            Lane::Render
+        } else {
+            Lane::User
+        }
+    }
+
+    /// Effect type flags for an effect created in this lane.
+    pub(crate) fn effect_flags(self) -> u32 {
+        match self {
+            Lane::Render => EFFECT | RENDER_EFFECT,
+            Lane::User => EFFECT | USER_EFFECT,
+            Lane::Idle => EFFECT | USER_EFFECT | IDLE_EFFECT,
+        }
+    }
+}
+
+/// Take the pending queue in lane order: render effects, then the rest.
+///
+/// Idle effects move to the idle queue instead.
+pub(crate) fn take_pending_by_lane() -> Vec<Weak<dyn AnyReaction>> {
+    let pending = with_context(|ctx| ctx.take_pending_reactions());
+
+    let mut render = Vec::new();
+    let mut rest = Vec::with_capacity(pending.len());
+    for reaction_weak in pending {
+        match reaction_weak.upgrade().map(|r| Lane::of(r.flags())) {
+            Some(Lane::Render) => render.push(reaction_weak),
+            Some(Lane::Idle) => with_context(|ctx| ctx.idle_queue.borrow_mut().push(reaction_weak)),
+            Some(Lane::User) => rest.push(reaction_weak),
+            None => {}
+        }
+    }
+
+    render.append(&mut rest);
+    render
+}
+
+/// Run the idle lane: every idle effect whose dependencies changed.
+///
+/// Anything they trigger in the other lanes flushes before this returns.
+/// Idle effects scheduled while this runs wait for the next call.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_in_lane, flush_idle, signal, Lane};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let count = signal(0);
+/// let saved = Rc::new(Cell::new(-1));
+/// let _dispose = effect_in_lane(Lane::Idle, {
+///     let (count, saved) = (count.clone(), saved.clone());
+///     move || saved.set(count.get())
+/// });
+///
+/// count.set(5);
+/// assert_eq!(saved.get(), -1); // idle work waits
+///
+/// flush_idle();
+/// assert_eq!(saved.get(), 5);
+/// ```
+pub fn flush_idle() {
+    // Idle effects still sitting in the pending queue (e.g. inside a batch)
+    let pending = with_context(|ctx| ctx.take_pending_reactions());
+    let mut idle = with_context(|ctx| ctx.idle_queue.take());
+    with_context(|ctx| {
+        for reaction_weak in pending {
+            match reaction_weak.upgrade() {
+                Some(r) if Lane::of(r.flags()) == Lane::Idle => idle.push(reaction_weak),
+                _ => ctx.add_pending_reaction(reaction_weak),
+            }
+        }
+    });
+
+    {
+        let _scope = FlushScope::enter();
+        for reaction_weak in idle {
+            if let Some(reaction) = reaction_weak.upgrade() {
+                if is_runnable(&reaction) && needs_run(&*reaction) {
+                    reaction.update();
+                }
+            }
+        }
+    }
+
+    flush_sync();
+}
+
+/// True if idle effects are waiting for `flush_idle()`.
+pub fn has_idle_work() -> bool {
+    with_context(|ctx| {
+        ctx.idle_queue
+            .borrow()
+            .iter()
+            .filter_map(|w| w.upgrade())
+            .any(|r| (r.flags() & (INERT | DESTROYED)) == 0 && is_dirty(&*r))
+    })
+}
 
 // =============================================================================
 // SCHEDULE EFFECT
@@ -50,22 +413,13 @@ pub fn schedule_effect(effect: Rc<EffectInner>) {
         if (flags & ROOT_EFFECT) != 0 {
             ctx.add_queued_root_effect(Rc::downgrade(&reaction));
         }
-
-        // Sync effects (RENDER_EFFECT) flush immediately
-        // Note: In Rust without microtasks, we flush immediately for all effects
-        // unless in a batch
-        if !ctx.is_flushing_sync() {
-            // Flush immediately
-            // We need to exit with_context before calling flush_sync
-            // to avoid nested borrows
-        }
     });
 
     // Flush outside of with_context to avoid nested borrows
-    let should_flush = with_context(|ctx| !ctx.is_batching() && !ctx.is_flushing_sync());
-
-    if should_flush {
-        flush_sync_inner(None);
+    if should_auto_flush() {
+        flush_queued();
+    } else {
+        request_flush();
     }
 }
 
@@ -73,268 +427,469 @@ pub fn schedule_effect(effect: Rc<EffectInner>) {
 // FLUSH EFFECTS
 // =============================================================================
 
-/// Flush all queued root effects.
+/// Flush all queued root effects, and everything they schedule.
 ///
-/// Processes each root effect and its children.
+/// Same as [`flush_sync()`], minus the cross-thread and channel pumping.
 pub fn flush_effects() {
-    let roots = with_context(|ctx| ctx.take_queued_root_effects());
-
-    for root_weak in roots {
-        if let Some(root) = root_weak.upgrade() {
-            // Skip inert (paused) effects
-            if (root.flags() & INERT) != 0 {
-                continue;
-            }
-
-            // Update the root if dirty
-            if is_dirty(&*root) {
-                // Downcast to EffectInner
-                if let Some(_effect) = root.as_any().downcast_ref::<EffectInner>() {
-                    // We need an Rc, get it via the context
-                    // Actually, we have a problem here - we have &EffectInner, not Rc<EffectInner>
-                    // Let's restructure this
-                }
-            }
-        }
-    }
+    flush_queued();
 }
 
-/// Flush all queued effects (internal version that takes Rc directly).
-fn flush_queued_effects() {
-    let roots = with_context(|ctx| ctx.take_queued_root_effects());
-
-    for root_weak in roots {
-        if let Some(root) = root_weak.upgrade() {
-            // Skip inert (paused) effects
-            if (root.flags() & INERT) != 0 {
-                continue;
-            }
-
-            // Check if it's an effect
-            if let Some(_effect_ref) = root.as_any().downcast_ref::<EffectInner>() {
-                // We need the Rc<EffectInner>
-                // The problem is we have Rc<dyn AnyReaction> and need Rc<EffectInner>
-                // We can't directly downcast Rc<dyn Trait> to Rc<Concrete>
-                // Solution: Store the Rc<EffectInner> directly in the queue instead of Weak<dyn AnyReaction>
-
-                // For now, we'll work around this by updating via the trait interface
-                if is_dirty(&*root) {
-                    // Run the effect's update method
-                    root.update();
-                }
-            }
-        }
-    }
+/// Flush pending reactions from a batch.
+///
+/// Runs at the end of the outermost batch; effects the flush schedules run
+/// before it returns.
+pub fn flush_pending_reactions() {
+    flush_queued();
 }
 
 // =============================================================================
-// FLUSH PENDING REACTIONS
+// UPDATE DEPTH
 // =============================================================================
 
-/// Flush pending reactions from a batch.
-pub fn flush_pending_reactions() {
-    let reactions = with_context(|ctx| ctx.take_pending_reactions());
-
-    for reaction_weak in reactions {
-        if let Some(reaction) = reaction_weak.upgrade() {
-            // Skip inert (paused) effects
-            if (reaction.flags() & INERT) != 0 {
-                continue;
-            }
+/// A flush gave up after `max_depth` passes because effects kept
+/// re-triggering each other.
+///
+/// Returned by [`try_flush()`]; every other flush panics with this as the
+/// message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateDepthExceeded {
+    /// The limit that was hit (see [`set_max_update_depth()`])
+    pub max_depth: u32,
+    /// The effects still queued to run again: name (`set_effect_name()`)
+    /// and creation site
+    pub effects: Vec<String>,
+}
 
-            if is_dirty(&*reaction) {
-                // Check if it's an effect
-                if (reaction.flags() & EFFECT) != 0 {
-                    reaction.update();
+impl UpdateDepthExceeded {
+    /// Report the effects left in the pending queue.
+    pub(crate) fn from_pending(max_depth: u32) -> Self {
+        let effects = with_context(|ctx| {
+            let pending = ctx.pending_reactions.borrow();
+            let roots = ctx.queued_root_effects.borrow();
+
+            let mut seen: Vec<*const ()> = Vec::new();
+            let mut effects = Vec::new();
+            for reaction in pending.iter().chain(roots.iter()).filter_map(Weak::upgrade) {
+                let ptr = Rc::as_ptr(&reaction) as *const ();
+                if seen.contains(&ptr) {
+                    continue;
+                }
+                seen.push(ptr);
+                if let Some(effect) = reaction.as_any().downcast_ref::<EffectInner>() {
+                    effects.push(effect.label());
                 }
-                // Deriveds are handled by their next read
             }
+            effects
+        });
+        Self { max_depth, effects }
+    }
+}
+
+impl std::fmt::Display for UpdateDepthExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Maximum update depth exceeded ({} flush passes). This can happen when an \
+             effect continuously triggers itself.",
+            self.max_depth
+        )?;
+        if !self.effects.is_empty() {
+            write!(f, " Still re-running: {}", self.effects.join(", "))?;
         }
+        Ok(())
     }
 }
 
+impl std::error::Error for UpdateDepthExceeded {}
+
+/// Set how many flush passes (effects re-triggering effects) a flush allows
+/// before giving up. Applies to the current thread.
+///
+/// Shorthand for changing `Config::max_update_depth` with
+/// `set_thread_config()`. Default: 1000.
+pub fn set_max_update_depth(max: u32) {
+    update_config(|c| c.max_update_depth = max);
+}
+
 // =============================================================================
 // FLUSH SYNC
 // =============================================================================
 
-/// Maximum flush iterations before we consider it an infinite loop
-const MAX_FLUSH_COUNT: u32 = 1000;
-
-/// Synchronously flush all pending updates.
+/// Synchronously flush all pending updates, returning how many effect runs
+/// that took.
 ///
 /// Runs all effects immediately instead of waiting for a microtask.
 /// Detects infinite loops where effects keep triggering themselves.
-pub fn flush_sync() {
-    flush_sync_inner(None);
+/// With the `sync` feature, closures posted from other threads run first.
+///
+/// Safe to call anywhere:
+/// - Inside a batch, it runs the effects of the writes made so far; later
+///   writes in the batch run when it ends.
+/// - Inside an effect (a nested flush), it runs everything else that is
+///   pending. Effects that are mid-run, like the caller, are left queued and
+///   run once they return.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{batch, effect_sync, flush_sync, signal};
+///
+/// let count = signal(0);
+/// let _log = effect_sync({
+///     let count = count.clone();
+///     move || {
+///         count.get();
+///     }
+/// });
+///
+/// batch(|| {
+///     count.set(1);
+///     assert_eq!(flush_sync(), 1); // the effect saw 1 already
+///     assert_eq!(flush_sync(), 0); // nothing left to run
+/// });
+/// ```
+pub fn flush_sync() -> usize {
+    #[cfg(feature = "sync")]
+    crate::reactivity::inbox::drain_posted();
+    crate::interop::pump_receivers();
+
+    match flush_sync_inner(None) {
+        Ok(runs) => runs,
+        Err(error) => panic!("{error}"),
+    }
 }
 
-/// Synchronously flush with optional function to run.
+/// Like [`flush_sync()`], but returns an error instead of panicking when
+/// effects keep re-triggering each other past the max update depth.
+/// On success, returns the number of effect runs.
+///
+/// The error names the effects involved. They stay queued, so dispose or fix
+/// them before flushing again.
+///
+/// # Example
 ///
-/// If a function is provided, effects are flushed, then the function
-/// runs, then effects are flushed again.
-pub fn flush_sync_with<T: 'static>(f: impl FnOnce() -> T + 'static) -> T {
-    flush_sync_inner(Some(Box::new(|| Box::new(f()) as Box<dyn std::any::Any>)))
-        .downcast::<T>()
-        .ok()
-        .map(|b| *b)
-        .expect("flush_sync_with: type mismatch")
+/// ```
+/// use spark_signals::{
+///     effect, set_effect_name, set_max_update_depth, signal, try_flush, TestScheduler,
+/// };
+///
+/// let _scheduler = TestScheduler::new();
+/// set_max_update_depth(50);
+///
+/// // Each effect writes what the other reads
+/// let (ping, pong) = (signal(0), signal(0));
+/// let forward = effect({
+///     let (ping, pong) = (ping.clone(), pong.clone());
+///     move || {
+///         set_effect_name("forward");
+///         pong.set(ping.get() + 1);
+///     }
+/// });
+/// let back = effect(move || {
+///     set_effect_name("back");
+///     ping.set(pong.get() + 1);
+/// });
+///
+/// let error = try_flush().unwrap_err();
+/// assert_eq!(error.max_depth, 50);
+/// // "... Still re-running: back (src/main.rs:14:12)"
+/// assert!(error.to_string().contains("back ("));
+///
+/// forward();
+/// back();
+/// ```
+pub fn try_flush() -> Result<usize, UpdateDepthExceeded> {
+    #[cfg(feature = "sync")]
+    crate::reactivity::inbox::drain_posted();
+    crate::interop::pump_receivers();
+
+    flush_sync_inner(None)
 }
 
-/// Inner flush implementation.
-fn flush_sync_inner(f: Option<Box<dyn FnOnce() -> Box<dyn std::any::Any>>>) -> Box<dyn std::any::Any> {
-    let was_flushing = with_context(|ctx| {
-        let was = ctx.is_flushing_sync();
-        ctx.set_flushing_sync(true);
-        was
-    });
+/// Synchronously flush, run `f`, then flush again, so the effects of `f`'s
+/// writes have all run when this returns. Returns `f`'s result.
+///
+/// Nests like [`flush_sync()`].
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect_sync, flush_sync_with, signal};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let count = signal(0);
+/// let seen = Rc::new(Cell::new(0));
+/// let _log = effect_sync({
+///     let (count, seen) = (count.clone(), seen.clone());
+///     move || seen.set(count.get())
+/// });
+///
+/// let doubled = flush_sync_with(|| {
+///     count.set(4);
+///     count.get() * 2
+/// });
+/// assert_eq!((doubled, seen.get()), (8, 4));
+/// ```
+pub fn flush_sync_with<T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "sync")]
+    crate::reactivity::inbox::drain_posted();
+    crate::interop::pump_receivers();
+
+    let mut f = Some(f);
+    let mut result = None;
+    let mut run = || result = f.take().map(|f| f());
+    if let Err(error) = flush_sync_inner(Some(&mut run)) {
+        panic!("{error}");
+    }
+    result.expect("flush_sync_with: closure did not run")
+}
 
-    let result: Box<dyn std::any::Any> = {
-        let mut flush_count = 0u32;
+/// Run a queued effect, unless it is mid-run further up the stack (a nested
+/// flush from inside an effect); those go to `deferred`. Returns true if
+/// the effect ran.
+fn run_or_defer(
+    reaction_weak: Weak<dyn AnyReaction>,
+    deferred: &mut Vec<Weak<dyn AnyReaction>>,
+) -> bool {
+    let Some(reaction) = reaction_weak.upgrade() else {
+        return false;
+    };
+    let flags = reaction.flags();
+    if (flags & (INERT | DESTROYED)) != 0 || (flags & EFFECT) == 0 || !is_dirty(&*reaction) {
+        return false;
+    }
+    if (flags & REACTION_IS_UPDATING) != 0 {
+        deferred.push(reaction_weak);
+        return false;
+    }
+    if !needs_run(&*reaction) {
+        return false;
+    }
+    reaction.update();
+    true
+}
 
-        // Run the provided function first if given
-        let result = if let Some(func) = f {
-            flush_queued_effects();
-            func()
-        } else {
-            Box::new(()) as Box<dyn std::any::Any>
-        };
-
-        // Keep flushing until no more effects
-        loop {
-            flush_count += 1;
-            if flush_count > MAX_FLUSH_COUNT {
-                panic!(
-                    "Maximum update depth exceeded. This can happen when an effect \
-                     continuously triggers itself. Check for effects that write to \
-                     signals they depend on without proper guards."
-                );
-            }
+/// Run everything queued, panicking past the max update depth. Returns the
+/// number of effect runs.
+pub(crate) fn flush_queued() -> usize {
+    match flush_sync_inner(None) {
+        Ok(runs) => runs,
+        Err(error) => panic!("{error}"),
+    }
+}
 
-            // Flush root effects
-            let roots = with_context(|ctx| ctx.take_queued_root_effects());
+/// The flush loop behind every flush: `flush_sync()`, a write outside a
+/// batch, the end of a batch.
+///
+/// Each pass forwards deferred repeaters, then runs the queued effects
+/// (root effects first, render lane before user lane), until a pass finds
+/// nothing queued or `Config::flush_budget` is used up. Returns the number
+/// of effect runs.
+fn flush_sync_inner(f: Option<&mut dyn FnMut()>) -> Result<usize, UpdateDepthExceeded> {
+    let _scope = FlushScope::enter();
+
+    #[cfg(feature = "profiling")]
+    let started = std::time::Instant::now();
+
+    let _flushing = SyncFlushGuard::enter();
+
+    let (max_flush_count, budget) = with_config(|c| (c.max_update_depth, c.flush_budget));
+    // There is no clock on wasm32
+    let deadline = budget
+        .filter(|_| !cfg!(target_arch = "wasm32"))
+        .map(|budget| std::time::Instant::now() + budget);
+    let out_of_time = |runs: usize| {
+        runs > 0 && deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline)
+    };
+    let mut flush_count = 0u32;
+    let mut runs = 0usize;
+    let mut deferred = Vec::new();
 
-            if roots.is_empty() {
-                // Also flush pending reactions from batch
-                let pending = with_context(|ctx| ctx.take_pending_reactions());
+    // Run the provided function first if given
+    if let Some(func) = f {
+        for root in with_context(|ctx| ctx.take_queued_root_effects()) {
+            runs += usize::from(run_or_defer(root, &mut deferred));
+        }
+        func();
+    }
 
-                if pending.is_empty() {
-                    break;
-                }
+    // Keep flushing until no more effects
+    let result = loop {
+        flush_count += 1;
+        if flush_count > max_flush_count {
+            break Err(UpdateDepthExceeded::from_pending(max_flush_count));
+        }
 
-                // Flush pending reactions
-                for reaction_weak in pending {
-                    if let Some(reaction) = reaction_weak.upgrade() {
-                        if (reaction.flags() & INERT) != 0 {
-                            continue;
-                        }
-
-                        if is_dirty(&*reaction) && (reaction.flags() & EFFECT) != 0 {
-                            reaction.update();
-                        }
-                    }
-                }
-                continue;
-            }
+        // Deferred repeaters forward before any effect in this pass
+        flush_deferred_repeaters();
 
-            for root_weak in roots {
-                if let Some(root) = root_weak.upgrade() {
-                    if (root.flags() & INERT) != 0 {
-                        continue;
-                    }
+        // Flush root effects, then pending reactions from batch
+        let mut queued = with_context(|ctx| ctx.take_queued_root_effects());
+        if queued.is_empty() {
+            queued = take_pending_by_lane();
+        }
+        if queued.is_empty() {
+            break Ok(runs);
+        }
 
-                    if is_dirty(&*root) {
-                        root.update();
-                    }
-                }
+        let mut rest = Vec::new();
+        for reaction_weak in queued {
+            if out_of_time(runs) {
+                rest.push(reaction_weak);
+            } else {
+                runs += usize::from(run_or_defer(reaction_weak, &mut deferred));
             }
         }
 
-        result
+        // Out of budget: the rest waits for the next flush
+        if !rest.is_empty() {
+            with_context(|ctx| {
+                for reaction in rest {
+                    ctx.add_pending_reaction(reaction);
+                }
+            });
+            request_flush();
+            break Ok(runs);
+        }
     };
 
-    with_context(|ctx| ctx.set_flushing_sync(was_flushing));
+    // Effects still running further up pick these up when they return
+    with_context(|ctx| {
+        for reaction in deferred {
+            ctx.add_pending_reaction(reaction);
+        }
+    });
+
+    #[cfg(feature = "profiling")]
+    crate::profiling::record_flush(started);
 
     result
 }
 
 // =============================================================================
-// SPECIALIZED SCHEDULING FOR EFFECT INNER
+// STEPPING (TestScheduler)
 // =============================================================================
 
-/// Schedule an EffectInner directly (preferred method).
+/// True if a queued effect has to run.
 ///
-/// This version keeps the Rc<EffectInner> and can call update_effect properly.
-pub fn schedule_effect_inner(effect: Rc<EffectInner>) {
-    let flags = effect.flags();
-
-    // If we're in a batch or already flushing, just mark for later
-    let should_run_now = with_context(|ctx| {
-        // Add to pending
-        ctx.add_pending_reaction(Rc::downgrade(&(effect.clone() as Rc<dyn AnyReaction>)));
+/// DIRTY effects always run. A MAYBE_DIRTY effect only reached the queue
+/// through deriveds, so those are brought up to date first, each chain in
+/// dependency order (deepest first), and the effect runs only if one of its
+/// dependencies was written since its last run. Otherwise it is marked
+/// clean: a derived that recomputed to the same value doesn't re-run it.
+///
+/// Each dependency is brought up to date by `update_derived_chain()`,
+/// which levels the stale deriveds under it and updates them by height, so
+/// a derived is only checked once everything it reads is final. The pass is
+/// per effect and in the order the effect read its dependencies, stopping
+/// at the first change: deriveds an effect may no longer read (behind a
+/// branch that just flipped) are not computed ahead of the run, and writes
+/// made by effects earlier in the queue are seen by the ones after them.
+pub(crate) fn needs_run(reaction: &dyn AnyReaction) -> bool {
+    let flags = reaction.flags();
+    if (flags & DIRTY) != 0 {
+        return true;
+    }
+    if (flags & MAYBE_DIRTY) == 0 {
+        return false;
+    }
+    let Some(verified) = reaction.verified_version() else {
+        return true;
+    };
 
-        // Check if we should run now
-        !ctx.is_batching() && !ctx.is_flushing_sync()
+    let mut deps = Vec::new();
+    reaction.for_each_dep(&mut |dep| {
+        deps.push(dep.clone());
+        true
     });
-
-    if should_run_now {
-        // Sync effects (RENDER_EFFECT) or all effects in Rust run immediately
-        if (flags & RENDER_EFFECT) != 0 || (flags & EFFECT) != 0 {
-            run_effect_flush();
-        }
+    // Stops at the first change; the run pulls the remaining deriveds
+    let changed = deps.into_iter().any(|dep| {
+        update_derived_chain(dep.clone());
+        dep.write_version() > verified
+    });
+    if !changed {
+        set_signal_status(reaction, CLEAN);
     }
+    changed
 }
 
-/// Run effect flush - processes pending effects with proper Rc handling.
-fn run_effect_flush() {
-    let was_flushing = with_context(|ctx| {
-        let was = ctx.is_flushing_sync();
-        ctx.set_flushing_sync(true);
-        was
-    });
+/// True if a queued reaction is an effect that may need to run.
+fn is_runnable(reaction: &Rc<dyn AnyReaction>) -> bool {
+    let flags = reaction.flags();
+    (flags & EFFECT) != 0 && (flags & (INERT | DESTROYED)) == 0 && is_dirty(&**reaction)
+}
 
-    let mut flush_count = 0u32;
+/// Run the first runnable effect in the pending queue.
+///
+/// Effects queued behind it stay queued, ahead of anything the run itself
+/// schedules. Returns false if nothing was runnable.
+pub(crate) fn run_next_pending() -> bool {
+    let _scope = FlushScope::enter();
+    flush_deferred_repeaters();
+
+    let mut pending = with_context(|ctx| ctx.take_pending_reactions()).into_iter();
+    let mut ran = false;
+    for reaction_weak in pending.by_ref() {
+        if let Some(reaction) = reaction_weak.upgrade() {
+            if is_runnable(&reaction)
+                && Lane::of(reaction.flags()) != Lane::Idle
+                && needs_run(&*reaction)
+            {
+                reaction.update();
+                ran = true;
+                break;
+            }
+        }
+    }
 
-    loop {
-        flush_count += 1;
-        if flush_count > MAX_FLUSH_COUNT {
-            with_context(|ctx| ctx.set_flushing_sync(was_flushing));
-            panic!(
-                "Maximum update depth exceeded. This can happen when an effect \
-                 continuously triggers itself."
-            );
+    let scheduled = with_context(|ctx| ctx.take_pending_reactions());
+    with_context(|ctx| {
+        for reaction_weak in pending.chain(scheduled) {
+            ctx.add_pending_reaction(reaction_weak);
         }
+    });
+    ran
+}
 
-        let pending = with_context(|ctx| ctx.take_pending_reactions());
+/// Number of distinct runnable effects in the pending queue.
+pub(crate) fn pending_effect_count() -> usize {
+    let pending = with_context(|ctx| ctx.take_pending_reactions());
 
-        if pending.is_empty() {
-            break;
+    let mut seen: Vec<*const ()> = Vec::new();
+    for reaction in pending.iter().filter_map(|w| w.upgrade()) {
+        let ptr = Rc::as_ptr(&reaction) as *const ();
+        if is_runnable(&reaction) && Lane::of(reaction.flags()) != Lane::Idle && !seen.contains(&ptr) {
+            seen.push(ptr);
         }
+    }
 
+    with_context(|ctx| {
         for reaction_weak in pending {
-            if let Some(reaction) = reaction_weak.upgrade() {
-                if (reaction.flags() & INERT) != 0 {
-                    continue;
-                }
+            ctx.add_pending_reaction(reaction_weak);
+        }
+    });
+    seen.len()
+}
 
-                if !is_dirty(&*reaction) {
-                    continue;
-                }
+// =============================================================================
+// SPECIALIZED SCHEDULING FOR EFFECT INNER
+// =============================================================================
 
-                // Check if it's an effect
-                if (reaction.flags() & EFFECT) != 0 {
-                    // Try to get as EffectInner
-                    if reaction.as_any().is::<EffectInner>() {
-                        // We need to reconstruct the Rc<EffectInner>
-                        // This is tricky because we only have Rc<dyn AnyReaction>
-                        // For now, use the update() trait method
-                        reaction.update();
-                    }
-                }
-            }
-        }
-    }
+/// Schedule an EffectInner directly (preferred method).
+///
+/// This version keeps the Rc<EffectInner> and can call update_effect properly.
+pub fn schedule_effect_inner(effect: Rc<EffectInner>) {
+    // If we're in a batch or already flushing, just mark for later
+    with_context(|ctx| {
+        ctx.add_pending_reaction(Rc::downgrade(&(effect.clone() as Rc<dyn AnyReaction>)));
+    });
 
-    with_context(|ctx| ctx.set_flushing_sync(was_flushing));
+    if should_auto_flush() {
+        flush_queued();
+    } else {
+        request_flush();
+    }
 }
 
 // =============================================================================
@@ -344,8 +899,9 @@ fn run_effect_flush() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::config::Config;
     use crate::primitives::effect::EffectInner;
-    use std::cell::Cell;
+    use std::cell::{Cell, RefCell};
 
     #[test]
     fn flush_sync_runs_pending_effects() {
@@ -376,8 +932,69 @@ mod tests {
 
     #[test]
     fn max_flush_count_prevents_infinite_loop() {
-        // Just verify the constant exists and is reasonable
-        assert_eq!(MAX_FLUSH_COUNT, 1000);
+        // Just verify the default exists and is reasonable
+        assert_eq!(Config::default().max_update_depth, 1000);
+    }
+
+    #[test]
+    fn try_flush_reports_effects_past_max_update_depth() {
+        use crate::primitives::effect::{effect, set_effect_name};
+        use crate::primitives::signal::signal;
+        use crate::reactivity::test_scheduler::TestScheduler;
+
+        let scheduler = TestScheduler::new();
+        set_max_update_depth(20);
+
+        // Each effect writes what the other reads
+        let (ping, pong) = (signal(0), signal(0));
+        let forward = effect({
+            let (ping, pong) = (ping.clone(), pong.clone());
+            move || {
+                set_effect_name("forward");
+                pong.set(ping.get() + 1);
+            }
+        });
+        let back = effect(move || {
+            set_effect_name("back");
+            ping.set(pong.get() + 1);
+        });
+
+        let error = try_flush().unwrap_err();
+        assert_eq!(error.max_depth, 20);
+        assert_eq!(error.effects.len(), 1);
+        assert!(error.effects[0].starts_with("back (src/reactivity/scheduling.rs:"));
+        assert!(error.to_string().starts_with("Maximum update depth exceeded (20 flush passes)"));
+        assert!(error.to_string().ends_with(&format!("Still re-running: {}", error.effects[0])));
+
+        // Still queued; gone once disposed
+        assert_eq!(scheduler.pending(), 1);
+        forward();
+        back();
+        assert_eq!(try_flush(), Ok(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum update depth exceeded (5 flush passes)")]
+    fn flush_sync_panics_past_max_update_depth() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+
+        set_max_update_depth(5);
+        let (ping, pong) = (signal(0), signal(0));
+        let _forward = effect_sync({
+            let (ping, pong) = (ping.clone(), pong.clone());
+            move || {
+                pong.set(ping.get() + 1);
+            }
+        });
+        let _back = effect_sync({
+            let (ping, pong) = (ping.clone(), pong.clone());
+            move || {
+                ping.set(pong.get() + 1);
+            }
+        });
+        // The next write sets them off
+        ping.set(0);
     }
 
     #[test]
@@ -412,4 +1029,639 @@ mod tests {
         // Effect should have run
         assert_eq!(run_count.get(), 1);
     }
+
+    #[test]
+    fn flush_hooks_fire_once_per_outermost_flush() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let starts = Rc::new(Cell::new(0));
+        let ends = Rc::new(Cell::new(0));
+        let a = signal(0);
+        let b = signal(0);
+
+        // a -> effect writes b -> second effect (nested flush)
+        let _e1 = effect_sync({
+            let a = a.clone();
+            let b = b.clone();
+            move || {
+                b.set(a.get() * 2);
+            }
+        });
+        let _e2 = effect_sync({
+            let b = b.clone();
+            move || {
+                b.get();
+            }
+        });
+        let _e3 = effect_sync({
+            let a = a.clone();
+            move || {
+                a.get();
+            }
+        });
+
+        let remove_start = on_flush_start({
+            let starts = starts.clone();
+            move || starts.set(starts.get() + 1)
+        });
+        let remove_end = on_flush_end({
+            let ends = ends.clone();
+            move || ends.set(ends.get() + 1)
+        });
+
+        a.set(1);
+        assert_eq!((starts.get(), ends.get()), (1, 1));
+
+        batch(|| {
+            a.set(2);
+            a.set(3);
+        });
+        assert_eq!((starts.get(), ends.get()), (2, 2));
+
+        remove_start();
+        remove_end();
+        a.set(4);
+        assert_eq!((starts.get(), ends.get()), (2, 2));
+    }
+
+    #[test]
+    fn flush_hooks_run_in_registration_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let removers: Vec<_> = (0..3)
+            .map(|i| {
+                let log = log.clone();
+                on_flush_end(move || log.borrow_mut().push(i))
+            })
+            .collect();
+
+        flush_sync();
+        assert_eq!(*log.borrow(), vec![0, 1, 2]);
+
+        for remove in removers {
+            remove();
+        }
+        flush_sync();
+        assert_eq!(log.borrow().len(), 3);
+    }
+
+    #[test]
+    fn lane_of_flags() {
+        assert_eq!(Lane::of(EFFECT | USER_EFFECT), Lane::User);
+        assert_eq!(Lane::of(EFFECT | RENDER_EFFECT | USER_EFFECT), Lane::User);
+        for lane in [Lane::Render, Lane::User, Lane::Idle] {
+            assert_eq!(Lane::of(lane.effect_flags()), lane);
+        }
+    }
+
+    #[test]
+    fn idle_effects_wait_for_flush_idle() {
+        use crate::primitives::effect::effect_in_lane;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let count = signal(0);
+        let runs = Rc::new(RefCell::new(Vec::new()));
+        let _dispose = effect_in_lane(Lane::Idle, {
+            let (count, runs) = (count.clone(), runs.clone());
+            move || runs.borrow_mut().push(count.get())
+        });
+
+        // Not even the first run happens before flush_idle()
+        flush_sync();
+        assert!(runs.borrow().is_empty());
+        assert!(has_idle_work());
+
+        flush_idle();
+        assert_eq!(*runs.borrow(), vec![0]);
+        assert!(!has_idle_work());
+
+        // Several changes collapse into one idle run
+        count.set(1);
+        batch(|| count.set(2));
+        assert_eq!(*runs.borrow(), vec![0]);
+        flush_idle();
+        assert_eq!(*runs.borrow(), vec![0, 2]);
+
+        flush_idle();
+        assert_eq!(runs.borrow().len(), 2);
+    }
+
+    #[test]
+    fn render_lane_runs_before_user_lane() {
+        use crate::primitives::effect::{effect, effect_in_lane};
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let count = signal(0);
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let lanes = [Lane::User, Lane::Render, Lane::User, Lane::Render];
+        let _disposers: Vec<_> = lanes
+            .iter()
+            .enumerate()
+            .map(|(i, &lane)| {
+                let (count, order) = (count.clone(), order.clone());
+                effect_in_lane(lane, move || {
+                    count.get();
+                    order.borrow_mut().push(i);
+                })
+            })
+            .collect();
+        let _user = effect({
+            let (count, order) = (count.clone(), order.clone());
+            move || {
+                count.get();
+                order.borrow_mut().push(9);
+            }
+        });
+
+        order.borrow_mut().clear();
+        batch(|| count.set(1));
+        assert_eq!(*order.borrow(), vec![1, 3, 0, 2, 9]);
+    }
+
+    #[test]
+    fn held_effects_request_a_host_flush() {
+        use crate::primitives::effect::effect;
+        use crate::primitives::signal::signal;
+
+        let requests = Rc::new(Cell::new(0));
+        set_manual_flush(true);
+        set_flush_request(Some(Rc::new({
+            let requests = requests.clone();
+            move || requests.set(requests.get() + 1)
+        })));
+
+        let count = signal(0);
+        let seen = Rc::new(Cell::new(-1));
+        let _dispose = effect({
+            let (count, seen) = (count.clone(), seen.clone());
+            move || seen.set(count.get())
+        });
+        assert_eq!(seen.get(), -1);
+        assert_eq!(requests.get(), 1);
+
+        // The host flushes when asked
+        flush_sync();
+        assert_eq!(seen.get(), 0);
+
+        count.set(5);
+        assert_eq!(seen.get(), 0);
+        assert_eq!(requests.get(), 2);
+
+        set_flush_request(None);
+        set_manual_flush(false);
+        flush_sync();
+        assert_eq!(seen.get(), 5);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_spans_cover_runtime_work() {
+        use std::sync::{Arc, Mutex};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        /// Records "name" or "name(label)" for every span created
+        struct Spans(Arc<Mutex<Vec<String>>>);
+
+        struct Label<'a>(&'a mut String);
+
+        impl tracing::field::Visit for Label<'_> {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "name" {
+                    *self.0 = format!("{value:?}");
+                }
+            }
+        }
+
+        impl tracing::Subscriber for Spans {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut label = String::new();
+                span.record(&mut Label(&mut label));
+                let mut spans = self.0.lock().unwrap();
+                spans.push(match label.as_str() {
+                    "" => span.metadata().name().to_string(),
+                    label => format!("{}({label})", span.metadata().name()),
+                });
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = Spans(spans.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let count = crate::primitives::signal::signal(1);
+            let doubled = crate::primitives::derived::derived({
+                let count = count.clone();
+                move || count.get() * 2
+            });
+            doubled.set_debug_name("doubled");
+            let _dispose = crate::primitives::effect::effect_sync(move || {
+                crate::primitives::effect::set_effect_name("log");
+                doubled.get();
+            });
+            spans.lock().unwrap().clear();
+
+            crate::reactivity::batching::batch(|| count.set(2));
+        });
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans[0], "batch");
+        assert!(spans.contains(&"flush".to_string()));
+        assert!(spans.contains(&r#"derived("doubled")"#.to_string()));
+        assert!(spans.contains(&"effect(log)".to_string()));
+    }
+
+    #[test]
+    fn nested_flush_sync_defers_running_effects() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+
+        let (steps, other) = (signal(0), signal(0));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (other, seen) = (other.clone(), seen.clone());
+            move || seen.borrow_mut().push(other.get())
+        });
+        let nested_runs = Rc::new(RefCell::new(Vec::new()));
+        let _step = effect_sync({
+            let (steps, other, nested_runs) = (steps.clone(), other.clone(), nested_runs.clone());
+            move || {
+                let step = steps.get();
+                if (1..3).contains(&step) {
+                    // Re-dirties this effect, which is still running
+                    steps.set(step + 1);
+                    other.set(step);
+                    nested_runs.borrow_mut().push(flush_sync());
+                }
+            }
+        });
+
+        steps.set(1);
+        assert_eq!(steps.get(), 3);
+        assert_eq!(*seen.borrow(), [0, 1, 2]);
+        assert_eq!(*nested_runs.borrow(), [1, 1]);
+        assert_eq!(flush_sync(), 0);
+    }
+
+    #[test]
+    fn flush_sync_inside_batch_runs_writes_so_far() {
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let count = signal(0);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (count, seen) = (count.clone(), seen.clone());
+            move || seen.borrow_mut().push(count.get())
+        });
+
+        batch(|| {
+            count.set(1);
+            assert_eq!(flush_sync(), 1);
+            assert!(with_context(|ctx| ctx.is_batching() && !ctx.is_flushing_sync()));
+            count.set(2);
+            count.set(3);
+            assert_eq!(*seen.borrow(), [0, 1]);
+        });
+        assert_eq!(*seen.borrow(), [0, 1, 3]);
+    }
+
+    #[test]
+    fn flush_sync_inside_derived_runs_other_effects() {
+        use crate::primitives::derived::derived;
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let (count, other) = (signal(1), signal(0));
+        let seen = Rc::new(Cell::new(-1));
+        let _watch = effect_sync({
+            let (other, seen) = (other.clone(), seen.clone());
+            move || seen.set(other.get())
+        });
+        let flushed = Rc::new(Cell::new(0));
+        let doubled = derived({
+            let (count, flushed) = (count.clone(), flushed.clone());
+            move || {
+                flushed.set(flush_sync());
+                count.get() * 2
+            }
+        });
+
+        batch(|| {
+            other.set(5);
+            assert_eq!(doubled.get(), 2);
+        });
+        assert_eq!((seen.get(), flushed.get()), (5, 1));
+    }
+
+    #[test]
+    fn flush_budget_leaves_the_rest_for_the_next_flush() {
+        use crate::core::config::update_config;
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use std::time::Duration;
+
+        let count = signal(0);
+        let runs = Rc::new(RefCell::new(Vec::new()));
+        let _effects: Vec<_> = (0..3)
+            .map(|i| {
+                let (count, runs) = (count.clone(), runs.clone());
+                effect_sync(move || {
+                    count.get();
+                    runs.borrow_mut().push(i);
+                    std::thread::sleep(Duration::from_millis(2));
+                })
+            })
+            .collect();
+        runs.borrow_mut().clear();
+
+        update_config(|c| c.flush_budget = Some(Duration::from_millis(1)));
+        count.set(1);
+        assert_eq!(*runs.borrow(), [0]);
+        assert_eq!(flush_sync(), 1);
+        assert_eq!(*runs.borrow(), [0, 1]);
+
+        update_config(|c| c.flush_budget = None);
+        assert_eq!(flush_sync(), 1);
+        assert_eq!(*runs.borrow(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn depth_error_leaves_no_flush_in_progress() {
+        use crate::primitives::effect::effect;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::test_scheduler::TestScheduler;
+
+        let _scheduler = TestScheduler::new();
+        set_max_update_depth(10);
+        let (ping, pong) = (signal(0), signal(0));
+        let forward = effect({
+            let (ping, pong) = (ping.clone(), pong.clone());
+            move || {
+                pong.set(ping.get() + 1);
+            }
+        });
+        let back = effect({
+            let (ping, pong) = (ping.clone(), pong.clone());
+            move || {
+                ping.set(pong.get() + 1);
+            }
+        });
+
+        assert!(try_flush().is_err());
+        assert_eq!(current_flush(), None);
+        assert!(!with_context(|ctx| ctx.is_flushing_sync()));
+
+        // The next flush starts over with a full budget
+        forward();
+        back();
+        assert_eq!(try_flush(), Ok(0));
+        ping.set(1);
+        assert_eq!(try_flush(), Ok(0));
+    }
+
+    #[test]
+    fn diamond_runs_once_without_glitches() {
+        use crate::primitives::derived::derived;
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+        use crate::testing::count_recomputations;
+
+        //     a
+        //    / \
+        //   b   c
+        //    \ /
+        //     d
+        let a = signal(1);
+        let b = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+        let c = derived({
+            let a = a.clone();
+            move || a.get() * 3
+        });
+        let d = derived({
+            let (b, c) = (b.clone(), c.clone());
+            move || b.get() + c.get()
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (a, d, seen) = (a.clone(), d.clone(), seen.clone());
+            move || {
+                let (a, d) = (a.get(), d.get());
+                assert_eq!(d, 5 * a, "glitch: d = {d} for a = {a}");
+                seen.borrow_mut().push(d);
+            }
+        });
+        let recomputes = count_recomputations(&d);
+
+        a.set(2);
+        batch(|| {
+            a.set(3);
+            a.set(4);
+        });
+        assert_eq!(*seen.borrow(), [5, 10, 20]);
+        assert_eq!(recomputes.get(), 2);
+    }
+
+    #[test]
+    fn uneven_diamond_updates_by_height() {
+        use crate::primitives::derived::derived;
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+        use crate::testing::count_recomputations;
+
+        // `total` reads `tens` directly and through `plus_one`, so `tens`
+        // sits at two heights below it: `plus_one` must only be checked
+        // once `tens` has recomputed
+        let n = signal(1);
+        let tens = derived({
+            let n = n.clone();
+            move || n.get() * 10
+        });
+        let plus_one = derived({
+            let tens = tens.clone();
+            move || tens.get() + 1
+        });
+        let total = derived({
+            let (tens, plus_one) = (tens.clone(), plus_one.clone());
+            move || tens.get() + plus_one.get()
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (total, seen) = (total.clone(), seen.clone());
+            move || seen.borrow_mut().push(total.get())
+        });
+        let counts = [&tens, &plus_one, &total].map(count_recomputations);
+
+        n.set(2);
+        batch(|| {
+            n.set(3);
+            n.set(4);
+        });
+        assert_eq!(*seen.borrow(), [21, 41, 81]);
+        assert_eq!(counts.map(|count| count.get()), [2, 2, 2]);
+
+        // Read directly, outside any effect
+        n.set(5);
+        assert_eq!(total.get(), 101);
+    }
+
+    #[test]
+    fn deep_chain_recomputes_each_link_once() {
+        use crate::primitives::derived::{derived, Derived};
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::testing::count_recomputations;
+
+        let source = signal(0);
+        let mut links: Vec<Derived<i32>> = Vec::new();
+        for i in 0..50 {
+            let link = match links.last() {
+                // One link clamps, so changes below 0 stop there
+                Some(prev) if i == 25 => {
+                    let prev = prev.clone();
+                    derived(move || prev.get().max(0))
+                }
+                Some(prev) => {
+                    let prev = prev.clone();
+                    derived(move || prev.get() + 1)
+                }
+                None => {
+                    let source = source.clone();
+                    derived(move || source.get())
+                }
+            };
+            links.push(link);
+        }
+        let last = links.last().unwrap().clone();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (source, last, seen) = (source.clone(), last.clone(), seen.clone());
+            move || {
+                let value = last.get();
+                // 24 links below the clamp, 24 above it
+                let source = crate::reactivity::batching::untrack(|| source.get());
+                assert_eq!(value, source.max(-24) + 48);
+                seen.borrow_mut().push(value);
+            }
+        });
+        let counters: Vec<_> = links.iter().map(count_recomputations).collect();
+
+        source.set(5);
+        assert_eq!(*seen.borrow(), [48, 53]);
+        assert!(counters.iter().all(|c| c.get() == 1));
+
+        // Clamped at link 25: the links above it and the effect stay put
+        source.set(-30);
+        source.set(-40);
+        assert_eq!(*seen.borrow(), [48, 53, 24]);
+        assert!(counters[..=25].iter().all(|c| c.get() == 3));
+        assert!(counters[26..].iter().all(|c| c.get() == 2));
+    }
+
+    #[test]
+    fn conditional_dependencies_follow_the_branch() {
+        use crate::primitives::derived::derived;
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+
+        let (use_left, left, right) = (signal(true), signal(1), signal(10));
+        let picked = derived({
+            let (use_left, left, right) = (use_left.clone(), left.clone(), right.clone());
+            move || if use_left.get() { left.get() } else { right.get() }
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let _watch = effect_sync({
+            let (use_left, left, right) = (use_left.clone(), left.clone(), right.clone());
+            let (picked, seen) = (picked.clone(), seen.clone());
+            move || {
+                let value = picked.get();
+                let expected = if use_left.get() { left.get() } else { right.get() };
+                assert_eq!(value, expected, "glitch");
+                seen.borrow_mut().push(value);
+            }
+        });
+
+        // The unread branch doesn't re-run anything
+        right.set(11);
+        assert_eq!(*seen.borrow(), [1]);
+
+        // Switch branches and write the new one in the same batch
+        batch(|| {
+            use_left.set(false);
+            right.set(12);
+            left.set(2);
+        });
+        assert_eq!(*seen.borrow(), [1, 12]);
+
+        // Now the old branch is the unread one
+        left.set(3);
+        assert_eq!(*seen.borrow(), [1, 12]);
+        right.set(13);
+        assert_eq!(*seen.borrow(), [1, 12, 13]);
+    }
+
+    #[test]
+    fn unchanged_deriveds_stop_propagation() {
+        use crate::primitives::derived::derived;
+        use crate::primitives::effect::effect_sync;
+        use crate::primitives::signal::signal;
+        use crate::reactivity::batching::batch;
+        use crate::testing::count_recomputations;
+
+        let count = signal(1);
+        let parity = derived({
+            let count = count.clone();
+            move || count.get() % 2
+        });
+        let label = derived({
+            let parity = parity.clone();
+            move || if parity.get() == 0 { "even" } else { "odd" }
+        });
+        let runs = Rc::new(Cell::new(0));
+        let _show = effect_sync({
+            let (label, runs) = (label.clone(), runs.clone());
+            move || {
+                label.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        let recomputes = count_recomputations(&label);
+
+        // Parity recomputes to the same value: nothing below it does
+        count.set(3);
+        batch(|| {
+            count.set(5);
+            count.set(7);
+        });
+        assert_eq!(runs.get(), 1);
+        assert_eq!(recomputes.get(), 0);
+
+        // A real change runs the effect once per batch
+        batch(|| {
+            count.set(8);
+            count.set(10);
+        });
+        assert_eq!(runs.get(), 2);
+        assert_eq!(recomputes.get(), 1);
+        assert_eq!(label.get(), "even");
+    }
 }
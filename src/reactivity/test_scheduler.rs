@@ -0,0 +1,272 @@
+// ============================================================================
+// spark-signals - Test Scheduler
+// Deterministic, step-through effect execution for tests
+// ============================================================================
+//
+// Normally a write outside a batch flushes its effects before returning.
+// While a TestScheduler is alive on the thread, scheduled effects are only
+// queued: writes, batch ends and `effect()` creation leave them pending, and
+// the test decides when they run - one at a time with `step()`, or all of
+// them with `run_until_idle()`. In between, the test can assert on dirty
+// flags, derived values and the queue itself.
+//
+// `flush_sync()` / `tick()` still flush everything when called explicitly.
+// ============================================================================
+
+use crate::core::context::{enter_runtime, Runtime};
+
+use crate::reactivity::scheduling::{
+    flush_sync, pending_effect_count, run_next_pending, set_manual_flush,
+};
+
+/// Steps before `run_until_idle()` assumes effects keep re-scheduling each other
+const MAX_IDLE_STEPS: usize = 10_000;
+
+/// Holds effects in a queue until the test runs them.
+///
+/// Installed for the current thread on creation and removed on drop. Any
+/// effects still pending at drop are flushed, so later code sees a settled
+/// graph.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{effect, signal, TestScheduler};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let scheduler = TestScheduler::new();
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let count = signal(0);
+///
+/// let _dispose = effect({
+///     let (count, log) = (count.clone(), log.clone());
+///     move || log.borrow_mut().push(count.get())
+/// });
+///
+/// // Nothing runs until the test says so
+/// assert!(log.borrow().is_empty());
+/// assert_eq!(scheduler.pending(), 1);
+///
+/// assert!(scheduler.step());
+/// assert_eq!(*log.borrow(), vec![0]);
+///
+/// count.set(1);
+/// count.set(2);
+/// assert_eq!(scheduler.pending(), 1);
+/// assert_eq!(scheduler.run_until_idle(), 1);
+/// assert_eq!(*log.borrow(), vec![0, 2]);
+/// ```
+pub struct TestScheduler {
+    /// The runtime it was installed in (None = the thread's own context).
+    /// Thread-bound, like the graph it controls.
+    runtime: Option<Runtime>,
+}
+
+impl TestScheduler {
+    /// Install a test scheduler in the current context: the thread's, or
+    /// the entered `Runtime`'s. It controls that context wherever it is used.
+    pub fn new() -> Self {
+        set_manual_flush(true);
+        Self {
+            runtime: Runtime::current(),
+        }
+    }
+
+    /// Number of effects waiting to run.
+    pub fn pending(&self) -> usize {
+        enter_runtime(self.runtime.as_ref(), pending_effect_count)
+    }
+
+    /// True if no effect is waiting to run.
+    pub fn is_idle(&self) -> bool {
+        self.pending() == 0
+    }
+
+    /// Run the oldest pending effect. Returns false if none was pending.
+    ///
+    /// Effects the run schedules are queued behind those already waiting.
+    pub fn step(&self) -> bool {
+        enter_runtime(self.runtime.as_ref(), run_next_pending)
+    }
+
+    /// Run pending effects (including ones they schedule) until none are
+    /// left. Returns the number of effect runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if effects are still being scheduled after 10,000 runs.
+    pub fn run_until_idle(&self) -> usize {
+        let mut steps = 0;
+        while self.step() {
+            steps += 1;
+            if steps >= MAX_IDLE_STEPS {
+                panic!(
+                    "TestScheduler::run_until_idle: effects still pending after {} runs. \
+                     An effect is probably re-triggering itself.",
+                    MAX_IDLE_STEPS
+                );
+            }
+        }
+        steps
+    }
+}
+
+impl Default for TestScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestScheduler {
+    fn drop(&mut self) {
+        enter_runtime(self.runtime.as_ref(), || {
+            set_manual_flush(false);
+            if !std::thread::panicking() {
+                flush_sync();
+            }
+        });
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constants::{DIRTY, MAYBE_DIRTY};
+    use crate::core::types::AnyReaction;
+    use crate::primitives::derived::derived;
+    use crate::primitives::effect::Effect;
+    use crate::primitives::signal::signal;
+    use crate::reactivity::batching::batch;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn logging_effect(log: &Log, name: &'static str, read: impl Fn() + 'static) -> Effect {
+        let log = log.clone();
+        Effect::new(move || {
+            read();
+            log.borrow_mut().push(name);
+        })
+    }
+
+    #[test]
+    fn writes_leave_effects_dirty_until_stepped() {
+        let scheduler = TestScheduler::new();
+        let a = signal(1);
+        let doubled = derived({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+        let log: Log = Rc::default();
+
+        let effect = logging_effect(&log, "e", {
+            let doubled = doubled.clone();
+            move || {
+                doubled.get();
+            }
+        });
+        scheduler.run_until_idle();
+
+        a.set(5);
+        // Intermediate state: effect marked, not yet run; derived reads fresh
+        assert!(effect.inner().flags() & (DIRTY | MAYBE_DIRTY) != 0);
+        assert_eq!(*log.borrow(), vec!["e"]);
+        assert_eq!(doubled.get(), 10);
+
+        assert!(scheduler.step());
+        assert!(!scheduler.step());
+        assert_eq!(*log.borrow(), vec!["e", "e"]);
+        assert!(scheduler.is_idle());
+    }
+
+    #[test]
+    fn step_runs_effects_in_queue_order() {
+        let scheduler = TestScheduler::new();
+        let a = signal(0);
+        let log: Log = Rc::default();
+
+        let _first = logging_effect(&log, "first", {
+            let a = a.clone();
+            move || {
+                a.get();
+            }
+        });
+        let _second = logging_effect(&log, "second", {
+            let a = a.clone();
+            move || {
+                a.get();
+            }
+        });
+        scheduler.run_until_idle();
+        log.borrow_mut().clear();
+
+        batch(|| a.set(1));
+        assert_eq!(scheduler.pending(), 2);
+
+        scheduler.step();
+        assert_eq!(*log.borrow(), vec!["first"]);
+        scheduler.step();
+        assert_eq!(*log.borrow(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn cascades_queue_behind_waiting_effects() {
+        let scheduler = TestScheduler::new();
+        let a = signal(0);
+        let b = signal(0);
+        let log: Log = Rc::default();
+
+        let _writer = logging_effect(&log, "writer", {
+            let (a, b) = (a.clone(), b.clone());
+            move || {
+                b.set(a.get());
+            }
+        });
+        let _other = logging_effect(&log, "other", {
+            let a = a.clone();
+            move || {
+                a.get();
+            }
+        });
+        let _reader = logging_effect(&log, "reader", {
+            let b = b.clone();
+            move || {
+                b.get();
+            }
+        });
+        assert_eq!(scheduler.run_until_idle(), 3);
+        log.borrow_mut().clear();
+
+        a.set(1);
+        assert_eq!(scheduler.run_until_idle(), 3);
+        assert_eq!(*log.borrow(), vec!["writer", "other", "reader"]);
+    }
+
+    #[test]
+    fn drop_restores_automatic_flushing() {
+        let a = signal(0);
+        let log: Log = Rc::default();
+
+        let scheduler = TestScheduler::new();
+        let _effect = logging_effect(&log, "e", {
+            let a = a.clone();
+            move || {
+                a.get();
+            }
+        });
+        assert!(log.borrow().is_empty());
+
+        // Pending work is flushed on drop
+        drop(scheduler);
+        assert_eq!(*log.borrow(), vec!["e"]);
+
+        a.set(1);
+        assert_eq!(*log.borrow(), vec!["e", "e"]);
+    }
+}
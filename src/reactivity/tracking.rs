@@ -8,11 +8,26 @@
 // before mutating, using the "collect-then-mutate" pattern.
 // ============================================================================
 
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 use crate::core::constants::*;
 use crate::core::context::with_context;
-use crate::core::types::{AnyReaction, AnySource};
+use crate::core::types::{AnyReaction, AnySource, DepList};
+
+// =============================================================================
+// LINK REACTION - Add a reaction to a source's reactions list
+// =============================================================================
+
+/// Add `reaction` to `source`'s reactions.
+///
+/// Goes through here (rather than `AnySource::add_reaction`) so sources that
+/// gain reactions are known to `stats()`.
+pub(crate) fn link_reaction(source: &Rc<dyn AnySource>, reaction: Weak<dyn AnyReaction>) {
+    if source.reaction_count() == 0 {
+        with_context(|ctx| ctx.register_linked_source(source));
+    }
+    source.add_reaction(reaction);
+}
 
 // =============================================================================
 // TRACK READ - Register dependency when reading a signal
@@ -44,6 +59,13 @@ pub fn track_read(source: Rc<dyn AnySource>) {
             None => return,
         };
 
+        if source.owner().is_some_and(|owner| !owner.accepts(ctx)) {
+            panic!(
+                "Cannot read a signal created in another Runtime inside a reaction. \
+                 Read it untracked, or create the reaction in that runtime."
+            );
+        }
+
         // Check if we're in the reaction's update cycle
         if (reaction.flags() & REACTION_IS_UPDATING) != 0 {
             // Version-based deduplication: only add if not already tracked this cycle
@@ -65,7 +87,7 @@ pub fn track_read(source: Rc<dyn AnySource>) {
             reaction.add_dep(source.clone());
 
             // Add reaction to source's reactions
-            source.add_reaction(Rc::downgrade(&reaction));
+            link_reaction(&source, Rc::downgrade(&reaction));
         }
     });
 }
@@ -93,6 +115,35 @@ pub fn notify_write(source: Rc<dyn AnySource>) {
         }
     });
 
+    // Notify and flush in the source's own context, wherever the write is
+    match source.owner() {
+        Some(owner) if !with_context(|ctx| owner.accepts(ctx)) => {
+            let owner = owner.clone();
+            owner.enter(|| propagate_write(source));
+        }
+        _ => propagate_write(source),
+    }
+}
+
+/// The part of `notify_write` that runs in the source's context.
+fn propagate_write(source: Rc<dyn AnySource>) {
+    #[cfg(feature = "profiling")]
+    crate::profiling::record(crate::profiling::NodeKind::Signal, crate::profiling::node_id(&source));
+
+    if with_context(|ctx| ctx.is_recording()) {
+        crate::record::record_write(&source);
+    }
+
+    // Plain `subscribe()` callbacks (outside the graph). A standalone write
+    // they see is a flush boundary even without effects, so flush hooks
+    // run after them.
+    let subscribed = crate::primitives::subscribe::has_subscribers(&source);
+    let standalone = subscribed && crate::reactivity::scheduling::should_auto_flush();
+    let _scope = standalone.then(crate::reactivity::scheduling::FlushScope::enter);
+    if subscribed {
+        crate::primitives::subscribe::notify_subscribers(&source);
+    }
+
     // Mark all reactions as dirty
     mark_reactions(source, DIRTY);
 }
@@ -123,6 +174,7 @@ pub fn notify_write(source: Rc<dyn AnySource>) {
 pub fn mark_reactions(source: Rc<dyn AnySource>, status: u32) {
     // Collect effects to schedule (we can't schedule inside with_context)
     let mut effects_to_schedule: Vec<Rc<dyn AnyReaction>> = Vec::new();
+    let mut queued_repeaters = false;
 
     // Use iterative approach with explicit stack
     let mut stack: Vec<(Rc<dyn AnySource>, u32)> = vec![(source, status)];
@@ -161,13 +213,12 @@ pub fn mark_reactions(source: Rc<dyn AnySource>, status: u32) {
                     stack.push((derived_as_source, MAYBE_DIRTY));
                 }
             } else if (flags & REPEATER) != 0 {
-                // Inline write-through for repeaters — runs during mark_reactions, not scheduled
+                // Write-through for repeaters — inline ones forward now, deferred
+                // ones queue for the start of the next flush (never scheduled)
                 if not_dirty {
-                    // Downcast to RepeaterInner and call forward()
                     if let Some(repeater) = reaction.as_any().downcast_ref::<crate::primitives::repeater::RepeaterInner>() {
-                        repeater.forward();
+                        queued_repeaters |= repeater.notify();
                     }
-                    set_signal_status(&*reaction, CLEAN);
                 }
             } else if not_dirty && (flags & EFFECT) != 0 {
                 // For effects that just became dirty, schedule them for execution
@@ -176,79 +227,36 @@ pub fn mark_reactions(source: Rc<dyn AnySource>, status: u32) {
         }
     }
 
-    // Schedule all dirty effects
-    for effect in effects_to_schedule {
-        schedule_effect(effect);
+    if !queued_repeaters && effects_to_schedule.is_empty() {
+        return;
     }
-}
-
-/// Schedule an effect for execution.
-///
-/// Adds the effect to the pending queue and triggers a flush.
-fn schedule_effect(effect: Rc<dyn AnyReaction>) {
-    with_context(|ctx| {
-        ctx.add_pending_reaction(Rc::downgrade(&effect));
-    });
 
-    // Flush immediately (Rust doesn't have microtasks)
-    // Check if we're already flushing to avoid recursion
-    let should_flush = with_context(|ctx| !ctx.is_batching() && !ctx.is_flushing_sync());
+    // A write outside a batch or flush is one flush boundary, however many
+    // effects it schedules (each schedule_effect below may flush on its own)
+    let standalone = crate::reactivity::scheduling::should_auto_flush();
+    let _scope = standalone.then(crate::reactivity::scheduling::FlushScope::enter);
 
-    if should_flush {
-        flush_pending_effects();
+    // Deferred repeaters forward before any effect runs. Outside a batch or
+    // flush nothing else would pick them up, so forward them now.
+    if queued_repeaters && standalone {
+        crate::primitives::repeater::flush_deferred_repeaters();
     }
-}
 
-/// Flush all pending effects.
-fn flush_pending_effects() {
-    let was_flushing = with_context(|ctx| {
-        let was = ctx.is_flushing_sync();
-        ctx.set_flushing_sync(true);
-        was
-    });
-
-    const MAX_ITERATIONS: u32 = 1000;
-    let mut iterations = 0;
-
-    loop {
-        iterations += 1;
-        if iterations > MAX_ITERATIONS {
-            with_context(|ctx| ctx.set_flushing_sync(was_flushing));
-            panic!(
-                "Maximum update depth exceeded. This can happen when an effect \
-                 continuously triggers itself."
-            );
-        }
-
-        let pending = with_context(|ctx| ctx.take_pending_reactions());
-
-        if pending.is_empty() {
-            break;
+    // Queue all dirty effects, then flush them together so the flush can
+    // order them by lane
+    with_context(|ctx| {
+        for effect in &effects_to_schedule {
+            ctx.add_pending_reaction(Rc::downgrade(effect));
         }
+    });
 
-        for reaction_weak in pending {
-            if let Some(reaction) = reaction_weak.upgrade() {
-                let flags = reaction.flags();
-
-                // Skip inert (paused) or destroyed effects
-                if (flags & (INERT | DESTROYED)) != 0 {
-                    continue;
-                }
-
-                // Only run if still dirty
-                if !is_dirty(&*reaction) {
-                    continue;
-                }
-
-                // Run the effect
-                if (flags & EFFECT) != 0 {
-                    reaction.update();
-                }
-            }
-        }
+    // Flush immediately (Rust doesn't have microtasks)
+    // Check if we're already flushing to avoid recursion
+    if crate::reactivity::scheduling::should_auto_flush() {
+        crate::reactivity::scheduling::flush_queued();
+    } else {
+        crate::reactivity::scheduling::request_flush();
     }
-
-    with_context(|ctx| ctx.set_flushing_sync(was_flushing));
 }
 
 // =============================================================================
@@ -316,8 +324,8 @@ pub fn is_dirty(reaction: &dyn AnyReaction) -> bool {
 /// ensures we don't hold conflicting borrows.
 pub fn remove_reactions(reaction: Rc<dyn AnyReaction>, start: usize) {
     // Collect deps to remove from (starting at index 'start')
-    let deps_to_remove: Vec<Rc<dyn AnySource>> = {
-        let mut collected = Vec::new();
+    let deps_to_remove: DepList = {
+        let mut collected = DepList::new();
         let mut idx = 0;
         reaction.for_each_dep(&mut |dep| {
             if idx >= start {
@@ -371,7 +379,7 @@ fn remove_reaction_from_source(reaction: &Rc<dyn AnyReaction>, source: &dyn AnyS
 pub fn install_dependencies(reaction: Rc<dyn AnyReaction>, skipped: usize) {
     with_context(|ctx| {
         // Take the new deps collected during execution
-        let new_deps = ctx.swap_new_deps(Vec::new());
+        let new_deps = ctx.swap_new_deps(DepList::new());
 
         if new_deps.is_empty() && skipped == 0 {
             // No dependencies at all
@@ -385,7 +393,7 @@ pub fn install_dependencies(reaction: Rc<dyn AnyReaction>, skipped: usize) {
         // Add new deps to the reaction
         for dep in &new_deps {
             reaction.add_dep(dep.clone());
-            dep.add_reaction(Rc::downgrade(&reaction));
+            link_reaction(dep, Rc::downgrade(&reaction));
         }
     });
 }
@@ -856,7 +864,7 @@ mod tests {
 
             // Clean up
             ctx.set_active_reaction(None);
-            ctx.swap_new_deps(Vec::new());
+            ctx.swap_new_deps(DepList::new());
         });
     }
 }
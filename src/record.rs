@@ -0,0 +1,380 @@
+// ============================================================================
+// spark-signals - Write Recording & Replay
+// Log signal writes and play them back against a fresh graph
+// ============================================================================
+//
+// For reproducing bugs and stepping back through state:
+//
+//     let recorder = Recorder::new();
+//     recorder.track("count", &count);
+//     recorder.start();
+//     // ... run the app ...
+//     let log = recorder.take_log();
+//
+//     // Later, against a freshly built graph:
+//     let replayer = Replayer::new();
+//     replayer.bind("count", &count);
+//     replayer.replay(&log)?;
+//
+// Signals are tracked under a key rather than their id, so a log recorded in
+// one graph replays into another. Values are stored as text (`Recordable`).
+// While a recorder is started, the context's recording flag makes
+// `notify_write` report every write; untracked sources are ignored.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::core::context::{try_with_context, with_context};
+use crate::core::types::AnySource;
+use crate::primitives::signal::Signal;
+use crate::reactivity::batching::untrack;
+
+// =============================================================================
+// RECORDABLE VALUES
+// =============================================================================
+
+/// A value that can be written to a log as text and read back.
+///
+/// Implemented for every `Display + FromStr` type (numbers, `bool`,
+/// `String`, ...).
+pub trait Recordable: Sized {
+    /// The value as text.
+    fn encode(&self) -> String;
+
+    /// Parse a value written by `encode`.
+    fn decode(text: &str) -> Option<Self>;
+}
+
+impl<T: fmt::Display + FromStr> Recordable for T {
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+/// One recorded write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteRecord {
+    /// Position in the recording, starting at 0
+    pub seq: u64,
+    /// Id of the written source in the recorded graph
+    pub source: usize,
+    /// Key the source was tracked under
+    pub key: String,
+    /// The new value, encoded
+    pub value: String,
+}
+
+/// Why a log couldn't be replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// No signal is bound to this key
+    UnknownKey(String),
+    /// The bound signal's type couldn't decode the value
+    Decode { key: String, value: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::UnknownKey(key) => write!(f, "no signal bound to {key:?}"),
+            ReplayError::Decode { key, value } => {
+                write!(f, "signal {key:?} can't decode {value:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+fn source_id(source: &Rc<dyn AnySource>) -> usize {
+    Rc::as_ptr(source) as *const () as usize
+}
+
+// =============================================================================
+// RECORDER
+// =============================================================================
+
+/// Reads a tracked signal's current value, if it is still alive
+type Encoder = Rc<dyn Fn() -> Option<String>>;
+
+/// A recorder's tracked signals and log, shared with the context it is
+/// started in.
+#[derive(Default)]
+pub(crate) struct RecorderState {
+    tracked: RefCell<HashMap<usize, (String, Encoder)>>,
+    log: RefCell<Vec<WriteRecord>>,
+    next_seq: Cell<u64>,
+}
+
+/// Log a write (called from `notify_write` while recording).
+pub(crate) fn record_write(source: &Rc<dyn AnySource>) {
+    let Some(state) = with_context(|ctx| ctx.recorder.borrow().clone()) else {
+        return;
+    };
+    let id = source_id(source);
+    let Some((key, encode)) = state.tracked.borrow().get(&id).cloned() else {
+        return;
+    };
+    let Some(value) = encode() else {
+        return;
+    };
+
+    let seq = state.next_seq.get();
+    state.next_seq.set(seq + 1);
+    state.log.borrow_mut().push(WriteRecord {
+        seq,
+        source: id,
+        key,
+        value,
+    });
+}
+
+/// Records writes to tracked signals, in order.
+///
+/// One recorder can be started per context (the thread's, or a `Runtime`'s);
+/// starting another stops the first.
+/// Dropping a started recorder stops it.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::record::{Recorder, Replayer};
+/// use spark_signals::signal;
+///
+/// let count = signal(0);
+/// let recorder = Recorder::new();
+/// recorder.track("count", &count);
+/// recorder.start();
+/// count.set(1);
+/// count.set(5);
+/// recorder.stop();
+/// count.set(9); // not recorded
+///
+/// let log = recorder.take_log();
+/// assert_eq!(log.len(), 2);
+///
+/// // Replay into another graph
+/// let fresh = signal(0);
+/// let replayer = Replayer::new();
+/// replayer.bind("count", &fresh);
+/// replayer.replay(&log).unwrap();
+/// assert_eq!(fresh.get(), 5);
+/// ```
+#[derive(Default)]
+pub struct Recorder {
+    state: Rc<RecorderState>,
+}
+
+impl Recorder {
+    /// A recorder tracking nothing yet, not started.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record writes to `signal` under `key`.
+    pub fn track<T>(&self, key: impl Into<String>, signal: &Signal<T>) -> &Self
+    where
+        T: Recordable + 'static,
+    {
+        let weak = signal.downgrade();
+        let encode: Encoder = Rc::new(move || {
+            weak.upgrade()
+                .map(|signal| untrack(|| signal.with(T::encode)))
+        });
+        let id = source_id(&signal.as_any_source());
+        self.state
+            .tracked
+            .borrow_mut()
+            .insert(id, (key.into(), encode));
+        self
+    }
+
+    /// Start recording writes made in the current context.
+    pub fn start(&self) {
+        with_context(|ctx| {
+            *ctx.recorder.borrow_mut() = Some(self.state.clone());
+            ctx.set_recording(true);
+        });
+    }
+
+    /// Stop recording. The log is kept.
+    pub fn stop(&self) {
+        if !self.is_recording() {
+            return;
+        }
+        try_with_context(|ctx| {
+            ctx.recorder.borrow_mut().take();
+            ctx.set_recording(false);
+        });
+    }
+
+    /// True while this recorder is started in the current context.
+    pub fn is_recording(&self) -> bool {
+        let mut recording = false;
+        try_with_context(|ctx| {
+            recording = ctx
+                .recorder
+                .borrow()
+                .as_ref()
+                .is_some_and(|state| Rc::ptr_eq(state, &self.state));
+        });
+        recording
+    }
+
+    /// The writes recorded so far.
+    pub fn log(&self) -> Vec<WriteRecord> {
+        self.state.log.borrow().clone()
+    }
+
+    /// The writes recorded so far, clearing the log.
+    pub fn take_log(&self) -> Vec<WriteRecord> {
+        std::mem::take(&mut *self.state.log.borrow_mut())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// =============================================================================
+// REPLAYER
+// =============================================================================
+
+/// Writes a decoded value, or returns false if it can't be decoded
+type Decoder = Rc<dyn Fn(&str) -> bool>;
+
+/// Plays a recorded log back into signals bound by key.
+#[derive(Default)]
+pub struct Replayer {
+    bound: RefCell<HashMap<String, Decoder>>,
+}
+
+impl Replayer {
+    /// A replayer with no signals bound.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replay writes recorded under `key` into `signal`.
+    pub fn bind<T>(&self, key: impl Into<String>, signal: &Signal<T>) -> &Self
+    where
+        T: Recordable + Clone + 'static,
+    {
+        let signal = signal.clone();
+        let decode: Decoder = Rc::new(move |text: &str| match T::decode(text) {
+            Some(value) => {
+                signal.set(value);
+                true
+            }
+            None => false,
+        });
+        self.bound.borrow_mut().insert(key.into(), decode);
+        self
+    }
+
+    /// Apply one recorded write.
+    pub fn apply(&self, record: &WriteRecord) -> Result<(), ReplayError> {
+        let decode = self
+            .bound
+            .borrow()
+            .get(&record.key)
+            .cloned()
+            .ok_or_else(|| ReplayError::UnknownKey(record.key.clone()))?;
+        if decode(&record.value) {
+            Ok(())
+        } else {
+            Err(ReplayError::Decode {
+                key: record.key.clone(),
+                value: record.value.clone(),
+            })
+        }
+    }
+
+    /// Apply every write in order, each as its own update (effects run after
+    /// each one, as they did while recording). Stops at the first error.
+    pub fn replay(&self, log: &[WriteRecord]) -> Result<(), ReplayError> {
+        log.iter().try_for_each(|record| self.apply(record))
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::signal::signal;
+
+    #[test]
+    fn replay_reproduces_effect_output() {
+        let run = |count: &Signal<i32>, label: &Signal<String>| {
+            let seen = Rc::new(RefCell::new(Vec::new()));
+            let dispose = effect_sync({
+                let (count, label, seen) = (count.clone(), label.clone(), seen.clone());
+                move || {
+                    seen.borrow_mut()
+                        .push(format!("{}={}", label.get(), count.get()))
+                }
+            });
+            (seen, dispose)
+        };
+
+        let (count, label) = (signal(0), signal(String::from("a")));
+        let (seen, _dispose) = run(&count, &label);
+        let untracked = signal(0);
+        let recorder = Recorder::new();
+        recorder.track("count", &count).track("label", &label);
+        recorder.start();
+        assert!(with_context(|ctx| ctx.is_recording()));
+
+        count.set(1);
+        label.set("b".into());
+        untracked.set(3);
+        count.set(2);
+        let log = recorder.take_log();
+        drop(recorder);
+        assert!(!with_context(|ctx| ctx.is_recording()));
+
+        let writes: Vec<_> = log
+            .iter()
+            .map(|r| (r.seq, r.key.as_str(), r.value.as_str()))
+            .collect();
+        assert_eq!(
+            writes,
+            [(0, "count", "1"), (1, "label", "b"), (2, "count", "2")]
+        );
+        assert_eq!(log[0].source, log[2].source);
+
+        // Fresh graph, same wiring
+        let (count2, label2) = (signal(0), signal(String::from("a")));
+        let (seen2, _dispose2) = run(&count2, &label2);
+        let replayer = Replayer::new();
+        replayer.bind("count", &count2).bind("label", &label2);
+        replayer.replay(&log).unwrap();
+        assert_eq!(*seen.borrow(), *seen2.borrow());
+
+        let bad = WriteRecord {
+            value: "two".into(),
+            ..log[0].clone()
+        };
+        assert_eq!(
+            replayer.apply(&bad),
+            Err(ReplayError::Decode {
+                key: "count".into(),
+                value: "two".into()
+            })
+        );
+    }
+}
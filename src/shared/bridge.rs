@@ -0,0 +1,234 @@
+// ============================================================================
+// spark-signals - Shared Array Bridge
+// Copy dirty indices of a ReactiveSharedArray into slot arrays
+// ============================================================================
+//
+// Writes from the other side only set a dirty byte; nothing in the reactive
+// graph changes until someone polls. A binding does the polling:
+//
+//     let binding = bind_shared_to_signals(array.clone(), slots.clone());
+//     binding.pump_once(); // after the other side wrote
+//
+// Each pump reads the dirty indices, writes their values into the slot array,
+// clears the flags and bumps the array version, all in one batch, so effects
+// reading either the slots or the array run once per pump. `pump()` does this
+// in a loop, blocking on a wake flag in between.
+// ============================================================================
+
+use std::rc::Rc;
+use std::sync::atomic::AtomicI32;
+
+use crate::primitives::slot::{SlotArray, TrackedSlotArray};
+use crate::reactivity::batching::batch;
+use crate::shared::{wait_for_wake_timeout, ReactiveSharedArray};
+
+// =============================================================================
+// SLOT SINK
+// =============================================================================
+
+/// A slot array a [`SharedBinding`] can write into.
+pub trait SlotSink<T> {
+    /// Make room for `len` slots.
+    fn reserve_slots(&self, len: usize);
+
+    /// Write the static value of slot `index`.
+    fn write_slot(&self, index: usize, value: T);
+}
+
+impl<T: Clone + PartialEq + 'static> SlotSink<T> for SlotArray<T> {
+    fn reserve_slots(&self, len: usize) {
+        self.ensure_capacity(len);
+    }
+
+    fn write_slot(&self, index: usize, value: T) {
+        self.set_value(index, value);
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> SlotSink<T> for TrackedSlotArray<T> {
+    fn reserve_slots(&self, len: usize) {
+        self.ensure_capacity(len);
+    }
+
+    /// Also marks `index` in the tracked array's dirty set.
+    fn write_slot(&self, index: usize, value: T) {
+        self.set_value(index, value);
+    }
+}
+
+// =============================================================================
+// SHARED BINDING
+// =============================================================================
+
+/// Copies writes from a [`ReactiveSharedArray`] into a slot array.
+/// Created with [`bind_shared_to_signals()`].
+pub struct SharedBinding<T: Copy, S> {
+    array: Rc<ReactiveSharedArray<T>>,
+    slots: Rc<S>,
+}
+
+/// Bind `array` to `slots`: every element is copied now, and each
+/// [`SharedBinding::pump_once()`] copies the elements written since.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{bind_shared_to_signals, effect_sync, slot_array, ReactiveSharedArray};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let mut data = [1.0f32, 2.0];
+/// let mut dirty = [0u8; 2];
+/// let (data_ptr, dirty_ptr) = (data.as_mut_ptr(), dirty.as_mut_ptr());
+/// let array = Rc::new(unsafe { ReactiveSharedArray::new(data_ptr, 2, dirty_ptr) });
+///
+/// let widths = Rc::new(slot_array(Some(0.0f32)));
+/// let binding = bind_shared_to_signals(array, widths.clone());
+///
+/// let seen = Rc::new(Cell::new(0.0));
+/// let _dispose = effect_sync({
+///     let (widths, seen) = (widths.clone(), seen.clone());
+///     move || seen.set(widths.get(1).unwrap())
+/// });
+/// assert_eq!(seen.get(), 2.0);
+///
+/// // The other side writes index 1 and marks it dirty
+/// unsafe {
+///     *data_ptr.add(1) = 8.0;
+///     *dirty_ptr.add(1) = 1;
+/// }
+/// assert_eq!(binding.pump_once(), vec![1]);
+/// assert_eq!(seen.get(), 8.0);
+/// ```
+pub fn bind_shared_to_signals<T, S>(
+    array: Rc<ReactiveSharedArray<T>>,
+    slots: Rc<S>,
+) -> SharedBinding<T, S>
+where
+    T: Copy,
+    S: SlotSink<T>,
+{
+    batch(|| {
+        slots.reserve_slots(array.len());
+        for (index, value) in array.iter().enumerate() {
+            slots.write_slot(index, value);
+        }
+    });
+    SharedBinding { array, slots }
+}
+
+impl<T: Copy, S: SlotSink<T>> SharedBinding<T, S> {
+    /// Copy the dirty elements into the slots, clear their flags and bump the
+    /// array version. Returns the indices that were dirty.
+    ///
+    /// Effects depending on the slots or the array run once, after the pump.
+    pub fn pump_once(&self) -> Vec<usize> {
+        batch(|| {
+            let dirty = self.array.dirty_indices();
+            for &index in &dirty {
+                self.slots.write_slot(index, self.array.get(index));
+            }
+            // Clears the flags we just read (and any set since), bumps the
+            // version and notifies readers of the array itself
+            self.array.poll_dirty();
+            dirty
+        })
+    }
+
+    /// Pump every time `wake_flag` is set, until `keep_running` returns false.
+    ///
+    /// Waits at most `timeout_us` between checks of `keep_running`, and pumps
+    /// after a timeout too, so a write whose wake was missed is still picked
+    /// up.
+    pub fn pump(
+        &self,
+        wake_flag: &AtomicI32,
+        timeout_us: u32,
+        mut keep_running: impl FnMut() -> bool,
+    ) {
+        while keep_running() {
+            wait_for_wake_timeout(wake_flag, timeout_us);
+            self.pump_once();
+        }
+    }
+
+    /// The bound shared array.
+    pub fn array(&self) -> &Rc<ReactiveSharedArray<T>> {
+        &self.array
+    }
+
+    /// The slot array written to.
+    pub fn slots(&self) -> &Rc<S> {
+        &self.slots
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use crate::primitives::slot::{dirty_set, tracked_slot_array};
+    use std::cell::Cell;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn pump_writes_dirty_indices_into_tracked_slots() {
+        let mut data = [1u32, 2, 3];
+        let mut dirty = [0u8; 3];
+        let (data_ptr, dirty_ptr) = (data.as_mut_ptr(), dirty.as_mut_ptr());
+        let array = Rc::new(unsafe { ReactiveSharedArray::new(data_ptr, 3, dirty_ptr) });
+
+        let slots = Rc::new(tracked_slot_array(Some(0u32), dirty_set()));
+        let binding = bind_shared_to_signals(array.clone(), slots.clone());
+        assert_eq!(slots.peek(2), Some(3));
+        slots.dirty().borrow_mut().clear();
+
+        let runs = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let (slots, runs) = (slots.clone(), runs.clone());
+            move || {
+                slots.get(0);
+                slots.get(2);
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        unsafe {
+            *data_ptr = 10;
+            *data_ptr.add(2) = 30;
+            *dirty_ptr = 1;
+            *dirty_ptr.add(2) = 1;
+        }
+        assert_eq!(binding.pump_once(), vec![0, 2]);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(slots.peek(0), Some(10));
+        assert_eq!(array.dirty_indices(), Vec::<usize>::new());
+        assert_eq!(array.version(), 1);
+
+        let mut marked: Vec<usize> = slots.dirty().borrow().iter().copied().collect();
+        marked.sort();
+        assert_eq!(marked, [0, 2]);
+
+        // Nothing dirty: nothing written, nothing re-run
+        assert!(binding.pump_once().is_empty());
+        assert_eq!(runs.get(), 2);
+
+        // The loop pumps after the wake and stops when told to
+        let wake = AtomicI32::new(1);
+        unsafe {
+            *data_ptr.add(1) = 20;
+            *dirty_ptr.add(1) = 1;
+        }
+        let mut rounds = 0;
+        binding.pump(&wake, 1_000, || {
+            rounds += 1;
+            rounds == 1
+        });
+        assert_eq!(slots.peek(1), Some(20));
+        assert_eq!(wake.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,286 @@
+// ============================================================================
+// spark-signals - InterpolatedSharedArray
+//
+// Double-buffered reader for shared f32 arrays that are written at a lower
+// rate than they are consumed (e.g. a 20 Hz simulation driving a 60 Hz
+// renderer). Each pulled frame keeps the previous and current values plus
+// the time they arrived, so readers can sample smoothly in between.
+//
+// Only indices reported dirty by the array are copied into the history, so
+// a sparse update costs O(changed), not O(len).
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use crate::reactivity::batching::batch;
+use crate::reactivity::tracking::track_read;
+use crate::shared::ReactiveSharedArray;
+
+// =============================================================================
+// INTERPOLATED SHARED ARRAY
+// =============================================================================
+
+/// A `ReactiveSharedArray<f32>` with a one-frame history for interpolation.
+///
+/// Call `pull(now)` whenever the writer may have produced a frame (once per
+/// consumer frame is fine). Sampling then blends each index between the
+/// value before and after the most recent frame.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{InterpolatedSharedArray, ReactiveSharedArray};
+/// use std::time::{Duration, Instant};
+///
+/// let mut data = [0.0f32; 2];
+/// let mut dirty = [0u8; 2];
+/// let (data_ptr, dirty_ptr) = (data.as_mut_ptr(), dirty.as_mut_ptr());
+/// let array = unsafe { ReactiveSharedArray::new(data_ptr, 2, dirty_ptr) };
+/// let lerped = InterpolatedSharedArray::new(array);
+///
+/// let t0 = Instant::now();
+/// lerped.pull(t0);
+///
+/// // The writer moves index 0 from 0.0 to 10.0, 50ms later
+/// unsafe {
+///     *data_ptr = 10.0;
+///     *dirty_ptr = 1;
+/// }
+/// let t1 = t0 + Duration::from_millis(50);
+/// assert_eq!(lerped.pull(t1), vec![0]);
+///
+/// assert_eq!(lerped.sample(0, 0.25), 2.5);
+/// // Halfway through the next frame interval
+/// assert_eq!(lerped.sample_time(0, t1 + Duration::from_millis(25)), 5.0);
+/// ```
+pub struct InterpolatedSharedArray {
+    array: ReactiveSharedArray<f32>,
+    /// Values before the most recent frame
+    previous: RefCell<Vec<f32>>,
+    /// Values as of the most recent frame
+    current: RefCell<Vec<f32>>,
+    /// Indices changed by the most recent frame (their previous != current)
+    last_changed: RefCell<Vec<usize>>,
+    /// Arrival time of the frame before the most recent one
+    previous_time: Cell<Option<Instant>>,
+    /// Arrival time of the most recent frame
+    current_time: Cell<Option<Instant>>,
+}
+
+impl InterpolatedSharedArray {
+    /// Wrap an array. Both history buffers start as a copy of its contents.
+    pub fn new(array: ReactiveSharedArray<f32>) -> Self {
+        let snapshot: Vec<f32> = array.iter().collect();
+        Self {
+            array,
+            previous: RefCell::new(snapshot.clone()),
+            current: RefCell::new(snapshot),
+            last_changed: RefCell::new(Vec::new()),
+            previous_time: Cell::new(None),
+            current_time: Cell::new(None),
+        }
+    }
+
+    /// The wrapped array.
+    pub fn array(&self) -> &ReactiveSharedArray<f32> {
+        &self.array
+    }
+
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Take in a new frame if the writer marked anything dirty.
+    ///
+    /// Dirty indices shift their current value into the history and read
+    /// the new one; indices that changed in the previous frame but not in
+    /// this one settle (previous = current). `now` is recorded as the
+    /// frame's arrival time. The array's reactive sources are notified as
+    /// by `ReactiveSharedArray::poll_dirty()`, after the history is updated.
+    ///
+    /// Returns the indices that were dirty (empty if no frame arrived).
+    pub fn pull(&self, now: Instant) -> Vec<usize> {
+        batch(|| {
+            let dirty = self.array.poll_dirty();
+            if dirty.is_empty() {
+                // First pull establishes the timeline
+                if self.current_time.get().is_none() {
+                    self.current_time.set(Some(now));
+                }
+                return dirty;
+            }
+
+            let mut previous = self.previous.borrow_mut();
+            let mut current = self.current.borrow_mut();
+            let mut last_changed = self.last_changed.borrow_mut();
+
+            for &index in last_changed.iter() {
+                previous[index] = current[index];
+            }
+            for &index in &dirty {
+                previous[index] = current[index];
+                current[index] = self.array.get(index);
+            }
+            last_changed.clone_from(&dirty);
+
+            self.previous_time.set(self.current_time.get());
+            self.current_time.set(Some(now));
+            dirty
+        })
+    }
+
+    /// Blend an index between its previous and current value.
+    ///
+    /// `alpha` is clamped to `0.0..=1.0` (0 = previous, 1 = current).
+    /// Tracks the index like `ReactiveSharedArray::get_tracked()`.
+    pub fn sample(&self, index: usize, alpha: f32) -> f32 {
+        track_read(self.array.index_source(index));
+        let alpha = alpha.clamp(0.0, 1.0);
+        let previous = self.previous.borrow()[index];
+        let current = self.current.borrow()[index];
+        previous + (current - previous) * alpha
+    }
+
+    /// Sample an index at wall-clock time `now`.
+    ///
+    /// Rendering runs one frame behind the writer: the blend goes from the
+    /// previous to the current value over one frame interval starting when
+    /// the current frame arrived, then holds the current value.
+    pub fn sample_time(&self, index: usize, now: Instant) -> f32 {
+        self.sample(index, self.alpha_at(now))
+    }
+
+    /// Interpolation factor for `now` (see `sample_time()`).
+    ///
+    /// 1.0 until two frames have arrived.
+    pub fn alpha_at(&self, now: Instant) -> f32 {
+        match (self.frame_interval(), self.current_time.get()) {
+            (Some(interval), Some(arrived)) if !interval.is_zero() => {
+                let elapsed = now.saturating_duration_since(arrived);
+                (elapsed.as_secs_f64() / interval.as_secs_f64()).min(1.0) as f32
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Time between the two most recent frames.
+    pub fn frame_interval(&self) -> Option<Duration> {
+        let previous = self.previous_time.get()?;
+        let current = self.current_time.get()?;
+        Some(current.saturating_duration_since(previous))
+    }
+
+    /// Values before the most recent frame (untracked).
+    pub fn previous(&self, index: usize) -> f32 {
+        self.previous.borrow()[index]
+    }
+
+    /// Values as of the most recent frame (untracked).
+    pub fn current(&self, index: usize) -> f32 {
+        self.current.borrow()[index]
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::effect::effect_sync;
+    use std::rc::Rc;
+
+    struct Buffer {
+        data: Vec<f32>,
+        dirty: Vec<u8>,
+    }
+
+    impl Buffer {
+        fn new(values: &[f32]) -> Self {
+            Self {
+                data: values.to_vec(),
+                dirty: vec![0; values.len()],
+            }
+        }
+
+        fn array(&mut self) -> ReactiveSharedArray<f32> {
+            let len = self.data.len();
+            unsafe { ReactiveSharedArray::new(self.data.as_mut_ptr(), len, self.dirty.as_mut_ptr()) }
+        }
+
+        /// Write like the other side would: through the raw pointers
+        fn write(&mut self, index: usize, value: f32) {
+            unsafe {
+                *self.data.as_mut_ptr().add(index) = value;
+                *self.dirty.as_mut_ptr().add(index) = 1;
+            }
+        }
+    }
+
+    #[test]
+    fn only_dirty_indices_shift_history() {
+        let mut buffer = Buffer::new(&[1.0, 2.0, 3.0]);
+        let lerped = InterpolatedSharedArray::new(buffer.array());
+        let t0 = Instant::now();
+
+        buffer.write(0, 10.0);
+        assert_eq!(lerped.pull(t0), vec![0]);
+        assert_eq!((lerped.previous(0), lerped.current(0)), (1.0, 10.0));
+        assert_eq!((lerped.previous(1), lerped.current(1)), (2.0, 2.0));
+
+        // Next frame touches only index 2; index 0 settles
+        buffer.write(2, 30.0);
+        lerped.pull(t0 + Duration::from_millis(50));
+        assert_eq!((lerped.previous(0), lerped.current(0)), (10.0, 10.0));
+        assert_eq!((lerped.previous(2), lerped.current(2)), (3.0, 30.0));
+    }
+
+    #[test]
+    fn sample_time_follows_frame_interval() {
+        let mut buffer = Buffer::new(&[0.0]);
+        let lerped = InterpolatedSharedArray::new(buffer.array());
+        let t0 = Instant::now();
+
+        // No frames yet: hold the current value
+        assert_eq!(lerped.alpha_at(t0), 1.0);
+        lerped.pull(t0);
+
+        buffer.write(0, 100.0);
+        let t1 = t0 + Duration::from_millis(100);
+        lerped.pull(t1);
+        assert_eq!(lerped.frame_interval(), Some(Duration::from_millis(100)));
+
+        assert_eq!(lerped.sample_time(0, t1), 0.0);
+        let mid = lerped.sample_time(0, t1 + Duration::from_millis(40));
+        assert!((mid - 40.0).abs() < 1e-3, "{mid}");
+        assert_eq!(lerped.sample_time(0, t1 + Duration::from_millis(500)), 100.0);
+
+        // A pull with nothing dirty is not a frame
+        assert!(lerped.pull(t1 + Duration::from_millis(60)).is_empty());
+        assert_eq!(lerped.frame_interval(), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn effects_see_updated_history() {
+        let mut buffer = Buffer::new(&[0.0, 0.0]);
+        let lerped = Rc::new(InterpolatedSharedArray::new(buffer.array()));
+        let seen = Rc::new(Cell::new(-1.0));
+
+        let _dispose = effect_sync({
+            let lerped = lerped.clone();
+            let seen = seen.clone();
+            move || seen.set(lerped.sample(1, 0.5))
+        });
+        assert_eq!(seen.get(), 0.0);
+
+        buffer.write(1, 8.0);
+        lerped.pull(Instant::now());
+        assert_eq!(seen.get(), 4.0);
+    }
+}
@@ -0,0 +1,378 @@
+// ============================================================================
+// spark-signals - Shared Layout
+// Named, typed regions over one shared buffer
+// ============================================================================
+//
+// Instead of hand-computing byte offsets on both sides of the bridge:
+//
+//     let layout = SharedLayout::new()
+//         .input::<f32>("positions", 1024)
+//         .output::<f32>("widths", 256);
+//
+//     // Allocate layout.size() bytes (e.g. a SharedArrayBuffer), then:
+//     let regions = unsafe { layout.bind(base_ptr, byte_len) }?;
+//     let positions = regions.input::<f32>("positions")?;
+//     let widths = regions.output::<f32>("widths")?;
+//
+// Regions are laid out in declaration order, each aligned for its type.
+// Inputs (written by the other side) get a dirty-flag byte per element right
+// after their data. `regions()` reports every offset, for handing the layout
+// to the other side.
+// ============================================================================
+
+use std::any::{type_name, TypeId};
+use std::fmt;
+use std::mem::{align_of, size_of};
+
+use crate::shared::{MutableSharedArray, ReactiveSharedArray};
+
+// =============================================================================
+// REGIONS
+// =============================================================================
+
+/// Which side writes a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// Written by the other side, read reactively (`ReactiveSharedArray`)
+    Input,
+    /// Written by Rust (`MutableSharedArray`)
+    Output,
+}
+
+/// One named region of a [`SharedLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionInfo {
+    /// Region name
+    pub name: String,
+    /// Who writes the region
+    pub kind: RegionKind,
+    /// Byte offset of the first element
+    pub offset: usize,
+    /// Number of elements
+    pub len: usize,
+    /// Size of one element in bytes
+    pub element_size: usize,
+    /// Byte offset of the dirty flags (inputs only, one byte per element)
+    pub dirty_offset: Option<usize>,
+    /// Element type name, for diagnostics
+    pub type_name: &'static str,
+    type_id: TypeId,
+    align: usize,
+}
+
+/// Why a layout couldn't be bound or a region couldn't be created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The buffer is smaller than `SharedLayout::size()`
+    BufferTooSmall { needed: usize, actual: usize },
+    /// The buffer start isn't aligned for the most aligned region
+    Misaligned { align: usize },
+    /// No region has this name
+    UnknownRegion(String),
+    /// The region holds a different element type
+    TypeMismatch {
+        name: String,
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// The region was declared as the other kind
+    KindMismatch { name: String, declared: RegionKind },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::BufferTooSmall { needed, actual } => {
+                write!(f, "shared buffer has {actual} bytes, layout needs {needed}")
+            }
+            LayoutError::Misaligned { align } => {
+                write!(f, "shared buffer is not aligned to {align} bytes")
+            }
+            LayoutError::UnknownRegion(name) => write!(f, "no region named {name:?}"),
+            LayoutError::TypeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(f, "region {name:?} holds {actual}, not {expected}"),
+            LayoutError::KindMismatch { name, declared } => {
+                write!(f, "region {name:?} is declared as {declared:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+// =============================================================================
+// SHARED LAYOUT
+// =============================================================================
+
+/// Builder for the layout of a shared buffer: named, typed regions with
+/// computed offsets.
+#[derive(Debug, Clone)]
+pub struct SharedLayout {
+    regions: Vec<RegionInfo>,
+    size: usize,
+    align: usize,
+}
+
+impl SharedLayout {
+    /// An empty layout.
+    pub fn new() -> Self {
+        Self {
+            regions: Vec::new(),
+            size: 0,
+            align: 1,
+        }
+    }
+
+    /// Add a region of `len` elements written by the other side.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a region with this name already exists.
+    pub fn input<T: Copy + 'static>(self, name: impl Into<String>, len: usize) -> Self {
+        self.region::<T>(name.into(), len, RegionKind::Input)
+    }
+
+    /// Add a region of `len` elements written by Rust.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a region with this name already exists.
+    pub fn output<T: Copy + 'static>(self, name: impl Into<String>, len: usize) -> Self {
+        self.region::<T>(name.into(), len, RegionKind::Output)
+    }
+
+    fn region<T: Copy + 'static>(mut self, name: String, len: usize, kind: RegionKind) -> Self {
+        assert!(
+            self.get(&name).is_none(),
+            "SharedLayout: duplicate region {name:?}"
+        );
+
+        let align = align_of::<T>();
+        let offset = self.size.next_multiple_of(align);
+        let mut end = offset + len * size_of::<T>();
+        let dirty_offset = (kind == RegionKind::Input).then(|| {
+            let dirty = end;
+            end += len;
+            dirty
+        });
+
+        self.size = end;
+        self.align = self.align.max(align);
+        self.regions.push(RegionInfo {
+            name,
+            kind,
+            offset,
+            len,
+            element_size: size_of::<T>(),
+            dirty_offset,
+            type_name: type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+            align,
+        });
+        self
+    }
+
+    /// Total size in bytes the buffer needs.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Alignment the buffer start needs (that of the most aligned region).
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// All regions, in declaration (and memory) order.
+    pub fn regions(&self) -> &[RegionInfo] {
+        &self.regions
+    }
+
+    /// The region called `name`.
+    pub fn get(&self, name: &str) -> Option<&RegionInfo> {
+        self.regions.iter().find(|r| r.name == name)
+    }
+
+    /// Check the buffer and hand out its regions.
+    ///
+    /// # Safety
+    ///
+    /// - `base_ptr` must point to `len` bytes of valid memory
+    /// - The memory must stay valid for as long as any array created from
+    ///   the returned `SharedRegions` is alive
+    pub unsafe fn bind(&self, base_ptr: *mut u8, len: usize) -> Result<SharedRegions, LayoutError> {
+        if len < self.size {
+            return Err(LayoutError::BufferTooSmall {
+                needed: self.size,
+                actual: len,
+            });
+        }
+        if (base_ptr as usize) % self.align != 0 {
+            return Err(LayoutError::Misaligned { align: self.align });
+        }
+        Ok(SharedRegions {
+            base_ptr,
+            layout: self.clone(),
+        })
+    }
+}
+
+impl Default for SharedLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// SHARED REGIONS
+// =============================================================================
+
+/// A [`SharedLayout`] bound to a buffer. Creates the arrays for its regions.
+pub struct SharedRegions {
+    base_ptr: *mut u8,
+    layout: SharedLayout,
+}
+
+impl SharedRegions {
+    /// The layout this buffer was bound with.
+    pub fn layout(&self) -> &SharedLayout {
+        &self.layout
+    }
+
+    fn find<T: 'static>(&self, name: &str, kind: RegionKind) -> Result<&RegionInfo, LayoutError> {
+        let region = self
+            .layout
+            .get(name)
+            .ok_or_else(|| LayoutError::UnknownRegion(name.to_string()))?;
+        if region.type_id != TypeId::of::<T>() {
+            return Err(LayoutError::TypeMismatch {
+                name: name.to_string(),
+                expected: type_name::<T>(),
+                actual: region.type_name,
+            });
+        }
+        if region.kind != kind {
+            return Err(LayoutError::KindMismatch {
+                name: name.to_string(),
+                declared: region.kind,
+            });
+        }
+        debug_assert_eq!(region.offset % region.align, 0);
+        Ok(region)
+    }
+
+    /// The reactive array for input region `name`.
+    pub fn input<T: Copy + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<ReactiveSharedArray<T>, LayoutError> {
+        let region = self.find::<T>(name, RegionKind::Input)?;
+        let dirty_offset = region.dirty_offset.expect("inputs have dirty flags");
+        // Safety: offsets were computed and bounds-checked by the layout
+        Ok(unsafe {
+            ReactiveSharedArray::new(
+                self.base_ptr.add(region.offset) as *const T,
+                region.len,
+                self.base_ptr.add(dirty_offset),
+            )
+        })
+    }
+
+    /// The writable array for output region `name`.
+    pub fn output<T: Copy + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<MutableSharedArray<T>, LayoutError> {
+        let region = self.find::<T>(name, RegionKind::Output)?;
+        // Safety: offsets were computed and bounds-checked by the layout
+        Ok(unsafe {
+            MutableSharedArray::new(self.base_ptr.add(region.offset) as *mut T, region.len)
+        })
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_are_aligned_and_sequential() {
+        let layout = SharedLayout::new()
+            .input::<u8>("flags", 3)
+            .output::<f64>("sizes", 2)
+            .input::<u32>("ids", 1);
+
+        let offsets: Vec<_> = layout
+            .regions()
+            .iter()
+            .map(|r| (r.offset, r.dirty_offset))
+            .collect();
+        // flags: 3 bytes + 3 dirty; sizes aligned to 8; ids after 16 bytes
+        assert_eq!(offsets, [(0, Some(3)), (8, None), (24, Some(28))]);
+        assert_eq!(layout.size(), 29);
+        assert_eq!(layout.align(), 8);
+    }
+
+    #[test]
+    fn bound_regions_share_the_buffer() {
+        let layout = SharedLayout::new()
+            .input::<f32>("input", 2)
+            .output::<f32>("output", 2);
+        let mut memory = vec![0u64; layout.size().div_ceil(8)];
+        let base = memory.as_mut_ptr() as *mut u8;
+        let regions = unsafe { layout.bind(base, layout.size()) }.unwrap();
+
+        let input = regions.input::<f32>("input").unwrap();
+        let output = regions.output::<f32>("output").unwrap();
+        output.set(1, 2.5);
+
+        // The other side writes input[0] and flags it
+        let info = layout.get("input").unwrap();
+        unsafe {
+            *(base.add(info.offset) as *mut f32) = 4.0;
+            *base.add(info.dirty_offset.unwrap()) = 1;
+        }
+        assert_eq!(input.get(0), 4.0);
+        assert_eq!(input.dirty_indices(), vec![0]);
+        assert_eq!(
+            unsafe { *(base.add(layout.get("output").unwrap().offset) as *const f32).add(1) },
+            2.5
+        );
+    }
+
+    #[test]
+    fn mismatches_are_reported() {
+        let layout = SharedLayout::new().output::<u32>("out", 4);
+        let mut memory = vec![0u32; 4];
+        let base = memory.as_mut_ptr() as *mut u8;
+
+        let too_small = unsafe { layout.bind(base, 8) };
+        assert_eq!(
+            too_small.err(),
+            Some(LayoutError::BufferTooSmall {
+                needed: 16,
+                actual: 8
+            })
+        );
+
+        let regions = unsafe { layout.bind(base, 16) }.unwrap();
+        assert!(matches!(
+            regions.output::<f32>("out"),
+            Err(LayoutError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            regions.input::<u32>("out"),
+            Err(LayoutError::KindMismatch { .. })
+        ));
+        assert!(matches!(
+            regions.output::<u32>("missing"),
+            Err(LayoutError::UnknownRegion(_))
+        ));
+    }
+}
@@ -8,15 +8,35 @@
 // Key features:
 // - Direct pointer access to shared memory (no copying)
 // - Per-index dirty tracking for sparse updates
-// - Cross-platform wait mechanism (futex on Linux, ulock on macOS)
-// - Full integration with reactive tracking
+// - Cross-platform wait/wake (futex on Linux, ulock on macOS, WaitOnAddress on Windows)
+// - Full integration with reactive tracking (as_source / poll_dirty)
+// - Frame interpolation for low-rate writers (InterpolatedSharedArray)
+// - Computed region offsets over one buffer (SharedLayout)
+// - Dirty-index pumping into slot arrays (bind_shared_to_signals)
+// - Write-side dirty flags for output arrays (set_and_mark / flush_dirty)
+// - Tear-free element access via atomics (get_atomic / set_atomic)
 // ============================================================================
 
+pub mod bridge;
+pub mod interpolated;
+pub mod layout;
 pub mod notify;
 pub mod shared_slot_buffer;
 
+use std::collections::HashMap;
 use std::marker::PhantomData;
-use std::sync::atomic::{AtomicI32, AtomicU32, Ordering};
+use std::rc::Rc;
+use std::sync::atomic::{
+    AtomicBool, AtomicI32, AtomicI64, AtomicU32, AtomicU64, AtomicU8, Ordering,
+};
+use std::sync::Mutex;
+
+use crate::core::context::{try_with_context, with_context};
+use crate::core::types::AnySource;
+use crate::primitives::signal::{signal, Signal};
+use crate::reactivity::batching::batch;
+use crate::reactivity::tracking::track_read;
+use crate::shared::notify::{platform_wait, platform_wait_timeout, platform_wake};
 
 // =============================================================================
 // CROSS-PLATFORM WAIT
@@ -24,10 +44,10 @@ use std::sync::atomic::{AtomicI32, AtomicU32, Ordering};
 
 /// Wait for the wake flag to become non-zero.
 ///
-/// Uses platform-specific primitives:
+/// Uses platform-specific primitives (see `notify`):
 /// - Linux: futex_wait
 /// - macOS: __ulock_wait
-/// - Windows: WaitOnAddress (not yet implemented)
+/// - Windows: WaitOnAddress
 ///
 /// Returns immediately if the flag is already non-zero.
 pub fn wait_for_wake(wake_flag: &AtomicI32) {
@@ -64,99 +84,59 @@ pub fn wait_for_wake_timeout(wake_flag: &AtomicI32, timeout_us: u32) -> bool {
     }
 }
 
-#[cfg(target_os = "linux")]
-fn platform_wait(flag: &AtomicI32, expected: i32) {
-    unsafe {
-        libc::syscall(
-            libc::SYS_futex,
-            flag as *const AtomicI32,
-            libc::FUTEX_WAIT,
-            expected,
-            std::ptr::null::<libc::timespec>(),
-        );
-    }
-}
-
-#[cfg(target_os = "linux")]
-fn platform_wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
-    let timeout = libc::timespec {
-        tv_sec: (timeout_us / 1_000_000) as i64,
-        tv_nsec: ((timeout_us % 1_000_000) * 1000) as i64,
-    };
-    unsafe {
-        libc::syscall(
-            libc::SYS_futex,
-            flag as *const AtomicI32,
-            libc::FUTEX_WAIT,
-            expected,
-            &timeout as *const libc::timespec,
-        );
-    }
-}
-
-#[cfg(target_os = "macos")]
-fn platform_wait(flag: &AtomicI32, expected: i32) {
-    // macOS uses __ulock_wait
-    // UL_COMPARE_AND_WAIT = 1
-    unsafe extern "C" {
-        fn __ulock_wait(operation: u32, addr: *const AtomicI32, value: u64, timeout: u32) -> i32;
-    }
-    unsafe {
-        __ulock_wait(1, flag, expected as u64, 0);
-    }
-}
+// =============================================================================
+// ATOMIC ELEMENTS
+// =============================================================================
 
-#[cfg(target_os = "macos")]
-fn platform_wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
-    unsafe extern "C" {
-        fn __ulock_wait(operation: u32, addr: *const AtomicI32, value: u64, timeout: u32) -> i32;
-    }
-    unsafe {
-        __ulock_wait(1, flag, expected as u64, timeout_us);
-    }
-}
+/// Element types that can be loaded and stored atomically in shared memory.
+///
+/// Floats go through the same-width unsigned atomic with bit casting
+/// (`AtomicU32` for `f32`, `AtomicU64` for `f64`), matching what the other
+/// side does with `Atomics.load/store` on an integer view of the buffer.
+pub trait AtomicElement: Copy {
+    /// Atomically load the element at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid and aligned for the atomic type.
+    unsafe fn atomic_load(ptr: *const Self, order: Ordering) -> Self;
 
-#[cfg(target_os = "windows")]
-fn platform_wait(flag: &AtomicI32, expected: i32) {
-    // Windows uses WaitOnAddress
-    extern "system" {
-        fn WaitOnAddress(
-            address: *const AtomicI32,
-            compare_address: *const i32,
-            address_size: usize,
-            milliseconds: u32,
-        ) -> i32;
-    }
-    unsafe {
-        WaitOnAddress(flag, &expected, std::mem::size_of::<i32>(), u32::MAX);
-    }
+    /// Atomically store `value` at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for writes and aligned for the atomic type.
+    unsafe fn atomic_store(ptr: *mut Self, value: Self, order: Ordering);
 }
 
-#[cfg(target_os = "windows")]
-fn platform_wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
-    extern "system" {
-        fn WaitOnAddress(
-            address: *const AtomicI32,
-            compare_address: *const i32,
-            address_size: usize,
-            milliseconds: u32,
-        ) -> i32;
-    }
-    let timeout_ms = timeout_us / 1000;
-    unsafe {
-        WaitOnAddress(flag, &expected, std::mem::size_of::<i32>(), timeout_ms);
-    }
-}
+macro_rules! impl_atomic_element {
+    ($($t:ty => $atomic:ty, $bits:ty, $to_bits:expr, $from_bits:expr;)*) => {$(
+        impl AtomicElement for $t {
+            #[inline]
+            unsafe fn atomic_load(ptr: *const Self, order: Ordering) -> Self {
+                debug_assert!(ptr.cast::<$atomic>().is_aligned(), "misaligned atomic element");
+                let atomic = unsafe { <$atomic>::from_ptr(ptr as *mut $bits) };
+                ($from_bits)(atomic.load(order))
+            }
 
-// Fallback for other platforms (busy wait - not recommended for production)
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-fn platform_wait(_flag: &AtomicI32, _expected: i32) {
-    std::thread::sleep(std::time::Duration::from_micros(100));
+            #[inline]
+            unsafe fn atomic_store(ptr: *mut Self, value: Self, order: Ordering) {
+                debug_assert!(ptr.cast::<$atomic>().is_aligned(), "misaligned atomic element");
+                let atomic = unsafe { <$atomic>::from_ptr(ptr as *mut $bits) };
+                atomic.store(($to_bits)(value), order);
+            }
+        }
+    )*};
 }
 
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-fn platform_wait_timeout(_flag: &AtomicI32, _expected: i32, timeout_us: u32) {
-    std::thread::sleep(std::time::Duration::from_micros(timeout_us as u64));
+impl_atomic_element! {
+    f32 => AtomicU32, u32, f32::to_bits, f32::from_bits;
+    f64 => AtomicU64, u64, f64::to_bits, f64::from_bits;
+    u8 => AtomicU8, u8, |v| v, |v| v;
+    u32 => AtomicU32, u32, |v| v, |v| v;
+    i32 => AtomicI32, i32, |v| v, |v| v;
+    u64 => AtomicU64, u64, |v| v, |v| v;
+    i64 => AtomicI64, i64, |v| v, |v| v;
 }
 
 // =============================================================================
@@ -243,6 +223,35 @@ impl SharedBufferContext {
 unsafe impl Send for SharedBufferContext {}
 unsafe impl Sync for SharedBufferContext {}
 
+// =============================================================================
+// GRAPH SOURCES (per reactive context)
+// =============================================================================
+
+/// Reactive sources standing in for a shared array on the reactive thread.
+///
+/// The array itself is `Send + Sync` and can't own `Rc`s, so its sources
+/// live in the reactive context (the thread's, or each `Runtime`'s that
+/// reads the array), keyed by the array's id.
+pub(crate) struct ArraySources {
+    /// Mirrors the array's atomic version
+    version: Signal<u32>,
+    /// Per-index change counters, created on demand
+    indices: HashMap<usize, Signal<u32>>,
+}
+
+static NEXT_ARRAY_ID: AtomicU64 = AtomicU64::new(1);
+
+fn with_array_sources<R>(id: u64, version: u32, f: impl FnOnce(&mut ArraySources) -> R) -> R {
+    with_context(|ctx| {
+        let mut sources = ctx.array_sources.borrow_mut();
+        let entry = sources.entry(id).or_insert_with(|| ArraySources {
+            version: signal(version),
+            indices: HashMap::new(),
+        });
+        f(entry)
+    })
+}
+
 // =============================================================================
 // REACTIVE SHARED ARRAY
 // =============================================================================
@@ -262,6 +271,8 @@ pub struct ReactiveSharedArray<T: Copy> {
     dirty: *const u8,
     /// Signal version for coarse-grained change detection
     version: AtomicU32,
+    /// Key for this array's reactive sources
+    id: u64,
     _marker: PhantomData<T>,
 }
 
@@ -283,6 +294,7 @@ impl<T: Copy> ReactiveSharedArray<T> {
             len,
             dirty,
             version: AtomicU32::new(0),
+            id: NEXT_ARRAY_ID.fetch_add(1, Ordering::Relaxed),
             _marker: PhantomData,
         }
     }
@@ -307,6 +319,20 @@ impl<T: Copy> ReactiveSharedArray<T> {
         unsafe { *self.ptr.add(index) }
     }
 
+    /// Atomically load the value at `index`.
+    ///
+    /// Unlike `get()`, never observes a half-written element when the
+    /// other side writes concurrently with `Atomics.store`. Use `Acquire`
+    /// to also see the writes made before it.
+    #[inline]
+    pub fn get_atomic(&self, index: usize, order: Ordering) -> T
+    where
+        T: AtomicElement,
+    {
+        debug_assert!(index < self.len, "index out of bounds");
+        unsafe { T::atomic_load(self.ptr.add(index), order) }
+    }
+
     /// Check if an index is marked dirty.
     #[inline]
     pub fn is_dirty(&self, index: usize) -> bool {
@@ -361,6 +387,124 @@ impl<T: Copy> ReactiveSharedArray<T> {
     pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
         (0..self.len).map(move |i| self.get(i))
     }
+
+    // =========================================================================
+    // Reactive graph integration
+    // =========================================================================
+
+    /// Coarse-grained reactive source for the whole array.
+    ///
+    /// Its write version advances whenever `poll_dirty()` observes a new
+    /// atomic version, so deriveds and effects that track it re-run after
+    /// any change. Sources are per reactive thread.
+    pub fn as_source(&self) -> Rc<dyn AnySource> {
+        with_array_sources(self.id, self.version(), |sources| {
+            sources.version.as_any_source()
+        })
+    }
+
+    /// Reactive source for a single index, created on demand.
+    ///
+    /// Notified by `poll_dirty()` when that index's dirty flag is set.
+    pub fn index_source(&self, index: usize) -> Rc<dyn AnySource> {
+        debug_assert!(index < self.len, "index out of bounds");
+        with_array_sources(self.id, self.version(), |sources| {
+            sources
+                .indices
+                .entry(index)
+                .or_insert_with(|| signal(0))
+                .as_any_source()
+        })
+    }
+
+    /// Reactive read - tracks the index's source, then reads the value.
+    #[inline]
+    pub fn get_tracked(&self, index: usize) -> T {
+        track_read(self.index_source(index));
+        self.get(index)
+    }
+
+    /// Reactive read of the whole array's version (tracks `as_source()`).
+    pub fn track(&self) -> u32 {
+        track_read(self.as_source());
+        self.version()
+    }
+
+    /// Push changes made by the other side into the reactive graph.
+    ///
+    /// Call once per frame on the reactive thread. Clears every dirty flag,
+    /// notifies the sources of those indices, bumps the atomic version if
+    /// anything was dirty, and syncs the coarse source with it. All
+    /// notifications happen in one batch.
+    ///
+    /// Returns the indices that were dirty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::{effect_sync, ReactiveSharedArray};
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// let mut data = [1.0f32, 2.0, 3.0];
+    /// let mut dirty = [0u8; 3];
+    /// let (data_ptr, dirty_ptr) = (data.as_mut_ptr(), dirty.as_mut_ptr());
+    /// let array = Rc::new(unsafe { ReactiveSharedArray::new(data_ptr, 3, dirty_ptr) });
+    ///
+    /// let seen = Rc::new(Cell::new(0.0));
+    /// let _dispose = effect_sync({
+    ///     let array = array.clone();
+    ///     let seen = seen.clone();
+    ///     move || seen.set(array.get_tracked(1))
+    /// });
+    ///
+    /// // The other side writes index 1 and marks it dirty
+    /// unsafe {
+    ///     *data_ptr.add(1) = 20.0;
+    ///     *dirty_ptr.add(1) = 1;
+    /// }
+    /// assert_eq!(array.poll_dirty(), vec![1]);
+    /// assert_eq!(seen.get(), 20.0);
+    /// ```
+    pub fn poll_dirty(&self) -> Vec<usize> {
+        let dirty = self.dirty_indices();
+        for &index in &dirty {
+            self.clear_dirty(index);
+        }
+        if !dirty.is_empty() {
+            self.bump_version();
+        }
+
+        let version = self.version();
+        let (coarse, changed) = with_array_sources(self.id, version, |sources| {
+            let changed: Vec<Signal<u32>> = dirty
+                .iter()
+                .filter_map(|index| sources.indices.get(index).cloned())
+                .collect();
+            (sources.version.clone(), changed)
+        });
+
+        batch(|| {
+            for source in changed {
+                source.update(|n| *n = n.wrapping_add(1));
+            }
+            coarse.set(version);
+        });
+
+        dirty
+    }
+}
+
+impl<T: Copy> Drop for ReactiveSharedArray<T> {
+    fn drop(&mut self) {
+        // Sources only exist in contexts that used the array; this cleans
+        // up the current one's entry. A runtime's entry goes with the runtime.
+        try_with_context(|ctx| {
+            if let Ok(mut sources) = ctx.array_sources.try_borrow_mut() {
+                sources.remove(&self.id);
+            }
+        });
+    }
 }
 
 // =============================================================================
@@ -371,9 +515,21 @@ impl<T: Copy> ReactiveSharedArray<T> {
 ///
 /// Used for output arrays where Rust writes computed results that
 /// TypeScript reads.
+///
+/// Created `with_dirty_flags`, it mirrors the read side: `set_and_mark()`
+/// records changed indices, and `flush_dirty()` sets their dirty bytes and
+/// raises the wake flag, so the consumer only reads what changed.
 pub struct MutableSharedArray<T: Copy> {
     ptr: *mut T,
     len: usize,
+    /// Dirty flags for the consumer (one byte per element), or null
+    dirty: *mut u8,
+    /// Wake flag raised by `flush_dirty()`, or null
+    wake_flag: *const AtomicI32,
+    /// Indices marked since the last `flush_dirty()`
+    marked: Mutex<Vec<usize>>,
+    /// Whether anything was marked since the last `flush_dirty()`
+    has_marked: AtomicBool,
     _marker: PhantomData<T>,
 }
 
@@ -390,14 +546,41 @@ impl<T: Copy> MutableSharedArray<T> {
     /// - The memory must remain valid for the lifetime of this array
     /// - No other code should write to this memory region
     pub unsafe fn new(ptr: *mut T, len: usize) -> Self {
+        unsafe { Self::with_dirty_flags(ptr, len, std::ptr::null_mut(), std::ptr::null()) }
+    }
+
+    /// Create a mutable shared array with a dirty-flag region and wake flag
+    /// for `flush_dirty()`. Either may be null.
+    ///
+    /// # Safety
+    ///
+    /// - Same as `new()`
+    /// - `dirty`, if not null, must point to `len` bytes of shared memory
+    /// - `wake_flag`, if not null, must point to a shared `AtomicI32`
+    /// - Both must remain valid for the lifetime of this array
+    pub unsafe fn with_dirty_flags(
+        ptr: *mut T,
+        len: usize,
+        dirty: *mut u8,
+        wake_flag: *const AtomicI32,
+    ) -> Self {
         Self {
             ptr,
             len,
+            dirty,
+            wake_flag,
+            marked: Mutex::new(Vec::new()),
+            has_marked: AtomicBool::new(false),
             _marker: PhantomData,
         }
     }
 
     /// Create from a SharedBufferContext with byte offset.
+    ///
+    /// # Safety
+    ///
+    /// - `byte_offset + len * size_of::<T>()` must lie within the context's buffer
+    /// - `byte_offset` must be correctly aligned for `T`
     pub unsafe fn from_context(ctx: &SharedBufferContext, byte_offset: usize, len: usize) -> Self {
         unsafe {
             let ptr = ctx.base_ptr.add(byte_offset) as *mut T;
@@ -405,6 +588,26 @@ impl<T: Copy> MutableSharedArray<T> {
         }
     }
 
+    /// Create from a SharedBufferContext with its own dirty flags at
+    /// `dirty_offset`, waking through the context's wake flag.
+    ///
+    /// # Safety
+    ///
+    /// - Same as `from_context()`
+    /// - `dirty_offset + len` must lie within the context's buffer and not
+    ///   overlap other regions
+    pub unsafe fn from_context_with_dirty(
+        ctx: &SharedBufferContext,
+        byte_offset: usize,
+        len: usize,
+        dirty_offset: usize,
+    ) -> Self {
+        unsafe {
+            let ptr = ctx.base_ptr.add(byte_offset) as *mut T;
+            Self::with_dirty_flags(ptr, len, ctx.base_ptr.add(dirty_offset), ctx.wake_flag)
+        }
+    }
+
     /// Get a value at the given index.
     #[inline]
     pub fn get(&self, index: usize) -> T {
@@ -421,6 +624,111 @@ impl<T: Copy> MutableSharedArray<T> {
         }
     }
 
+    /// Atomically load the value at `index`.
+    #[inline]
+    pub fn get_atomic(&self, index: usize, order: Ordering) -> T
+    where
+        T: AtomicElement,
+    {
+        debug_assert!(index < self.len, "index out of bounds");
+        unsafe { T::atomic_load(self.ptr.add(index), order) }
+    }
+
+    /// Atomically store `value` at `index`, so a concurrent reader using
+    /// `Atomics.load` never sees a torn element. Use `Release` to publish
+    /// the writes made before it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::MutableSharedArray;
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// let mut data = [0.0f64; 2];
+    /// let out = unsafe { MutableSharedArray::new(data.as_mut_ptr(), 2) };
+    /// out.set_atomic(1, 2.5, Ordering::Release);
+    /// assert_eq!(out.get_atomic(1, Ordering::Acquire), 2.5);
+    /// ```
+    #[inline]
+    pub fn set_atomic(&self, index: usize, value: T, order: Ordering)
+    where
+        T: AtomicElement,
+    {
+        debug_assert!(index < self.len, "index out of bounds");
+        unsafe { T::atomic_store(self.ptr.add(index), value, order) }
+    }
+
+    /// Set a value and mark the index changed for the next `flush_dirty()`.
+    ///
+    /// Without a dirty-flag region only the wake is recorded.
+    #[inline]
+    pub fn set_and_mark(&self, index: usize, value: T) {
+        self.set(index, value);
+        if !self.dirty.is_null() {
+            self.marked
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(index);
+        }
+        self.has_marked.store(true, Ordering::Relaxed);
+    }
+
+    /// Publish the indices marked since the last flush: set their dirty
+    /// bytes, then raise and wake the wake flag.
+    ///
+    /// Call once per frame, after the writes. Does nothing if nothing was
+    /// marked. Returns the number of indices flagged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spark_signals::MutableSharedArray;
+    /// use std::sync::atomic::{AtomicI32, Ordering};
+    ///
+    /// let mut data = [0.0f32; 4];
+    /// let mut dirty = [0u8; 4];
+    /// let wake = AtomicI32::new(0);
+    /// let widths = unsafe {
+    ///     MutableSharedArray::with_dirty_flags(data.as_mut_ptr(), 4, dirty.as_mut_ptr(), &wake)
+    /// };
+    ///
+    /// widths.set_and_mark(2, 120.0);
+    /// widths.set_and_mark(2, 140.0);
+    /// assert_eq!(widths.flush_dirty(), 1);
+    /// assert_eq!(dirty, [0, 0, 1, 0]);
+    /// assert_eq!(wake.load(Ordering::SeqCst), 1);
+    /// ```
+    pub fn flush_dirty(&self) -> usize {
+        if !self.has_marked.swap(false, Ordering::Relaxed) {
+            return 0;
+        }
+
+        let mut marked = {
+            let mut marked = self.marked.lock().unwrap_or_else(|e| e.into_inner());
+            std::mem::take(&mut *marked)
+        };
+        marked.sort_unstable();
+        marked.dedup();
+        for &index in &marked {
+            debug_assert!(index < self.len, "index out of bounds");
+            unsafe {
+                *self.dirty.add(index) = 1;
+            }
+        }
+
+        if let Some(flag) = unsafe { self.wake_flag.as_ref() } {
+            // SeqCst store publishes the data and dirty bytes written above
+            flag.store(1, Ordering::SeqCst);
+            platform_wake(flag);
+        }
+        marked.len()
+    }
+
+    /// True if the array was created with a dirty-flag region.
+    pub fn has_dirty_flags(&self) -> bool {
+        !self.dirty.is_null()
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
@@ -469,19 +777,20 @@ mod tests {
     #[test]
     fn test_reactive_shared_array_basic() {
         // Create a simple buffer
-        let mut buffer = vec![1.0f32, 2.0, 3.0, 4.0, 5.0];
-        let mut dirty = vec![0u8; 5];
+        let buffer = [1.0f32, 2.0, 3.0, 4.0, 5.0];
+        let mut dirty = [0u8; 5];
+        let dirty_ptr = dirty.as_mut_ptr();
 
         let array = unsafe {
-            ReactiveSharedArray::new(buffer.as_ptr(), buffer.len(), dirty.as_ptr())
+            ReactiveSharedArray::new(buffer.as_ptr(), buffer.len(), dirty_ptr)
         };
 
         assert_eq!(array.len(), 5);
         assert_eq!(array.get(0), 1.0);
         assert_eq!(array.get(4), 5.0);
 
-        // Test dirty tracking
-        dirty[2] = 1;
+        // Test dirty tracking (simulate the other side writing the flag)
+        unsafe { *dirty_ptr.add(2) = 1 };
         assert!(!array.is_dirty(0));
         assert!(array.is_dirty(2));
 
@@ -503,10 +812,68 @@ mod tests {
         assert_eq!(array.get(2), 20.0);
     }
 
+    #[test]
+    fn mutable_array_flushes_marked_indices() {
+        let mut buffer = [0u32; 4];
+        let mut dirty = [0u8; 4];
+        let wake = AtomicI32::new(0);
+        let array = unsafe {
+            MutableSharedArray::with_dirty_flags(
+                buffer.as_mut_ptr(),
+                4,
+                dirty.as_mut_ptr(),
+                &wake,
+            )
+        };
+        assert!(array.has_dirty_flags());
+        assert_eq!(array.flush_dirty(), 0);
+        assert_eq!(wake.load(Ordering::SeqCst), 0);
+
+        array.set(0, 5); // unmarked
+        array.set_and_mark(3, 7);
+        array.set_and_mark(1, 9);
+        assert_eq!(dirty, [0, 0, 0, 0]);
+        assert_eq!(array.flush_dirty(), 2);
+        assert_eq!(dirty, [0, 1, 0, 1]);
+        assert!(wait_for_wake_timeout(&wake, 0));
+        assert_eq!(buffer, [5, 9, 0, 7]);
+
+        // No dirty region: only the wake
+        let data = buffer.as_mut_ptr();
+        let plain =
+            unsafe { MutableSharedArray::with_dirty_flags(data, 4, std::ptr::null_mut(), &wake) };
+        plain.set_and_mark(2, 1);
+        assert_eq!(plain.flush_dirty(), 0);
+        assert_eq!(wake.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn atomic_access_round_trips_bits() {
+        let mut floats = [0.0f32; 3];
+        let out = unsafe { MutableSharedArray::new(floats.as_mut_ptr(), 3) };
+        out.set_atomic(0, -1.5, Ordering::Release);
+        out.set_atomic(2, f32::NAN, Ordering::SeqCst);
+
+        let dirty = [0u8; 3];
+        let input = unsafe { ReactiveSharedArray::new(floats.as_ptr(), 3, dirty.as_ptr()) };
+        assert_eq!(input.get_atomic(0, Ordering::Acquire), -1.5);
+        assert!(input.get_atomic(2, Ordering::Relaxed).is_nan());
+
+        // The other side writes through an integer view
+        let bits = unsafe { AtomicU32::from_ptr(floats.as_mut_ptr().add(1).cast()) };
+        bits.store(7.25f32.to_bits(), Ordering::Release);
+        assert_eq!(input.get_atomic(1, Ordering::Acquire), 7.25);
+
+        let mut ints = [0i64; 1];
+        let out = unsafe { MutableSharedArray::new(ints.as_mut_ptr(), 1) };
+        out.set_atomic(0, -9, Ordering::Relaxed);
+        assert_eq!(out.get_atomic(0, Ordering::Relaxed), -9);
+    }
+
     #[test]
     fn test_version_tracking() {
-        let buffer = vec![1.0f32; 5];
-        let dirty = vec![0u8; 5];
+        let buffer = [1.0f32; 5];
+        let dirty = [0u8; 5];
 
         let array = unsafe {
             ReactiveSharedArray::new(buffer.as_ptr(), buffer.len(), dirty.as_ptr())
@@ -518,4 +885,94 @@ mod tests {
         array.bump_version();
         assert_eq!(array.version(), 2);
     }
+
+    #[test]
+    fn poll_dirty_notifies_index_sources() {
+        use crate::primitives::effect::effect_sync;
+        use std::cell::Cell;
+
+        let mut buffer = [1.0f32, 2.0, 3.0, 4.0];
+        let mut dirty = [0u8; 4];
+        let data_ptr = buffer.as_mut_ptr();
+        let dirty_ptr = dirty.as_mut_ptr();
+        let array = Rc::new(unsafe { ReactiveSharedArray::new(data_ptr, 4, dirty_ptr) });
+
+        let seen_one = Rc::new(Cell::new(0.0));
+        let runs_one = Rc::new(Cell::new(0));
+        let _e1 = effect_sync({
+            let array = array.clone();
+            let seen_one = seen_one.clone();
+            let runs_one = runs_one.clone();
+            move || {
+                seen_one.set(array.get_tracked(1));
+                runs_one.set(runs_one.get() + 1);
+            }
+        });
+
+        let runs_three = Rc::new(Cell::new(0));
+        let _e3 = effect_sync({
+            let array = array.clone();
+            let runs_three = runs_three.clone();
+            move || {
+                array.get_tracked(3);
+                runs_three.set(runs_three.get() + 1);
+            }
+        });
+
+        // Other side writes index 1
+        unsafe {
+            *data_ptr.add(1) = 20.0;
+            *dirty_ptr.add(1) = 1;
+        }
+        assert_eq!(array.poll_dirty(), vec![1]);
+        assert_eq!(seen_one.get(), 20.0);
+        assert_eq!(runs_one.get(), 2);
+        assert_eq!(runs_three.get(), 1);
+        assert!(!array.is_dirty(1));
+
+        // Nothing dirty: no notifications
+        assert!(array.poll_dirty().is_empty());
+        assert_eq!(runs_one.get(), 2);
+    }
+
+    #[test]
+    fn as_source_mirrors_atomic_version() {
+        use crate::primitives::effect::effect_sync;
+        use std::cell::Cell;
+
+        let buffer = [0u32; 2];
+        let dirty = [0u8; 2];
+        let array = Rc::new(unsafe { ReactiveSharedArray::new(buffer.as_ptr(), 2, dirty.as_ptr()) });
+
+        let runs = Rc::new(Cell::new(0));
+        let _dispose = effect_sync({
+            let array = array.clone();
+            let runs = runs.clone();
+            move || {
+                array.track();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        // Version bumped from elsewhere (e.g. another thread) is picked up on poll
+        let before = array.as_source().write_version();
+        array.bump_version();
+        assert_eq!(runs.get(), 1);
+        array.poll_dirty();
+        assert_eq!(runs.get(), 2);
+        assert_ne!(array.as_source().write_version(), before);
+    }
+
+    #[test]
+    fn dropping_array_releases_sources() {
+        let buffer = [0u8; 2];
+        let dirty = [0u8; 2];
+        let array = unsafe { ReactiveSharedArray::new(buffer.as_ptr(), 2, dirty.as_ptr()) };
+        let id = array.id;
+        array.index_source(0);
+
+        assert!(with_context(|ctx| ctx.array_sources.borrow().contains_key(&id)));
+        drop(array);
+        assert!(!with_context(|ctx| ctx.array_sources.borrow().contains_key(&id)));
+    }
 }
@@ -3,6 +3,9 @@
 //
 // Pluggable cross-side notification mechanism.
 // Counterpart to the TypeScript Notifier interface.
+//
+// Also home to the platform wait/wake primitives (futex, ulock,
+// WaitOnAddress) behind `wait_for_wake()` and `notify_one()/notify_all()`.
 // ============================================================================
 
 use std::sync::atomic::{AtomicI32, Ordering};
@@ -69,54 +72,227 @@ impl Notifier for NoopNotifier {
 }
 
 // =============================================================================
-// PLATFORM WAKE
+// PLATFORM WAIT / WAKE
 // =============================================================================
+//
+// Address-based wait/wake on a 32-bit atomic, the native counterpart of
+// `Atomics.wait` / `Atomics.notify`:
+//
+// - Linux: futex (FUTEX_WAIT / FUTEX_WAKE)
+// - macOS: __ulock_wait / __ulock_wake
+// - Windows: WaitOnAddress / WakeByAddressSingle / WakeByAddressAll
+//   (linked from synchronization.lib)
+// - Elsewhere: waits sleep briefly and wakes are no-ops; waiters poll.
+//
+// Waits may return spuriously; callers re-check the flag in a loop.
 
-/// Wake a thread waiting on the given atomic flag.
-///
-/// Counterpart to `platform_wait()` in shared/mod.rs.
-///
-/// Uses platform-specific primitives:
-/// - Linux: futex_wake
-/// - macOS: __ulock_wake
-/// - Windows: WakeByAddressSingle
 #[cfg(target_os = "linux")]
-pub fn platform_wake(flag: &AtomicI32) {
-    unsafe {
-        libc::syscall(
-            libc::SYS_futex,
-            flag as *const AtomicI32,
-            libc::FUTEX_WAKE,
-            1i32, // wake one waiter
-        );
+mod sys {
+    use std::sync::atomic::AtomicI32;
+
+    fn futex_wait(flag: &AtomicI32, expected: i32, timeout: Option<&libc::timespec>) {
+        let timeout = timeout.map_or(std::ptr::null(), |t| t as *const libc::timespec);
+        unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                flag as *const AtomicI32,
+                libc::FUTEX_WAIT,
+                expected,
+                timeout,
+            );
+        }
+    }
+
+    pub fn wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
+        let timeout = libc::timespec {
+            tv_sec: (timeout_us / 1_000_000) as i64,
+            tv_nsec: ((timeout_us % 1_000_000) * 1000) as i64,
+        };
+        futex_wait(flag, expected, Some(&timeout));
+    }
+
+    pub fn wait(flag: &AtomicI32, expected: i32) {
+        futex_wait(flag, expected, None);
+    }
+
+    fn wake(flag: &AtomicI32, count: i32) {
+        unsafe {
+            libc::syscall(libc::SYS_futex, flag as *const AtomicI32, libc::FUTEX_WAKE, count);
+        }
+    }
+
+    pub fn wake_one(flag: &AtomicI32) {
+        wake(flag, 1);
+    }
+
+    pub fn wake_all(flag: &AtomicI32) {
+        wake(flag, i32::MAX);
     }
 }
 
 #[cfg(target_os = "macos")]
-pub fn platform_wake(flag: &AtomicI32) {
-    // macOS uses __ulock_wake
-    // UL_COMPARE_AND_WAIT = 1
+mod sys {
+    use std::sync::atomic::AtomicI32;
+
+    /// Compare the 32-bit value at the address
+    const UL_COMPARE_AND_WAIT: u32 = 1;
+    /// Wake every waiter instead of one
+    const ULF_WAKE_ALL: u32 = 0x100;
+
     unsafe extern "C" {
+        fn __ulock_wait(operation: u32, addr: *const AtomicI32, value: u64, timeout_us: u32) -> i32;
         fn __ulock_wake(operation: u32, addr: *const AtomicI32, wake_value: u64) -> i32;
     }
-    unsafe {
-        __ulock_wake(1, flag, 0);
+
+    pub fn wait(flag: &AtomicI32, expected: i32) {
+        // A timeout of 0 waits forever
+        unsafe {
+            __ulock_wait(UL_COMPARE_AND_WAIT, flag, expected as u64, 0);
+        }
+    }
+
+    pub fn wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
+        unsafe {
+            __ulock_wait(UL_COMPARE_AND_WAIT, flag, expected as u64, timeout_us.max(1));
+        }
+    }
+
+    pub fn wake_one(flag: &AtomicI32) {
+        unsafe {
+            __ulock_wake(UL_COMPARE_AND_WAIT, flag, 0);
+        }
+    }
+
+    pub fn wake_all(flag: &AtomicI32) {
+        unsafe {
+            __ulock_wake(UL_COMPARE_AND_WAIT | ULF_WAKE_ALL, flag, 0);
+        }
     }
 }
 
 #[cfg(target_os = "windows")]
-pub fn platform_wake(flag: &AtomicI32) {
-    extern "system" {
-        fn WakeByAddressSingle(address: *const AtomicI32);
+mod sys {
+    use std::ffi::c_void;
+    use std::sync::atomic::AtomicI32;
+
+    /// Wait forever
+    const INFINITE: u32 = u32::MAX;
+
+    #[link(name = "synchronization")]
+    unsafe extern "system" {
+        fn WaitOnAddress(
+            address: *const c_void,
+            compare_address: *const c_void,
+            address_size: usize,
+            milliseconds: u32,
+        ) -> i32;
+        fn WakeByAddressSingle(address: *const c_void);
+        fn WakeByAddressAll(address: *const c_void);
+    }
+
+    fn wait_ms(flag: &AtomicI32, expected: i32, milliseconds: u32) {
+        unsafe {
+            WaitOnAddress(
+                flag.as_ptr() as *const c_void,
+                &expected as *const i32 as *const c_void,
+                std::mem::size_of::<i32>(),
+                milliseconds,
+            );
+        }
+    }
+
+    pub fn wait(flag: &AtomicI32, expected: i32) {
+        wait_ms(flag, expected, INFINITE);
     }
-    unsafe {
-        WakeByAddressSingle(flag);
+
+    pub fn wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
+        // Round up so short timeouts still block, and never hit INFINITE
+        wait_ms(flag, expected, timeout_us.div_ceil(1000).min(INFINITE - 1));
+    }
+
+    pub fn wake_one(flag: &AtomicI32) {
+        unsafe {
+            WakeByAddressSingle(flag.as_ptr() as *const c_void);
+        }
+    }
+
+    pub fn wake_all(flag: &AtomicI32) {
+        unsafe {
+            WakeByAddressAll(flag.as_ptr() as *const c_void);
+        }
     }
 }
 
 #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-pub fn platform_wake(_flag: &AtomicI32) {
-    // Fallback: no-op. The waiter will poll.
+mod sys {
+    use std::sync::atomic::AtomicI32;
+    use std::time::Duration;
+
+    // Busy-ish wait - not recommended for production
+    pub fn wait(_flag: &AtomicI32, _expected: i32) {
+        std::thread::sleep(Duration::from_micros(100));
+    }
+
+    pub fn wait_timeout(_flag: &AtomicI32, _expected: i32, timeout_us: u32) {
+        std::thread::sleep(Duration::from_micros(timeout_us as u64));
+    }
+
+    pub fn wake_one(_flag: &AtomicI32) {}
+
+    pub fn wake_all(_flag: &AtomicI32) {}
+}
+
+/// Block while `flag` holds `expected` (may wake spuriously).
+pub(crate) fn platform_wait(flag: &AtomicI32, expected: i32) {
+    sys::wait(flag, expected);
+}
+
+/// Block while `flag` holds `expected`, for at most `timeout_us`
+/// microseconds (may wake early or spuriously).
+pub(crate) fn platform_wait_timeout(flag: &AtomicI32, expected: i32, timeout_us: u32) {
+    sys::wait_timeout(flag, expected, timeout_us);
+}
+
+/// Wake one thread waiting on `flag`.
+///
+/// Does not change the flag; store the new value first. The native
+/// counterpart of `Atomics.notify(array, index, 1)`.
+pub fn notify_one(flag: &AtomicI32) {
+    sys::wake_one(flag);
+}
+
+/// Wake every thread waiting on `flag`.
+///
+/// Does not change the flag; store the new value first. The native
+/// counterpart of `Atomics.notify(array, index)`.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::{notify_all, wait_for_wake};
+/// use std::sync::atomic::{AtomicI32, Ordering};
+/// use std::sync::Arc;
+///
+/// let flag = Arc::new(AtomicI32::new(0));
+/// let waiter = std::thread::spawn({
+///     let flag = flag.clone();
+///     move || wait_for_wake(&flag)
+/// });
+///
+/// flag.store(1, Ordering::SeqCst);
+/// notify_all(&flag);
+/// waiter.join().unwrap();
+/// ```
+pub fn notify_all(flag: &AtomicI32) {
+    sys::wake_all(flag);
+}
+
+/// Wake a thread waiting on the given atomic flag.
+///
+/// Counterpart to `wait_for_wake()` in shared/mod.rs. Same as
+/// `notify_one()`.
+pub fn platform_wake(flag: &AtomicI32) {
+    notify_one(flag);
 }
 
 // =============================================================================
@@ -148,4 +324,46 @@ mod tests {
         let flag = AtomicI32::new(0);
         platform_wake(&flag); // should not panic even with no waiters
     }
+
+    #[test]
+    fn wait_returns_when_value_differs() {
+        // The kernel compares before sleeping, so this must not block
+        let flag = AtomicI32::new(1);
+        platform_wait(&flag, 0);
+        platform_wait_timeout(&flag, 0, 1_000_000);
+    }
+
+    #[test]
+    fn wait_timeout_expires() {
+        let flag = AtomicI32::new(0);
+        let start = std::time::Instant::now();
+        platform_wait_timeout(&flag, 0, 2_000);
+        assert_eq!(flag.load(Ordering::SeqCst), 0);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn notify_all_wakes_every_waiter() {
+        use std::sync::Arc;
+
+        let flag = Arc::new(AtomicI32::new(0));
+        let waiters: Vec<_> = (0..4)
+            .map(|_| {
+                let flag = flag.clone();
+                std::thread::spawn(move || {
+                    while flag.load(Ordering::SeqCst) == 0 {
+                        platform_wait(&flag, 0);
+                    }
+                })
+            })
+            .collect();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        flag.store(1, Ordering::SeqCst);
+        notify_all(&flag);
+
+        for waiter in waiters {
+            waiter.join().unwrap();
+        }
+    }
 }
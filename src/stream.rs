@@ -0,0 +1,329 @@
+// ============================================================================
+// spark-signals - Stream Adapters
+// futures::Stream in and out of the reactive graph
+// ============================================================================
+//
+// Async code consumes and produces `Stream`s; the graph deals in signals.
+//
+//     // Signal -> stream: the current value, then each change
+//     let mut changes = count.to_stream();
+//     while let Some(n) = changes.next().await { ... }
+//
+//     // Stream -> signal: spawn the driver on any local executor
+//     let (latest, driver) = signal_from_stream(messages, String::new());
+//     spawn_local(driver);
+//
+// `to_stream()` is backed by an effect that stores the value it read and
+// wakes the consumer, so writes between two polls coalesce into one item.
+// The stream runs no executor of its own: effects run wherever the graph
+// flushes them.
+//
+// Enabled with the `async` feature.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+use crate::primitives::derived::Derived;
+use crate::primitives::effect::effect;
+use crate::primitives::signal::{signal, ReadSignal, Signal, WeakSignal};
+
+// =============================================================================
+// SIGNAL -> STREAM
+// =============================================================================
+
+struct Mailbox<T> {
+    /// Latest value not yet yielded
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// A stream of a signal's or derived's values, from
+/// [`Signal::to_stream()`] / [`Derived::to_stream()`].
+///
+/// Yields the current value first, then the latest value after each change
+/// (changes between two polls yield once). Never ends; dropping the stream
+/// disposes its effect.
+#[must_use = "streams do nothing unless polled"]
+pub struct SignalStream<T> {
+    mailbox: Rc<RefCell<Mailbox<T>>>,
+    dispose: Option<Box<dyn FnOnce()>>,
+}
+
+impl<T: 'static> SignalStream<T> {
+    fn new(mut read: impl FnMut() -> T + 'static) -> Self {
+        let mailbox = Rc::new(RefCell::new(Mailbox {
+            value: None,
+            waker: None,
+        }));
+        let dispose = effect({
+            let mailbox = mailbox.clone();
+            move || {
+                let value = read();
+                let waker = {
+                    let mut mailbox = mailbox.borrow_mut();
+                    mailbox.value = Some(value);
+                    mailbox.waker.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        });
+        Self {
+            mailbox,
+            dispose: Some(Box::new(dispose)),
+        }
+    }
+}
+
+impl<T> Stream for SignalStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut mailbox = self.mailbox.borrow_mut();
+        match mailbox.value.take() {
+            Some(value) => Poll::Ready(Some(value)),
+            None => {
+                mailbox.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for SignalStream<T> {
+    fn drop(&mut self) {
+        if let Some(dispose) = self.dispose.take() {
+            dispose();
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for SignalStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalStream")
+            .field("ready", &self.mailbox.borrow().value.is_some())
+            .finish()
+    }
+}
+
+impl<T: Clone + 'static> Signal<T> {
+    /// A stream of this signal's values: the current one, then each change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_core::Stream;
+    /// use spark_signals::signal;
+    /// use std::pin::pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// let count = signal(1);
+    /// let mut changes = pin!(count.to_stream());
+    /// let mut cx = Context::from_waker(Waker::noop());
+    ///
+    /// assert_eq!(changes.as_mut().poll_next(&mut cx), Poll::Ready(Some(1)));
+    /// assert_eq!(changes.as_mut().poll_next(&mut cx), Poll::Pending);
+    ///
+    /// count.set(2);
+    /// count.set(3);
+    /// assert_eq!(changes.as_mut().poll_next(&mut cx), Poll::Ready(Some(3)));
+    /// ```
+    pub fn to_stream(&self) -> SignalStream<T> {
+        let signal = self.clone();
+        SignalStream::new(move || signal.get())
+    }
+}
+
+impl<T: Clone + 'static> Derived<T> {
+    /// A stream of this derived's values: the current one, then each change.
+    pub fn to_stream(&self) -> SignalStream<T> {
+        let derived = self.clone();
+        SignalStream::new(move || derived.get())
+    }
+}
+
+// =============================================================================
+// STREAM -> SIGNAL
+// =============================================================================
+
+/// Future returned by [`signal_from_stream()`]; writes each item of the
+/// stream to the signal.
+///
+/// Completes when the stream ends or the signal is dropped.
+#[must_use = "futures do nothing unless polled"]
+pub struct StreamDriver<S: Stream> {
+    stream: Pin<Box<S>>,
+    target: WeakSignal<S::Item>,
+}
+
+impl<S> Future for StreamDriver<S>
+where
+    S: Stream,
+    S::Item: PartialEq + 'static,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            let Some(target) = self.target.upgrade() else {
+                return Poll::Ready(());
+            };
+            match self.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    target.set(item);
+                }
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S: Stream> std::fmt::Debug for StreamDriver<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamDriver").finish_non_exhaustive()
+    }
+}
+
+/// A signal that follows `stream`, starting at `initial`.
+///
+/// Nothing happens until the returned driver is polled: spawn it on a local
+/// executor (e.g. `tokio::task::spawn_local`). Each item is written to the
+/// signal as it arrives.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::stream::signal_from_stream;
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// # struct Iter<I>(I);
+/// # impl<I: Iterator + Unpin> futures_core::Stream for Iter<I> {
+/// #     type Item = I::Item;
+/// #     fn poll_next(
+/// #         mut self: std::pin::Pin<&mut Self>,
+/// #         _: &mut Context<'_>,
+/// #     ) -> Poll<Option<I::Item>> {
+/// #         Poll::Ready(self.0.next())
+/// #     }
+/// # }
+/// let (latest, driver) = signal_from_stream(Iter([1, 2, 3].into_iter()), 0);
+/// assert_eq!(latest.get(), 0);
+///
+/// let mut driver = pin!(driver);
+/// let mut cx = Context::from_waker(Waker::noop());
+/// assert_eq!(driver.as_mut().poll(&mut cx), Poll::Ready(()));
+/// assert_eq!(latest.get(), 3);
+/// ```
+pub fn signal_from_stream<S>(
+    stream: S,
+    initial: S::Item,
+) -> (ReadSignal<S::Item>, StreamDriver<S>)
+where
+    S: Stream,
+    S::Item: PartialEq + 'static,
+{
+    let target = signal(initial);
+    let driver = StreamDriver {
+        stream: Box::pin(stream),
+        target: target.downgrade(),
+    };
+    (target.split().0, driver)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::derived::derived;
+    use crate::reactivity::batching::batch;
+    use std::cell::Cell;
+    use std::pin::pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn derived_stream_wakes_once_per_change() {
+        let count = signal(1);
+        let doubled = derived({
+            let count = count.clone();
+            move || count.get() * 2
+        });
+        let wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(wakes.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut stream = pin!(doubled.to_stream());
+        assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(2)));
+        assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Pending);
+
+        batch(|| {
+            count.set(2);
+            count.set(5);
+        });
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+        assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(10)));
+        assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Pending);
+    }
+
+    /// Yields Pending once between items, like a real source.
+    struct Staggered {
+        items: Vec<i32>,
+        ready: Cell<bool>,
+    }
+
+    impl Stream for Staggered {
+        type Item = i32;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<i32>> {
+            if !self.ready.replace(!self.ready.get()) {
+                return Poll::Pending;
+            }
+            Poll::Ready(if self.items.is_empty() {
+                None
+            } else {
+                Some(self.items.remove(0))
+            })
+        }
+    }
+
+    #[test]
+    fn driver_writes_items_until_the_signal_is_dropped() {
+        let stream = Staggered {
+            items: vec![1, 2, 3],
+            ready: Cell::new(false),
+        };
+        let (latest, driver) = signal_from_stream(stream, 0);
+        let mut driver = pin!(driver);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(driver.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(latest.get(), 0);
+        assert_eq!(driver.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(latest.get(), 1);
+
+        drop(latest);
+        assert_eq!(driver.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+}
@@ -0,0 +1,799 @@
+// ============================================================================
+// spark-signals - Thread-Safe Primitives
+// SyncSignal, SyncDerived and SyncEffect, shareable across threads
+// ============================================================================
+//
+// The main graph is Rc/RefCell and lives on one thread. This module is a
+// small, separate graph built on Arc, RwLock and atomics, for state that
+// worker threads read and write directly (e.g. alongside the shared-memory
+// arrays in `shared`).
+//
+// How it differs from the single-threaded graph:
+//
+// - Versions come from one global atomic counter. Every read records the
+//   version it saw, so a reaction can tell whether anything it used changed.
+// - Dependency tracking uses a per-thread observer stack; a derived or effect
+//   computing on one thread only records reads made on that thread.
+// - Deriveds are lazy: a write marks them dirty, the next read recomputes.
+// - Effects run synchronously on the thread whose write dirtied them, one
+//   run at a time. A write that arrives while an effect is running (from
+//   the effect itself or another thread) makes the running thread loop
+//   once more instead of blocking.
+// - There is no batching or effect tree; dropping the last `SyncEffect`
+//   handle disposes the effect.
+//
+// The two graphs don't track each other. Bridge them with `post()` /
+// `SignalSender` when a worker result should drive thread-local signals.
+//
+// Enabled with the `sync` feature.
+// ============================================================================
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, Weak};
+
+/// Runs an effect may loop through before it is considered runaway.
+const MAX_EFFECT_ROUNDS: u32 = 1000;
+
+/// Global write counter shared by every sync source.
+static WRITE_VERSION: AtomicU64 = AtomicU64::new(0);
+
+fn next_version() -> u64 {
+    WRITE_VERSION.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+/// Lock a mutex, ignoring poisoning (a panicking effect must not wedge
+/// every later writer).
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// =============================================================================
+// GRAPH TRAITS
+// =============================================================================
+
+/// Something that can be read and depended on.
+trait SyncSource: Send + Sync {
+    /// Write version of the current value
+    fn version(&self) -> u64;
+
+    /// Bring the value up to date (recomputes dirty deriveds)
+    fn refresh(&self);
+
+    fn subscribe(&self, reaction: Weak<dyn SyncReaction>);
+
+    fn unsubscribe(&self, reaction: &Weak<dyn SyncReaction>);
+}
+
+/// Something that reacts to its sources changing.
+trait SyncReaction: Send + Sync {
+    fn mark_dirty(&self);
+}
+
+/// A dependency recorded during a computation: the source and the
+/// version it had when read.
+type SyncDep = (Arc<dyn SyncSource>, u64);
+
+fn same_source(a: &Arc<dyn SyncSource>, b: &Arc<dyn SyncSource>) -> bool {
+    std::ptr::addr_eq(Arc::as_ptr(a), Arc::as_ptr(b))
+}
+
+/// Subscriber list shared by signals and deriveds.
+#[derive(Default)]
+struct Subscribers {
+    list: Mutex<Vec<Weak<dyn SyncReaction>>>,
+}
+
+impl Subscribers {
+    fn add(&self, reaction: Weak<dyn SyncReaction>) {
+        let mut list = lock(&self.list);
+        if !list.iter().any(|r| Weak::ptr_eq(r, &reaction)) {
+            list.push(reaction);
+        }
+    }
+
+    fn remove(&self, reaction: &Weak<dyn SyncReaction>) {
+        lock(&self.list).retain(|r| !Weak::ptr_eq(r, reaction));
+    }
+
+    /// Mark every live subscriber dirty. The list lock is released first,
+    /// since marking an effect dirty may run it.
+    fn notify(&self) {
+        let live: Vec<Arc<dyn SyncReaction>> = {
+            let mut list = lock(&self.list);
+            list.retain(|r| r.strong_count() > 0);
+            list.iter().filter_map(Weak::upgrade).collect()
+        };
+        for reaction in live {
+            reaction.mark_dirty();
+        }
+    }
+
+    fn len(&self) -> usize {
+        lock(&self.list).iter().filter(|r| r.strong_count() > 0).count()
+    }
+}
+
+// =============================================================================
+// OBSERVER STACK (per thread)
+// =============================================================================
+
+thread_local! {
+    /// Reads recorded by the computations running on this thread, innermost last
+    static OBSERVERS: RefCell<Vec<Vec<SyncDep>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record a read in the innermost computation on this thread, if any.
+fn record_read(source: Arc<dyn SyncSource>, version: u64) {
+    OBSERVERS.with(|observers| {
+        if let Some(frame) = observers.borrow_mut().last_mut() {
+            if !frame.iter().any(|(dep, _)| same_source(dep, &source)) {
+                frame.push((source, version));
+            }
+        }
+    });
+}
+
+/// Run `f`, returning its result and the sources it read.
+fn observe<R>(f: impl FnOnce() -> R) -> (R, Vec<SyncDep>) {
+    /// Pops the frame even if `f` panics
+    struct Frame;
+    impl Drop for Frame {
+        fn drop(&mut self) {
+            OBSERVERS.with(|observers| observers.borrow_mut().pop());
+        }
+    }
+
+    OBSERVERS.with(|observers| observers.borrow_mut().push(Vec::new()));
+    let frame = Frame;
+    let result = f();
+    let deps = OBSERVERS.with(|observers| observers.borrow_mut().last_mut().map(std::mem::take));
+    drop(frame);
+    (result, deps.unwrap_or_default())
+}
+
+/// Point a reaction at its new dependency set, dropping the old one.
+fn resubscribe(reaction: &Weak<dyn SyncReaction>, old: &[SyncDep], new: &[SyncDep]) {
+    for (dep, _) in old {
+        if !new.iter().any(|(d, _)| same_source(d, dep)) {
+            dep.unsubscribe(reaction);
+        }
+    }
+    for (dep, _) in new {
+        dep.subscribe(reaction.clone());
+    }
+}
+
+/// True if any dependency moved past the version recorded for it.
+fn deps_changed(deps: &[SyncDep]) -> bool {
+    deps.iter().any(|(dep, version)| {
+        dep.refresh();
+        dep.version() != *version
+    })
+}
+
+// =============================================================================
+// SYNC SIGNAL
+// =============================================================================
+
+struct SignalNode<T> {
+    value: RwLock<T>,
+    version: AtomicU64,
+    subscribers: Subscribers,
+}
+
+impl<T: Send + Sync> SyncSource for SignalNode<T> {
+    fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    fn refresh(&self) {}
+
+    fn subscribe(&self, reaction: Weak<dyn SyncReaction>) {
+        self.subscribers.add(reaction);
+    }
+
+    fn unsubscribe(&self, reaction: &Weak<dyn SyncReaction>) {
+        self.subscribers.remove(reaction);
+    }
+}
+
+/// A signal that can be shared and written across threads.
+///
+/// Cloning shares the signal.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::sync::{sync_effect, sync_signal};
+/// use std::sync::atomic::{AtomicI32, Ordering};
+/// use std::sync::Arc;
+///
+/// let progress = sync_signal(0);
+/// let seen = Arc::new(AtomicI32::new(-1));
+///
+/// let _effect = sync_effect({
+///     let (progress, seen) = (progress.clone(), seen.clone());
+///     move || seen.store(progress.get(), Ordering::SeqCst)
+/// });
+///
+/// let worker = std::thread::spawn({
+///     let progress = progress.clone();
+///     move || progress.set(100)
+/// });
+/// worker.join().unwrap();
+///
+/// assert_eq!(seen.load(Ordering::SeqCst), 100);
+/// ```
+pub struct SyncSignal<T> {
+    node: Arc<SignalNode<T>>,
+}
+
+impl<T> Clone for SyncSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
+}
+
+impl<T> SyncSignal<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Create a signal.
+    pub fn new(value: T) -> Self {
+        Self {
+            node: Arc::new(SignalNode {
+                value: RwLock::new(value),
+                version: AtomicU64::new(next_version()),
+                subscribers: Subscribers::default(),
+            }),
+        }
+    }
+
+    /// Read the value, tracking it in the computation running on this thread.
+    pub fn get(&self) -> T {
+        let value = self.node.value.read().unwrap_or_else(|e| e.into_inner());
+        record_read(self.node.clone(), self.node.version());
+        value.clone()
+    }
+
+    /// Read the value without tracking.
+    pub fn peek(&self) -> T {
+        self.node.value.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Write a value. Dependents are notified if it differs from the old one.
+    pub fn set(&self, value: T) {
+        {
+            let mut current = self.node.value.write().unwrap_or_else(|e| e.into_inner());
+            if *current == value {
+                return;
+            }
+            *current = value;
+            // Bumped under the write lock so a reader never pairs the new
+            // value with the old version
+            self.node.version.store(next_version(), Ordering::SeqCst);
+        }
+        self.node.subscribers.notify();
+    }
+
+    /// Modify the value in place; dependents are notified if it changed.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.peek();
+        f(&mut value);
+        self.set(value);
+    }
+
+    /// Number of live deriveds and effects depending on this signal.
+    pub fn subscriber_count(&self) -> usize {
+        self.node.subscribers.len()
+    }
+}
+
+/// Create a thread-safe signal.
+pub fn sync_signal<T>(value: T) -> SyncSignal<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    SyncSignal::new(value)
+}
+
+// =============================================================================
+// SYNC DERIVED
+// =============================================================================
+
+type DerivedFn<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+struct DerivedNode<T> {
+    func: DerivedFn<T>,
+    value: RwLock<Option<T>>,
+    version: AtomicU64,
+    dirty: AtomicBool,
+    /// Bumped by every `mark_dirty()`, so a computation can tell it raced a write
+    marks: AtomicU64,
+    deps: Mutex<Vec<SyncDep>>,
+    /// Serializes recomputation
+    compute: Mutex<()>,
+    subscribers: Subscribers,
+    self_weak: Weak<DerivedNode<T>>,
+}
+
+impl<T> DerivedNode<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn as_reaction(&self) -> Weak<dyn SyncReaction> {
+        self.self_weak.clone()
+    }
+
+    fn ensure_fresh(&self) {
+        if !self.dirty.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let _compute = lock(&self.compute);
+        // Someone else may have recomputed while we waited
+        if !self.dirty.load(Ordering::SeqCst) {
+            return;
+        }
+        // `dirty` stays set until the new value is stored, so readers on
+        // other threads wait on `compute` instead of reading an old (or no)
+        // value
+        let marks = self.marks.load(Ordering::SeqCst);
+
+        let (next, deps) = observe(&self.func);
+
+        let old_deps = std::mem::replace(&mut *lock(&self.deps), deps.clone());
+        resubscribe(&self.as_reaction(), &old_deps, &deps);
+
+        {
+            let mut value = self.value.write().unwrap_or_else(|e| e.into_inner());
+            if value.as_ref() != Some(&next) {
+                *value = Some(next);
+                self.version.store(next_version(), Ordering::SeqCst);
+            }
+        }
+        self.dirty.store(false, Ordering::SeqCst);
+
+        // A source written mid-computation leaves this result stale: either
+        // it was marked while we computed, or written before we subscribed
+        if self.marks.load(Ordering::SeqCst) != marks || deps_changed(&deps) {
+            self.dirty.store(true, Ordering::SeqCst);
+            self.subscribers.notify();
+        }
+    }
+}
+
+impl<T> SyncSource for DerivedNode<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    fn refresh(&self) {
+        self.ensure_fresh();
+    }
+
+    fn subscribe(&self, reaction: Weak<dyn SyncReaction>) {
+        self.subscribers.add(reaction);
+    }
+
+    fn unsubscribe(&self, reaction: &Weak<dyn SyncReaction>) {
+        self.subscribers.remove(reaction);
+    }
+}
+
+impl<T> SyncReaction for DerivedNode<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn mark_dirty(&self) {
+        self.marks.fetch_add(1, Ordering::SeqCst);
+        // Only the first mark propagates; dependents are already dirty after that
+        if !self.dirty.swap(true, Ordering::SeqCst) {
+            self.subscribers.notify();
+        }
+    }
+}
+
+/// A lazily computed value over sync signals, shareable across threads.
+///
+/// Recomputes on the reading thread when a dependency changed, and only
+/// notifies dependents' checks of a new version when the result differs.
+pub struct SyncDerived<T> {
+    node: Arc<DerivedNode<T>>,
+}
+
+impl<T> Clone for SyncDerived<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
+}
+
+impl<T> SyncDerived<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Create a derived. Nothing is computed until the first read.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self {
+            node: Arc::new_cyclic(|self_weak| DerivedNode {
+                func: Box::new(f),
+                value: RwLock::new(None),
+                version: AtomicU64::new(0),
+                dirty: AtomicBool::new(true),
+                marks: AtomicU64::new(0),
+                deps: Mutex::new(Vec::new()),
+                compute: Mutex::new(()),
+                subscribers: Subscribers::default(),
+                self_weak: self_weak.clone(),
+            }),
+        }
+    }
+
+    /// Read the value (recomputing if needed), tracking it.
+    pub fn get(&self) -> T {
+        self.node.ensure_fresh();
+        let value = self.node.value.read().unwrap_or_else(|e| e.into_inner());
+        record_read(self.node.clone(), self.node.version());
+        value.clone().expect("derived computed before read")
+    }
+
+    /// Read the value (recomputing if needed) without tracking.
+    pub fn peek(&self) -> T {
+        self.node.ensure_fresh();
+        self.node
+            .value
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .expect("derived computed before read")
+    }
+
+    /// True if a dependency changed since the last computation.
+    pub fn is_dirty(&self) -> bool {
+        self.node.dirty.load(Ordering::SeqCst)
+    }
+}
+
+/// Create a thread-safe derived.
+pub fn sync_derived<T, F>(f: F) -> SyncDerived<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    SyncDerived::new(f)
+}
+
+// =============================================================================
+// SYNC EFFECT
+// =============================================================================
+
+type EffectBody = Box<dyn FnMut() + Send>;
+
+struct EffectNode {
+    func: Mutex<EffectBody>,
+    deps: Mutex<Vec<SyncDep>>,
+    dirty: AtomicBool,
+    /// Held by the thread currently running the effect
+    running: AtomicBool,
+    ran: AtomicBool,
+    disposed: AtomicBool,
+    self_weak: Weak<EffectNode>,
+}
+
+impl EffectNode {
+    fn as_reaction(&self) -> Weak<dyn SyncReaction> {
+        self.self_weak.clone()
+    }
+
+    /// Run until no longer dirty, unless another run is already in progress
+    /// (that run picks up the dirty flag).
+    fn run(&self) {
+        /// Releases the run claim even if the body panics
+        struct Claim<'a>(&'a AtomicBool);
+        impl Drop for Claim<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, Ordering::SeqCst);
+            }
+        }
+
+        loop {
+            if self.running.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let claim = Claim(&self.running);
+
+            let mut rounds = 0;
+            while self.dirty.swap(false, Ordering::SeqCst) {
+                if self.disposed.load(Ordering::SeqCst) {
+                    break;
+                }
+                rounds += 1;
+                if rounds > MAX_EFFECT_ROUNDS {
+                    panic!(
+                        "Maximum update depth exceeded. A sync effect keeps writing \
+                         a signal it depends on."
+                    );
+                }
+                self.run_once();
+            }
+
+            drop(claim);
+
+            // A mark that landed between our last check and the release saw
+            // the effect as running and returned; take it here.
+            if !self.dirty.load(Ordering::SeqCst) || self.disposed.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+    }
+
+    fn run_once(&self) {
+        let first = !self.ran.swap(true, Ordering::SeqCst);
+        let deps = lock(&self.deps).clone();
+        if !first && !deps_changed(&deps) {
+            return;
+        }
+
+        let ((), deps) = observe(|| (lock(&self.func))());
+
+        let old_deps = std::mem::replace(&mut *lock(&self.deps), deps.clone());
+        resubscribe(&self.as_reaction(), &old_deps, &deps);
+
+        // Writes during the run to sources it had already read (including
+        // its own writes on the first run) happened before we subscribed
+        if deps_changed(&deps) {
+            self.dirty.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn dispose(&self) {
+        if self.disposed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let deps = std::mem::take(&mut *lock(&self.deps));
+        let reaction = self.as_reaction();
+        for (dep, _) in deps {
+            dep.unsubscribe(&reaction);
+        }
+    }
+}
+
+impl SyncReaction for EffectNode {
+    fn mark_dirty(&self) {
+        if self.disposed.load(Ordering::SeqCst) {
+            return;
+        }
+        self.dirty.store(true, Ordering::SeqCst);
+        self.run();
+    }
+}
+
+/// A side effect over sync signals and deriveds.
+///
+/// Runs once on creation, then on the thread of each write that changes
+/// something it read. Dropping the last handle (or calling `dispose()`)
+/// stops it. Cloning shares the effect.
+pub struct SyncEffect {
+    node: Arc<EffectNode>,
+}
+
+impl Clone for SyncEffect {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
+}
+
+impl SyncEffect {
+    /// Create an effect and run it immediately on this thread.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let node = Arc::new_cyclic(|self_weak| EffectNode {
+            func: Mutex::new(Box::new(f)),
+            deps: Mutex::new(Vec::new()),
+            dirty: AtomicBool::new(true),
+            running: AtomicBool::new(false),
+            ran: AtomicBool::new(false),
+            disposed: AtomicBool::new(false),
+            self_weak: self_weak.clone(),
+        });
+        node.run();
+        Self { node }
+    }
+
+    /// Stop the effect and drop its subscriptions.
+    pub fn dispose(&self) {
+        self.node.dispose();
+    }
+
+    pub fn is_disposed(&self) -> bool {
+        self.node.disposed.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for SyncEffect {
+    fn drop(&mut self) {
+        // Subscribers only hold weak references, so the last handle owns it
+        if Arc::strong_count(&self.node) == 1 {
+            self.node.dispose();
+        }
+    }
+}
+
+/// Create a thread-safe effect.
+pub fn sync_effect<F>(f: F) -> SyncEffect
+where
+    F: FnMut() + Send + 'static,
+{
+    SyncEffect::new(f)
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicI32, AtomicUsize};
+
+    #[test]
+    fn derived_recomputes_lazily() {
+        let count = sync_signal(1);
+        let computes = Arc::new(AtomicUsize::new(0));
+        let doubled = sync_derived({
+            let (count, computes) = (count.clone(), computes.clone());
+            move || {
+                computes.fetch_add(1, Ordering::SeqCst);
+                count.get() * 2
+            }
+        });
+
+        assert_eq!(computes.load(Ordering::SeqCst), 0);
+        assert_eq!(doubled.get(), 2);
+        assert_eq!(doubled.get(), 2);
+        assert_eq!(computes.load(Ordering::SeqCst), 1);
+
+        count.set(5);
+        assert!(doubled.is_dirty());
+        assert_eq!(doubled.peek(), 10);
+        assert_eq!(computes.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn effect_skips_when_derived_result_is_unchanged() {
+        let n = sync_signal(2);
+        let parity = sync_derived({
+            let n = n.clone();
+            move || n.get() % 2
+        });
+        let runs = Arc::new(AtomicUsize::new(0));
+        let _effect = sync_effect({
+            let (parity, runs) = (parity.clone(), runs.clone());
+            move || {
+                parity.get();
+                runs.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        n.set(4);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        n.set(5);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn writes_from_many_threads_reach_the_effect() {
+        let total = sync_signal(0);
+        let last_seen = Arc::new(AtomicI32::new(0));
+        let _effect = sync_effect({
+            let (total, last_seen) = (total.clone(), last_seen.clone());
+            move || last_seen.store(total.get(), Ordering::SeqCst)
+        });
+
+        let workers: Vec<_> = (0..8)
+            .map(|_| {
+                let total = total.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        total.update(|t| *t += 1);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        // update() is read-modify-write, so concurrent increments may be
+        // lost; the effect must still have seen the final value
+        assert_eq!(last_seen.load(Ordering::SeqCst), total.peek());
+    }
+
+    #[test]
+    fn effect_writing_its_own_dependency_settles() {
+        let n = sync_signal(0);
+        let _effect = sync_effect({
+            let n = n.clone();
+            move || {
+                let value = n.get();
+                if value < 10 {
+                    n.set(value + 1);
+                }
+            }
+        });
+        assert_eq!(n.peek(), 10);
+    }
+
+    #[test]
+    fn dropping_the_effect_unsubscribes() {
+        let a = sync_signal(0);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let effect = sync_effect({
+            let (a, runs) = (a.clone(), runs.clone());
+            move || {
+                a.get();
+                runs.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        assert_eq!(a.subscriber_count(), 1);
+
+        let handle = effect.clone();
+        drop(effect);
+        a.set(1);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        drop(handle);
+        assert_eq!(a.subscriber_count(), 0);
+        a.set(2);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+    #[test]
+    fn concurrent_reads_wait_for_the_computation() {
+        let n = sync_signal(1);
+        let slow = sync_derived({
+            let n = n.clone();
+            move || {
+                let value = n.get();
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                value * 10
+            }
+        });
+
+        let read_together = |expected: i32| {
+            let barrier = Arc::new(std::sync::Barrier::new(4));
+            let readers: Vec<_> = (0..4)
+                .map(|_| {
+                    let (slow, barrier) = (slow.clone(), barrier.clone());
+                    std::thread::spawn(move || {
+                        barrier.wait();
+                        slow.get()
+                    })
+                })
+                .collect();
+            for reader in readers {
+                assert_eq!(reader.join().unwrap(), expected);
+            }
+        };
+
+        // First read: nobody may see the value before it exists
+        read_together(10);
+        // Later reads: nobody may see the old value while it is recomputed
+        n.set(2);
+        read_together(20);
+    }
+}
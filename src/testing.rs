@@ -0,0 +1,302 @@
+// ============================================================================
+// spark-signals - Testing Utilities
+// Isolation, run counters and a recording scheduler for tests
+// ============================================================================
+//
+// The reactive graph is thread-local, and cargo runs tests on a shared pool
+// of threads, so config, hooks and stray effects can leak between tests.
+//
+// - `with_isolated_runtime(|| ...)` runs a test body on a fresh thread, with
+//   a fresh context and config.
+// - `assert_effect_runs!(n, || ...)` checks how many effects ran.
+// - `count_recomputations(&derived)` counts one derived's recomputes.
+// - `RecordingScheduler` holds effects like `TestScheduler` and logs the
+//   order they run in.
+//
+// The counts live in the reactive context, which keeps them whether or not
+// this module is used: a counter bump per effect run. The log and recompute
+// counters only do work while a scheduler or counter is installed.
+// ============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::panic::resume_unwind;
+use std::rc::Rc;
+
+use crate::core::context::{try_with_context, with_context};
+use crate::primitives::derived::Derived;
+use crate::reactivity::test_scheduler::TestScheduler;
+
+// =============================================================================
+// ISOLATION
+// =============================================================================
+
+/// Run `f` against a fresh reactive runtime and return its result.
+///
+/// `f` runs on a new thread, so it starts with an empty context, default
+/// config and no hooks, and nothing it creates outlives it. A panic in `f`
+/// (e.g. a failed assertion) is resumed on the calling thread.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::testing::with_isolated_runtime;
+/// use spark_signals::{derived, signal};
+///
+/// let doubled = with_isolated_runtime(|| {
+///     let count = signal(21);
+///     let doubled = derived({
+///         let count = count.clone();
+///         move || count.get() * 2
+///     });
+///     doubled.get()
+/// });
+/// assert_eq!(doubled, 42);
+/// ```
+pub fn with_isolated_runtime<R, F>(f: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .name("spark-signals-isolated".into())
+            .spawn_scoped(scope, f)
+            .expect("failed to spawn isolated runtime thread")
+            .join()
+            .unwrap_or_else(|panic| resume_unwind(panic))
+    })
+}
+
+// =============================================================================
+// RUN COUNTERS
+// =============================================================================
+
+/// Number of effect runs in the current context (the thread's, or the
+/// entered `Runtime`'s) so far.
+///
+/// Take the difference around the code under test, or use
+/// [`assert_effect_runs!`](crate::assert_effect_runs).
+pub fn effect_run_count() -> u64 {
+    with_context(|ctx| ctx.effect_run_count())
+}
+
+/// Recompute count of one derived, from [`count_recomputations()`].
+///
+/// Stops counting when the last clone is dropped.
+#[derive(Clone)]
+pub struct RecomputeCounter {
+    id: usize,
+    count: Rc<Cell<u64>>,
+}
+
+impl RecomputeCounter {
+    /// Recomputes since the counter was created or last reset.
+    pub fn get(&self) -> u64 {
+        self.count.get()
+    }
+
+    /// Start counting from zero again.
+    pub fn reset(&self) {
+        self.count.set(0);
+    }
+}
+
+impl Drop for RecomputeCounter {
+    fn drop(&mut self) {
+        // The map holds one reference; this was the last counter
+        if Rc::strong_count(&self.count) == 2 {
+            try_with_context(|ctx| {
+                ctx.recompute_counters.borrow_mut().remove(&self.id);
+            });
+        }
+    }
+}
+
+/// Count the recomputes of `derived` from now on.
+///
+/// Counters for the same derived share one count.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::testing::count_recomputations;
+/// use spark_signals::{derived, signal};
+///
+/// let count = signal(1);
+/// let doubled = derived({
+///     let count = count.clone();
+///     move || count.get() * 2
+/// });
+/// let recomputes = count_recomputations(&doubled);
+///
+/// count.set(2);
+/// count.set(3);
+/// assert_eq!(recomputes.get(), 0); // lazy: nothing read it yet
+/// assert_eq!(doubled.get(), 6);
+/// assert_eq!(recomputes.get(), 1);
+/// ```
+pub fn count_recomputations<T: Clone + 'static>(derived: &Derived<T>) -> RecomputeCounter {
+    let id = Rc::as_ptr(derived.inner()) as *const () as usize;
+    let count = with_context(|ctx| {
+        ctx.recompute_counters.borrow_mut().entry(id).or_default().clone()
+    });
+    RecomputeCounter { id, count }
+}
+
+// =============================================================================
+// RECORDING SCHEDULER
+// =============================================================================
+
+/// A [`TestScheduler`] that also logs every effect run on the thread, by
+/// effect name (see `set_effect_name`) or, if unnamed, by creation site.
+///
+/// # Example
+///
+/// ```
+/// use spark_signals::testing::RecordingScheduler;
+/// use spark_signals::{effect, set_effect_name, signal};
+///
+/// let scheduler = RecordingScheduler::new();
+/// let count = signal(0);
+/// let _a = effect({
+///     let count = count.clone();
+///     move || {
+///         set_effect_name("a");
+///         count.get();
+///     }
+/// });
+///
+/// scheduler.run_until_idle();
+/// count.set(1);
+/// scheduler.run_until_idle();
+/// assert_eq!(scheduler.take_log(), ["a", "a"]);
+/// ```
+pub struct RecordingScheduler {
+    scheduler: TestScheduler,
+    log: Rc<RefCell<Vec<String>>>,
+    previous: Option<Rc<RefCell<Vec<String>>>>,
+}
+
+impl RecordingScheduler {
+    /// Install a recording test scheduler on the current thread.
+    pub fn new() -> Self {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let previous = with_context(|ctx| ctx.run_log.replace(Some(log.clone())));
+        Self {
+            scheduler: TestScheduler::new(),
+            log,
+            previous,
+        }
+    }
+
+    /// Run the oldest pending effect. Returns false if none was pending.
+    pub fn step(&self) -> bool {
+        self.scheduler.step()
+    }
+
+    /// Run pending effects until none are left. Returns the number of runs.
+    pub fn run_until_idle(&self) -> usize {
+        self.scheduler.run_until_idle()
+    }
+
+    /// Number of effects waiting to run.
+    pub fn pending(&self) -> usize {
+        self.scheduler.pending()
+    }
+
+    /// The effects run so far, in order.
+    pub fn log(&self) -> Vec<String> {
+        self.log.borrow().clone()
+    }
+
+    /// The effects run so far, clearing the log.
+    pub fn take_log(&self) -> Vec<String> {
+        std::mem::take(&mut *self.log.borrow_mut())
+    }
+}
+
+impl Default for RecordingScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RecordingScheduler {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        try_with_context(|ctx| *ctx.run_log.borrow_mut() = previous);
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{current_config, set_thread_config, Config};
+    use crate::primitives::derived::derived;
+    use crate::primitives::effect::effect;
+    use crate::primitives::signal::signal;
+
+    #[test]
+    fn isolated_runtimes_start_fresh() {
+        set_thread_config(Config {
+            max_update_depth: 7,
+            ..Config::default()
+        });
+        let depth = with_isolated_runtime(|| current_config().max_update_depth);
+        assert_eq!(depth, Config::default().max_update_depth);
+
+        let result = std::panic::catch_unwind(|| with_isolated_runtime(|| panic!("inner")));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn counters_follow_runs_and_recomputes() {
+        with_isolated_runtime(|| {
+            let count = signal(1);
+            let even = derived({
+                let count = count.clone();
+                move || count.get() % 2 == 0
+            });
+            let recomputes = count_recomputations(&even);
+            let _log = effect({
+                let even = even.clone();
+                move || {
+                    even.get();
+                }
+            });
+            assert_eq!(recomputes.get(), 1);
+
+            // Unchanged value: nothing recomputes or runs
+            crate::assert_effect_runs!(0, || count.set(1));
+            crate::assert_effect_runs!(1, || count.set(4));
+            assert_eq!(recomputes.get(), 2);
+
+            drop(recomputes);
+            with_context(|ctx| assert!(ctx.recompute_counters.borrow().is_empty()));
+        });
+    }
+
+    #[test]
+    fn runs_are_counted_per_runtime() {
+        let runtime = crate::core::context::Runtime::new();
+        let before = effect_run_count();
+        let (count, _log) = runtime.enter(|| {
+            let count = signal(0);
+            let log = effect({
+                let count = count.clone();
+                move || {
+                    count.get();
+                }
+            });
+            (count, log)
+        });
+        runtime.enter(|| count.set(1));
+
+        assert_eq!(runtime.enter(effect_run_count), 2);
+        assert_eq!(effect_run_count(), before);
+    }
+}
@@ -0,0 +1,202 @@
+// ============================================================================
+// spark-signals - WASM Bindings
+// wasm-bindgen classes so the crate can be the reactive core in a browser
+// ============================================================================
+//
+// Mirrors the TypeScript API with `JsValue` payloads:
+//
+//     import { signal, derived, effect, batch, useMicrotaskScheduler } from "spark-signals";
+//
+//     useMicrotaskScheduler();
+//     const count = signal(1);
+//     const double = derived(() => count.get() * 2);
+//     const fx = effect(() => console.log(double.get()));
+//     batch(() => { count.set(2); count.set(3); });
+//     fx.dispose();
+//
+// Values compare with `Object.is`, like the TS package. By default effects
+// run synchronously after a write, as everywhere else in the crate;
+// `useMicrotaskScheduler()` defers them to a `queueMicrotask` flush instead,
+// matching the TS package's timing.
+// ============================================================================
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use js_sys::{Function, Object};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::primitives::derived::{derived_with_equals, Derived};
+use crate::primitives::effect::{effect_with_cleanup, CleanupFn, DisposeFn};
+use crate::primitives::signal::{signal_with_equals, Signal};
+use crate::reactivity::batching::{batch, untrack};
+use crate::core::context::Runtime;
+use crate::reactivity::scheduling::{
+    flush_sync, has_flush_request, set_flush_request, set_manual_flush,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = queueMicrotask)]
+    fn queue_microtask(callback: &Function);
+}
+
+fn object_is(a: &JsValue, b: &JsValue) -> bool {
+    Object::is(a, b)
+}
+
+fn result_is(a: &Result<JsValue, JsValue>, b: &Result<JsValue, JsValue>) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) | (Err(a), Err(b)) => Object::is(a, b),
+        _ => false,
+    }
+}
+
+// =============================================================================
+// SIGNAL
+// =============================================================================
+
+/// A writable signal holding any JS value.
+#[wasm_bindgen(js_name = Signal)]
+pub struct JsSignal {
+    inner: Signal<JsValue>,
+}
+
+#[wasm_bindgen(js_class = Signal)]
+impl JsSignal {
+    /// Read the value (tracked).
+    pub fn get(&self) -> JsValue {
+        self.inner.get()
+    }
+
+    /// Read the value without tracking.
+    pub fn peek(&self) -> JsValue {
+        untrack(|| self.inner.get())
+    }
+
+    /// Write the value. Returns false if it was `Object.is`-equal.
+    pub fn set(&self, value: JsValue) -> bool {
+        self.inner.set(value)
+    }
+}
+
+/// Create a signal.
+#[wasm_bindgen(js_name = signal)]
+pub fn js_signal(initial: JsValue) -> JsSignal {
+    JsSignal {
+        inner: signal_with_equals(initial, object_is),
+    }
+}
+
+// =============================================================================
+// DERIVED
+// =============================================================================
+
+/// A cached computation over other signals and deriveds.
+#[wasm_bindgen(js_name = Derived)]
+pub struct JsDerived {
+    inner: Derived<Result<JsValue, JsValue>>,
+}
+
+#[wasm_bindgen(js_class = Derived)]
+impl JsDerived {
+    /// Read the value (tracked). Rethrows what the computation threw.
+    pub fn get(&self) -> Result<JsValue, JsValue> {
+        self.inner.get()
+    }
+}
+
+/// Create a derived from a JS function. A thrown error is cached like a
+/// value and rethrown by `get()`.
+#[wasm_bindgen(js_name = derived)]
+pub fn js_derived(compute: Function) -> JsDerived {
+    JsDerived {
+        inner: derived_with_equals(move || compute.call0(&JsValue::NULL), result_is),
+    }
+}
+
+// =============================================================================
+// EFFECT
+// =============================================================================
+
+/// A running effect. Call `dispose()` to stop it.
+#[wasm_bindgen(js_name = Effect)]
+pub struct JsEffect {
+    dispose: Option<DisposeFn>,
+}
+
+#[wasm_bindgen(js_class = Effect)]
+impl JsEffect {
+    /// Stop the effect, running its last cleanup.
+    pub fn dispose(&mut self) {
+        if let Some(dispose) = self.dispose.take() {
+            dispose();
+        }
+    }
+}
+
+/// Create an effect from a JS function. If it returns a function, that is
+/// called as cleanup before the next run and on dispose.
+///
+/// # Panics
+///
+/// An exception thrown by `run` is raised as a panic of the effect, like a
+/// panicking Rust effect.
+#[wasm_bindgen(js_name = effect)]
+pub fn js_effect(run: Function) -> JsEffect {
+    let dispose = effect_with_cleanup(move || {
+        let returned = match run.call0(&JsValue::NULL) {
+            Ok(returned) => returned,
+            Err(error) => panic!("effect threw {error:?}"),
+        };
+        returned.dyn_into::<Function>().ok().map(|cleanup| {
+            Box::new(move || {
+                let _ = cleanup.call0(&JsValue::NULL);
+            }) as CleanupFn
+        })
+    });
+    JsEffect {
+        dispose: Some(Box::new(dispose)),
+    }
+}
+
+// =============================================================================
+// BATCH & SCHEDULER
+// =============================================================================
+
+/// Run `f` as one batch: effects run once, after it returns.
+#[wasm_bindgen(js_name = batch)]
+pub fn js_batch(f: Function) -> Result<JsValue, JsValue> {
+    batch(|| f.call0(&JsValue::NULL))
+}
+
+/// Run effects on a microtask after the writes that scheduled them, instead
+/// of synchronously. Several writes in one task then cause one flush.
+///
+/// Applies to the current context (from Rust, an entered `Runtime`'s).
+/// Calling it again has no effect.
+#[wasm_bindgen(js_name = useMicrotaskScheduler)]
+pub fn use_microtask_scheduler() {
+    if has_flush_request() {
+        return;
+    }
+
+    let runtime = Runtime::current();
+    let queued = Rc::new(Cell::new(false));
+    set_manual_flush(true);
+    set_flush_request(Some(Rc::new(move || {
+        if queued.replace(true) {
+            return;
+        }
+        let (queued, runtime) = (queued.clone(), runtime.clone());
+        let flush = Closure::once_into_js(move || {
+            queued.set(false);
+            match runtime {
+                Some(runtime) => runtime.enter(flush_sync),
+                None => flush_sync(),
+            }
+        });
+        queue_microtask(flush.unchecked_ref());
+    })));
+}
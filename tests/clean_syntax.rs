@@ -1,4 +1,4 @@
-use spark_signals::{derived, effect, signal, cloned};
+use spark_signals::{derived, effect, signal};
 
 #[test]
 fn macro_derived_syntax() {
@@ -40,3 +40,36 @@ fn macro_nested_usage() {
     
     assert_eq!(d.get(), 2);
 }
+
+#[test]
+fn macro_captures_named_expressions() {
+    struct Size {
+        width: spark_signals::Signal<i32>,
+        height: spark_signals::Signal<i32>,
+    }
+
+    let size = Size {
+        width: signal(2),
+        height: signal(3),
+    };
+    let area = derived!(w = size.width, h = size.height => w.get() * h.get());
+    assert_eq!(area.get(), 6);
+
+    size.width.set(4);
+    assert_eq!(area.get(), 12);
+}
+
+#[test]
+fn macro_effect_sync_syntax() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let a = signal(1);
+    let seen = Rc::new(Cell::new(0));
+
+    let _dispose = spark_signals::effect_sync!(a, seen => seen.set(a.get() * 10));
+    assert_eq!(seen.get(), 10);
+
+    a.set(2);
+    assert_eq!(seen.get(), 20);
+}
@@ -1,6 +1,6 @@
 use spark_signals::{
     create_selector_eq, effect_scope, effect_sync, effect_sync_with_cleanup,
-    linked_signal, signal, Effect, EffectScope
+    linked_signal, signal,
 };
 use std::cell::Cell;
 use std::rc::Rc;
@@ -110,7 +110,7 @@ fn test_scope_drop_stops_effects() {
     {
         let scope = effect_scope(false);
         scope.run(|| {
-            effect_sync(move || {
+            let _ = effect_sync(move || {
                 let _ = count_clone.get();
                 run_count_clone.set(run_count_clone.get() + 1);
             });
@@ -140,7 +140,7 @@ fn test_scope_clone_does_not_stop() {
         scope2.run(|| {
             let count = count.clone();
             let run_count = run_count_clone.clone();
-            effect_sync(move || {
+            let _ = effect_sync(move || {
                 let _ = count.get();
                 run_count.set(run_count.get() + 1);
             });
@@ -1,4 +1,4 @@
-use spark_signals::{prop, signal, reactive_prop, PropValue};
+use spark_signals::{prop, signal, reactive_prop};
 
 #[test]
 fn macro_prop_syntax() {
@@ -0,0 +1,65 @@
+#![cfg(feature = "macros")]
+
+use spark_signals::{derived, effect_sync, signal, PropValue, Props};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Props)]
+struct ButtonProps {
+    label: PropValue<String>,
+    #[props(default = 80)]
+    width: PropValue<u32>,
+    disabled: PropValue<bool>,
+}
+
+#[test]
+fn builder_accepts_values_signals_and_closures() {
+    let busy = signal(false);
+    let base = signal(10);
+    let props = ButtonProps::builder()
+        .label(String::from("Save"))
+        .disabled(busy.clone())
+        .width({
+            let base = base.clone();
+            move || base.get() * 2
+        })
+        .build();
+
+    assert_eq!(props.label.peek(), "Save");
+    assert_eq!(props.width.peek(), 20);
+    assert!(!props.disabled.peek());
+
+    let defaults = ButtonProps::builder().build();
+    assert_eq!(defaults.label.peek(), "");
+    assert_eq!(defaults.width.peek(), 80);
+}
+
+#[test]
+fn into_slots_binds_every_field_at_once() {
+    let busy = signal(false);
+    let label = derived(|| String::from("Open"));
+    let slots = ButtonPropsSlots::new();
+
+    let runs = Rc::new(Cell::new(0));
+    let _watch = effect_sync({
+        let (disabled, width, runs) = (slots.disabled.slot(3), slots.width.slot(3), runs.clone());
+        move || {
+            disabled.get();
+            width.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    ButtonProps::builder()
+        .label(label)
+        .disabled(&busy)
+        .build()
+        .into_slots(&slots, 3);
+    assert_eq!(runs.get(), 2);
+    assert_eq!(slots.label.get(3).as_deref(), Some("Open"));
+    assert_eq!(slots.width.get(3), Some(80));
+
+    busy.set(true);
+    assert_eq!(slots.disabled.get(3), Some(true));
+    assert_eq!(runs.get(), 3);
+}